@@ -11,7 +11,7 @@ use std::{num::ParseIntError, path::PathBuf, str::FromStr};
 use time::{serde::iso8601, OffsetDateTime};
 use url::Url;
 
-use crate::{custom_emoji::CustomEmoji, AccountId, RoleId};
+use crate::{custom_emoji::CustomEmoji, AccountId, AccountWarningId, RoleId, StatusId};
 
 /// A struct representing an Account.
 ///
@@ -157,6 +157,157 @@ pub struct Account {
     pub url: Url,
     /// The username of the account, not including domain. See also [the API reference](https://docs.joinmastodon.org/entities/Account/#username)
     pub username: String,
+    /// The extra fields Pleroma and Akkoma add to accounts, if the server
+    /// sent them.
+    #[cfg(feature = "pleroma")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pleroma: Option<crate::pleroma::AccountExtensions>,
+}
+
+impl Account {
+    /// Whether the account lives on the server the client is connected to,
+    /// or on another one, going by its `acct`.
+    ///
+    /// ```
+    /// use mastodon_async_entities::account::Origin;
+    /// # use mastodon_async_entities::account::Account;
+    /// # fn show(results: Vec<Account>) {
+    /// for account in results {
+    ///     match account.origin() {
+    ///         Origin::Local => println!("{} (here)", account.acct),
+    ///         Origin::Remote { domain } => println!("{} (from {domain})", account.username),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn origin(&self) -> Origin<'_> {
+        match self.acct.split_once('@') {
+            Some((_, domain)) => Origin::Remote { domain },
+            None => Origin::Local,
+        }
+    }
+
+    /// Whether the account lives on the server the client is connected to.
+    pub fn is_local(&self) -> bool {
+        self.origin() == Origin::Local
+    }
+
+    /// Whether the account has moved to another one.
+    pub fn has_moved(&self) -> bool {
+        self.moved.is_some()
+    }
+
+    /// The account this one moved to, if it has moved.
+    pub fn moved_to(&self) -> Option<&Account> {
+        self.moved.as_deref()
+    }
+
+    /// The account at the end of the account's moves, following the account
+    /// it moved to in case that has moved as well, or the account itself if
+    /// it hasn't moved. Migration tooling can use it to find where to follow
+    /// someone now.
+    ///
+    /// ```
+    /// # use mastodon_async_entities::account::Account;
+    /// # fn show(following: Vec<Account>) {
+    /// for account in following.iter().filter(|it| it.has_moved()) {
+    ///     println!("{} is now {}", account.acct, account.current_account().acct);
+    /// }
+    /// # }
+    /// ```
+    pub fn current_account(&self) -> &Account {
+        let mut account = self;
+        while let Some(moved) = account.moved_to() {
+            account = moved;
+        }
+        account
+    }
+}
+
+/// Where an account lives, relative to the server which returned it. See
+/// [`Account::origin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Origin<'a> {
+    /// The account is on the same server.
+    Local,
+    /// The account is on another server, and has been federated.
+    Remote {
+        /// The domain of the server the account is on.
+        domain: &'a str,
+    },
+}
+
+/// A warning a moderator sent an account, along with the action taken
+/// against it, if any.
+///
+/// See also [the API reference](https://docs.joinmastodon.org/entities/AccountWarning/)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Warning {
+    /// The ID of the warning in the database.
+    pub id: AccountWarningId,
+    /// The action taken against the account.
+    pub action: WarningAction,
+    /// The message from the moderator to the account.
+    pub text: String,
+    /// The statuses the warning is about, if any.
+    #[serde(default)]
+    pub status_ids: Option<Vec<StatusId>>,
+    /// The account the warning was sent to.
+    pub target_account: Account,
+    /// The account's appeal against the warning, if it has appealed.
+    pub appeal: Option<Appeal>,
+    /// When the warning was sent.
+    #[serde(with = "iso8601")]
+    pub created_at: OffsetDateTime,
+}
+
+/// The action a moderator took against an account. See [`Warning::action`].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningAction {
+    /// No action was taken, only a warning sent.
+    None,
+    /// The account's login was disabled.
+    Disable,
+    /// Some of the account's statuses were marked sensitive.
+    MarkStatusesAsSensitive,
+    /// Some of the account's statuses were deleted.
+    DeleteStatuses,
+    /// All of the account's media will be marked sensitive.
+    Sensitive,
+    /// The account was limited.
+    Silence,
+    /// The account was suspended.
+    Suspend,
+    /// An action this version of the crate doesn't know about.
+    #[cfg_attr(not(feature = "strict"), serde(other))]
+    Unknown,
+}
+
+/// An account's appeal against a moderator's warning.
+///
+/// See also [the API reference](https://docs.joinmastodon.org/entities/Appeal/)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Appeal {
+    /// The account's reasons for appealing.
+    pub text: String,
+    /// Whether the appeal has been decided yet, and how.
+    pub state: AppealState,
+}
+
+/// Whether an appeal has been decided. See [`Appeal::state`].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum AppealState {
+    /// The appeal was approved, and the action undone.
+    Approved,
+    /// The appeal was rejected.
+    Rejected,
+    /// The appeal hasn't been decided yet.
+    Pending,
+    /// A state this version of the crate doesn't know about.
+    #[cfg_attr(not(feature = "strict"), serde(other))]
+    Unknown,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -168,6 +319,9 @@ pub struct CredentialAccount {
     /// methods that [verify credentials](https://docs.joinmastodon.org/methods/accounts/#verify_credentials)
     /// and [update credentials](https://docs.joinmastodon.org/methods/accounts/#update_credentials). See also [the API reference](https://docs.joinmastodon.org/entities/Account/#source)
     pub source: Source,
+    /// The role assigned to the account, if any. See also [the API reference](https://docs.joinmastodon.org/entities/Account/#role)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
 }
 
 /// A single name: value pair from a user's profile
@@ -487,13 +641,25 @@ pub enum RolePermissions {
 mod fields_attributes_ser {
     use super::*;
     use serde::ser::{SerializeMap, Serializer};
+
+    /// The part of a field that can be set; the server verifies links itself.
+    #[derive(Serialize)]
+    struct Attribute<'a> {
+        name: &'a str,
+        value: &'a str,
+    }
+
     pub fn ser<S>(attrs: &[MetadataField], serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         let mut map = serializer.serialize_map(Some(attrs.len()))?;
         for (i, field) in attrs.iter().enumerate() {
-            map.serialize_entry(&i, &field)?;
+            let attribute = Attribute {
+                name: &field.name,
+                value: &field.value,
+            };
+            map.serialize_entry(&i, &attribute)?;
         }
         map.end()
     }
@@ -512,23 +678,130 @@ pub struct FamiliarFollowers {
 
 /// Represents a suggested account to follow and an associated reason for the
 /// suggestion.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/Suggestion/)
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct Suggestion {
-    source: SuggestionSource,
-    account: Account,
+    /// Why the account is suggested, superseded by `sources` in Mastodon
+    /// 4.3.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SuggestionSource>,
+    /// All the reasons why the account is suggested.
+    #[serde(default)]
+    pub sources: Vec<SuggestionReason>,
+    /// The suggested account.
+    pub account: Account,
 }
 
+/// Why an account is suggested, as reported before Mastodon 4.3.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
 #[serde(rename_all = "snake_case")]
 pub enum SuggestionSource {
+    /// The account was manually recommended by the server's staff.
     Staff,
+    /// The client account has interacted with the suggested account.
     PastInteractions,
+    /// The account has many interactions or followers on the server.
     Global,
 }
 
+/// One of the reasons why an account is suggested.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionReason {
+    /// The account was manually recommended by the server's staff.
+    Featured,
+    /// The account has many followers on the server.
+    MostFollowed,
+    /// The account has had many interactions on the server recently.
+    MostInteractions,
+    /// Accounts the client account recently followed are followed by the
+    /// same people as this one.
+    SimilarToRecentlyFollowed,
+    /// The account is followed by accounts the client account follows.
+    FriendsOfFriends,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{form_round_trip_test, test::account_json};
+
+    #[test]
+    fn test_suggestion() {
+        let example = r#"{
+          "source": "past_interactions",
+          "sources": ["featured", "friends_of_friends"],
+          "account": {
+            "id": "1",
+            "username": "Gargron",
+            "acct": "Gargron",
+            "display_name": "Eugen",
+            "locked": false,
+            "bot": false,
+            "created_at": "2016-03-16T14:34:26.392Z",
+            "note": "",
+            "url": "https://mastodon.social/@Gargron",
+            "avatar": "https://files.mastodon.social/accounts/avatars/000/000/001/original/d96d39a0abb45b92.jpg",
+            "avatar_static": "https://files.mastodon.social/accounts/avatars/000/000/001/original/d96d39a0abb45b92.jpg",
+            "header": "https://files.mastodon.social/accounts/headers/000/000/001/original/c91b871f294ea63e.png",
+            "header_static": "https://files.mastodon.social/accounts/headers/000/000/001/original/c91b871f294ea63e.png",
+            "followers_count": 0,
+            "following_count": 0,
+            "statuses_count": 1,
+            "last_status_at": null
+          }
+        }"#;
+        let suggestion: Suggestion = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(suggestion.source, Some(SuggestionSource::PastInteractions));
+        assert_eq!(
+            suggestion.sources,
+            [
+                SuggestionReason::Featured,
+                SuggestionReason::FriendsOfFriends
+            ]
+        );
+        assert_eq!(suggestion.account.acct, "Gargron");
+    }
+
+    #[test]
+    fn test_origin() {
+        let account = |acct: &str| -> Account {
+            serde_json::from_value(account_json("1", acct)).expect("deserialize")
+        };
+        let local = account("Gargron");
+        assert_eq!(local.origin(), Origin::Local);
+        assert!(local.is_local());
+        let remote = account("Gargron@mastodon.social");
+        assert_eq!(
+            remote.origin(),
+            Origin::Remote {
+                domain: "mastodon.social"
+            }
+        );
+        assert!(!remote.is_local());
+    }
+
+    #[test]
+    fn test_moved() {
+        let account = |acct: &str, moved: Option<&str>| -> String {
+            let mut account = account_json("1", acct);
+            if let Some(moved) = moved {
+                account["moved"] = serde_json::from_str(moved).expect("parse");
+            }
+            account.to_string()
+        };
+        let newest = account("c@example.net", None);
+        let newer = account("b@example.org", Some(&newest));
+        let subject: Account =
+            serde_json::from_str(&account("a@example.com", Some(&newer))).expect("deserialize");
+        assert!(subject.has_moved());
+        assert_eq!(subject.moved_to().expect("moved").acct, "b@example.org");
+        assert_eq!(subject.current_account().acct, "c@example.net");
+        let current: Account = serde_json::from_str(&newest).expect("deserialize");
+        assert!(!current.has_moved());
+        assert_eq!(current.current_account().acct, "c@example.net");
+    }
 
     #[test]
     fn test_color_parse() {
@@ -668,4 +941,32 @@ mod tests {
         assert!(!subject.bot);
         assert_eq!(subject.followers_count, 547);
     }
+
+    form_round_trip_test!(
+        test_credentials_minimal(Credentials, serialize only): Credentials::default()
+    );
+    form_round_trip_test!(
+        test_credentials_full(Credentials, serialize only): CredentialsBuilder::default()
+            .display_name("Gargron")
+            .note("Founder")
+            .avatar("avatar.png")
+            .header("header.png")
+            .locked(Some(true))
+            .bot(Some(false))
+            .discoverable(Some(true))
+            .source(UpdateSourceBuilder::default()
+                .privacy(crate::visibility::Visibility::Private)
+                .sensitive(true)
+                .language(Language::Eng)
+                .build()
+                .unwrap())
+            .fields_attribute("Pronouns", "they/them")
+            .build()
+            .unwrap()
+    );
+    form_round_trip_test!(
+        test_update_source_minimal(UpdateSource, serialize only): UpdateSourceBuilder::default()
+            .build()
+            .unwrap()
+    );
 }
@@ -16,6 +16,9 @@ pub struct Tag {
     /// Whether the hashtag has not been reviewed yet to approve or deny its
     /// trending.
     pub requires_review: bool,
+    /// Whether the hashtag is shown in the directory and search results.
+    /// Only sent by Mastodon 4.4 and later.
+    pub listable: Option<bool>,
 }
 
 #[cfg(test)]
@@ -75,5 +78,6 @@ mod tests {
         assert!(tag.trendable);
         assert!(tag.usable);
         assert!(!tag.requires_review);
+        assert_eq!(tag.listable, None);
     }
 }
@@ -43,7 +43,8 @@ pub struct Announcement {
     pub reactions: Vec<Reaction>,
 }
 
-/// Represents an emoji reaction to an Announcement.
+/// Represents an emoji reaction to an Announcement, or to a status on servers
+/// which support reactions to statuses.
 ///
 /// See also [the API documentation](https://docs.joinmastodon.org/entities/Reaction/)
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -60,6 +61,20 @@ pub struct Reaction {
     pub static_url: Option<Url>,
 }
 
+/// The new count of a reaction to an announcement, as sent by the streaming
+/// API when someone adds or removes one.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/methods/streaming/#announcement.reaction)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReactionUpdate {
+    /// The emoji used for the reaction. Either a unicode emoji, or a custom emoji’s shortcode.
+    pub name: String,
+    /// The total number of users who have added this reaction.
+    pub count: i64,
+    /// The announcement which was reacted to.
+    pub announcement_id: AnnouncementId,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Account {
     /// The account ID of the mentioned user.
@@ -1,11 +1,19 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::VapidKey;
+use crate::{ApplicationId, VapidKey};
 
 /// Represents an application that interfaces with the REST API to access
 /// accounts or post statuses.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Application {
+    /// The ID of the application. Only returned when registering or verifying
+    /// the credentials of an application.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub id: Option<ApplicationId>,
     /// The name of your application.
     pub name: String,
     /// The website associated with your application
@@ -19,6 +27,60 @@ pub struct Application {
     pub client_secret: Option<String>,
 }
 
+impl Application {
+    /// Whether the given application, as attached to a status, is this one.
+    ///
+    /// Statuses only carry the name and website of the application which
+    /// posted them, so those are what is compared.
+    ///
+    /// ```
+    /// use mastodon_async_entities::prelude::*;
+    ///
+    /// let app: Application = serde_json::from_str(r#"{
+    ///     "name": "my-bot",
+    ///     "website": "https://bot.example.com",
+    ///     "vapid_key": "the-vapid-key"
+    /// }"#).unwrap();
+    /// let posted_by: status::Application = app.clone().into();
+    /// assert!(app.posted(&posted_by));
+    /// ```
+    pub fn posted(&self, app: &crate::status::Application) -> bool {
+        self.name == app.name && self.website == app.website
+    }
+}
+
+impl From<Application> for crate::status::Application {
+    fn from(value: Application) -> Self {
+        Self {
+            name: value.name,
+            website: value.website,
+        }
+    }
+}
+
+/// The ID is a string in the response to POST /api/v1/apps, but a number
+/// wherever else an application is referred to.
+fn deserialize_id<'de, D>(deserializer: D) -> Result<Option<ApplicationId>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Id {
+        Number(i64),
+        String(String),
+    }
+
+    match Option::<Id>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Id::Number(id)) => Ok(Some(ApplicationId::new(id))),
+        Some(Id::String(id)) => id
+            .parse::<i64>()
+            .map(|id| Some(ApplicationId::new(id)))
+            .map_err(serde::de::Error::custom),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -31,6 +93,33 @@ mod tests {
   "vapid_key": "BCk-QqERU0q-CfYZjcuB6lnyyOYfJ2AifKqfeGIm7Z-HiTU5T9eTG5GxVA0_OH5mMlI4UkkDTpaZwozy0TzdZ2M="
 }"#;
         let app: Application = serde_json::from_str(example).expect("deserialize");
-        assert_eq!(app.name, "test app")
+        assert_eq!(app.name, "test app");
+        assert!(app.id.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_registered() {
+        let example = r#"{
+  "id": "563419",
+  "name": "test app",
+  "website": "https://app.example.com",
+  "redirect_uri": "urn:ietf:wg:oauth:2.0:oob",
+  "client_id": "TWhM-tNSuncnqN7DBJmoyeLnk6K3iJJ71KKXxgL1hPM",
+  "client_secret": "ZEaFUFmF0umgBX1qKJDjaU99Q31lDkOU8NutzTOoliw",
+  "vapid_key": "BCk-QqERU0q-CfYZjcuB6lnyyOYfJ2AifKqfeGIm7Z-HiTU5T9eTG5GxVA0_OH5mMlI4UkkDTpaZwozy0TzdZ2M="
+}"#;
+        let app: Application = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(app.id, Some(ApplicationId::new(563419)));
+
+        let status_app = crate::status::Application {
+            name: "test app".to_string(),
+            website: Some("https://app.example.com".to_string()),
+        };
+        assert!(app.posted(&status_app));
+        let other = crate::status::Application {
+            name: "Web".to_string(),
+            website: None,
+        };
+        assert!(!app.posted(&other));
     }
 }
@@ -50,6 +50,16 @@ impl Attachment {
     pub fn is_done_processing(&self) -> bool {
         self.url.is_some()
     }
+
+    /// A placeholder for the media, decoded from its blurhash, `width`
+    /// pixels wide and `height` pixels high, to show until the media itself
+    /// has been downloaded. See [`blurhash_compat::decode`](crate::blurhash_compat::decode)
+    /// for the format of the pixels. Returns `None` if the attachment has no
+    /// valid blurhash.
+    #[cfg(feature = "blurhash")]
+    pub fn decode_blurhash(&self, width: u32, height: u32) -> Option<Vec<u8>> {
+        crate::blurhash_compat::decode(self.blurhash.as_deref()?, width, height)
+    }
 }
 
 /// Metadata about some attachment.
@@ -97,7 +107,9 @@ pub enum MediaType {
     Video,
     /// Looping, soundless animation
     Gifv,
-    /// Unknown format.
+    /// Unknown format, or a type this version of the crate doesn't know
+    /// about.
+    #[cfg_attr(not(feature = "strict"), serde(other))]
     Unknown,
 }
 
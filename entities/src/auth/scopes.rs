@@ -166,6 +166,56 @@ impl Scopes {
         Scopes::new(Scope::Push)
     }
 
+    /// Represents the full "admin:read" scope, for reading through the admin
+    /// API
+    ///
+    /// ```
+    /// use mastodon_async_entities::prelude::*;
+    ///
+    /// let scope = Scopes::admin_read_all();
+    /// assert_eq!(&format!("{}", scope), "admin:read");
+    /// ```
+    pub fn admin_read_all() -> Scopes {
+        Scopes::new(Scope::AdminRead(None))
+    }
+
+    /// Represents a specific "admin:read:___" scope
+    ///
+    /// ```
+    /// use mastodon_async_entities::auth::scopes::{Admin, Scopes};
+    ///
+    /// let scope = Scopes::admin_read(Admin::Reports);
+    /// assert_eq!(&format!("{}", scope), "admin:read:reports");
+    /// ```
+    pub fn admin_read(subscope: Admin) -> Scopes {
+        Scopes::new(Scope::AdminRead(Some(subscope)))
+    }
+
+    /// Represents the full "admin:write" scope, for moderating through the
+    /// admin API
+    ///
+    /// ```
+    /// use mastodon_async_entities::prelude::*;
+    ///
+    /// let scope = Scopes::admin_write_all();
+    /// assert_eq!(&format!("{}", scope), "admin:write");
+    /// ```
+    pub fn admin_write_all() -> Scopes {
+        Scopes::new(Scope::AdminWrite(None))
+    }
+
+    /// Represents a specific "admin:write:___" scope
+    ///
+    /// ```
+    /// use mastodon_async_entities::auth::scopes::{Admin, Scopes};
+    ///
+    /// let scope = Scopes::admin_write(Admin::Accounts);
+    /// assert_eq!(&format!("{}", scope), "admin:write:accounts");
+    /// ```
+    pub fn admin_write(subscope: Admin) -> Scopes {
+        Scopes::new(Scope::AdminWrite(Some(subscope)))
+    }
+
     /// Combines 2 scopes together
     ///
     /// // Example
@@ -214,6 +264,8 @@ impl PartialEq for Scopes {
     }
 }
 
+impl Eq for Scopes {}
+
 impl Default for Scopes {
     fn default() -> Scopes {
         Scopes::read_all()
@@ -270,6 +322,12 @@ pub enum Scope {
     Follow,
     /// Push permissions
     Push,
+    /// Read only permissions for the admin API.
+    #[serde(rename = "admin:read")]
+    AdminRead(Option<Admin>),
+    /// Write permissions for the admin API.
+    #[serde(rename = "admin:write")]
+    AdminWrite(Option<Admin>),
 }
 
 impl FromStr for Scope {
@@ -281,6 +339,16 @@ impl FromStr for Scope {
             "write" => Scope::Write(None),
             "follow" => Scope::Follow,
             "push" => Scope::Push,
+            "admin:read" => Scope::AdminRead(None),
+            "admin:write" => Scope::AdminWrite(None),
+            read if read.starts_with("admin:read:") => {
+                let a: Admin = Admin::from_str(&read[11..])?;
+                Scope::AdminRead(Some(a))
+            }
+            write if write.starts_with("admin:write:") => {
+                let a: Admin = Admin::from_str(&write[12..])?;
+                Scope::AdminWrite(Some(a))
+            }
             read if read.starts_with("read:") => {
                 let r: Read = Read::from_str(&read[5..])?;
                 Scope::Read(Some(r))
@@ -303,31 +371,26 @@ impl PartialOrd for Scope {
 impl Ord for Scope {
     fn cmp(&self, other: &Scope) -> Ordering {
         match (*self, *other) {
-            (Scope::Read(None), Scope::Read(None)) => Ordering::Equal,
-            (Scope::Read(None), Scope::Read(Some(..))) => Ordering::Less,
-            (Scope::Read(Some(..)), Scope::Read(None)) => Ordering::Greater,
-            (Scope::Read(Some(ref a)), Scope::Read(Some(ref b))) => a.cmp(b),
-
-            (Scope::Write(None), Scope::Write(None)) => Ordering::Equal,
-            (Scope::Write(None), Scope::Write(Some(..))) => Ordering::Less,
-            (Scope::Write(Some(..)), Scope::Write(None)) => Ordering::Greater,
-            (Scope::Write(Some(ref a)), Scope::Write(Some(ref b))) => a.cmp(b),
-
-            (Scope::Read(..), Scope::Write(..)) => Ordering::Less,
-            (Scope::Read(..), Scope::Follow) => Ordering::Less,
-            (Scope::Read(..), Scope::Push) => Ordering::Less,
-
-            (Scope::Write(..), Scope::Read(..)) => Ordering::Greater,
-            (Scope::Write(..), Scope::Follow) => Ordering::Less,
-            (Scope::Write(..), Scope::Push) => Ordering::Less,
-
-            (Scope::Follow, Scope::Read(..)) => Ordering::Greater,
-            (Scope::Follow, Scope::Write(..)) => Ordering::Greater,
-            (Scope::Follow, Scope::Follow) => Ordering::Equal,
-            (Scope::Follow, Scope::Push) => Ordering::Less,
-
-            (Scope::Push, Scope::Push) => Ordering::Equal,
-            (Scope::Push, _) => Ordering::Greater,
+            (Scope::Read(ref a), Scope::Read(ref b)) => a.cmp(b),
+            (Scope::Write(ref a), Scope::Write(ref b)) => a.cmp(b),
+            (Scope::AdminRead(ref a), Scope::AdminRead(ref b)) => a.cmp(b),
+            (Scope::AdminWrite(ref a), Scope::AdminWrite(ref b)) => a.cmp(b),
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
+    }
+}
+
+impl Scope {
+    /// Where scopes of this kind sort among the others; the broad scope of
+    /// each kind sorts before its narrower ones.
+    fn rank(&self) -> u8 {
+        match self {
+            Scope::Read(_) => 0,
+            Scope::Write(_) => 1,
+            Scope::Follow => 2,
+            Scope::Push => 3,
+            Scope::AdminRead(_) => 4,
+            Scope::AdminWrite(_) => 5,
         }
     }
 }
@@ -342,6 +405,10 @@ impl fmt::Display for Scope {
             Write(None) => "write",
             Follow => "follow",
             Push => "push",
+            AdminRead(Some(ref a)) => return write!(f, "admin:read:{a}"),
+            AdminRead(None) => "admin:read",
+            AdminWrite(Some(ref a)) => return write!(f, "admin:write:{a}"),
+            AdminWrite(None) => "admin:write",
         };
         write!(f, "{}", s)
     }
@@ -523,6 +590,78 @@ impl fmt::Display for Write {
     }
 }
 
+/// Represents the granular "admin:read:___" and "admin:write:___" oauth
+/// scopes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum Admin {
+    /// Accounts
+    Accounts,
+    /// Canonical email blocks
+    CanonicalEmailBlocks,
+    /// Domain allows
+    DomainAllows,
+    /// Domain blocks
+    DomainBlocks,
+    /// Email domain blocks
+    EmailDomainBlocks,
+    /// IP blocks
+    IpBlocks,
+    /// Reports
+    Reports,
+}
+
+impl FromStr for Admin {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Admin, Self::Err> {
+        Ok(match s {
+            "accounts" => Admin::Accounts,
+            "canonical_email_blocks" => Admin::CanonicalEmailBlocks,
+            "domain_allows" => Admin::DomainAllows,
+            "domain_blocks" => Admin::DomainBlocks,
+            "email_domain_blocks" => Admin::EmailDomainBlocks,
+            "ip_blocks" => Admin::IpBlocks,
+            "reports" => Admin::Reports,
+            _ => return Err(Error::UnknownScope(s.to_owned())),
+        })
+    }
+}
+
+impl PartialOrd for Admin {
+    fn partial_cmp(&self, other: &Admin) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Admin {
+    fn cmp(&self, other: &Admin) -> Ordering {
+        let a = format!("{:?}", self);
+        let b = format!("{:?}", other);
+        a.cmp(&b)
+    }
+}
+
+/// Only the part after "admin:read:" or "admin:write:", which depends on
+/// the [`Scope`] it belongs to.
+impl fmt::Display for Admin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match *self {
+                Admin::Accounts => "accounts",
+                Admin::CanonicalEmailBlocks => "canonical_email_blocks",
+                Admin::DomainAllows => "domain_allows",
+                Admin::DomainBlocks => "domain_blocks",
+                Admin::EmailDomainBlocks => "email_domain_blocks",
+                Admin::IpBlocks => "ip_blocks",
+                Admin::Reports => "reports",
+            }
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -758,6 +897,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_admin_scopes() {
+        let tests = [
+            ("admin:read", Scope::AdminRead(None)),
+            (
+                "admin:read:accounts",
+                Scope::AdminRead(Some(Admin::Accounts)),
+            ),
+            (
+                "admin:read:canonical_email_blocks",
+                Scope::AdminRead(Some(Admin::CanonicalEmailBlocks)),
+            ),
+            ("admin:write", Scope::AdminWrite(None)),
+            (
+                "admin:write:domain_allows",
+                Scope::AdminWrite(Some(Admin::DomainAllows)),
+            ),
+            (
+                "admin:write:ip_blocks",
+                Scope::AdminWrite(Some(Admin::IpBlocks)),
+            ),
+        ];
+        for (source, expected) in &tests {
+            let result =
+                Scope::from_str(source).unwrap_or_else(|_| panic!("Couldn't parse '{}'", &source));
+            assert_eq!(result, *expected);
+            assert_eq!(&result.to_string(), source);
+        }
+        assert!(Scope::from_str("admin:read:statuses").is_err());
+
+        let scopes = Scopes::admin_write(Admin::Reports)
+            | Scopes::read_all()
+            | Scopes::admin_read_all()
+            | Scopes::push();
+        assert_eq!(
+            scopes.to_string(),
+            "read push admin:read admin:write:reports"
+        );
+    }
+
     #[test]
     fn test_scopes_str_round_trip() {
         let original = "read write follow push";
@@ -16,6 +16,22 @@ pub struct Token {
     /// When the token was generated.
     #[serde(with = "timestamp")]
     pub created_at: OffsetDateTime,
+    /// How many seconds after `created_at` the token expires, if it does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in: Option<u64>,
+    /// A token which can be exchanged for a new access token once this one
+    /// expires, if the server issued one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+}
+
+impl Token {
+    /// When the token expires, if it does.
+    pub fn expires_at(&self) -> Option<OffsetDateTime> {
+        let expires_in = i64::try_from(self.expires_in?).ok()?;
+        self.created_at
+            .checked_add(time::Duration::seconds(expires_in))
+    }
 }
 
 #[cfg(test)]
@@ -43,5 +59,28 @@ mod tests {
         assert!(subject.scope.contains(&Scope::Follow));
         assert!(subject.scope.contains(&Scope::Push));
         assert_eq!(subject.created_at.unix_timestamp(), 1573979017);
+        assert!(subject.expires_at().is_none());
+        assert!(subject.refresh_token.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_expiring() {
+        let example = r#"{
+          "access_token": "ZA-Yj3aBD8U8Cm7lKUp-lm9O9BmDgdhHzDeqsY8tlL0",
+          "token_type": "Bearer",
+          "scope": "read",
+          "created_at": 1573979017,
+          "expires_in": 3600,
+          "refresh_token": "x8JcVYPcI7Z6NQ2dWkTnBmwVNsC4Ko4bJxkW0s3tZ8Q"
+        }"#;
+        let subject: Token = serde_json::from_str(example).unwrap();
+        assert_eq!(
+            subject.expires_at().map(OffsetDateTime::unix_timestamp),
+            Some(1573979017 + 3600)
+        );
+        assert_eq!(
+            subject.refresh_token.as_deref(),
+            Some("x8JcVYPcI7Z6NQ2dWkTnBmwVNsC4Ko4bJxkW0s3tZ8Q")
+        );
     }
 }
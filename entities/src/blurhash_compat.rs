@@ -0,0 +1,17 @@
+/// Decode a [BlurHash](https://github.com/woltapp/blurhash) into a
+/// placeholder image `width` pixels wide and `height` pixels high, as RGBA,
+/// four bytes per pixel, row by row. Returns `None` if the hash isn't valid.
+///
+/// Blurhashes only hold a few colours, so a small image, scaled up for
+/// display, looks the same as a large one and is much quicker to decode.
+///
+/// ```
+/// use mastodon_async_entities::blurhash_compat::decode;
+///
+/// let pixels = decode("LEHV6nWB2yk8pyo0adR*.7kCMdnj", 32, 24).unwrap();
+/// assert_eq!(pixels.len(), 32 * 24 * 4);
+/// assert!(decode("not a blurhash", 32, 24).is_none());
+/// ```
+pub fn decode(hash: &str, width: u32, height: u32) -> Option<Vec<u8>> {
+    blurhash::decode(hash, width, height, 1.0).ok()
+}
@@ -46,6 +46,16 @@ pub struct Card {
     pub blurhash: Option<String>,
 }
 
+#[cfg(feature = "blurhash")]
+impl Card {
+    /// A placeholder for the preview image, decoded from its blurhash,
+    /// `width` pixels wide and `height` pixels high. See
+    /// [`Attachment::decode_blurhash`](crate::attachment::Attachment::decode_blurhash).
+    pub fn decode_blurhash(&self, width: u32, height: u32) -> Option<Vec<u8>> {
+        crate::blurhash_compat::decode(self.blurhash.as_deref()?, width, height)
+    }
+}
+
 /// The type of the preview card.
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
 #[serde(rename_all = "lowercase")]
@@ -58,6 +68,9 @@ pub enum Type {
     Video,
     /// iframe OEmbed. Not currently accepted, so won’t show up in practice.
     Rich,
+    /// A type this version of the crate doesn't know about.
+    #[cfg_attr(not(feature = "strict"), serde(other))]
+    Unknown,
 }
 
 /// A preview card which holds a trending link
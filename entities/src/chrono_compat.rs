@@ -0,0 +1,75 @@
+use chrono::{DateTime, TimeZone, Utc};
+use time::{error::ComponentRange, OffsetDateTime};
+
+/// Conversion of the timestamps on entities into [`chrono`] types.
+///
+/// ```
+/// use mastodon_async_entities::chrono_compat::ToChrono;
+/// use time::OffsetDateTime;
+///
+/// let created_at = OffsetDateTime::from_unix_timestamp(1_668_579_300).unwrap();
+/// assert_eq!(created_at.to_chrono().timestamp(), 1_668_579_300);
+/// let edited_at: Option<OffsetDateTime> = None;
+/// assert!(edited_at.to_chrono().is_none());
+/// ```
+pub trait ToChrono {
+    /// The equivalent chrono type.
+    type Output;
+
+    /// Convert to the equivalent chrono type, in UTC.
+    fn to_chrono(&self) -> Self::Output;
+}
+
+impl ToChrono for OffsetDateTime {
+    type Output = DateTime<Utc>;
+
+    fn to_chrono(&self) -> DateTime<Utc> {
+        // every OffsetDateTime is within the range chrono supports.
+        DateTime::from_timestamp(self.unix_timestamp(), self.nanosecond())
+            .expect("OffsetDateTime out of range for chrono")
+    }
+}
+
+impl ToChrono for Option<OffsetDateTime> {
+    type Output = Option<DateTime<Utc>>;
+
+    fn to_chrono(&self) -> Option<DateTime<Utc>> {
+        self.as_ref().map(ToChrono::to_chrono)
+    }
+}
+
+/// Convert a chrono date-time into the [`OffsetDateTime`] used by the
+/// entities, e.g. to fill in a form.
+///
+/// Fails if the date is outside the range supported by [`time`].
+pub fn from_chrono<Tz: TimeZone>(value: &DateTime<Tz>) -> Result<OffsetDateTime, ComponentRange> {
+    let value = value.with_timezone(&Utc);
+    Ok(OffsetDateTime::from_unix_timestamp(value.timestamp())?
+        + time::Duration::nanoseconds(value.timestamp_subsec_nanos().into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::FixedOffset;
+    use time::format_description::well_known::Iso8601;
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let time = OffsetDateTime::parse("2019-11-24T19:39:39.337Z", &Iso8601::PARSING)
+            .expect("parse time");
+        let chrono = time.to_chrono();
+        assert_eq!(chrono.to_rfc3339(), "2019-11-24T19:39:39.337+00:00");
+        assert_eq!(from_chrono(&chrono).expect("convert back"), time);
+
+        let offset = chrono.with_timezone(&FixedOffset::east_opt(3600).expect("offset"));
+        assert_eq!(from_chrono(&offset).expect("convert offset"), time);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let far_future = DateTime::<Utc>::MAX_UTC;
+        assert!(from_chrono(&far_future).is_err());
+    }
+}
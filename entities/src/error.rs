@@ -7,6 +7,21 @@ pub enum Error {
     VisibilityParsingError { invalid: String },
     #[error("unknown scope {0}")]
     UnknownScope(String),
-    #[error(transparent)]
+    #[error("the required field wasn't set before calling build(): {0}")]
     Builder(#[from] derive_builder::UninitializedFieldError),
+    #[error("the status is {count} characters long, more than the {max} the server allows")]
+    StatusTooLong { count: usize, max: usize },
+    #[error("the status has {count} media attachments, more than the {max} the server allows")]
+    TooManyMedia { count: usize, max: usize },
+}
+
+impl Error {
+    /// The name of the field which wasn't set, if a builder failed because a
+    /// required field was missing.
+    pub fn missing_field(&self) -> Option<&'static str> {
+        match self {
+            Error::Builder(err) => Some(err.field_name()),
+            _ => None,
+        }
+    }
 }
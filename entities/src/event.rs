@@ -1,4 +1,10 @@
-use crate::{notification::Notification, status::Status};
+use crate::{
+    announcement::{Announcement, ReactionUpdate},
+    conversation::Conversation,
+    notification::Notification,
+    status::Status,
+    AnnouncementId,
+};
 use derive_is_enum_variant::is_enum_variant;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +17,33 @@ pub enum Event {
     Notification(Notification),
     /// Delete event
     Delete(String),
+    /// A status was edited. Contains the status as it is after the edit.
+    StatusUpdate(Status),
     /// FiltersChanged event
     FiltersChanged,
+    /// A direct conversation was started or updated.
+    Conversation(Conversation),
+    /// An announcement was published or edited.
+    Announcement(Announcement),
+    /// Someone added or removed a reaction to an announcement.
+    AnnouncementReaction(ReactionUpdate),
+    /// An announcement was deleted.
+    AnnouncementDelete(AnnouncementId),
+    /// The connection to the streaming API was lost and has been
+    /// re-established. Events which occurred while disconnected may have been
+    /// missed. This is never sent by the server, only by clients which
+    /// reconnect automatically.
+    ConnectionReset,
+    /// The server sent a keepalive comment, showing that the connection is
+    /// still alive even though nothing has happened. Mastodon sends one
+    /// every few seconds.
+    Heartbeat,
+    /// An event this version of the crate doesn't recognise, such as
+    /// `encrypted_message`, passed through instead of ending the stream.
+    Unknown {
+        /// The name of the event.
+        event: String,
+        /// The payload of the event, unparsed.
+        payload: String,
+    },
 }
@@ -0,0 +1,215 @@
+use derive_builder::Builder;
+use isolang::Language;
+use serde::{Deserialize, Serialize};
+use time::Duration;
+
+use crate::helpers::serde_opt_duration_as_seconds;
+
+/// Options for searching for accounts.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let search = forms::account::Search::builder("gargron")
+///     .limit(5u64)
+///     .resolve(true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(serde_json::to_string_pretty(&search).unwrap(), r#"{
+///   "q": "gargron",
+///   "limit": 5,
+///   "resolve": true
+/// }"#);
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/accounts/#search)
+#[derive(Builder, Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Search {
+    /// What to search for.
+    #[builder(setter(custom))]
+    q: String,
+    /// Maximum number of results to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    limit: Option<u64>,
+    /// Skip this many results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    offset: Option<u64>,
+    /// Look up remote accounts with WebFinger when `q` is an exact address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    resolve: Option<bool>,
+    /// Only return accounts the client account is following.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    following: Option<bool>,
+}
+
+impl Search {
+    /// Start building a search for the given text.
+    pub fn builder(q: impl Into<String>) -> SearchBuilder {
+        SearchBuilder {
+            q: Some(q.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Options for muting an account.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+/// use time::ext::NumericalDuration;
+///
+/// let options = forms::account::MuteOptions::builder()
+///     .notifications(false)
+///     .duration(1.hours())
+///     .build()
+///     .unwrap();
+/// assert_eq!(serde_json::to_string_pretty(&options).unwrap(), r#"{
+///   "notifications": false,
+///   "duration": 3600
+/// }"#);
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/accounts/#mute)
+#[derive(Builder, Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct MuteOptions {
+    /// Whether to mute notifications from the account as well as its
+    /// statuses. The server defaults to `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    notifications: Option<bool>,
+    /// How long the mute should last. Without one, or with a duration of
+    /// zero, the mute lasts until the account is unmuted.
+    #[serde(
+        with = "serde_opt_duration_as_seconds",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    #[builder(default, setter(into, strip_option))]
+    duration: Option<Duration>,
+}
+
+impl MuteOptions {
+    /// Get a builder for mute options.
+    pub fn builder() -> MuteOptionsBuilder {
+        Default::default()
+    }
+}
+
+/// Options for following an account, or for changing how an account which
+/// is already followed is followed. Only the options which are set are sent,
+/// and the server leaves the others as they were.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+/// use isolang::Language;
+///
+/// let options = forms::account::FollowOptions::builder()
+///     .reblogs(false)
+///     .add_language(Language::Eng)
+///     .add_language(Language::Deu)
+///     .build()
+///     .unwrap();
+/// assert_eq!(serde_json::to_string_pretty(&options).unwrap(), r#"{
+///   "reblogs": false,
+///   "languages": [
+///     "eng",
+///     "deu"
+///   ]
+/// }"#);
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/accounts/#follow)
+#[derive(Builder, Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct FollowOptions {
+    /// Whether to show the account's boosts in the home timeline. The server
+    /// defaults to `true` for a new follow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    reblogs: Option<bool>,
+    /// Whether to be notified when the account posts. The server defaults to
+    /// `false` for a new follow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    notify: Option<bool>,
+    /// Only show statuses, and boosts, in these languages in the home
+    /// timeline. An empty list shows all languages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    languages: Option<Vec<Language>>,
+}
+
+impl FollowOptions {
+    /// Get a builder for follow options.
+    pub fn builder() -> FollowOptionsBuilder {
+        Default::default()
+    }
+
+    /// Whether to show the account's boosts, if set.
+    pub fn reblogs(&self) -> Option<bool> {
+        self.reblogs
+    }
+
+    /// Whether to be notified when the account posts, if set.
+    pub fn notify(&self) -> Option<bool> {
+        self.notify
+    }
+
+    /// The languages to show statuses in, if set.
+    pub fn languages(&self) -> Option<&[Language]> {
+        self.languages.as_deref()
+    }
+
+    /// Whether no option is set, so that sending these options would
+    /// change nothing about an existing follow.
+    pub fn is_empty(&self) -> bool {
+        self.reblogs.is_none() && self.notify.is_none() && self.languages.is_none()
+    }
+}
+
+impl FollowOptionsBuilder {
+    /// Add a language to show statuses in.
+    pub fn add_language(&mut self, language: Language) -> &mut Self {
+        self.languages
+            .get_or_insert(None)
+            .get_or_insert_with(Vec::new)
+            .push(language);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_round_trip_test;
+
+    form_round_trip_test!(test_minimal(Search): Search::builder("gargron").build().unwrap());
+    form_round_trip_test!(test_full(Search): Search::builder("gargron")
+        .limit(5u64)
+        .offset(10u64)
+        .resolve(true)
+        .following(false)
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_mute_minimal(MuteOptions): MuteOptions::builder().build().unwrap());
+    form_round_trip_test!(test_mute_full(MuteOptions): MuteOptions::builder()
+        .notifications(true)
+        .duration(Duration::minutes(5))
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_follow_minimal(FollowOptions): FollowOptions::builder()
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_follow_full(FollowOptions): FollowOptions::builder()
+        .reblogs(true)
+        .notify(true)
+        .add_language(Language::Fra)
+        .build()
+        .unwrap());
+}
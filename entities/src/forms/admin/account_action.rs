@@ -0,0 +1,89 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::ReportId;
+
+/// Form for moderating an account, optionally to resolve a report about it.
+///
+/// ```
+/// use mastodon_async_entities::{forms::admin::account_action::Type, prelude::*};
+///
+/// let action = forms::admin::account_action::Action::builder(Type::Sensitive)
+///     .report_id(ReportId::new("42"))
+///     .text("Please mark your gore sensitive.")
+///     .build()
+///     .unwrap();
+/// assert_eq!(serde_json::to_string_pretty(&action).unwrap(), r#"{
+///   "type": "sensitive",
+///   "report_id": "42",
+///   "text": "Please mark your gore sensitive."
+/// }"#);
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/accounts/#action)
+#[derive(Builder, Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Action {
+    /// What to do to the account.
+    #[serde(rename = "type")]
+    #[builder(setter(custom))]
+    action_type: Type,
+    /// The report which this action resolves.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    report_id: Option<ReportId>,
+    /// The ID of a preset warning to send to the account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    warning_preset_id: Option<String>,
+    /// A message to send to the account along with the warning.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    text: Option<String>,
+    /// Whether to email the account's owner about the action. The server
+    /// sends one unless this is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    send_email_notification: Option<bool>,
+}
+
+impl Action {
+    /// Start building an action of the given type.
+    pub fn builder(action_type: Type) -> ActionBuilder {
+        ActionBuilder {
+            action_type: Some(action_type),
+            ..Default::default()
+        }
+    }
+}
+
+/// What to do to a moderated account.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Type {
+    /// Only resolve the report, or send a warning.
+    None,
+    /// Mark all of the account's media as sensitive.
+    Sensitive,
+    /// Prevent the account's owner from logging in.
+    Disable,
+    /// Limit the account's visibility to those who follow it.
+    Silence,
+    /// Suspend the account, removing its content.
+    Suspend,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_round_trip_test;
+
+    form_round_trip_test!(test_minimal(Action): Action::builder(Type::None).build().unwrap());
+    form_round_trip_test!(test_full(Action): Action::builder(Type::Suspend)
+        .report_id(ReportId::new("42"))
+        .warning_preset_id("1")
+        .text("Goodbye.")
+        .send_email_notification(false)
+        .build()
+        .unwrap());
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// Form for blocking sign-ups from an email address, or from any address
+/// which canonicalizes to the same one.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let block = forms::admin::canonical_email_block::Add::Email("spam@example.com".to_string());
+/// assert_eq!(
+///     serde_json::to_string(&block).unwrap(),
+///     r#"{"email":"spam@example.com"}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/canonical_email_blocks/#create)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Add {
+    /// The email address to canonicalize, hash and block.
+    Email(String),
+    /// The SHA256 hash of an already canonicalized email address.
+    CanonicalEmailHash(String),
+}
+
+/// Form for checking which canonical email blocks match an email address.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let test = forms::admin::canonical_email_block::Test::new("spam@example.com");
+/// assert_eq!(
+///     serde_json::to_string(&test).unwrap(),
+///     r#"{"email":"spam@example.com"}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/canonical_email_blocks/#test)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Test {
+    /// The email address to canonicalize and check.
+    pub email: String,
+}
+
+impl Test {
+    /// Check the given email address.
+    pub fn new(email: impl Into<String>) -> Self {
+        Self {
+            email: email.into(),
+        }
+    }
+}
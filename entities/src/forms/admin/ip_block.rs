@@ -0,0 +1,133 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use time::Duration;
+
+use crate::{admin::ip_block::Severity, helpers::serde_opt_duration_as_seconds};
+
+/// Form for blocking an IP address range.
+///
+/// ```
+/// use mastodon_async_entities::{admin::ip_block::Severity, prelude::*};
+/// use time::ext::NumericalDuration;
+///
+/// let block = forms::admin::ip_block::Add::builder("192.0.2.0/24", Severity::SignUpBlock)
+///     .comment("spam signups")
+///     .expires_in(1.days())
+///     .build()
+///     .unwrap();
+/// assert_eq!(serde_json::to_string_pretty(&block).unwrap(), r#"{
+///   "ip": "192.0.2.0/24",
+///   "severity": "sign_up_block",
+///   "comment": "spam signups",
+///   "expires_in": 86400
+/// }"#);
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/ip_blocks/#create)
+#[derive(Builder, Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Add {
+    /// The IP address and prefix to block.
+    #[builder(setter(custom))]
+    ip: String,
+    /// The policy to apply to this IP range.
+    #[builder(setter(custom))]
+    severity: Severity,
+    /// The reason for this IP block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    comment: Option<String>,
+    /// How long from now the block should expire.
+    #[serde(
+        with = "serde_opt_duration_as_seconds",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    #[builder(default, setter(into, strip_option))]
+    expires_in: Option<Duration>,
+}
+
+impl Add {
+    /// Start building a block of the given IP range with the given policy.
+    pub fn builder(ip: impl Into<String>, severity: Severity) -> AddBuilder {
+        AddBuilder {
+            ip: Some(ip.into()),
+            severity: Some(severity),
+            ..Default::default()
+        }
+    }
+}
+
+/// Form for changing an existing IP block. Only the fields which are set are
+/// changed.
+///
+/// ```
+/// use mastodon_async_entities::{admin::ip_block::Severity, prelude::*};
+///
+/// let update = forms::admin::ip_block::Update::builder()
+///     .severity(Severity::NoAccess)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&update).unwrap(),
+///     r#"{"severity":"no_access"}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/ip_blocks/#update)
+#[derive(Builder, Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Update {
+    /// The IP address and prefix to block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    ip: Option<String>,
+    /// The policy to apply to this IP range.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    severity: Option<Severity>,
+    /// The reason for this IP block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    comment: Option<String>,
+    /// How long from now the block should expire.
+    #[serde(
+        with = "serde_opt_duration_as_seconds",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    #[builder(default, setter(into, strip_option))]
+    expires_in: Option<Duration>,
+}
+
+impl Update {
+    /// Start building a change to an IP block.
+    pub fn builder() -> UpdateBuilder {
+        UpdateBuilder::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::ext::NumericalDuration;
+
+    use super::*;
+    use crate::form_round_trip_test;
+
+    form_round_trip_test!(test_add_minimal(Add): Add::builder("192.0.2.0/24", Severity::NoAccess)
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_add(Add): Add::builder("192.0.2.0/24", Severity::SignUpBlock)
+        .comment("spam signups")
+        .expires_in(1.days())
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_update_minimal(Update): Update::builder().build().unwrap());
+    form_round_trip_test!(test_update(Update): Update::builder()
+        .ip("192.0.2.0/24")
+        .severity(Severity::SignUpRequiresApproval)
+        .comment("spam signups")
+        .expires_in(1.days())
+        .build()
+        .unwrap());
+}
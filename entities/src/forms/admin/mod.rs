@@ -0,0 +1,8 @@
+/// Forms for moderating accounts
+pub mod account_action;
+/// Forms for blocking canonicalized email addresses
+pub mod canonical_email_block;
+/// Forms for blocking IP ranges
+pub mod ip_block;
+/// Forms for managing hashtags
+pub mod tag;
@@ -0,0 +1,64 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+/// Form for changing how a hashtag is treated. Only the fields which are set
+/// are changed.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let update = forms::admin::tag::Update::builder()
+///     .trendable(false)
+///     .listable(false)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&update).unwrap(),
+///     r#"{"trendable":false,"listable":false}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/tags/#update)
+#[derive(Builder, Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Update {
+    /// The name to show the hashtag with, which may only differ from its
+    /// name in letter case.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    display_name: Option<String>,
+    /// Whether the hashtag is linked in statuses and can be followed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    usable: Option<bool>,
+    /// Whether the hashtag is allowed to trend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    trendable: Option<bool>,
+    /// Whether the hashtag is shown in the directory and search results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    listable: Option<bool>,
+}
+
+impl Update {
+    /// Start building a change to a hashtag.
+    pub fn builder() -> UpdateBuilder {
+        UpdateBuilder::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_round_trip_test;
+
+    form_round_trip_test!(test_update_minimal(Update): Update::builder().build().unwrap());
+    form_round_trip_test!(test_update(Update): Update::builder()
+        .display_name("CatURDay")
+        .usable(true)
+        .trendable(false)
+        .listable(true)
+        .build()
+        .unwrap());
+}
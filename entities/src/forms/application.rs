@@ -66,6 +66,7 @@ impl TryFrom<ApplicationBuilder> for Application {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::form_round_trip_test;
 
     #[test]
     fn test_app_builder() {
@@ -73,6 +74,13 @@ mod tests {
         assert_eq!(builder, ApplicationBuilder::default());
     }
 
+    #[test]
+    fn test_app_builder_missing_name() {
+        let err = Application::builder().build().unwrap_err();
+        assert_eq!(err.missing_field(), Some("client_name"));
+        assert!(err.to_string().contains("client_name"));
+    }
+
     #[test]
     fn test_app_scopes() {
         let mut builder = Application::builder();
@@ -149,4 +157,16 @@ mod tests {
             .expect("Couldn't make ApplicationBuilder into App");
         assert_eq!(expected, result);
     }
+
+    form_round_trip_test!(test_app_minimal(Application, serialize only): Application::builder()
+        .client_name("test")
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_app_full(Application, serialize only): Application::builder()
+        .client_name("test")
+        .redirect_uris("https://example.com/callback")
+        .scopes(auth::Scopes::all())
+        .website("https://example.com")
+        .build()
+        .unwrap());
 }
@@ -63,7 +63,7 @@ pub struct Add {
     #[builder(default, setter(into, strip_option))]
     expires_in: Option<Duration>,
     /// A list of keywords to be added to the newly-created filter
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     #[builder(default, setter(into, strip_option))]
     keywords_attributes: Vec<add::Keyword>,
 }
@@ -197,7 +197,7 @@ pub struct Update {
     #[builder(default, setter(strip_option, into))]
     title: Option<String>,
     /// Where the filter should be applied. Specify at least one.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     #[builder(default, setter(into))]
     context: Vec<filter::Context>,
     /// The policy to be applied when the filter is matched.
@@ -213,7 +213,7 @@ pub struct Update {
     #[builder(default, setter(strip_option, into))]
     expires_in: Option<Duration>,
     /// A list of keywords to be added to the newly-created filter
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     #[builder(default, setter(into))]
     keywords_attributes: Vec<update::Keyword>,
 }
@@ -261,7 +261,7 @@ pub mod update {
         #[builder(default, setter(strip_option, into))]
         id: Option<String>,
         /// If true, will remove the keyword with the given ID.
-        #[serde(skip_serializing_if = "is_false")]
+        #[serde(skip_serializing_if = "is_false", default)]
         #[builder(default)]
         destroy: bool,
     }
@@ -283,3 +283,34 @@ impl Status {
         Self { status_id }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use time::ext::NumericalDuration;
+
+    use super::*;
+    use crate::form_round_trip_test;
+
+    form_round_trip_test!(test_add_minimal(Add): Add::builder("test")
+        .add_context(filter::Context::Home)
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_add(Add): Add::builder("test")
+        .add_context(filter::Context::Home)
+        .add_context(filter::Context::Thread)
+        .filter_action(filter::Action::Warn)
+        .expires_in(60.seconds())
+        .keyword(add::Keyword::whole_word("test"))
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_update_minimal(Update): Update::builder().build().unwrap());
+    form_round_trip_test!(test_update(Update): Update::builder()
+        .title("test")
+        .add_context(filter::Context::Public)
+        .filter_action(filter::Action::Hide)
+        .expires_in(60.seconds())
+        .keyword(update::Keyword::builder().id("1").destroy(true).build().unwrap())
+        .keyword(update::Keyword::builder().keyword("test").whole_word(true).build().unwrap())
+        .build()
+        .unwrap());
+}
@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use derive_builder::Builder;
+
+use crate::attachment::FocalPoint;
+
+/// Form for uploading media, to attach to a status.
+///
+/// ```
+/// use mastodon_async_entities::{attachment::FocalPoint, prelude::*};
+///
+/// let upload = forms::media::Upload::builder("cat.mp4")
+///     .thumbnail("cat.png")
+///     .description("A cat chasing a laser pointer")
+///     .focus(FocalPoint { x: 0.0, y: 0.25 })
+///     .build()
+///     .unwrap();
+/// assert_eq!(upload.file.to_str(), Some("cat.mp4"));
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/media/#v2)
+#[derive(Builder, Debug, Clone, PartialEq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Upload {
+    /// The file to upload.
+    #[builder(setter(custom))]
+    pub file: PathBuf,
+    /// An image to use as the thumbnail of a video or audio file.
+    #[builder(default, setter(into, strip_option))]
+    pub thumbnail: Option<PathBuf>,
+    /// The description/alt-text of the media, for the visually impaired.
+    #[builder(default, setter(into, strip_option))]
+    pub description: Option<String>,
+    /// The point of an image which should remain in view when it is cropped
+    /// for previews.
+    #[builder(default, setter(strip_option))]
+    pub focus: Option<FocalPoint>,
+}
+
+impl Upload {
+    /// Start building an upload of the given file.
+    pub fn builder(file: impl Into<PathBuf>) -> UploadBuilder {
+        UploadBuilder {
+            file: Some(file.into()),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let upload = Upload::builder("cat.png").build().expect("build");
+        assert_eq!(upload.file, PathBuf::from("cat.png"));
+        assert!(upload.thumbnail.is_none());
+        assert!(upload.description.is_none());
+        assert!(upload.focus.is_none());
+    }
+}
@@ -1,4 +1,14 @@
+pub mod account;
+pub mod admin;
 pub mod application;
 pub mod filter;
+pub mod media;
+pub mod notification;
+pub mod oauth;
+#[cfg(feature = "pleroma")]
+pub mod pleroma;
+pub mod report;
+pub mod search;
+pub mod timeline;
 
 pub use application::{Application, ApplicationBuilder};
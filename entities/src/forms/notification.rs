@@ -0,0 +1,213 @@
+use derive_builder::Builder;
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+
+use crate::{
+    notification::{policy::FilterAction, Type},
+    AccountId, NotificationId,
+};
+
+/// Options for filtering and paginating the notifications list.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let options = forms::notification::Options::builder()
+///     .add_type(notification::Type::Mention)
+///     .add_type(notification::Type::Status)
+///     .account_id(AccountId::new("1"))
+///     .limit(10u64)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     options.to_query_string(),
+///     "types%5B%5D=mention&types%5B%5D=status&account_id=1&limit=10"
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/notifications/#get)
+#[derive(Builder, Debug, Default, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Options {
+    /// Types to include in the result.
+    #[builder(default, setter(into))]
+    types: Vec<Type>,
+    /// Types to exclude from the results.
+    #[builder(default, setter(into))]
+    exclude_types: Vec<Type>,
+    /// Return only notifications received from the specified account.
+    #[builder(default, setter(into, strip_option))]
+    account_id: Option<AccountId>,
+    /// All results returned will be lesser than this ID.
+    #[builder(default, setter(into, strip_option))]
+    max_id: Option<NotificationId>,
+    /// All results returned will be greater than this ID.
+    #[builder(default, setter(into, strip_option))]
+    since_id: Option<NotificationId>,
+    /// Returns results immediately newer than this ID.
+    #[builder(default, setter(into, strip_option))]
+    min_id: Option<NotificationId>,
+    /// Maximum number of results to return.
+    #[builder(default, setter(into, strip_option))]
+    limit: Option<u64>,
+}
+
+impl Options {
+    /// Get a builder for notification list options.
+    pub fn builder() -> OptionsBuilder {
+        Default::default()
+    }
+
+    /// Serialize these options as a URL query string, without the leading
+    /// `?`.
+    pub fn to_query_string(&self) -> String {
+        let mut pairs = url::form_urlencoded::Serializer::new(String::new());
+        for (key, value) in self.pairs() {
+            pairs.append_pair(key, &value);
+        }
+        pairs.finish()
+    }
+
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![];
+        for kind in &self.types {
+            pairs.push(("types[]", kind.to_string()));
+        }
+        for kind in &self.exclude_types {
+            pairs.push(("exclude_types[]", kind.to_string()));
+        }
+        if let Some(account_id) = &self.account_id {
+            pairs.push(("account_id", account_id.to_string()));
+        }
+        if let Some(max_id) = &self.max_id {
+            pairs.push(("max_id", max_id.to_string()));
+        }
+        if let Some(since_id) = &self.since_id {
+            pairs.push(("since_id", since_id.to_string()));
+        }
+        if let Some(min_id) = &self.min_id {
+            pairs.push(("min_id", min_id.to_string()));
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", limit.to_string()));
+        }
+        pairs
+    }
+}
+
+/// Serializes as a map with repeated `types[]` and `exclude_types[]` keys, so
+/// that it can be passed to `serde_urlencoded`.
+impl Serialize for Options {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let pairs = self.pairs();
+        let mut map = serializer.serialize_map(Some(pairs.len()))?;
+        for (key, value) in pairs {
+            map.serialize_entry(key, &value)?;
+        }
+        map.end()
+    }
+}
+
+impl OptionsBuilder {
+    /// Include the given type in the results. May be specified multiple times.
+    pub fn add_type(&mut self, kind: Type) -> &mut Self {
+        self.types.get_or_insert_with(Default::default).push(kind);
+        self
+    }
+
+    /// Exclude the given type from the results. May be specified multiple
+    /// times.
+    pub fn exclude_type(&mut self, kind: Type) -> &mut Self {
+        self.exclude_types
+            .get_or_insert_with(Default::default)
+            .push(kind);
+        self
+    }
+}
+
+/// Form for updating the user's notification filtering policy. Fields which
+/// are not set are left unchanged.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+/// use notification::policy::FilterAction;
+///
+/// let form = forms::notification::Policy::builder()
+///     .for_not_following(FilterAction::Filter)
+///     .for_new_accounts(FilterAction::Drop)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&form).unwrap(),
+///     r#"{"for_not_following":"filter","for_new_accounts":"drop"}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/notifications/#update-the-filtering-policy-for-notifications)
+#[derive(Builder, Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Policy {
+    /// How to handle notifications from accounts the user doesn't follow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    for_not_following: Option<FilterAction>,
+    /// How to handle notifications from accounts which don't follow the
+    /// user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    for_not_followers: Option<FilterAction>,
+    /// How to handle notifications from accounts created in the past 30
+    /// days.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    for_new_accounts: Option<FilterAction>,
+    /// How to handle notifications from private mentions which the user
+    /// didn't solicit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    for_private_mentions: Option<FilterAction>,
+    /// How to handle notifications from accounts limited by a moderator.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    for_limited_accounts: Option<FilterAction>,
+}
+
+impl Policy {
+    /// Get a builder for the notification policy update form.
+    pub fn builder() -> PolicyBuilder {
+        Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_round_trip_test;
+
+    #[test]
+    fn test_options_query_string() {
+        let options = Options::builder()
+            .exclude_type(Type::Follow)
+            .exclude_type(Type::SignUp)
+            .max_id(NotificationId::new("5"))
+            .build()
+            .expect("build options");
+        assert_eq!(
+            options.to_query_string(),
+            "exclude_types%5B%5D=follow&exclude_types%5B%5D=admin.sign_up&max_id=5"
+        );
+        assert_eq!(Options::default().to_query_string(), "");
+    }
+
+    form_round_trip_test!(test_policy_minimal(Policy): Policy::builder().build().unwrap());
+    form_round_trip_test!(test_policy(Policy): Policy::builder()
+        .for_not_following(FilterAction::Filter)
+        .for_not_followers(FilterAction::Accept)
+        .for_new_accounts(FilterAction::Drop)
+        .for_private_mentions(FilterAction::Filter)
+        .for_limited_accounts(FilterAction::Drop)
+        .build()
+        .unwrap());
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use crate::auth::scopes::Scopes;
+
+/// The query of the page which asks the user to authorize an app, at
+/// `/oauth/authorize`.
+///
+/// ```
+/// use mastodon_async_entities::{forms::oauth::AuthorizationRequest, prelude::*};
+///
+/// let request = AuthorizationRequest::new("app", "urn:ietf:wg:oauth:2.0:oob", Scopes::read_all());
+/// assert_eq!(request.response_type, "code");
+/// assert!(!request.force_login);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorizationRequest {
+    /// The ID the server gave the app when it was registered.
+    pub client_id: String,
+    /// Where the user is sent with the authorization code once they have
+    /// authorized the app.
+    pub redirect_uri: String,
+    /// The scopes the app asks for.
+    pub scope: Scopes,
+    /// What the user is redirected with; always `code`.
+    pub response_type: String,
+    /// Whether to ask the user to log in even if they already are, e.g. to
+    /// authorize the app for another of their accounts.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub force_login: bool,
+}
+
+impl AuthorizationRequest {
+    /// Ask the user to authorize an app.
+    pub fn new(
+        client_id: impl Into<String>,
+        redirect_uri: impl Into<String>,
+        scope: Scopes,
+    ) -> Self {
+        AuthorizationRequest {
+            client_id: client_id.into(),
+            redirect_uri: redirect_uri.into(),
+            scope,
+            response_type: "code".to_string(),
+            force_login: false,
+        }
+    }
+}
+
+/// A request for an access token, sent form-encoded in the body of a `POST`
+/// to `/oauth/token`, so that the client secret doesn't end up in the logs
+/// of any proxy on the way.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "grant_type", rename_all = "snake_case")]
+pub enum TokenRequest {
+    /// Exchange the code the user was redirected with, once they
+    /// authorized the app, for a token.
+    AuthorizationCode {
+        /// The ID the server gave the app when it was registered.
+        client_id: String,
+        /// The secret the server gave the app when it was registered.
+        client_secret: String,
+        /// The code the user was redirected with.
+        code: String,
+        /// The redirect URI the code was requested with.
+        redirect_uri: String,
+    },
+    /// Exchange a refresh token for a new access token, once the current
+    /// one expires.
+    RefreshToken {
+        /// The ID the server gave the app when it was registered.
+        client_id: String,
+        /// The secret the server gave the app when it was registered.
+        client_secret: String,
+        /// The refresh token issued with the current access token.
+        refresh_token: String,
+    },
+}
+
+impl TokenRequest {
+    /// Exchange the code the user was redirected with for a token.
+    pub fn authorization_code(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        code: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> Self {
+        TokenRequest::AuthorizationCode {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            code: code.into(),
+            redirect_uri: redirect_uri.into(),
+        }
+    }
+
+    /// Exchange a refresh token for a new access token.
+    pub fn refresh_token(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        refresh_token: impl Into<String>,
+    ) -> Self {
+        TokenRequest::RefreshToken {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            refresh_token: refresh_token.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_round_trip_test;
+
+    form_round_trip_test!(test_authorization_request(AuthorizationRequest): AuthorizationRequest {
+        force_login: true,
+        ..AuthorizationRequest::new("app", "https://example.com/callback", Scopes::all())
+    });
+
+    form_round_trip_test!(test_token_request(TokenRequest): TokenRequest::authorization_code(
+        "app",
+        "s3cr3t",
+        "c0de",
+        "urn:ietf:wg:oauth:2.0:oob"
+    ));
+
+    #[test]
+    fn test_grant_type() {
+        let request = TokenRequest::refresh_token("app", "s3cr3t", "refresh");
+        let value = serde_json::to_value(request).expect("serialize");
+        assert_eq!(value["grant_type"], "refresh_token");
+        assert_eq!(value["refresh_token"], "refresh");
+    }
+}
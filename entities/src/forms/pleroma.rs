@@ -0,0 +1,55 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::AttachmentId;
+
+/// A message to send in a Pleroma or Akkoma chat, with text, media, or both.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let message = forms::pleroma::ChatMessage::builder()
+///     .content("hi :blob:")
+///     .media_id(AttachmentId::new("1"))
+///     .build()
+///     .unwrap();
+/// assert_eq!(serde_json::to_string_pretty(&message).unwrap(), r#"{
+///   "content": "hi :blob:",
+///   "media_id": "1"
+/// }"#);
+/// ```
+#[derive(Builder, Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct ChatMessage {
+    /// The text of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    content: Option<String>,
+    /// Uploaded media to attach to the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    media_id: Option<AttachmentId>,
+}
+
+impl ChatMessage {
+    /// Start building a message.
+    pub fn builder() -> ChatMessageBuilder {
+        ChatMessageBuilder::default()
+    }
+
+    /// A message with only text.
+    pub fn text(content: impl Into<String>) -> Self {
+        ChatMessage {
+            content: Some(content.into()),
+            media_id: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_round_trip_test;
+
+    form_round_trip_test!(test_chat_message(ChatMessage): ChatMessage::text("hi"));
+}
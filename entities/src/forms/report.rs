@@ -0,0 +1,106 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{report::Category, AccountId, RuleId, StatusId};
+
+/// Form for reporting an account, and optionally some of its statuses, to
+/// the moderators.
+///
+/// ```
+/// use mastodon_async_entities::{prelude::*, report::Category};
+///
+/// let report = forms::report::Creation::builder(AccountId::new("108366849347798387"))
+///     .add_status(StatusId::new("108882889550545820"))
+///     .comment("Posts the same ad over and over")
+///     .category(Category::Violation)
+///     .add_rule(RuleId::new("3"))
+///     .forward(true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(serde_json::to_string_pretty(&report).unwrap(), r#"{
+///   "account_id": "108366849347798387",
+///   "status_ids": [
+///     "108882889550545820"
+///   ],
+///   "comment": "Posts the same ad over and over",
+///   "forward": true,
+///   "category": "violation",
+///   "rule_ids": [
+///     "3"
+///   ]
+/// }"#);
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/reports/#post)
+#[derive(Builder, Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Creation {
+    /// The account being reported.
+    #[builder(setter(custom))]
+    account_id: AccountId,
+    /// Statuses by the account to attach to the report, for context.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    #[builder(default, setter(into))]
+    status_ids: Vec<StatusId>,
+    /// The reason for the report, up to 1000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    comment: Option<String>,
+    /// Whether to also send the report to the moderators of the account's
+    /// server, if it is remote.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    forward: Option<bool>,
+    /// The generic reason for the report. The server defaults to
+    /// [`Category::Other`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    category: Option<Category>,
+    /// The server rules which were broken. Required by the server for
+    /// [`Category::Violation`], and ignored otherwise.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    #[builder(default, setter(into))]
+    rule_ids: Vec<RuleId>,
+}
+
+impl Creation {
+    /// Start building a report against the given account.
+    pub fn builder(account_id: AccountId) -> CreationBuilder {
+        CreationBuilder {
+            account_id: Some(account_id),
+            ..Default::default()
+        }
+    }
+}
+
+impl CreationBuilder {
+    /// Attach a status to the report.
+    pub fn add_status(&mut self, id: StatusId) -> &mut Self {
+        self.status_ids.get_or_insert_with(Vec::new).push(id);
+        self
+    }
+
+    /// Cite a rule the account broke.
+    pub fn add_rule(&mut self, id: RuleId) -> &mut Self {
+        self.rule_ids.get_or_insert_with(Vec::new).push(id);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_round_trip_test;
+
+    form_round_trip_test!(test_minimal(Creation): Creation::builder(AccountId::new("1"))
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_full(Creation): Creation::builder(AccountId::new("1"))
+        .status_ids(vec![StatusId::new("2"), StatusId::new("3")])
+        .comment("spam")
+        .forward(false)
+        .category(Category::Legal)
+        .add_rule(RuleId::new("4"))
+        .build()
+        .unwrap());
+}
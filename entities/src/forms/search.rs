@@ -0,0 +1,169 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{search_result::SearchResult, AccountId};
+
+/// Options for searching for accounts, statuses and hashtags.
+///
+/// ```
+/// use mastodon_async_entities::{forms::search::Type, prelude::*};
+///
+/// let search = forms::search::Options::builder("#rust")
+///     .search_type(Type::Hashtags)
+///     .limit(10u64)
+///     .exclude_unreviewed(true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(serde_json::to_string_pretty(&search).unwrap(), r##"{
+///   "q": "#rust",
+///   "type": "hashtags",
+///   "limit": 10,
+///   "exclude_unreviewed": true
+/// }"##);
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/search/#v2)
+#[derive(Builder, Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Options {
+    /// What to search for.
+    #[builder(setter(custom))]
+    q: String,
+    /// Only return results of this type.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    search_type: Option<Type>,
+    /// Only return statuses posted by this account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    account_id: Option<AccountId>,
+    /// Return results older than this ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    max_id: Option<String>,
+    /// Return results immediately newer than this ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    min_id: Option<String>,
+    /// Maximum number of results of each type to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    limit: Option<u64>,
+    /// Skip this many results. Only honoured when `search_type` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    offset: Option<u64>,
+    /// Look up remote accounts and statuses with WebFinger when `q` is an
+    /// exact address or URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    resolve: Option<bool>,
+    /// Only return accounts the client account is following.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    following: Option<bool>,
+    /// Leave out hashtags which haven't been reviewed by a moderator.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    exclude_unreviewed: Option<bool>,
+}
+
+impl Options {
+    /// Start building a search for the given text.
+    pub fn builder(q: impl Into<String>) -> OptionsBuilder {
+        OptionsBuilder {
+            q: Some(q.into()),
+            ..Default::default()
+        }
+    }
+
+    /// The options for the page of results after `results`, which were
+    /// returned for these options, or `None` if there were no results, or if
+    /// `search_type` isn't set, as the server then ignores `offset` and
+    /// would return the same results again.
+    pub fn next_page(&self, results: &SearchResult) -> Option<Self> {
+        let count = results.accounts.len() + results.statuses.len() + results.hashtags.len();
+        if count == 0 || self.search_type.is_none() {
+            return None;
+        }
+        Some(Options {
+            offset: Some(self.offset.unwrap_or_default() + count as u64),
+            ..self.clone()
+        })
+    }
+}
+
+/// The type of result to search for.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Type {
+    /// Accounts.
+    Accounts,
+    /// Hashtags.
+    Hashtags,
+    /// Statuses.
+    Statuses,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{form_round_trip_test, status::Tag};
+
+    form_round_trip_test!(test_minimal(Options): Options::builder("rust").build().unwrap());
+    form_round_trip_test!(test_full(Options): Options::builder("rust")
+        .search_type(Type::Statuses)
+        .account_id(AccountId::new("1"))
+        .max_id("10")
+        .min_id("2")
+        .limit(5u64)
+        .offset(5u64)
+        .resolve(false)
+        .following(true)
+        .exclude_unreviewed(true)
+        .build()
+        .unwrap());
+
+    #[test]
+    fn test_next_page() {
+        let options = Options::builder("rust")
+            .search_type(Type::Hashtags)
+            .limit(2u64)
+            .build()
+            .unwrap();
+        let tag = |name: &str| Tag {
+            name: name.to_string(),
+            url: format!("https://example.com/tags/{name}"),
+        };
+        let results = SearchResult {
+            accounts: vec![],
+            statuses: vec![],
+            hashtags: vec![tag("rust"), tag("rustlang")],
+        };
+        let next = options.next_page(&results).expect("next page");
+        assert_eq!(next.offset, Some(2));
+        assert_eq!(next.search_type, Some(Type::Hashtags));
+        let next = next.next_page(&results).expect("next page");
+        assert_eq!(next.offset, Some(4));
+
+        let results = SearchResult {
+            hashtags: vec![],
+            ..results
+        };
+        assert!(next.next_page(&results).is_none());
+    }
+
+    #[test]
+    fn test_next_page_without_type() {
+        let options = Options::builder("rust").build().unwrap();
+        let results = SearchResult {
+            accounts: vec![],
+            statuses: vec![],
+            hashtags: vec![Tag {
+                name: "rust".to_string(),
+                url: "https://example.com/tags/rust".to_string(),
+            }],
+        };
+        assert!(options.next_page(&results).is_none());
+    }
+}
@@ -0,0 +1,252 @@
+use derive_builder::Builder;
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+
+use crate::StatusId;
+
+/// Options for paginating and filtering any timeline.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let options = forms::timeline::Options::builder()
+///     .max_id(StatusId::new("103"))
+///     .limit(40u64)
+///     .only_media(true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(serde_json::to_string_pretty(&options).unwrap(), r#"{
+///   "max_id": "103",
+///   "limit": 40,
+///   "only_media": true
+/// }"#);
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/timelines/)
+#[derive(Builder, Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Options {
+    /// All results returned will be older than this ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    max_id: Option<StatusId>,
+    /// All results returned will be newer than this ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    since_id: Option<StatusId>,
+    /// Return results immediately newer than this ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    min_id: Option<StatusId>,
+    /// Maximum number of results to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    limit: Option<u64>,
+    /// Only return statuses originating from this server. Only used by the
+    /// public and hashtag timelines.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    local: Option<bool>,
+    /// Only return statuses originating from other servers. Only used by the
+    /// public timeline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    remote: Option<bool>,
+    /// Only return statuses with media attachments. Ignored by the home
+    /// timeline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    only_media: Option<bool>,
+}
+
+impl Options {
+    /// Get a builder for timeline options.
+    pub fn builder() -> OptionsBuilder {
+        Default::default()
+    }
+}
+
+/// Options for paginating and filtering a hashtag timeline, including other
+/// hashtags statuses must, or must not, use as well.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let options = forms::timeline::TagOptions::builder()
+///     .any_of("rustlang")
+///     .none_of("#crypto")
+///     .local(true)
+///     .limit(20u64)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     options.to_query_string(),
+///     "any%5B%5D=rustlang&none%5B%5D=crypto&local=true&limit=20"
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/timelines/#tag)
+#[derive(Builder, Debug, Default, Clone, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct TagOptions {
+    /// Also return statuses using any of these hashtags.
+    #[builder(default, setter(into))]
+    any: Vec<String>,
+    /// Only return statuses which also use all of these hashtags.
+    #[builder(default, setter(into))]
+    all: Vec<String>,
+    /// Don't return statuses which use any of these hashtags.
+    #[builder(default, setter(into))]
+    none: Vec<String>,
+    /// Only return statuses originating from this server.
+    #[builder(default, setter(strip_option))]
+    local: Option<bool>,
+    /// Only return statuses originating from other servers.
+    #[builder(default, setter(strip_option))]
+    remote: Option<bool>,
+    /// Only return statuses with media attachments.
+    #[builder(default, setter(strip_option))]
+    only_media: Option<bool>,
+    /// All results returned will be older than this ID.
+    #[builder(default, setter(into, strip_option))]
+    max_id: Option<StatusId>,
+    /// All results returned will be newer than this ID.
+    #[builder(default, setter(into, strip_option))]
+    since_id: Option<StatusId>,
+    /// Return results immediately newer than this ID.
+    #[builder(default, setter(into, strip_option))]
+    min_id: Option<StatusId>,
+    /// Maximum number of results to return.
+    #[builder(default, setter(into, strip_option))]
+    limit: Option<u64>,
+}
+
+impl TagOptions {
+    /// Get a builder for hashtag timeline options.
+    pub fn builder() -> TagOptionsBuilder {
+        Default::default()
+    }
+
+    /// Serialize these options as a URL query string, without the leading
+    /// `?`.
+    pub fn to_query_string(&self) -> String {
+        let mut pairs = url::form_urlencoded::Serializer::new(String::new());
+        for (key, value) in self.pairs() {
+            pairs.append_pair(key, &value);
+        }
+        pairs.finish()
+    }
+
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![];
+        for (key, tags) in [
+            ("any[]", &self.any),
+            ("all[]", &self.all),
+            ("none[]", &self.none),
+        ] {
+            for tag in tags {
+                pairs.push((key, tag.trim_start_matches('#').to_string()));
+            }
+        }
+        for (key, flag) in [
+            ("local", self.local),
+            ("remote", self.remote),
+            ("only_media", self.only_media),
+        ] {
+            if let Some(flag) = flag {
+                pairs.push((key, flag.to_string()));
+            }
+        }
+        for (key, id) in [
+            ("max_id", &self.max_id),
+            ("since_id", &self.since_id),
+            ("min_id", &self.min_id),
+        ] {
+            if let Some(id) = id {
+                pairs.push((key, id.to_string()));
+            }
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", limit.to_string()));
+        }
+        pairs
+    }
+}
+
+/// Serializes as a map with repeated `any[]`, `all[]` and `none[]` keys, so
+/// that it can be passed to `serde_urlencoded`.
+impl Serialize for TagOptions {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let pairs = self.pairs();
+        let mut map = serializer.serialize_map(Some(pairs.len()))?;
+        for (key, value) in pairs {
+            map.serialize_entry(key, &value)?;
+        }
+        map.end()
+    }
+}
+
+impl TagOptionsBuilder {
+    /// Also return statuses using this hashtag, given with or without the
+    /// leading `#`. May be specified multiple times.
+    pub fn any_of(&mut self, tag: impl Into<String>) -> &mut Self {
+        self.any
+            .get_or_insert_with(Default::default)
+            .push(tag.into());
+        self
+    }
+
+    /// Only return statuses which also use this hashtag, given with or
+    /// without the leading `#`. May be specified multiple times.
+    pub fn all_of(&mut self, tag: impl Into<String>) -> &mut Self {
+        self.all
+            .get_or_insert_with(Default::default)
+            .push(tag.into());
+        self
+    }
+
+    /// Don't return statuses which use this hashtag, given with or without
+    /// the leading `#`. May be specified multiple times.
+    pub fn none_of(&mut self, tag: impl Into<String>) -> &mut Self {
+        self.none
+            .get_or_insert_with(Default::default)
+            .push(tag.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_round_trip_test;
+
+    form_round_trip_test!(test_minimal(Options): Options::builder().build().unwrap());
+    form_round_trip_test!(test_full(Options): Options::builder()
+        .max_id(StatusId::new("10"))
+        .since_id(StatusId::new("1"))
+        .min_id(StatusId::new("2"))
+        .limit(20u64)
+        .local(false)
+        .remote(true)
+        .only_media(false)
+        .build()
+        .unwrap());
+
+    #[test]
+    fn test_tag_options_query_string() {
+        let options = TagOptions::builder()
+            .all_of("#rust")
+            .all_of("async")
+            .only_media(true)
+            .max_id(StatusId::new("7"))
+            .build()
+            .expect("build options");
+        assert_eq!(
+            options.to_query_string(),
+            "all%5B%5D=rust&all%5B%5D=async&only_media=true&max_id=7"
+        );
+        assert_eq!(TagOptions::default().to_query_string(), "");
+    }
+}
@@ -12,7 +12,7 @@ macro_rules! define_ids {
     };
     ($doc:literal as $name:ident(from $from_t:ty, as $ref_t:ident ref),) => {
         #[doc = concat!("Wrapper type for ", $doc)]
-        #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+        #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
         #[serde(transparent)]
         pub struct $name($from_t);
 
@@ -52,6 +52,7 @@ define_ids!(
     "a list ID" as ListId(from String, as str ref),
     "a mention ID" as MentionId(from String, as str ref),
     "a notification ID" as NotificationId(from String, as str ref),
+    "a notification request ID" as NotificationRequestId(from String, as str ref),
     "a subscription ID" as SubscriptionId(from String, as str ref),
     "a relationship ID" as RelationshipId(from String, as str ref),
     "a report ID" as ReportId(from String, as str ref),
@@ -69,8 +70,10 @@ define_ids!(
     "a conversation ID" as ConversationId(from String, as str ref),
     "a poll ID" as PollId(from String, as str ref),
     "a hashtag ID" as TagId(from String, as str ref),
-    "the ID of an application.
-
-As [`Application`](crate::application::Application) doesn't have an ID, I'm not sure what you're supposed to compare this to." as ApplicationId(from i64, as i64 ref),
+    "the ID of an event which severed relationships. See [`relationship::SeveranceEvent`](crate::relationship::SeveranceEvent)" as SeveranceEventId(from String, as str ref),
+    "the ID of a moderation warning. See [`account::Warning`](crate::account::Warning)" as AccountWarningId(from String, as str ref),
+    "the ID of a chat on Pleroma or Akkoma" as ChatId(from String, as str ref),
+    "the ID of a chat message on Pleroma or Akkoma" as ChatMessageId(from String, as str ref),
+    "the ID of an application. See [`Application::id`](crate::application::Application::id)" as ApplicationId(from i64, as i64 ref),
     "a role ID" as RoleId(from i64, as i64 ref),
 );
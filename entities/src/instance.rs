@@ -1,6 +1,7 @@
 //! Module containing everything related to an instance.
 use isolang::Language;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use time::{serde::iso8601, OffsetDateTime};
 use url::Url;
 
@@ -62,6 +63,16 @@ pub struct Thumbnail {
     pub versions: ThumbnailVersions,
 }
 
+#[cfg(feature = "blurhash")]
+impl Thumbnail {
+    /// A placeholder for the thumbnail, decoded from its blurhash, `width`
+    /// pixels wide and `height` pixels high. See
+    /// [`Attachment::decode_blurhash`](crate::attachment::Attachment::decode_blurhash).
+    pub fn decode_blurhash(&self, width: u32, height: u32) -> Option<Vec<u8>> {
+        crate::blurhash_compat::decode(&self.blurhash, width, height)
+    }
+}
+
 /// Links to scaled resolution images, for high DPI screens.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ThumbnailVersions {
@@ -118,6 +129,67 @@ pub struct DomainBlock {
     pub comment: Option<String>,
 }
 
+impl DomainBlock {
+    /// Whether the server hid part of the domain, replacing it with `*`s.
+    pub fn is_obfuscated(&self) -> bool {
+        self.domain.contains('*')
+    }
+
+    /// Whether [`DomainBlock::digest`] is the hash of [`DomainBlock::domain`].
+    ///
+    /// This is never the case for an obfuscated domain, whose digest is that
+    /// of the domain before it was obfuscated.
+    pub fn verify_digest(&self) -> bool {
+        digest(&self.domain) == self.digest.to_ascii_lowercase()
+    }
+
+    /// Whether this block applies to the given domain, i.e. it is the
+    /// blocked domain or one of its subdomains.
+    ///
+    /// Obfuscated domains are matched by hashing `domain` and each of its
+    /// parent domains and comparing them with the digest.
+    ///
+    /// ```
+    /// use mastodon_async_entities::instance::DomainBlock;
+    ///
+    /// let block: DomainBlock = serde_json::from_str(r#"{
+    ///     "domain": "bl*****.example",
+    ///     "digest": "1f30273176bf43428242811a3bd5e04653804cff849e2ac73a14aa6e00c66a48",
+    ///     "severity": "suspend",
+    ///     "comment": null
+    /// }"#).unwrap();
+    /// assert!(block.matches("blocked.example"));
+    /// assert!(block.matches("media.blocked.example"));
+    /// assert!(!block.matches("unblocked.example"));
+    /// ```
+    pub fn matches(&self, domain: &str) -> bool {
+        let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+        if !self.is_obfuscated() {
+            let blocked = self.domain.to_ascii_lowercase();
+            return domain == blocked || domain.ends_with(&format!(".{blocked}"));
+        }
+        let expected = self.digest.to_ascii_lowercase();
+        let mut candidate = domain.as_str();
+        loop {
+            if digest(candidate) == expected {
+                return true;
+            }
+            match candidate.split_once('.') {
+                Some((_, parent)) => candidate = parent,
+                None => return false,
+            }
+        }
+    }
+}
+
+/// The hex-encoded SHA256 digest of a domain, as used in [`DomainBlock`].
+fn digest(domain: &str) -> String {
+    Sha256::digest(domain.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
 /// Weekly activity on an instance
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Activity {
@@ -637,4 +709,28 @@ mod tests {
         assert!(subject.severity.is_suspend());
         assert_eq!(subject.comment.unwrap(), "Inappropriate content");
     }
+
+    #[test]
+    fn test_domain_block_matches() {
+        let block = DomainBlock {
+            domain: "blocked.example".to_string(),
+            digest: "1f30273176bf43428242811a3bd5e04653804cff849e2ac73a14aa6e00c66a48".to_string(),
+            severity: admin::domain::BlockSeverity::Silence,
+            comment: None,
+        };
+        assert!(!block.is_obfuscated());
+        assert!(block.verify_digest());
+        assert!(block.matches("Blocked.Example."));
+        assert!(block.matches("sub.blocked.example"));
+        assert!(!block.matches("notblocked.example"));
+
+        let obfuscated = DomainBlock {
+            domain: "blo****.example".to_string(),
+            ..block
+        };
+        assert!(obfuscated.is_obfuscated());
+        assert!(!obfuscated.verify_digest());
+        assert!(obfuscated.matches("sub.blocked.example"));
+        assert!(!obfuscated.matches("example"));
+    }
 }
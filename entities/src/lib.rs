@@ -19,8 +19,18 @@ pub mod application;
 pub mod attachment;
 /// Data structures for ser/de of auth-related resources
 pub mod auth;
+/// Rendering the blurhashes of media as placeholder images.
+///
+/// In order to use this module, enable the "blurhash" feature.
+#[cfg(feature = "blurhash")]
+pub mod blurhash_compat;
 /// Data structures for ser/de of card-related resources
 pub mod card;
+/// Conversions between the timestamps on entities and `chrono` types.
+///
+/// In order to use this module, enable the "chrono" feature.
+#[cfg(feature = "chrono")]
+pub mod chrono_compat;
 /// Data structures for ser/de of context-related resources
 pub mod context;
 /// Data structures for ser/de of conversation-related resources
@@ -48,6 +58,11 @@ pub mod marker;
 pub mod mention;
 /// Data structures for ser/de of notification-related resources
 pub mod notification;
+/// The extensions Pleroma and Akkoma make to the entities.
+///
+/// In order to use this module, enable the "pleroma" feature.
+#[cfg(feature = "pleroma")]
+pub mod pleroma;
 /// Data structures for working with user preferences.
 pub mod preferences;
 /// Data structures for ser/de of push-subscription-related resources
@@ -63,6 +78,8 @@ pub mod status;
 /// Data structures for ser/de of tags.
 pub mod tag;
 mod test;
+/// Counting the characters of statuses as the server does.
+pub mod text;
 /// Data structure for ser/de visibility
 pub mod visibility;
 
@@ -77,7 +94,7 @@ pub mod prelude {
     pub use super::{
         account::{
             self, /* for
-                  SuggestionSource, Suggestion, FamiliarFollowers, Color, Credentials,
+                  SuggestionSource, SuggestionReason, Suggestion, FamiliarFollowers, Color, Credentials,
                   CredentialsBuilder */
             Account, CredentialAccount, Role, RolePermissions, Source,
         },
@@ -95,7 +112,6 @@ pub mod prelude {
         custom_emoji::CustomEmoji,
         event::Event,
         filter::{self /* for Action, Keyword, Status, v1, Result, Context */, Filter},
-        forms,
         ids::*,
         instance::{
             self, /* for
@@ -104,9 +120,9 @@ pub mod prelude {
             DomainBlock, Instance,
         },
         list::{self /* for RepliesPolicy */, List},
-        marker::Marker,
+        marker::{Marker, Markers},
         mention::Mention,
-        notification::{self /* for Type */, Notification},
+        notification::{self /* for Type, Policy, Request */, Notification},
         preferences::Preferences,
         push::{
             self, /* for Alerts, AdminAlerts, add_subscription, update_data */
@@ -123,4 +139,56 @@ pub mod prelude {
         visibility::Visibility,
         Empty,
     };
+
+    /// The entities most applications deal with, for those which would
+    /// rather not glob-import the whole prelude.
+    ///
+    /// Nothing in it shares a name with anything else in it, unlike the
+    /// full prelude, where for example [`Account`](crate::account::Account)
+    /// shadows [`admin::Account`](crate::admin::Account).
+    ///
+    /// ```
+    /// use mastodon_async_entities::prelude::common::{Account, Scopes, Status, StatusId};
+    /// ```
+    // not `core`, which would shadow the core crate wherever the prelude is
+    // glob-imported
+    pub mod common {
+        pub use crate::{
+            account::{Account, CredentialAccount},
+            attachment::{Attachment, MediaType},
+            auth::{Scope, Scopes, Token},
+            context::Context,
+            conversation::Conversation,
+            custom_emoji::CustomEmoji,
+            event::Event,
+            filter::Filter,
+            ids::*,
+            instance::Instance,
+            list::List,
+            notification::Notification,
+            relationship::Relationship,
+            search_result::SearchResult,
+            status::{Poll, Status},
+            tag::Tag,
+            visibility::Visibility,
+            Empty,
+        };
+    }
+
+    /// The entities returned by the admin API, under their own names, such
+    /// as [`admin::Account`](crate::admin::Account).
+    pub mod admin {
+        pub use crate::admin::prelude::*;
+    }
+
+    /// Everything sent to the server: all of [`crate::forms`], as well as
+    /// new statuses and account credentials, which are built alongside the
+    /// entities they create.
+    pub mod forms {
+        pub use crate::{
+            account::{Credentials, CredentialsBuilder},
+            forms::*,
+            status::{NewStatus, NewStatusBuilder},
+        };
+    }
 }
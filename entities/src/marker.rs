@@ -1,20 +1,34 @@
-use crate::StatusId;
+use crate::{NotificationId, StatusId};
 use serde::{Deserialize, Serialize};
 use time::{serde::iso8601, OffsetDateTime};
 
 /// Represents the last read position within a user's timelines.
 ///
+/// The position in the home timeline is a status, while the position in the
+/// notifications is a notification, hence the `Id` parameter.
+///
 /// See also [the API documentation](https://docs.joinmastodon.org/entities/Marker/)
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
-pub struct Marker {
+pub struct Marker<Id = StatusId> {
     /// The ID of the most recently viewed entity.
-    pub last_read_id: StatusId,
+    pub last_read_id: Id,
     /// An incrementing counter, used for locking to prevent write conflicts.
     pub version: i64,
     #[serde(with = "iso8601")]
     pub updated_at: OffsetDateTime,
 }
 
+/// The saved read positions, as returned by GET /api/v1/markers.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Markers {
+    /// The position in the home timeline, if one has been saved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home: Option<Marker>,
+    /// The position in the notifications, if one has been saved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<Marker<NotificationId>>,
+}
+
 #[cfg(test)]
 mod tests {
     use time::format_description::well_known::Iso8601;
@@ -37,4 +51,20 @@ mod tests {
                 .expect("parse updated time")
         );
     }
+
+    #[test]
+    fn test_deserialize_markers() {
+        let example = r#"{
+          "notifications": {
+            "last_read_id": "35098814",
+            "version": 361,
+            "updated_at": "2019-11-26T22:37:25.239Z"
+          }
+        }"#;
+        let subject: Markers = serde_json::from_str(example).expect("deserialize");
+        assert!(subject.home.is_none());
+        let notifications = subject.notifications.expect("notifications marker");
+        assert_eq!(notifications.last_read_id, NotificationId::new("35098814"));
+        assert_eq!(notifications.version, 361);
+    }
 }
@@ -1,12 +1,19 @@
 //! Module containing all info about notifications.
 
-use crate::{admin::Report, NotificationId};
+use std::fmt;
+
+use crate::{
+    account::Warning, admin::Report, conversion, relationship::SeveranceEvent, NotificationId,
+    NotificationRequestId,
+};
 
 use super::{account::Account, status::Status};
 use derive_is_enum_variant::is_enum_variant;
 use serde::{Deserialize, Serialize};
 use time::{serde::iso8601, OffsetDateTime};
 
+pub use policy::Policy;
+
 /// Represents a notification of an event relevant to the user.
 ///
 /// See also [the API documentation](https://docs.joinmastodon.org/entities/Notification/)
@@ -30,6 +37,115 @@ pub struct Notification {
     /// Report that was the object of the notification. Attached when type of
     /// the notification is `admin.report`.
     pub report: Option<Report>,
+    /// The event which removed some of the user's relationships. Attached
+    /// when type of the notification is `severed_relationships`.
+    #[serde(default)]
+    pub event: Option<SeveranceEvent>,
+    /// The warning a moderator sent the user. Attached when type of the
+    /// notification is `moderation_warning`.
+    #[serde(default)]
+    pub moderation_warning: Option<Warning>,
+}
+
+impl Notification {
+    /// The notification's type together with what it's about, for matching
+    /// on without unwrapping [`status`](Self::status),
+    /// [`report`](Self::report), [`event`](Self::event) or
+    /// [`moderation_warning`](Self::moderation_warning).
+    ///
+    /// ```
+    /// use mastodon_async_entities::{notification::Kind, prelude::*};
+    ///
+    /// fn describe(notification: &Notification) -> String {
+    ///     let who = &notification.account.acct;
+    ///     match notification.kind() {
+    ///         Kind::Mention(status) => format!("{who} mentioned you: {}", status.content),
+    ///         Kind::Follow => format!("{who} followed you"),
+    ///         _ => format!("{who} did something"),
+    ///     }
+    /// }
+    /// ```
+    pub fn kind(&self) -> Kind<'_> {
+        let status = self.status.as_ref();
+        match (self.notification_type, status, &self.report) {
+            (Type::Mention, Some(status), _) => Kind::Mention(status),
+            (Type::Status, Some(status), _) => Kind::Status(status),
+            (Type::Reblog, Some(status), _) => Kind::Reblog(status),
+            (Type::Favourite, Some(status), _) => Kind::Favourite(status),
+            (Type::Follow, _, _) => Kind::Follow,
+            (Type::FollowRequest, _, _) => Kind::FollowRequest,
+            (Type::Poll, Some(status), _) => Kind::Poll(status),
+            (Type::Update, Some(status), _) => Kind::Update(status),
+            (Type::SignUp, _, _) => Kind::SignUp,
+            (Type::Report, _, Some(report)) => Kind::Report(report),
+            (Type::SeveredRelationships, _, _) => match &self.event {
+                Some(event) => Kind::SeveredRelationships(event),
+                None => Kind::Other(Type::SeveredRelationships),
+            },
+            (Type::ModerationWarning, _, _) => match &self.moderation_warning {
+                Some(warning) => Kind::ModerationWarning(warning),
+                None => Kind::Other(Type::ModerationWarning),
+            },
+            (other, _, _) => Kind::Other(other),
+        }
+    }
+
+    /// The account which mentioned the user and the status it mentioned
+    /// them in, if this is a mention.
+    pub fn mention(&self) -> Option<(&Account, &Status)> {
+        match self.kind() {
+            Kind::Mention(status) => Some((&self.account, status)),
+            _ => None,
+        }
+    }
+
+    /// The account which followed the user, if this is a new follower.
+    pub fn follow(&self) -> Option<&Account> {
+        matches!(self.kind(), Kind::Follow).then_some(&self.account)
+    }
+
+    /// The poll's status, if this is a poll the user voted in or created
+    /// having ended.
+    pub fn poll_ended(&self) -> Option<&Status> {
+        match self.kind() {
+            Kind::Poll(status) => Some(status),
+            _ => None,
+        }
+    }
+}
+
+/// A notification's type together with what it's about, as
+/// returned by [`Notification::kind`]. The account which caused the
+/// notification is always [`Notification::account`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind<'a> {
+    /// Someone mentioned you in this status
+    Mention(&'a Status),
+    /// Someone you enabled notifications for posted this status
+    Status(&'a Status),
+    /// Someone boosted this status of yours
+    Reblog(&'a Status),
+    /// Someone favourited this status of yours
+    Favourite(&'a Status),
+    /// Someone followed you
+    Follow,
+    /// Someone requested to follow you
+    FollowRequest,
+    /// The poll in this status, which you voted in or created, has ended
+    Poll(&'a Status),
+    /// This status, which you interacted with, has been edited
+    Update(&'a Status),
+    /// Someone signed up
+    SignUp,
+    /// This report has been filed
+    Report(&'a Report),
+    /// This event removed some of your follows or followers
+    SeveredRelationships(&'a SeveranceEvent),
+    /// A moderator sent you this warning
+    ModerationWarning(&'a Warning),
+    /// A type this version of the crate doesn't know about, or a
+    /// notification missing what its type calls for
+    Other(Type),
 }
 
 /// The type of notification.
@@ -58,4 +174,243 @@ pub enum Type {
     /// A new report has been filed
     #[serde(rename = "admin.report")]
     Report,
+    /// Some of your follows or followers were removed by a block or
+    /// suspension
+    SeveredRelationships,
+    /// A moderator has taken action against your account or sent you a
+    /// warning
+    ModerationWarning,
+    /// A type this version of the crate doesn't know about.
+    #[cfg_attr(not(feature = "strict"), serde(other))]
+    Unknown,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Type::Mention => "mention",
+            Type::Status => "status",
+            Type::Reblog => "reblog",
+            Type::Favourite => "favourite",
+            Type::Follow => "follow",
+            Type::FollowRequest => "follow_request",
+            Type::Poll => "poll",
+            Type::Update => "update",
+            Type::SignUp => "admin.sign_up",
+            Type::Report => "admin.report",
+            Type::SeveredRelationships => "severed_relationships",
+            Type::ModerationWarning => "moderation_warning",
+            Type::Unknown => "unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Represents a group of filtered notifications from a specific account.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/NotificationRequest/)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Request {
+    /// The id of the notification request in the database.
+    pub id: NotificationRequestId,
+    /// The timestamp of the notification request, i.e. when the first
+    /// filtered notification from that account was created.
+    #[serde(with = "iso8601")]
+    pub created_at: OffsetDateTime,
+    /// The timestamp of when the notification request was last updated.
+    #[serde(with = "iso8601")]
+    pub updated_at: OffsetDateTime,
+    /// The account that performed the actions that generated the filtered
+    /// notifications.
+    pub account: Account,
+    /// How many of this account's notifications were filtered.
+    #[serde(with = "conversion::string_to::u64")]
+    pub notifications_count: u64,
+    /// Most recent status associated with a filtered notification from that
+    /// account.
+    pub last_status: Option<Status>,
+}
+
+/// Types relating to the user's notification filtering policy.
+pub mod policy {
+    use derive_is_enum_variant::is_enum_variant;
+    use serde::{Deserialize, Serialize};
+
+    /// Represents the filtering policy applied to the user's notifications.
+    ///
+    /// See also [the API documentation](https://docs.joinmastodon.org/entities/NotificationPolicy/)
+    #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+    pub struct Policy {
+        /// How to handle notifications from accounts the user doesn't follow.
+        pub for_not_following: FilterAction,
+        /// How to handle notifications from accounts which don't follow the
+        /// user.
+        pub for_not_followers: FilterAction,
+        /// How to handle notifications from accounts created in the past 30
+        /// days.
+        pub for_new_accounts: FilterAction,
+        /// How to handle notifications from private mentions which the user
+        /// didn't solicit.
+        pub for_private_mentions: FilterAction,
+        /// How to handle notifications from accounts limited by a moderator.
+        pub for_limited_accounts: FilterAction,
+        /// Summary of the filtered notifications.
+        pub summary: Summary,
+    }
+
+    /// What happens to a notification which matches a policy.
+    #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+    #[serde(rename_all = "snake_case")]
+    pub enum FilterAction {
+        /// Show the notification normally.
+        Accept,
+        /// Put the notification in the filtered notifications requests.
+        Filter,
+        /// Don't create the notification at all.
+        Drop,
+        /// An action this version of the crate doesn't know about.
+        #[cfg_attr(not(feature = "strict"), serde(other))]
+        Unknown,
+    }
+
+    /// Summary of the filtered notifications.
+    #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+    pub struct Summary {
+        /// Number of different accounts from which the user has non-dismissed
+        /// filtered notifications. Capped at 100.
+        pub pending_requests_count: u64,
+        /// Number of total non-dismissed filtered notifications. May be
+        /// inaccurate.
+        pub pending_notifications_count: u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::account_json, StatusId};
+
+    #[test]
+    fn test_type_display_matches_serde() {
+        for kind in [
+            Type::Mention,
+            Type::FollowRequest,
+            Type::SignUp,
+            Type::Report,
+            Type::SeveredRelationships,
+            Type::ModerationWarning,
+        ] {
+            assert_eq!(
+                serde_json::to_string(&kind).expect("serialize"),
+                format!("\"{kind}\"")
+            );
+        }
+    }
+
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn test_deserialize_unknown_type() {
+        let kind: Type = serde_json::from_str(r#""annual_report""#).expect("deserialize");
+        assert!(kind.is_unknown());
+    }
+
+    fn notification(kind: &str) -> Notification {
+        let example = serde_json::json!({
+            "id": "34975861",
+            "type": kind,
+            "created_at": "2019-11-23T07:49:02.064Z",
+            "account": account_json("971724", "zsc"),
+            "status": null,
+            "report": null
+        });
+        serde_json::from_value(example).expect("deserialize notification")
+    }
+
+    #[test]
+    fn test_kind() {
+        let follow = notification("follow");
+        assert_eq!(follow.kind(), Kind::Follow);
+        assert_eq!(follow.follow().map(|it| it.acct.as_str()), Some("zsc"));
+        assert!(follow.mention().is_none());
+        // a mention without its status can't be matched as one
+        let mention = notification("mention");
+        assert_eq!(mention.kind(), Kind::Other(Type::Mention));
+        assert!(mention.mention().is_none());
+        assert!(mention.follow().is_none());
+        assert!(mention.poll_ended().is_none());
+    }
+
+    #[test]
+    fn test_severed_relationships() {
+        let mut value: serde_json::Value =
+            serde_json::to_value(notification("severed_relationships")).expect("serialize");
+        value["event"] = serde_json::json!({
+            "id": "1",
+            "type": "domain_block",
+            "purged": false,
+            "target_name": "spam.example",
+            "followers_count": 1,
+            "following_count": 2,
+            "created_at": "2024-03-19T14:32:00.000Z"
+        });
+        let subject: Notification = serde_json::from_value(value).expect("deserialize");
+        let Kind::SeveredRelationships(event) = subject.kind() else {
+            panic!("expected severed relationships, got {:?}", subject.kind());
+        };
+        assert_eq!(
+            event.event_type,
+            crate::relationship::SeveranceType::DomainBlock
+        );
+        assert_eq!(event.target_name, "spam.example");
+        assert_eq!(event.following_count, 2);
+    }
+
+    #[test]
+    fn test_moderation_warning() {
+        let mut value: serde_json::Value =
+            serde_json::to_value(notification("moderation_warning")).expect("serialize");
+        value["moderation_warning"] = serde_json::json!({
+            "id": "2",
+            "action": "mark_statuses_as_sensitive",
+            "text": "Please tag your spoilers",
+            "status_ids": ["103"],
+            "target_account": value["account"].clone(),
+            "appeal": { "text": "They were tagged", "state": "pending" },
+            "created_at": "2024-03-19T14:32:00.000Z"
+        });
+        let subject: Notification = serde_json::from_value(value).expect("deserialize");
+        let Kind::ModerationWarning(warning) = subject.kind() else {
+            panic!("expected a moderation warning, got {:?}", subject.kind());
+        };
+        assert!(warning.action.is_mark_statuses_as_sensitive());
+        assert_eq!(
+            warning.status_ids.as_deref(),
+            Some(&[StatusId::new("103")][..])
+        );
+        assert!(warning.appeal.as_ref().expect("appeal").state.is_pending());
+        // missing what its type calls for
+        let bare = notification("moderation_warning");
+        assert_eq!(bare.kind(), Kind::Other(Type::ModerationWarning));
+    }
+
+    #[test]
+    fn test_deserialize_policy() {
+        let example = r#"{
+          "for_not_following": "accept",
+          "for_not_followers": "filter",
+          "for_new_accounts": "drop",
+          "for_private_mentions": "filter",
+          "for_limited_accounts": "filter",
+          "summary": {
+            "pending_requests_count": 0,
+            "pending_notifications_count": 3
+          }
+        }"#;
+        let subject: Policy = serde_json::from_str(example).expect("deserialize");
+        assert!(subject.for_not_following.is_accept());
+        assert!(subject.for_not_followers.is_filter());
+        assert!(subject.for_new_accounts.is_drop());
+        assert_eq!(subject.summary.pending_notifications_count, 3);
+    }
 }
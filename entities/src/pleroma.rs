@@ -0,0 +1,298 @@
+//! The extensions Pleroma and Akkoma make to the Mastodon API.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use time::{serde::iso8601, OffsetDateTime};
+
+use crate::{
+    account::Account, attachment::Attachment, card::Card, custom_emoji::CustomEmoji, AccountId,
+    ChatId, ChatMessageId,
+};
+
+/// The extra fields Pleroma and Akkoma add to a status, under its `pleroma`
+/// key. See [`Status::pleroma`](crate::status::Status::pleroma).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct StatusExtensions {
+    /// Whether the status was posted on this server.
+    #[serde(default)]
+    pub local: bool,
+    /// The ID of the thread the status belongs to.
+    pub conversation_id: Option<u64>,
+    /// The ID of the direct conversation the status belongs to, if it is a
+    /// direct message.
+    pub direct_conversation_id: Option<u64>,
+    /// The address of the account the status replies to.
+    pub in_reply_to_account_acct: Option<String>,
+    /// The content of the status, by MIME type, e.g. `text/plain`.
+    #[serde(default)]
+    pub content: HashMap<String, String>,
+    /// The content warning of the status, by MIME type.
+    #[serde(default)]
+    pub spoiler_text: HashMap<String, String>,
+    /// When the status will be deleted, if it was posted to expire.
+    #[serde(default, with = "iso8601::option")]
+    pub expires_at: Option<OffsetDateTime>,
+    /// Whether the user has muted the thread the status belongs to.
+    #[serde(default)]
+    pub thread_muted: bool,
+    /// The emoji reactions to the status.
+    #[serde(default)]
+    pub emoji_reactions: Vec<EmojiReaction>,
+    /// Whether the status the status replies to is visible to the user.
+    #[serde(default)]
+    pub parent_visible: bool,
+    /// When the status was pinned to its author's profile, if it was.
+    #[serde(default, with = "iso8601::option")]
+    pub pinned_at: Option<OffsetDateTime>,
+}
+
+/// The extra fields Pleroma and Akkoma add to an account, under its
+/// `pleroma` key. See [`Account::pleroma`](crate::account::Account::pleroma).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AccountExtensions {
+    /// The ActivityPub ID of the account.
+    pub ap_id: Option<String>,
+    /// The ActivityPub IDs of the account's aliases.
+    #[serde(default)]
+    pub also_known_as: Vec<String>,
+    /// The image shown behind the account's profile.
+    pub background_image: Option<String>,
+    /// The moderation tags applied to the account, such as
+    /// `mrf_tag:media-strip`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Whether the account is an administrator of the server.
+    #[serde(default)]
+    pub is_admin: bool,
+    /// Whether the account is a moderator of the server.
+    #[serde(default)]
+    pub is_moderator: bool,
+    /// Whether the account's email address has been confirmed.
+    #[serde(default)]
+    pub is_confirmed: bool,
+    /// Whether the account hides its favourites.
+    #[serde(default)]
+    pub hide_favorites: bool,
+    /// Whether the account hides who follows it.
+    #[serde(default)]
+    pub hide_followers: bool,
+    /// Whether the account hides who it follows.
+    #[serde(default)]
+    pub hide_follows: bool,
+    /// Whether the account hides how many accounts follow it.
+    #[serde(default)]
+    pub hide_followers_count: bool,
+    /// Whether the account hides how many accounts it follows.
+    #[serde(default)]
+    pub hide_follows_count: bool,
+    /// The icon of the account's server.
+    pub favicon: Option<String>,
+    /// The account's birthday, as `YYYY-MM-DD`, if it shares it.
+    pub birthday: Option<String>,
+}
+
+/// An emoji reaction to a status.
+///
+/// ```
+/// use mastodon_async_entities::pleroma::EmojiReaction;
+///
+/// let reaction: EmojiReaction = serde_json::from_str(r#"{
+///     "name": "☕",
+///     "count": 2,
+///     "me": true,
+///     "account_ids": ["1", "2"]
+/// }"#).unwrap();
+/// assert_eq!(reaction.count, 2);
+/// assert!(reaction.url.is_none());
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct EmojiReaction {
+    /// The emoji: a unicode emoji, or the shortcode of a custom one.
+    pub name: String,
+    /// How many accounts reacted with it.
+    pub count: u64,
+    /// Whether the user reacted with it.
+    pub me: bool,
+    /// The image of a custom emoji.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// The IDs of the accounts which reacted with it.
+    #[serde(default)]
+    pub account_ids: Vec<AccountId>,
+    /// The accounts which reacted with it, when listing the reactions to a
+    /// status.
+    #[serde(default)]
+    pub accounts: Vec<Account>,
+}
+
+/// A chat between the user and one other account.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Chat {
+    /// The ID of the chat.
+    pub id: ChatId,
+    /// The account the user is chatting with.
+    pub account: Account,
+    /// How many messages the user hasn't read yet.
+    pub unread: u64,
+    /// The most recent message.
+    pub last_message: Option<ChatMessage>,
+    /// When a message was last sent.
+    #[serde(with = "iso8601")]
+    pub updated_at: OffsetDateTime,
+}
+
+/// A message sent in a [`Chat`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ChatMessage {
+    /// The ID of the message.
+    pub id: ChatMessageId,
+    /// The chat the message was sent in.
+    pub chat_id: ChatId,
+    /// The account which sent the message.
+    pub account_id: AccountId,
+    /// The HTML content of the message, if it has any.
+    pub content: Option<String>,
+    /// When the message was sent.
+    #[serde(with = "iso8601")]
+    pub created_at: OffsetDateTime,
+    /// Custom emoji used in the content.
+    #[serde(default)]
+    pub emojis: Vec<CustomEmoji>,
+    /// Media attached to the message.
+    pub attachment: Option<Attachment>,
+    /// A preview of a link in the content.
+    pub card: Option<Card>,
+    /// Whether the user hasn't read the message yet.
+    #[serde(default)]
+    pub unread: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::Status;
+
+    #[test]
+    fn test_chat() {
+        let example = r#"{
+            "id": "9ti3",
+            "account": {
+                "id": "9tga",
+                "username": "friend",
+                "acct": "friend@example.social",
+                "display_name": "Friend",
+                "locked": false,
+                "bot": false,
+                "created_at": "2020-04-01T00:00:00.000Z",
+                "note": "",
+                "url": "https://example.social/users/friend",
+                "avatar": "https://example.social/avatar.png",
+                "avatar_static": "https://example.social/avatar.png",
+                "header": "https://example.social/header.png",
+                "header_static": "https://example.social/header.png",
+                "followers_count": 1,
+                "following_count": 1,
+                "statuses_count": 1,
+                "emojis": [],
+                "fields": [],
+                "pleroma": {
+                    "ap_id": "https://example.social/users/friend",
+                    "is_admin": true,
+                    "hide_follows": true
+                }
+            },
+            "unread": 1,
+            "last_message": {
+                "id": "9ti4",
+                "chat_id": "9ti3",
+                "account_id": "9tga",
+                "content": "hi :blob:",
+                "created_at": "2020-04-21T15:06:45.000Z",
+                "emojis": [],
+                "attachment": null,
+                "card": null,
+                "unread": true
+            },
+            "updated_at": "2020-04-21T15:06:45.000Z"
+        }"#;
+        let chat: Chat = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(chat.id, ChatId::new("9ti3"));
+        let message = chat.last_message.expect("last message");
+        assert_eq!(message.chat_id, chat.id);
+        assert!(message.unread);
+        let extensions = chat.account.pleroma.expect("account extensions");
+        assert!(extensions.is_admin);
+        assert!(extensions.hide_follows);
+        assert!(!extensions.hide_followers);
+    }
+
+    #[test]
+    fn test_status_extensions() {
+        let example = r#"{
+            "id": "103270115826048975",
+            "created_at": "2019-12-08T03:48:33.901Z",
+            "in_reply_to_id": null,
+            "in_reply_to_account_id": null,
+            "sensitive": false,
+            "spoiler_text": "",
+            "visibility": "public",
+            "language": "en",
+            "uri": "https://example.social/objects/1",
+            "url": "https://example.social/notice/1",
+            "replies_count": 0,
+            "reblogs_count": 0,
+            "favourites_count": 0,
+            "favourited": false,
+            "reblogged": false,
+            "muted": false,
+            "bookmarked": false,
+            "content": "<p>tea or coffee?</p>",
+            "reblog": null,
+            "account": {
+                "id": "9tga",
+                "username": "friend",
+                "acct": "friend",
+                "display_name": "Friend",
+                "locked": false,
+                "bot": false,
+                "created_at": "2020-04-01T00:00:00.000Z",
+                "note": "",
+                "url": "https://example.social/users/friend",
+                "avatar": "https://example.social/avatar.png",
+                "avatar_static": "https://example.social/avatar.png",
+                "header": "https://example.social/header.png",
+                "header_static": "https://example.social/header.png",
+                "followers_count": 1,
+                "following_count": 1,
+                "statuses_count": 1,
+                "emojis": [],
+                "fields": []
+            },
+            "media_attachments": [],
+            "mentions": [],
+            "tags": [],
+            "emojis": [],
+            "card": null,
+            "poll": null,
+            "pleroma": {
+                "local": true,
+                "conversation_id": 42,
+                "content": {"text/plain": "tea or coffee?"},
+                "expires_at": null,
+                "thread_muted": false,
+                "emoji_reactions": [
+                    {"name": "☕", "count": 2, "me": true}
+                ]
+            }
+        }"#;
+        let status: Status = serde_json::from_str(example).expect("deserialize");
+        let extensions = status.pleroma.expect("status extensions");
+        assert!(extensions.local);
+        assert_eq!(extensions.conversation_id, Some(42));
+        assert_eq!(extensions.content["text/plain"], "tea or coffee?");
+        assert_eq!(extensions.emoji_reactions[0].name, "☕");
+        assert!(extensions.emoji_reactions[0].me);
+        assert!(status.account.pleroma.is_none());
+    }
+}
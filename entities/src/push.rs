@@ -8,35 +8,35 @@ use crate::SubscriptionId;
 #[builder(build_fn(error = "crate::error::Error"), default)]
 pub struct Alerts {
     /// flag for follow alerts
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[builder(setter(strip_option))]
     pub follow: Option<bool>,
     /// flag for favourite alerts
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[builder(setter(strip_option))]
     pub favourite: Option<bool>,
     /// flag for reblog alerts
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[builder(setter(strip_option))]
     pub reblog: Option<bool>,
     /// flag for mention alerts
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[builder(setter(strip_option))]
     pub mention: Option<bool>,
     /// Receive a push notification when a subscribed account posts a status?
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[builder(setter(strip_option))]
     pub status: Option<bool>,
     /// Receive a push notification when someone has requested to followed you?
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[builder(setter(strip_option))]
     pub follow_request: Option<bool>,
     /// Receive a push notification when a poll you voted in or created has ended?
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[builder(setter(strip_option))]
     pub poll: Option<bool>,
     /// Receive a push notification when a status you interacted with has been edited?
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[builder(setter(strip_option))]
     pub update: Option<bool>,
     /// Admin-related alerts settings
@@ -48,10 +48,18 @@ pub struct Alerts {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct AdminAlerts {
     /// Receive a push notification when a new user has signed up?
-    #[serde(default, rename = "admin.sign_up")]
+    #[serde(
+        default,
+        rename = "admin.sign_up",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub sign_up: Option<bool>,
     /// Receive a push notification when a new report has been filed?
-    #[serde(default, rename = "admin.report")]
+    #[serde(
+        default,
+        rename = "admin.report",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub report: Option<bool>,
 }
 
@@ -96,12 +104,33 @@ pub struct Subscription {
     pub server_key: String,
     /// Which alerts should be delivered to the endpoint.
     pub alerts: Alerts,
+    /// Whose notifications should be delivered to the endpoint. Missing
+    /// before Mastodon 4.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<Policy>,
+}
+
+/// Whose notifications a push subscription receives alerts for.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/methods/push/#create)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Policy {
+    /// Notifications from anyone.
+    #[default]
+    All,
+    /// Notifications from accounts the user follows.
+    Followed,
+    /// Notifications from accounts which follow the user.
+    Follower,
+    /// No notifications at all.
+    None,
 }
 
 pub mod add_subscription {
     use serde::Serialize;
 
-    use super::Alerts;
+    use super::{Alerts, Policy};
 
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
     pub struct Form {
@@ -124,17 +153,21 @@ pub mod add_subscription {
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
     pub struct Data {
         pub alerts: Option<Alerts>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub policy: Option<Policy>,
     }
 }
 
 pub mod update_data {
     use serde::Serialize;
 
-    use super::Alerts;
+    use super::{Alerts, Policy};
 
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
     pub struct Data {
         pub alerts: Option<Alerts>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub policy: Option<Policy>,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
@@ -147,6 +180,7 @@ pub mod update_data {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::form_round_trip_test;
 
     #[test]
     fn test_deserialize_subscription() {
@@ -175,6 +209,57 @@ mod tests {
         assert!(subject.alerts.status.is_none());
         assert!(subject.alerts.follow_request.is_none());
         assert!(subject.alerts.update.is_none());
+        assert!(subject.policy.is_none());
         assert_eq!(subject.server_key, "BCk-QqERU0q-CfYZjcuB6lnyyOYfJ2AifKqfeGIm7Z-HiTU5T9eTG5GxVA0_OH5mMlI4UkkDTpaZwozy0TzdZ2M=");
     }
+
+    #[test]
+    fn test_deserialize_subscription_with_policy() {
+        let example = r#"{
+          "id": "328184",
+          "endpoint": "https://yourdomain.example/listener",
+          "standard": true,
+          "alerts": {
+            "mention": true,
+            "status": false,
+            "update": true,
+            "admin.sign_up": true,
+            "admin.report": false
+          },
+          "policy": "follower",
+          "server_key": "BCk-QqERU0q-CfYZjcuB6lnyyOYfJ2AifKqfeGIm7Z-HiTU5T9eTG5GxVA0_OH5mMlI4UkkDTpaZwozy0TzdZ2M="
+        }"#;
+        let subject: Subscription = serde_json::from_str(example).unwrap();
+        assert_eq!(subject.policy, Some(Policy::Follower));
+        assert!(subject.alerts.update.unwrap());
+        assert!(!subject.alerts.status.unwrap());
+        assert!(subject.alerts.admin.sign_up.unwrap());
+        assert!(!subject.alerts.admin.report.unwrap());
+    }
+
+    #[test]
+    fn test_serialize_data() {
+        let mut data = update_data::Data::default();
+        let serialized = serde_json::to_value(&data).unwrap();
+        assert!(serialized.get("policy").is_none());
+        data.policy = Some(Policy::None);
+        let serialized = serde_json::to_value(&data).unwrap();
+        assert_eq!(serialized["policy"], "none");
+    }
+
+    form_round_trip_test!(test_alerts_minimal(Alerts): AlertsBuilder::default().build().unwrap());
+    form_round_trip_test!(test_alerts(Alerts): AlertsBuilder::default()
+        .follow(true)
+        .favourite(false)
+        .reblog(true)
+        .mention(true)
+        .status(false)
+        .follow_request(true)
+        .poll(false)
+        .update(true)
+        .build()
+        .unwrap());
+    form_round_trip_test!(
+        test_admin_alerts(Alerts): *Alerts::default().sign_up(true).report(false)
+    );
 }
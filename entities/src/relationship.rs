@@ -1,9 +1,11 @@
 //! module containing everything relating to a relationship with
 //! another account.
+use derive_is_enum_variant::is_enum_variant;
 use isolang::Language;
 use serde::{Deserialize, Serialize};
+use time::{serde::iso8601, OffsetDateTime};
 
-use crate::RelationshipId;
+use crate::{forms::account::FollowOptions, RelationshipId, SeveranceEventId};
 
 /// Represents the relationship between accounts, such as following / blocking / muting / etc.
 ///
@@ -42,6 +44,92 @@ pub struct Relationship {
     pub note: String,
 }
 
+impl Relationship {
+    /// The options out of `options` which would change how this account is
+    /// followed, so that an update sends only what differs. Languages are
+    /// compared regardless of order.
+    ///
+    /// ```
+    /// use mastodon_async_entities::prelude::*;
+    /// use forms::account::FollowOptions;
+    /// # let relationship: Relationship = serde_json::from_str(r#"{
+    /// #     "id": "1", "following": true, "showing_reblogs": true, "notifying": false,
+    /// #     "followed_by": false, "blocking": false, "blocked_by": false, "muting": false,
+    /// #     "muting_notifications": false, "requested": false, "domain_blocking": false,
+    /// #     "endorsed": false, "note": ""
+    /// # }"#).unwrap();
+    ///
+    /// let wanted = FollowOptions::builder().reblogs(true).notify(true).build().unwrap();
+    /// let changes = relationship.diff(&wanted);
+    /// assert_eq!(changes, FollowOptions::builder().notify(true).build().unwrap());
+    /// ```
+    pub fn diff(&self, options: &FollowOptions) -> FollowOptions {
+        let mut changes = FollowOptions::builder();
+        if let Some(reblogs) = options.reblogs().filter(|it| *it != self.showing_reblogs) {
+            changes.reblogs(reblogs);
+        }
+        if let Some(notify) = options.notify().filter(|it| *it != self.notifying) {
+            changes.notify(notify);
+        }
+        if let Some(languages) = options.languages() {
+            let mut wanted = languages.to_vec();
+            let mut current = self.languages.clone();
+            wanted.sort_by_key(|it| it.to_639_3());
+            wanted.dedup();
+            current.sort_by_key(|it| it.to_639_3());
+            current.dedup();
+            if wanted != current {
+                changes.languages(languages.to_vec());
+            }
+        }
+        changes
+            .build()
+            .expect("follow options have no required fields")
+    }
+}
+
+/// An event which removed some of the user's follows or followers, such as
+/// a block of a whole server.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/RelationshipSeveranceEvent/)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SeveranceEvent {
+    /// The ID of the event in the database.
+    pub id: SeveranceEventId,
+    /// What caused the relationships to be removed.
+    #[serde(rename = "type")]
+    pub event_type: SeveranceType,
+    /// Whether the list of removed relationships is no longer available.
+    pub purged: bool,
+    /// The domain or account which was blocked or suspended.
+    pub target_name: String,
+    /// How many of the user's followers were removed.
+    #[serde(default)]
+    pub followers_count: u64,
+    /// How many of the accounts the user followed were removed.
+    #[serde(default)]
+    pub following_count: u64,
+    /// When the event happened.
+    #[serde(with = "iso8601")]
+    pub created_at: OffsetDateTime,
+}
+
+/// What caused relationships to be removed. See
+/// [`SeveranceEvent::event_type`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum SeveranceType {
+    /// A moderator blocked a whole server.
+    DomainBlock,
+    /// The user blocked a whole server.
+    UserDomainBlock,
+    /// A moderator suspended an account.
+    AccountSuspension,
+    /// A cause this version of the crate doesn't know about.
+    #[cfg_attr(not(feature = "strict"), serde(other))]
+    Unknown,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +166,44 @@ mod tests {
         assert!(!subject.endorsed);
         assert!(subject.note.is_empty());
     }
+
+    #[test]
+    fn test_diff() {
+        let relationship = Relationship {
+            id: RelationshipId::new("1"),
+            following: true,
+            followed_by: false,
+            blocking: false,
+            blocked_by: false,
+            muting: false,
+            requested: false,
+            muting_notifications: false,
+            domain_blocking: false,
+            showing_reblogs: false,
+            notifying: true,
+            languages: vec![Language::Eng, Language::Deu],
+            endorsed: false,
+            note: String::new(),
+        };
+        let unchanged = FollowOptions::builder()
+            .reblogs(false)
+            .notify(true)
+            .languages(vec![Language::Deu, Language::Eng])
+            .build()
+            .unwrap();
+        assert!(relationship.diff(&unchanged).is_empty());
+
+        let wanted = FollowOptions::builder()
+            .reblogs(true)
+            .notify(true)
+            .languages(vec![Language::Eng])
+            .build()
+            .unwrap();
+        let expected = FollowOptions::builder()
+            .reblogs(true)
+            .languages(vec![Language::Eng])
+            .build()
+            .unwrap();
+        assert_eq!(relationship.diff(&wanted), expected);
+    }
 }
@@ -43,7 +43,11 @@ pub enum Category {
     Spam,
     /// Violates one or more specific rules
     Violation,
-    /// The default (catch-all) category
+    /// Illegal content, reported under the law of the server's jurisdiction
+    Legal,
+    /// The default (catch-all) category, also used for categories this
+    /// version of the crate doesn't know about.
+    #[cfg_attr(not(feature = "strict"), serde(other))]
     Other,
 }
 
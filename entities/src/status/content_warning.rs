@@ -0,0 +1,198 @@
+//! Automatically adding content warnings to new statuses.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// A set of rules for which content warnings must be attached to a status,
+/// based on its text. Apply it with
+/// [`NewStatusBuilder::apply_policy`](super::NewStatusBuilder::apply_policy).
+///
+/// Policies can be deserialized, e.g. from TOML:
+///
+/// ```toml
+/// [[rules]]
+/// keyword = "election"
+/// spoiler_text = "politics"
+///
+/// [[rules]]
+/// regex = "\\bspiders?\\b"
+/// spoiler_text = "spiders"
+/// sensitive = true
+/// ```
+///
+/// ```
+/// use mastodon_async_entities::status::content_warning::{CwPolicy, Matcher, Rule};
+///
+/// let policy = CwPolicy {
+///     rules: vec![Rule {
+///         matcher: Matcher::Keyword("election".to_string()),
+///         spoiler_text: "politics".to_string(),
+///         sensitive: false,
+///     }],
+/// };
+/// assert_eq!(
+///     policy.spoiler_text_for("Go vote in the Election!").as_deref(),
+///     Some("politics")
+/// );
+/// assert!(policy.spoiler_text_for("Nice weather today").is_none());
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CwPolicy {
+    /// The rules to check, in order.
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+/// A single rule of a [`CwPolicy`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Rule {
+    /// What in a status's text triggers this rule.
+    #[serde(flatten)]
+    pub matcher: Matcher,
+    /// The content warning to attach when this rule matches.
+    pub spoiler_text: String,
+    /// Whether to also mark the status as sensitive when this rule matches.
+    #[serde(default, skip_serializing_if = "crate::helpers::is_false")]
+    pub sensitive: bool,
+}
+
+/// How a [`Rule`] decides whether it applies to a status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Matcher {
+    /// The status contains the given keyword, ignoring case.
+    Keyword(String),
+    /// The regular expression matches somewhere in the status.
+    Regex(#[serde(with = "serde_regex")] Regex),
+}
+
+impl PartialEq for Matcher {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Matcher::Keyword(a), Matcher::Keyword(b)) => a == b,
+            (Matcher::Regex(a), Matcher::Regex(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl Matcher {
+    /// Whether the given status text triggers this matcher.
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            Matcher::Keyword(keyword) => text.to_lowercase().contains(&keyword.to_lowercase()),
+            Matcher::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+impl CwPolicy {
+    /// The rules which apply to the given status text.
+    pub fn matching<'a>(&'a self, text: &'a str) -> impl Iterator<Item = &'a Rule> + 'a {
+        self.rules.iter().filter(|rule| rule.matcher.is_match(text))
+    }
+
+    /// The distinct content warnings of all rules which apply to the given
+    /// status text, in the order the rules are listed.
+    pub fn warnings_for<'a>(&'a self, text: &'a str) -> Vec<&'a str> {
+        let mut warnings: Vec<&str> = vec![];
+        for rule in self.matching(text) {
+            if !warnings.contains(&rule.spoiler_text.as_str()) {
+                warnings.push(&rule.spoiler_text);
+            }
+        }
+        warnings
+    }
+
+    /// The content warning required for the given status text, combining
+    /// those of all matching rules, or `None` if no rule matches.
+    pub fn spoiler_text_for(&self, text: &str) -> Option<String> {
+        let warnings = self.warnings_for(text);
+        if warnings.is_empty() {
+            None
+        } else {
+            Some(warnings.join(", "))
+        }
+    }
+
+    /// Whether the given status text must be marked sensitive.
+    pub fn requires_sensitive(&self, text: &str) -> bool {
+        self.matching(text).any(|rule| rule.sensitive)
+    }
+}
+
+mod serde_regex {
+    use regex::Regex;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S>(regex: &Regex, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(regex.as_str())
+    }
+
+    pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<Regex, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern = String::deserialize(deserializer)?;
+        Regex::new(&pattern).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> CwPolicy {
+        serde_json::from_str(
+            r#"{
+                "rules": [
+                    {"keyword": "Election", "spoiler_text": "politics"},
+                    {"keyword": "vote", "spoiler_text": "politics"},
+                    {"regex": "\\bspiders?\\b", "spoiler_text": "spiders", "sensitive": true}
+                ]
+            }"#,
+        )
+        .expect("deserialize policy")
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let policy = policy();
+        assert_eq!(policy.rules.len(), 3);
+        assert_eq!(
+            policy.rules[0].matcher,
+            Matcher::Keyword("Election".to_string())
+        );
+        assert!(!policy.rules[0].sensitive);
+        assert!(matches!(policy.rules[2].matcher, Matcher::Regex(_)));
+        assert!(policy.rules[2].sensitive);
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let result: Result<CwPolicy, _> =
+            serde_json::from_str(r#"{"rules": [{"regex": "(", "spoiler_text": "oops"}]}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_spoiler_text_for() {
+        let policy = policy();
+        assert_eq!(
+            policy
+                .spoiler_text_for("go vote in the election")
+                .as_deref(),
+            Some("politics")
+        );
+        assert_eq!(
+            policy.spoiler_text_for("election spiders").as_deref(),
+            Some("politics, spiders")
+        );
+        assert!(policy.spoiler_text_for("spiderman").is_none());
+        assert!(policy.requires_sensitive("a spider"));
+        assert!(!policy.requires_sensitive("vote"));
+    }
+}
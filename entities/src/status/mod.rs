@@ -1,15 +1,18 @@
 //! Module containing all info relating to a status.
 
+pub mod content_warning;
 pub mod edit;
 /// For building a new status
 pub mod new;
 pub mod poll;
+pub mod quote;
 pub mod scheduled;
 pub mod source;
 
+pub use content_warning::CwPolicy;
 pub use edit::Edit;
 use isolang::Language;
-pub use new::{NewStatus, NewStatusBuilder};
+pub use new::{NewPoll, NewPollBuilder, NewStatus, NewStatusBuilder};
 pub use poll::{Poll, PollBuilder};
 pub use scheduled::Status as Scheduled;
 pub use source::Source;
@@ -104,6 +107,34 @@ pub struct Status {
     /// that matched this status.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub filtered: Vec<filter::Result>,
+    /// The status this status quotes, if it is a quote post.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<quote::Quote>,
+    /// Who may quote this status, if the server supports quote posts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_approval: Option<quote::Approval>,
+    /// The emoji reactions to this status, if the server supports them, as
+    /// glitch-soc and Firefish do.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<crate::announcement::Reaction>,
+    /// The extra fields Pleroma and Akkoma add to statuses, if the server
+    /// sent them.
+    #[cfg(feature = "pleroma")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pleroma: Option<crate::pleroma::StatusExtensions>,
+}
+
+impl Status {
+    /// Whether this status was posted using the given application, e.g. to
+    /// let a bot recognise its own posts.
+    ///
+    /// Always `false` if the server didn't say which application was used,
+    /// which is the case for statuses from other instances.
+    pub fn posted_with(&self, app: &crate::application::Application) -> bool {
+        self.application
+            .as_ref()
+            .is_some_and(|posted_by| app.posted(posted_by))
+    }
 }
 
 /// Represents a hashtag used within the content of a status.
@@ -253,7 +284,7 @@ mod tests {
             "https://mastodon.social/users/Gargron/statuses/103270115826048975"
         );
         assert_eq!(
-            status.url.expect("url").as_ref(),
+            status.url.as_ref().expect("url").as_ref(),
             "https://mastodon.social/@Gargron/103270115826048975"
         );
         assert_eq!(status.replies_count, 5);
@@ -265,6 +296,10 @@ mod tests {
         assert!(!status.bookmarked.expect("bookmarked"));
         assert_eq!(status.content, "<p>&quot;I lost my inheritance with one wrong digit on my sort code&quot;</p><p><a href=\"https://www.theguardian.com/money/2019/dec/07/i-lost-my-193000-inheritance-with-one-wrong-digit-on-my-sort-code\" rel=\"nofollow noopener noreferrer\" target=\"_blank\"><span class=\"invisible\">https://www.</span><span class=\"ellipsis\">theguardian.com/money/2019/dec</span><span class=\"invisible\">/07/i-lost-my-193000-inheritance-with-one-wrong-digit-on-my-sort-code</span}</p>");
         assert!(status.reblog.is_none());
+        let web: crate::application::Application =
+            serde_json::from_str(r#"{"name": "Web", "website": null, "vapid_key": "key"}"#)
+                .expect("web app");
+        assert!(status.posted_with(&web));
         let app = status.application.expect("application");
         assert_eq!(app.name, "Web");
         assert!(
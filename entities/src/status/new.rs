@@ -1,13 +1,18 @@
 use derive_builder::Builder;
 use isolang::Language;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use time::{serde::rfc3339, OffsetDateTime};
 
-use crate::{prelude::Visibility, AttachmentId};
+use super::{quote, CwPolicy};
+use crate::{
+    error::Error, instance::Instance, preferences::Preferences, prelude::Visibility,
+    text::character_count, AttachmentId, StatusId,
+};
 
 /// Represents a post that can be sent to the POST /api/v1/status endpoint
 ///
 /// See also [the API documentation](https://docs.joinmastodon.org/methods/statuses/#form-data-parameters)
-#[derive(Debug, Builder, Default, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Builder, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[builder(build_fn(error = "crate::error::Error"))]
 pub struct NewStatus {
     /// The text content of the status. If media_ids is provided, this becomes
@@ -51,11 +56,265 @@ pub struct NewStatus {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[builder(default, setter(into, strip_option))]
     pub content_type: Option<String>,
+    /// ID of the status to quote. Only supported by servers with quote posts
+    /// (Mastodon 4.5+).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    pub quoted_status_id: Option<StatusId>,
+    /// Who may quote the status. Only supported by servers with quote posts
+    /// (Mastodon 4.5+).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub quote_approval_policy: Option<quote::Policy>,
+    /// A poll to attach to the status. A status can't have both a poll and
+    /// media.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub poll: Option<NewPoll>,
+    /// When to publish the status, at least five minutes from now. A status
+    /// with this set is scheduled rather than posted straight away, so send
+    /// it with [`Mastodon::schedule_status()`](https://docs.rs/mastodon-async/latest/mastodon_async/mastodon/struct.Mastodon.html#method.schedule_status).
+    #[serde(
+        with = "rfc3339::option",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    #[builder(default, setter(strip_option))]
+    pub scheduled_at: Option<OffsetDateTime>,
+    /// ID of the status to quote, on servers which had quote posts before
+    /// Mastodon did, such as Fedibird and glitch-soc. Mastodon itself uses
+    /// [`quoted_status_id`](NewStatus::quoted_status_id).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    pub quote_id: Option<StatusId>,
+    /// Only show the status to users of the same server. Only supported by
+    /// some servers, such as glitch-soc and Hometown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub local_only: Option<bool>,
+}
+
+/// A poll to attach to a new status.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let poll = status::new::NewPollBuilder::default()
+///     .add_option("tea")
+///     .add_option("coffee")
+///     .expires_in(24 * 60 * 60)
+///     .build()
+///     .unwrap();
+/// let status = NewStatusBuilder::default()
+///     .status("tea or coffee?")
+///     .poll(poll)
+///     .build()
+///     .unwrap();
+/// assert_eq!(serde_json::to_string_pretty(&status.poll).unwrap(), r#"{
+///   "options": [
+///     "tea",
+///     "coffee"
+///   ],
+///   "expires_in": 86400
+/// }"#);
+/// ```
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/methods/statuses/#create)
+#[derive(Debug, Builder, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[builder(build_fn(error = "crate::error::Error"))]
+pub struct NewPoll {
+    /// The choices to vote for, of which there must be at least two.
+    #[builder(default, setter(into))]
+    pub options: Vec<String>,
+    /// How long the poll is open for, in seconds.
+    pub expires_in: u64,
+    /// Whether more than one choice may be voted for. Defaults to false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub multiple: Option<bool>,
+    /// Whether to hide how many votes each choice has until the poll ends.
+    /// Defaults to false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub hide_totals: Option<bool>,
+}
+
+impl NewPollBuilder {
+    /// Add a choice to vote for.
+    pub fn add_option(&mut self, option: impl Into<String>) -> &mut Self {
+        self.options
+            .get_or_insert_with(Vec::new)
+            .push(option.into());
+        self
+    }
+}
+
+/// Default values to be applied to each new status, so they don't have to be
+/// repeated for every post.
+///
+/// These can be configured by hand, or taken from the user's
+/// [`Preferences`].
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let defaults = status::new::Defaults {
+///     visibility: Some(Visibility::Unlisted),
+///     ..Default::default()
+/// };
+/// let status = NewStatusBuilder::with_defaults(&defaults)
+///     .status("hello")
+///     .build()
+///     .unwrap();
+/// assert_eq!(status.visibility, Some(Visibility::Unlisted));
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Defaults {
+    /// The visibility to post with, unless otherwise specified.
+    pub visibility: Option<Visibility>,
+    /// The language to post in, unless otherwise specified.
+    pub language: Option<Language>,
+    /// Whether posts should be marked sensitive, unless otherwise specified.
+    pub sensitive: Option<bool>,
+    /// The content type to post with, unless otherwise specified.
+    pub content_type: Option<String>,
+}
+
+impl AsRef<Defaults> for Defaults {
+    fn as_ref(&self) -> &Defaults {
+        self
+    }
+}
+
+impl From<Preferences> for Defaults {
+    fn from(value: Preferences) -> Self {
+        let defaults = value.posting.default;
+        Defaults {
+            visibility: Some(defaults.visibility),
+            language: defaults.language,
+            sensitive: Some(defaults.sensitive),
+            content_type: None,
+        }
+    }
+}
+
+impl NewStatusBuilder {
+    /// Create a builder pre-populated with the given defaults. Any of them
+    /// may still be overridden by calling the corresponding setter.
+    pub fn with_defaults(defaults: impl AsRef<Defaults>) -> Self {
+        let defaults = defaults.as_ref();
+        let mut builder = Self::default();
+        if let Some(visibility) = defaults.visibility {
+            builder.visibility(visibility);
+        }
+        if let Some(language) = defaults.language {
+            builder.language(language);
+        }
+        if let Some(sensitive) = defaults.sensitive {
+            builder.sensitive(sensitive);
+        }
+        if let Some(content_type) = &defaults.content_type {
+            builder.content_type(content_type.clone());
+        }
+        builder
+    }
+
+    /// Attach the content warnings required by the given policy for the
+    /// status text set so far, marking the status sensitive if a matching
+    /// rule says so.
+    ///
+    /// Warnings which are already part of the spoiler text aren't repeated.
+    /// Set the status text before calling this, since it can only check what
+    /// has been set.
+    ///
+    /// ```
+    /// use mastodon_async_entities::{prelude::*, status::content_warning::{Matcher, Rule}};
+    ///
+    /// let policy = status::CwPolicy {
+    ///     rules: vec![Rule {
+    ///         matcher: Matcher::Keyword("spider".to_string()),
+    ///         spoiler_text: "spiders".to_string(),
+    ///         sensitive: true,
+    ///     }],
+    /// };
+    /// let status = NewStatusBuilder::default()
+    ///     .status("look at this spider")
+    ///     .apply_policy(&policy)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(status.spoiler_text.as_deref(), Some("spiders"));
+    /// assert_eq!(status.sensitive, Some(true));
+    /// ```
+    pub fn apply_policy(&mut self, policy: &CwPolicy) -> &mut Self {
+        let Some(Some(text)) = &self.status else {
+            return self;
+        };
+        if policy.requires_sensitive(text) {
+            self.sensitive = Some(Some(true));
+        }
+        let existing = self.spoiler_text.clone().flatten().unwrap_or_default();
+        let missing: Vec<&str> = policy
+            .warnings_for(text)
+            .into_iter()
+            .filter(|warning| !existing.to_lowercase().contains(&warning.to_lowercase()))
+            .collect();
+        if missing.is_empty() {
+            return self;
+        }
+        let spoiler_text = if existing.trim().is_empty() {
+            missing.join(", ")
+        } else {
+            format!("{existing}, {}", missing.join(", "))
+        };
+        self.spoiler_text = Some(Some(spoiler_text));
+        self
+    }
+
+    /// Check the status set so far against the limits of the server it's
+    /// to be posted to, so that it can be fixed before posting instead of
+    /// being rejected. The status text and content warning together may only
+    /// be as long as the server allows, counted as the server counts them
+    /// (see [`character_count`]), and only so many media may be attached.
+    ///
+    /// ```
+    /// use mastodon_async_entities::{instance::Instance, prelude::*, Error};
+    ///
+    /// fn check(instance: &Instance) {
+    ///     let mut builder = NewStatusBuilder::default();
+    ///     builder.status("a".repeat(1000));
+    ///     let result = builder.validate_against(instance);
+    ///     assert!(matches!(result, Err(Error::StatusTooLong { count: 1000, .. })));
+    /// }
+    /// ```
+    pub fn validate_against(&self, instance: &Instance) -> Result<&Self, Error> {
+        let configuration = &instance.configuration;
+        let limits = &configuration.statuses;
+        let count = [&self.status, &self.spoiler_text]
+            .into_iter()
+            .filter_map(|text| text.as_ref()?.as_deref())
+            .map(|text| character_count(text, configuration))
+            .sum();
+        let max = usize::try_from(limits.max_characters).unwrap_or_default();
+        if count > max {
+            return Err(Error::StatusTooLong { count, max });
+        }
+        let count = self
+            .media_ids
+            .as_ref()
+            .and_then(Option::as_ref)
+            .map_or(0, Vec::len);
+        let max = usize::try_from(limits.max_media_attachments).unwrap_or_default();
+        if count > max {
+            return Err(Error::TooManyMedia { count, max });
+        }
+        Ok(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::form_round_trip_test;
     use isolang::Language;
     use serde_json;
 
@@ -74,10 +333,102 @@ mod tests {
             visibility: None,
             language: None,
             content_type: None,
+            quoted_status_id: None,
+            quote_approval_policy: None,
+            poll: None,
+            scheduled_at: None,
+            quote_id: None,
+            local_only: None,
         };
         assert_eq!(s, expected);
     }
 
+    #[test]
+    fn test_apply_policy() {
+        let policy: CwPolicy = serde_json::from_str(
+            r#"{
+                "rules": [
+                    {"keyword": "election", "spoiler_text": "politics"},
+                    {"keyword": "spider", "spoiler_text": "spiders", "sensitive": true}
+                ]
+            }"#,
+        )
+        .expect("deserialize policy");
+
+        let s = NewStatusBuilder::default()
+            .status("nice weather")
+            .apply_policy(&policy)
+            .build()
+            .expect("Couldn't build status");
+        assert!(s.spoiler_text.is_none());
+        assert!(s.sensitive.is_none());
+
+        let s = NewStatusBuilder::default()
+            .status("spiders at the election")
+            .apply_policy(&policy)
+            .build()
+            .expect("Couldn't build status");
+        assert_eq!(s.spoiler_text.as_deref(), Some("politics, spiders"));
+        assert_eq!(s.sensitive, Some(true));
+
+        let s = NewStatusBuilder::default()
+            .status("election results")
+            .spoiler_text("Politics")
+            .apply_policy(&policy)
+            .build()
+            .expect("Couldn't build status");
+        assert_eq!(s.spoiler_text.as_deref(), Some("Politics"));
+
+        let s = NewStatusBuilder::default()
+            .status("a spider voted in the election")
+            .spoiler_text("long post")
+            .apply_policy(&policy)
+            .build()
+            .expect("Couldn't build status");
+        assert_eq!(
+            s.spoiler_text.as_deref(),
+            Some("long post, politics, spiders")
+        );
+
+        let s = NewStatusBuilder::default()
+            .apply_policy(&policy)
+            .status("set too late: spider")
+            .build()
+            .expect("Couldn't build status");
+        assert!(s.spoiler_text.is_none());
+    }
+
+    #[test]
+    fn test_with_defaults() {
+        let defaults = Defaults {
+            visibility: Some(Visibility::Private),
+            language: Some(Language::Deu),
+            sensitive: Some(true),
+            content_type: Some("text/markdown".to_string()),
+        };
+        let s = NewStatusBuilder::with_defaults(&defaults)
+            .status("a status")
+            .visibility(Visibility::Direct)
+            .build()
+            .expect("Couldn't build status");
+        assert_eq!(s.visibility, Some(Visibility::Direct));
+        assert_eq!(s.language, Some(Language::Deu));
+        assert_eq!(s.sensitive, Some(true));
+        assert_eq!(s.content_type.as_deref(), Some("text/markdown"));
+
+        let s = NewStatusBuilder::with_defaults(Defaults::default())
+            .status("a status")
+            .build()
+            .expect("Couldn't build status");
+        assert_eq!(
+            s,
+            NewStatus {
+                status: Some("a status".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
     #[test]
     fn test_default_visibility() {
         let v: Visibility = Default::default();
@@ -125,4 +476,35 @@ mod tests {
             "{\"status\":\"a status\",\"language\":\"eng\"}"
         );
     }
+
+    form_round_trip_test!(test_new_minimal(NewStatus): NewStatusBuilder::default()
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_new_full(NewStatus): NewStatusBuilder::default()
+        .status("a status")
+        .in_reply_to_id("1")
+        .media_ids([AttachmentId::new("2")])
+        .sensitive(true)
+        .spoiler_text("a warning")
+        .visibility(Visibility::Unlisted)
+        .language(Language::Eng)
+        .content_type("text/markdown")
+        .build()
+        .unwrap());
+    form_round_trip_test!(test_new_poll(NewStatus): NewStatusBuilder::default()
+        .status("tea or coffee?")
+        .poll(
+            NewPollBuilder::default()
+                .add_option("tea")
+                .add_option("coffee")
+                .expires_in(300)
+                .multiple(true)
+                .build()
+                .unwrap()
+        )
+        .scheduled_at(OffsetDateTime::from_unix_timestamp(1_893_499_200).unwrap())
+        .quote_id(StatusId::new("3"))
+        .local_only(true)
+        .build()
+        .unwrap());
 }
@@ -0,0 +1,152 @@
+//! Quote posts, which embed another status, and who may quote a status.
+//!
+//! Servers only return these from Mastodon 4.5 onwards, so every field using
+//! them is optional, and unknown values deserialize to an `Unknown` variant
+//! rather than failing.
+
+use derive_is_enum_variant::is_enum_variant;
+use serde::{Deserialize, Serialize};
+
+use super::Status;
+use crate::StatusId;
+
+/// The status quoted by another status, and whether its author allowed it.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/Quote/)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Quote {
+    /// Whether the quote has been approved by the author of the quoted
+    /// status.
+    pub state: State,
+    /// The quoted status, if the quote was accepted and the status can be
+    /// shown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quoted_status: Option<Box<Status>>,
+    /// The ID of the quoted status, given instead of the status itself when
+    /// the quoting status is itself quoted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quoted_status_id: Option<StatusId>,
+}
+
+/// Whether a quote may be shown.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum State {
+    /// The quote hasn't been approved or rejected yet.
+    Pending,
+    /// The quote has been approved, and can be shown.
+    Accepted,
+    /// The author of the quoted status refused the quote.
+    Rejected,
+    /// The author of the quoted status approved the quote, then withdrew
+    /// the approval.
+    Revoked,
+    /// The quoted status has been deleted.
+    Deleted,
+    /// The user isn't allowed to see the quoted status.
+    Unauthorized,
+    /// The user has blocked the author of the quoted status.
+    BlockedAccount,
+    /// The user has blocked the domain of the quoted status.
+    BlockedDomain,
+    /// The user has muted the author of the quoted status.
+    MutedAccount,
+    /// A state this version of the crate doesn't know about.
+    #[cfg_attr(not(feature = "strict"), serde(other))]
+    Unknown,
+}
+
+/// Who may quote a status, and whether the user may.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/Status/#quote_approval)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Approval {
+    /// Who may quote the status without their quote being reviewed.
+    #[serde(default)]
+    pub automatic: Vec<Audience>,
+    /// Who may quote the status, subject to the author's approval.
+    #[serde(default)]
+    pub manual: Vec<Audience>,
+    /// How a quote by the user would be handled.
+    pub current_user: CurrentUser,
+}
+
+/// A group of accounts a quote policy can refer to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum Audience {
+    /// Anybody.
+    Public,
+    /// The author's followers.
+    Followers,
+    /// The accounts the author follows.
+    Following,
+    /// A policy the server couldn't express in these terms.
+    UnsupportedPolicy,
+    /// An audience this version of the crate doesn't know about.
+    #[cfg_attr(not(feature = "strict"), serde(other))]
+    Unknown,
+}
+
+/// How a quote by the user would be handled.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum CurrentUser {
+    /// The quote would be accepted straight away.
+    Automatic,
+    /// The quote would wait for the author's approval.
+    Manual,
+    /// The user may not quote the status.
+    Denied,
+    /// The server can't tell, e.g. because the status is remote.
+    #[cfg_attr(not(feature = "strict"), serde(other))]
+    Unknown,
+}
+
+/// Who may quote a new status, set with
+/// [`NewStatusBuilder::quote_approval_policy`](super::NewStatusBuilder::quote_approval_policy).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum Policy {
+    /// Anybody may quote the status.
+    Public,
+    /// Only the author's followers may quote the status.
+    Followers,
+    /// Only the author may quote the status.
+    Nobody,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_shallow_quote() {
+        let example = r#"{
+            "state": "accepted",
+            "quoted_status_id": "103270115826048975"
+        }"#;
+        let quote: Quote = serde_json::from_str(example).expect("deserialize");
+        assert!(quote.state.is_accepted());
+        assert!(quote.quoted_status.is_none());
+        assert_eq!(
+            quote.quoted_status_id,
+            Some(StatusId::new("103270115826048975"))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_approval() {
+        let example = r#"{
+            "automatic": ["followers", "some_future_audience"],
+            "manual": ["public"],
+            "current_user": "manual"
+        }"#;
+        let approval: Approval = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(approval.automatic, [Audience::Followers, Audience::Unknown]);
+        assert_eq!(approval.manual, [Audience::Public]);
+        assert!(approval.current_user.is_manual());
+        let state: State = serde_json::from_str(r#""some_future_state""#).expect("deserialize");
+        assert!(state.is_unknown());
+    }
+}
@@ -19,3 +19,80 @@ macro_rules! serde_value_test {
         }
     };
 }
+
+/// Check that a request form serializes without any `null` values, which the
+/// API rejects for most parameters, and, unless it's marked `serialize only`,
+/// that it deserializes back to a form which serializes the same way.
+#[macro_export]
+macro_rules! form_round_trip_test {
+    ($fn_name:ident($ty:ty, serialize only): $form:expr) => {
+        #[test]
+        fn $fn_name() {
+            let form: $ty = $form;
+            $crate::test::assert_no_nulls(&form);
+        }
+    };
+    ($fn_name:ident($ty:ty): $form:expr) => {
+        #[test]
+        fn $fn_name() {
+            let form: $ty = $form;
+            let as_value = $crate::test::assert_no_nulls(&form);
+            let parsed: $ty = serde_json::from_value(as_value.clone())
+                .expect(concat!(stringify!($ty), " failed to deserialize"));
+            let parsed_as_value = serde_json::to_value(parsed).expect("failed to convert to value");
+            assert_eq!(as_value, parsed_as_value);
+        }
+    };
+}
+
+/// Serialize a form, panicking with the path to the first `null` in it.
+#[cfg(test)]
+pub(crate) fn assert_no_nulls<T: serde::Serialize>(form: &T) -> serde_json::Value {
+    fn check(value: &serde_json::Value, path: &str) {
+        match value {
+            serde_json::Value::Null => panic!("{path} was serialized as null"),
+            serde_json::Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    check(item, &format!("{path}[{i}]"));
+                }
+            }
+            serde_json::Value::Object(fields) => {
+                for (key, field) in fields {
+                    check(field, &format!("{path}.{key}"));
+                }
+            }
+            _ => {}
+        }
+    }
+    let value = serde_json::to_value(form).expect("failed to convert to value");
+    check(&value, "form");
+    value
+}
+
+/// An account, as JSON, for tests. A remote `acct` has its username taken
+/// from it.
+#[cfg(test)]
+pub(crate) fn account_json(id: &str, acct: &str) -> serde_json::Value {
+    let username = acct.split('@').next().unwrap_or(acct);
+    serde_json::json!({
+        "id": id,
+        "username": username,
+        "acct": acct,
+        "display_name": username,
+        "locked": false,
+        "bot": false,
+        "created_at": "2016-03-16T14:34:26.392Z",
+        "note": "",
+        "url": format!("https://example.com/@{username}"),
+        "avatar": "https://example.com/avatar.jpg",
+        "avatar_static": "https://example.com/avatar.jpg",
+        "header": "https://example.com/header.png",
+        "header_static": "https://example.com/header.png",
+        "followers_count": 0,
+        "following_count": 0,
+        "statuses_count": 1,
+        "last_status_at": null,
+        "emojis": [],
+        "fields": []
+    })
+}
@@ -0,0 +1,101 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::instance::Configuration;
+
+/// Links, which count as a fixed number of characters however long they are.
+fn url_regex() -> &'static Regex {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    URL.get_or_init(|| {
+        Regex::new(r#"(?i)\bhttps?://[^\s<>"]*[^\s<>".,:;!?'")\]]"#).expect("valid URL regex")
+    })
+}
+
+/// Mentions, whose domain doesn't count. The character before the `@` is
+/// matched too, as the regex crate has no lookbehind.
+fn mention_regex() -> &'static Regex {
+    static MENTION: OnceLock<Regex> = OnceLock::new();
+    MENTION.get_or_init(|| {
+        Regex::new(r"(?i)(^|[^=/\w])@([a-z0-9_]+(?:[a-z0-9_.-]+[a-z0-9_]+)?)(?:@[\w.-]+\w+)?")
+            .expect("valid mention regex")
+    })
+}
+
+/// How many characters `text` counts as towards the server's limit on the
+/// length of statuses, following the rules Mastodon applies: every link
+/// counts as [`characters_reserved_per_url`](crate::instance::v1::configuration::Statuses::characters_reserved_per_url)
+/// characters, the domain of a mentioned account doesn't count, and
+/// characters are counted as they're displayed, so an emoji made of several
+/// code points counts once.
+///
+/// A content warning counts towards the same limit; add its count to the
+/// status's.
+///
+/// ```
+/// use mastodon_async_entities::{instance::Configuration, text::character_count};
+///
+/// fn fits(text: &str, configuration: &Configuration) -> bool {
+///     let count = character_count(text, configuration);
+///     i64::try_from(count).unwrap() <= configuration.statuses.max_characters
+/// }
+/// ```
+pub fn character_count(text: &str, configuration: &Configuration) -> usize {
+    let reserved =
+        usize::try_from(configuration.statuses.characters_reserved_per_url).unwrap_or_default();
+    let placeholder = "x".repeat(reserved);
+    let text = url_regex().replace_all(text, placeholder.as_str());
+    let text = mention_regex().replace_all(&text, "$1@$2");
+    text.graphemes(true).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configuration() -> Configuration {
+        serde_json::from_value(serde_json::json!({
+            "urls": {"streaming": "wss://mastodon.social"},
+            "accounts": {"max_featured_tags": 10},
+            "statuses": {
+                "max_characters": 500,
+                "max_media_attachments": 4,
+                "characters_reserved_per_url": 23
+            },
+            "media_attachments": {
+                "supported_mime_types": [],
+                "image_size_limit": 0,
+                "image_matrix_limit": 0,
+                "video_size_limit": 0,
+                "video_frame_rate_limit": 0,
+                "video_matrix_limit": 0
+            },
+            "polls": {
+                "max_options": 4,
+                "max_characters_per_option": 50,
+                "min_expiration": 300,
+                "max_expiration": 2629746
+            },
+            "translation": {"enabled": false}
+        }))
+        .expect("configuration")
+    }
+
+    #[test]
+    fn test_character_count() {
+        let configuration = configuration();
+        let count = |text| character_count(text, &configuration);
+        assert_eq!(count(""), 0);
+        assert_eq!(count("héllo"), 5);
+        // a short link still counts as 23
+        assert_eq!(count("http://a.co"), 23);
+        // trailing punctuation isn't part of the link
+        assert_eq!(count("(see https://example.com/path)."), 5 + 23 + 2);
+        assert_eq!(count("@alice@example.com and @bob"), 6 + 5 + 4);
+        // an address in a link isn't a mention
+        assert_eq!(count("https://example.com/@alice@example.org"), 23);
+        // nor is an email address
+        assert_eq!(count("alice@example.com"), 17);
+    }
+}
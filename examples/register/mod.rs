@@ -38,7 +38,7 @@ pub async fn get_mastodon_data() -> Result<Mastodon> {
 #[cfg(feature = "toml")]
 pub async fn register() -> Result<Mastodon> {
     let website = read_line("Please enter your mastodon instance url:")?;
-    let registration = Registration::new(website.trim())
+    let registration = Registration::new(website.trim())?
         .client_name("elefren-examples")
         .scopes(Scopes::all())
         .website("https://github.com/dscottboggs/mastodon-async")
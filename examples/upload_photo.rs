@@ -10,13 +10,12 @@ async fn run() -> Result<()> {
     let mastodon = register::get_mastodon_data().await?;
     let input = register::read_line("Enter the path to the photo you'd like to post: ")?;
     let description = register::read_line("describe the media?  ")?;
-    let description = if description.trim().is_empty() {
-        None
-    } else {
-        Some(description)
-    };
+    let mut upload = forms::media::Upload::builder(input);
+    if !description.trim().is_empty() {
+        upload.description(description);
+    }
 
-    let media = mastodon.media(input, description).await?;
+    let media = mastodon.media(upload.build()?).await?;
     let media = mastodon
         .wait_for_processing(media, Default::default())
         .await?;
@@ -0,0 +1,243 @@
+use crate::{
+    entities::{admin, prelude::*},
+    errors::{Error, Result},
+    Mastodon, Page,
+};
+
+/// A client for the admin API, acting as an account whose role grants
+/// permission to use it.
+///
+/// One can only be made with [`Mastodon::admin`], which checks the role of
+/// the authenticated account first, so code which is handed an
+/// `AdminClient` knows it is allowed to moderate. The server still checks
+/// each request against the specific permission it needs.
+#[derive(Debug, Clone)]
+pub struct AdminClient {
+    client: Mastodon,
+    role: Role,
+}
+
+/// Whether a role's permissions cover any part of the admin API.
+fn grants_admin_access(permissions: &RolePermissions) -> bool {
+    permissions.has_administrator()
+        || permissions.has_view_dashboard()
+        || permissions.has_manage_reports()
+        || permissions.has_manage_federation()
+        || permissions.has_manage_blocks()
+        || permissions.has_manage_taxonomies()
+        || permissions.has_manage_users()
+}
+
+impl AdminClient {
+    pub(crate) fn new(client: Mastodon, role: Option<Role>) -> Result<Self> {
+        match role {
+            Some(role) if grants_admin_access(&role.permissions) => {
+                Ok(AdminClient { client, role })
+            }
+            role => Err(Error::NotAdmin {
+                role: role.map(|role| role.name),
+            }),
+        }
+    }
+
+    /// The role which grants the account access to the admin API.
+    pub fn role(&self) -> &Role {
+        &self.role
+    }
+
+    /// The client the admin requests are made with, for user-level
+    /// requests.
+    pub fn client(&self) -> &Mastodon {
+        &self.client
+    }
+
+    /// Trending tags, including those which haven't been reviewed yet.
+    pub async fn trending_tags(&self) -> Result<Page<admin::Tag>> {
+        self.client.admin_trending_tags().await
+    }
+
+    /// Hashtags used on the server.
+    pub async fn tags(&self) -> Result<Page<admin::Tag>> {
+        self.client.admin_tags().await
+    }
+
+    /// A single hashtag.
+    pub async fn tag(&self, id: &TagId) -> Result<admin::Tag> {
+        self.client.admin_get_tag(id).await
+    }
+
+    /// Change whether a hashtag can be used, trend, or be listed.
+    pub async fn update_tag(
+        &self,
+        id: &TagId,
+        form: forms::admin::tag::Update,
+    ) -> Result<admin::Tag> {
+        self.client.admin_update_tag(id, form).await
+    }
+
+    /// Trending statuses, including those which haven't been reviewed yet.
+    pub async fn trending_statuses(&self) -> Result<Page<Status>> {
+        self.client.admin_trending_statuses().await
+    }
+
+    /// Trending links, including those which haven't been reviewed yet.
+    pub async fn trending_links(&self) -> Result<Page<TrendsLink>> {
+        self.client.admin_trending_links().await
+    }
+
+    /// Blocked email domains.
+    pub async fn email_domain_blocks(&self) -> Result<Page<EmailDomainBlock>> {
+        self.client.admin_email_domain_blocks().await
+    }
+
+    /// A single blocked email domain.
+    pub async fn email_domain_block(&self, id: &EmailDomainBlockId) -> Result<EmailDomainBlock> {
+        self.client.admin_email_domain_block(id).await
+    }
+
+    /// Block sign-ups from an email domain.
+    pub async fn block_email_domain(&self, domain: String) -> Result<EmailDomainBlock> {
+        self.client.admin_block_email_domain(domain).await
+    }
+
+    /// Allow sign-ups from a blocked email domain again.
+    pub async fn unblock_email_domain(&self, id: &EmailDomainBlockId) -> Result<()> {
+        self.client.admin_unblock_email_domain(id).await
+    }
+
+    /// Blocked IP ranges.
+    pub async fn ip_blocks(&self) -> Result<Page<IpBlock>> {
+        self.client.admin_ip_blocks().await
+    }
+
+    /// A single blocked IP range.
+    pub async fn ip_block(&self, id: &DomainBlockId) -> Result<IpBlock> {
+        self.client.admin_ip_block(id).await
+    }
+
+    /// Block an IP range.
+    pub async fn add_ip_block(&self, form: forms::admin::ip_block::Add) -> Result<IpBlock> {
+        self.client.admin_add_ip_block(form).await
+    }
+
+    /// Change how an IP range is blocked.
+    pub async fn update_ip_block(
+        &self,
+        id: &DomainBlockId,
+        form: forms::admin::ip_block::Update,
+    ) -> Result<IpBlock> {
+        self.client.admin_update_ip_block(id, form).await
+    }
+
+    /// Unblock an IP range.
+    pub async fn delete_ip_block(&self, id: &DomainBlockId) -> Result<()> {
+        self.client.admin_delete_ip_block(id).await
+    }
+
+    /// Blocked canonical email addresses.
+    pub async fn canonical_email_blocks(&self) -> Result<Page<CanonicalEmailBlock>> {
+        self.client.admin_canonical_email_blocks().await
+    }
+
+    /// A single blocked canonical email address.
+    pub async fn canonical_email_block(
+        &self,
+        id: &CanonicalEmailBlockId,
+    ) -> Result<CanonicalEmailBlock> {
+        self.client.admin_get_canonical_email_block(id).await
+    }
+
+    /// The canonical email blocks which match an email address.
+    pub async fn test_canonical_email_block(
+        &self,
+        form: forms::admin::canonical_email_block::Test,
+    ) -> Result<Vec<CanonicalEmailBlock>> {
+        self.client.admin_test_canonical_email_block(form).await
+    }
+
+    /// Block a canonical email address.
+    pub async fn create_canonical_email_block(
+        &self,
+        form: forms::admin::canonical_email_block::Add,
+    ) -> Result<CanonicalEmailBlock> {
+        self.client.admin_create_canonical_email_block(form).await
+    }
+
+    /// Unblock a canonical email address.
+    pub async fn delete_canonical_email_block(&self, id: &CanonicalEmailBlockId) -> Result<()> {
+        self.client.admin_delete_canonical_email_block(id).await
+    }
+
+    /// Reports filed by users.
+    pub async fn reports(&self) -> Result<Page<admin::Report>> {
+        self.client.admin_reports().await
+    }
+
+    /// A single report.
+    pub async fn report(&self, id: &ReportId) -> Result<admin::Report> {
+        self.client.admin_report(id).await
+    }
+
+    /// The statuses attached to a report, to triage it.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{entities::forms::admin::account_action::{Action, Type}, prelude::*};
+    /// tokio_test::block_on(async {
+    ///     let admin = Mastodon::from(Data::default()).admin().await.unwrap();
+    ///     let report = ReportId::new("42");
+    ///     let statuses = admin.report_statuses(&report).await.unwrap();
+    ///     if statuses.iter().any(|status| !status.media_attachments.is_empty()) {
+    ///         let target = &statuses[0].account.id;
+    ///         let action = Action::builder(Type::Sensitive).report_id(report).build().unwrap();
+    ///         admin.account_action(target, action).await.unwrap();
+    ///     }
+    /// });
+    /// ```
+    pub async fn report_statuses(&self, id: &ReportId) -> Result<Vec<Status>> {
+        self.client.admin_report_statuses(id).await
+    }
+
+    /// Take moderation action against an account.
+    pub async fn account_action(
+        &self,
+        id: &AccountId,
+        action: forms::admin::account_action::Action,
+    ) -> Result<()> {
+        self.client.admin_account_action(id, action).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Data;
+
+    fn role(name: &str, permissions: u64) -> Role {
+        let example = format!(
+            r##"{{
+                "id": 3,
+                "name": "{name}",
+                "color": "",
+                "position": 10,
+                "permissions": {permissions},
+                "highlighted": true,
+                "created_at": "2022-09-08T22:48:07.983Z",
+                "updated_at": "2022-09-08T22:48:07.983Z"
+            }}"##
+        );
+        serde_json::from_str(&example).expect("deserialize role")
+    }
+
+    #[test]
+    fn test_new() {
+        let client = Mastodon::from(Data::default());
+        let admin = AdminClient::new(client.clone(), Some(role("Owner", 0x1))).unwrap();
+        assert_eq!(admin.role().name, "Owner");
+        assert!(AdminClient::new(client.clone(), Some(role("Moderator", 0x10))).is_ok());
+
+        let err = AdminClient::new(client.clone(), Some(role("Inviter", 0x10000))).unwrap_err();
+        assert!(matches!(err, Error::NotAdmin { role: Some(name) } if name == "Inviter"));
+        let err = AdminClient::new(client, None).unwrap_err();
+        assert!(matches!(err, Error::NotAdmin { role: None }));
+    }
+}
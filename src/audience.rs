@@ -0,0 +1,185 @@
+use crate::entities::{
+    account::Account, mention::Mention, relationship::Relationship, status::Status,
+    visibility::Visibility, AccountId,
+};
+
+/// The effective audience of a status: who wrote it, who it addresses, and
+/// how widely it was published.
+///
+/// Obtain one with [`expand`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Audience {
+    /// The ID of the account which authored the status.
+    pub author: AccountId,
+    /// The Webfinger URI of the account which authored the status.
+    pub author_acct: String,
+    /// The accounts mentioned in the status. For private and direct statuses
+    /// these accounts can see the status regardless of whether they follow
+    /// the author.
+    pub mentioned: Vec<Mention>,
+    /// The visibility the status was posted with.
+    pub visibility: Visibility,
+}
+
+/// Compute the effective audience of the given status.
+///
+/// If the status is a reblog, the audience of the reblogged status is
+/// returned, since that's the content being shown.
+///
+/// ```
+/// use mastodon_async::{audience, prelude::*};
+///
+/// fn addressees(status: &Status) -> Vec<String> {
+///     audience::expand(status).accts().map(String::from).collect()
+/// }
+/// ```
+pub fn expand(status: &Status) -> Audience {
+    let status = status.reblog.as_deref().unwrap_or(status);
+    Audience {
+        author: status.account.id.clone(),
+        author_acct: status.account.acct.clone(),
+        mentioned: status.mentions.clone(),
+        visibility: status.visibility,
+    }
+}
+
+impl Audience {
+    /// The Webfinger URIs of everyone explicitly addressed by the status,
+    /// starting with the author.
+    pub fn accts(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.author_acct.as_str())
+            .chain(self.mentioned.iter().map(|mention| mention.acct.as_str()))
+    }
+
+    /// Whether the given account authored the status.
+    pub fn is_author(&self, account: &AccountId) -> bool {
+        &self.author == account
+    }
+
+    /// Whether the given account was mentioned in the status.
+    pub fn is_mentioned(&self, account: &AccountId) -> bool {
+        self.mentioned.iter().any(|mention| &mention.id == account)
+    }
+
+    /// Whether the status reaches people beyond those explicitly addressed,
+    /// i.e. it is public or unlisted.
+    pub fn is_broadcast(&self) -> bool {
+        matches!(self.visibility, Visibility::Public | Visibility::Unlisted)
+    }
+
+    /// Whether the given account can see the status.
+    ///
+    /// `relationship` is the relationship *from the given account* to the
+    /// author, as returned by [`Mastodon::relationships`](crate::Mastodon::relationships)
+    /// when authenticated as that account. When it is `None`, the account is
+    /// assumed not to follow the author and not to be blocked.
+    ///
+    /// The author and mentioned accounts can always see the status unless a
+    /// block is in place. Beyond that, public and unlisted statuses are
+    /// visible to everyone, private statuses only to followers, and direct
+    /// statuses to nobody else.
+    pub fn can_see(&self, account: &AccountId, relationship: Option<&Relationship>) -> bool {
+        if self.is_author(account) {
+            return true;
+        }
+        if relationship.is_some_and(|rel| rel.blocked_by || rel.blocking) {
+            return false;
+        }
+        if self.is_mentioned(account) {
+            return true;
+        }
+        match self.visibility {
+            Visibility::Public | Visibility::Unlisted => true,
+            Visibility::Private => relationship.is_some_and(|rel| rel.following),
+            Visibility::Direct => false,
+        }
+    }
+
+    /// Convenience wrapper around [`Audience::can_see`] taking an [`Account`].
+    pub fn can_account_see(&self, account: &Account, relationship: Option<&Relationship>) -> bool {
+        self.can_see(&account.id, relationship)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn status(visibility: &str) -> Status {
+        let mut status = fixtures::status_json("103270115826048975");
+        status["visibility"] = visibility.into();
+        status["content"] = "<p>hello @alice</p>".into();
+        status["mentions"] = serde_json::json!([{
+            "id": "2",
+            "username": "alice",
+            "acct": "alice@example.com",
+            "url": "https://example.com/@alice"
+        }]);
+        fixtures::from_json(status)
+    }
+
+    fn relationship(following: bool, blocked_by: bool) -> Relationship {
+        Relationship {
+            id: crate::entities::RelationshipId::new("1"),
+            following,
+            followed_by: false,
+            blocking: false,
+            blocked_by,
+            muting: false,
+            requested: false,
+            muting_notifications: false,
+            domain_blocking: false,
+            showing_reblogs: true,
+            notifying: false,
+            languages: vec![],
+            endorsed: false,
+            note: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_expand() {
+        let audience = expand(&status("private"));
+        assert_eq!(audience.author, AccountId::new("1"));
+        assert_eq!(audience.visibility, Visibility::Private);
+        assert_eq!(
+            audience.accts().collect::<Vec<_>>(),
+            vec!["Gargron", "alice@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_expand_reblog() {
+        let mut outer = status("public");
+        outer.mentions.clear();
+        outer.account.id = AccountId::new("3");
+        outer.reblog = Some(Box::new(status("unlisted")));
+        let audience = expand(&outer);
+        assert_eq!(audience.author, AccountId::new("1"));
+        assert_eq!(audience.visibility, Visibility::Unlisted);
+        assert!(audience.is_mentioned(&AccountId::new("2")));
+    }
+
+    #[test]
+    fn test_can_see() {
+        let stranger = AccountId::new("4");
+        let mentioned = AccountId::new("2");
+        let author = AccountId::new("1");
+
+        let public = expand(&status("public"));
+        assert!(public.can_see(&stranger, None));
+        assert!(!public.can_see(&stranger, Some(&relationship(false, true))));
+
+        let private = expand(&status("private"));
+        assert!(!private.can_see(&stranger, None));
+        assert!(private.can_see(&stranger, Some(&relationship(true, false))));
+        assert!(private.can_see(&mentioned, None));
+        assert!(private.can_see(&author, None));
+
+        let direct = expand(&status("direct"));
+        assert!(!direct.can_see(&stranger, Some(&relationship(true, false))));
+        assert!(direct.can_see(&mentioned, None));
+        assert!(!direct.can_see(&mentioned, Some(&relationship(false, true))));
+    }
+}
@@ -0,0 +1,156 @@
+use std::{future::Future, sync::Arc};
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{
+    entities::prelude::*, errors::Result, polling_time::PollingTime, timeline::Timeline, Data,
+    NewStatus,
+};
+
+/// A client which blocks the calling thread until each request is done,
+/// for programs which don't run an async runtime of their own.
+///
+/// It wraps an async [`Mastodon`](crate::Mastodon) client together with a
+/// single-threaded runtime to run its requests on. The most common requests
+/// have blocking methods here; any other can be made with
+/// [`block_on`](Self::block_on). Clones share the same runtime.
+///
+/// Its methods panic if they are called from within an async runtime.
+///
+/// ```no_run
+/// use mastodon_async::{blocking, prelude::*};
+///
+/// let client = blocking::Mastodon::new(Mastodon::from(Data::default())).unwrap();
+/// let status = NewStatusBuilder::default()
+///     .status("posted without async")
+///     .build()
+///     .unwrap();
+/// let status = client.new_status(status).unwrap();
+/// // anything else through the async client
+/// let id = status.id.clone();
+/// client.block_on(client.client().favourite(&id)).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Mastodon {
+    client: crate::Mastodon,
+    runtime: Arc<Runtime>,
+}
+
+impl Mastodon {
+    /// Wrap an async client.
+    pub fn new(client: crate::Mastodon) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Mastodon {
+            client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// A client for the given credentials.
+    pub fn from_data(data: Data) -> Result<Self> {
+        Self::new(crate::Mastodon::from(data))
+    }
+
+    /// The async client the requests are made with.
+    pub fn client(&self) -> &crate::Mastodon {
+        &self.client
+    }
+
+    /// Run a future, such as a request made with [`Self::client`], to
+    /// completion on the client's runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// The account the client is authenticated as. See
+    /// [`Mastodon::verify_credentials`](crate::Mastodon::verify_credentials).
+    pub fn verify_credentials(&self) -> Result<Account> {
+        self.block_on(self.client.verify_credentials())
+    }
+
+    /// Post a new status. See
+    /// [`Mastodon::new_status`](crate::Mastodon::new_status).
+    pub fn new_status(&self, status: NewStatus) -> Result<Status> {
+        self.block_on(self.client.new_status(status))
+    }
+
+    /// A single status. See
+    /// [`Mastodon::get_status`](crate::Mastodon::get_status).
+    pub fn get_status(&self, id: &StatusId) -> Result<Status> {
+        self.block_on(self.client.get_status(id))
+    }
+
+    /// Delete one of the user's statuses. See
+    /// [`Mastodon::delete_status`](crate::Mastodon::delete_status).
+    pub fn delete_status(&self, id: &StatusId) -> Result<()> {
+        self.block_on(self.client.delete_status(id))
+    }
+
+    /// The first page of the user's home timeline. See
+    /// [`Mastodon::get_home_timeline`](crate::Mastodon::get_home_timeline).
+    pub fn get_home_timeline(&self) -> Result<Vec<Status>> {
+        self.block_on(async { Ok(self.client.get_home_timeline().await?.initial_items) })
+    }
+
+    /// The first page of any timeline. See
+    /// [`Mastodon::timeline`](crate::Mastodon::timeline).
+    pub fn timeline(
+        &self,
+        timeline: &Timeline,
+        options: forms::timeline::Options,
+    ) -> Result<Vec<Status>> {
+        self.block_on(async { Ok(self.client.timeline(timeline, options).await?.initial_items) })
+    }
+
+    /// The first page of the user's notifications. See
+    /// [`Mastodon::notifications`](crate::Mastodon::notifications).
+    pub fn notifications(&self) -> Result<Vec<Notification>> {
+        self.block_on(async { Ok(self.client.notifications().await?.initial_items) })
+    }
+
+    /// A single account. See
+    /// [`Mastodon::get_account`](crate::Mastodon::get_account).
+    pub fn get_account(&self, id: &AccountId) -> Result<Account> {
+        self.block_on(self.client.get_account(id))
+    }
+
+    /// Follow an account. See [`Mastodon::follow`](crate::Mastodon::follow).
+    pub fn follow(&self, id: &AccountId) -> Result<Relationship> {
+        self.block_on(self.client.follow(id))
+    }
+
+    /// Unfollow an account. See
+    /// [`Mastodon::unfollow`](crate::Mastodon::unfollow).
+    pub fn unfollow(&self, id: &AccountId) -> Result<Relationship> {
+        self.block_on(self.client.unfollow(id))
+    }
+
+    /// Upload media, to attach to a status. See
+    /// [`Mastodon::media`](crate::Mastodon::media).
+    pub fn media(&self, form: forms::media::Upload) -> Result<Attachment> {
+        self.block_on(self.client.media(form))
+    }
+
+    /// Wait for uploaded media to be processed. See
+    /// [`Mastodon::wait_for_processing`](crate::Mastodon::wait_for_processing).
+    pub fn wait_for_processing(
+        &self,
+        attachment: Attachment,
+        polling_time: PollingTime,
+    ) -> Result<ProcessedAttachment> {
+        self.block_on(self.client.wait_for_processing(attachment, polling_time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_on() {
+        let client = Mastodon::from_data(Data::default()).expect("client");
+        let clone = client.clone();
+        assert_eq!(clone.block_on(async { 1 + 1 }), 2);
+        assert!(Arc::ptr_eq(&client.runtime, &clone.runtime));
+    }
+}
@@ -0,0 +1,247 @@
+use std::path::Path;
+
+use futures::{future, TryStream, TryStreamExt};
+use log::{debug, info};
+
+use crate::{
+    entities::event::Event,
+    errors::Result,
+    event_stream::{reconnecting, Backoff},
+    helpers::{cli, toml},
+    prelude::*,
+    Error,
+};
+
+/// The skeleton of a bot which responds to commands it is mentioned with,
+/// such as `@bot !roll 2d6`.
+///
+/// ```no_run
+/// use futures_util::TryStreamExt;
+/// use mastodon_async::bot::Bot;
+///
+/// tokio_test::block_on(async {
+///     let bot = Bot::load_or_register("https://botsin.space", "dice", "dice.toml")
+///         .await
+///         .unwrap()
+///         .with_prefix("!");
+///     bot.commands()
+///         .try_for_each(|command| {
+///             let bot = &bot;
+///             async move {
+///                 match command.name.as_str() {
+///                     "roll" => bot.reply(&command.status, "4").await?,
+///                     _ => bot.reply(&command.status, "I don't know that one").await?,
+///                 };
+///                 Ok(())
+///             }
+///         })
+///         .await
+///         .unwrap();
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bot {
+    client: Mastodon,
+    me: Account,
+    prefix: String,
+    backoff: Backoff,
+}
+
+/// A command a [`Bot`] was mentioned with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    /// The name of the command, without the prefix.
+    pub name: String,
+    /// The words following the name.
+    pub args: Vec<String>,
+    /// The status which mentioned the bot.
+    pub status: Status,
+}
+
+impl Bot {
+    /// A bot acting as the account the client is authenticated as.
+    pub async fn new(client: Mastodon) -> Result<Self> {
+        let me = client.verify_credentials().await?;
+        info!(acct = me.acct; "bot authenticated");
+        Ok(Bot {
+            client,
+            me,
+            prefix: "!".to_string(),
+            backoff: Backoff::default(),
+        })
+    }
+
+    /// Load the bot's credentials from the toml file at `path`, or, if there
+    /// isn't one, register an app called `name` on the instance at `base`,
+    /// authenticate on the command line and save the credentials to `path`.
+    pub async fn load_or_register(base: &str, name: &str, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let client = if path.exists() {
+            Mastodon::from(toml::from_file(path)?)
+        } else {
+            let registration = Registration::new(base)?
+                .client_name(name)
+                .scopes(Scopes::read_all() | Scopes::write_all())
+                .build()
+                .await?;
+            let client = cli::authenticate(registration).await?;
+            toml::to_file(&client.data, path)?;
+            client
+        };
+        Self::new(client).await
+    }
+
+    /// Only treat words starting with `prefix` as commands. Defaults to `!`.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// How to reconnect to the streaming API when the connection is lost.
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// The client the bot makes requests with.
+    pub fn client(&self) -> &Mastodon {
+        &self.client
+    }
+
+    /// The bot's own account.
+    pub fn account(&self) -> &Account {
+        &self.me
+    }
+
+    /// The command in a status, if its text, after any leading mentions,
+    /// starts with the prefix. Statuses by the bot itself are ignored.
+    pub fn parse_command(&self, status: &Status) -> Option<Command> {
+        if status.account.id == self.me.id {
+            return None;
+        }
+        let text = plain_text(&status.content);
+        let mut words = text
+            .split_whitespace()
+            .skip_while(|word| word.starts_with('@'));
+        let name = words.next()?.strip_prefix(self.prefix.as_str())?;
+        if name.is_empty() {
+            return None;
+        }
+        Some(Command {
+            name: name.to_string(),
+            args: words.map(str::to_string).collect(),
+            status: status.clone(),
+        })
+    }
+
+    /// The commands the bot is mentioned with, as they arrive. The
+    /// connection to the streaming API is re-established whenever it's lost.
+    pub fn commands(&self) -> impl TryStream<Ok = Command, Error = Error> + '_ {
+        reconnecting(&self.client, self.backoff, || {
+            self.client.stream_notifications()
+        })
+        .try_filter_map(|(event, _client)| {
+            let command = match event {
+                Event::Notification(notification) => notification
+                    .mention()
+                    .and_then(|(_account, status)| self.parse_command(status)),
+                _ => None,
+            };
+            if let Some(command) = &command {
+                debug!(name = command.name, status = command.status.id.as_ref(); "received command");
+            }
+            future::ready(Ok(command))
+        })
+    }
+
+    /// Reply to a status, mentioning its author, with the same visibility.
+    pub async fn reply(&self, to: &Status, text: impl AsRef<str>) -> Result<Status> {
+        let status = NewStatusBuilder::default()
+            .status(format!("@{} {}", to.account.acct, text.as_ref()))
+            .in_reply_to_id(to.id.to_string())
+            .visibility(to.visibility)
+            .build()?;
+        self.client.new_status(status).await
+    }
+}
+
+/// The text of a status's HTML content, with tags removed and the most common
+/// entities decoded.
+fn plain_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = rest[start + 1..start + end].trim_matches('/');
+        let name = tag.split_whitespace().next().unwrap_or_default();
+        // paragraphs and line breaks separate words, inline tags don't
+        if name == "p" || name == "br" {
+            text.push(' ');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn status(account_id: &str, content: &str) -> Status {
+        let mut status = fixtures::status_json("1");
+        status["content"] = content.into();
+        status["account"] = fixtures::account_json(account_id, &format!("user{account_id}"));
+        fixtures::from_json(status)
+    }
+
+    fn bot() -> Bot {
+        Bot {
+            client: Mastodon::from(Data::default()),
+            me: fixtures::account("1", "user1"),
+            prefix: "!".to_string(),
+            backoff: Backoff::default(),
+        }
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(
+            plain_text("<p>fish &amp; chips<br>&lt;3</p>")
+                .split_whitespace()
+                .collect::<Vec<_>>(),
+            ["fish", "&", "chips", "<3"]
+        );
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let bot = bot();
+        let mention = r#"<p><span class="h-card"><a href="https://botsin.space/@user1" class="u-url mention">@<span>user1</span></a></span> !roll 2d6 &amp; 1d4</p>"#;
+        let command = bot.parse_command(&status("2", mention)).expect("command");
+        assert_eq!(command.name, "roll");
+        assert_eq!(command.args, ["2d6", "&", "1d4"]);
+
+        assert!(bot
+            .parse_command(&status("2", "<p>@user1 roll</p>"))
+            .is_none());
+        assert!(bot.parse_command(&status("2", "<p>@user1 !</p>")).is_none());
+        // the bot's own statuses are never commands
+        assert!(bot.parse_command(&status("1", "<p>!roll</p>")).is_none());
+
+        let bot = bot.with_prefix("/");
+        let command = bot
+            .parse_command(&status("2", "<p>/help</p>"))
+            .expect("command");
+        assert_eq!(command.name, "help");
+        assert!(command.args.is_empty());
+    }
+}
@@ -0,0 +1,427 @@
+use std::time::Duration;
+
+#[cfg(any(feature = "default", feature = "rustls-tls"))]
+use reqwest::Certificate;
+use reqwest::{Client, Proxy};
+
+use crate::{errors::Result, mastodon::parse_base, Data, Mastodon};
+
+/// How to set up the HTTP client requests are sent with: proxies, trusted
+/// certificates, timeouts, the user agent and connection pooling.
+///
+/// Anything left unset keeps reqwest's default. The same settings can be
+/// used for [`Registration::new_with_config`](crate::Registration::new_with_config),
+/// [`MastodonUnauthenticated::new_with_config`](crate::MastodonUnauthenticated::new_with_config)
+/// and, through [`MastodonBuilder`], for [`Mastodon`].
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use mastodon_async::{builder::HttpConfig, Registration};
+///
+/// let mut config = HttpConfig::default();
+/// config
+///     .user_agent("my cool app")
+///     .proxy(reqwest::Proxy::all("http://localhost:8080").unwrap())
+///     .connect_timeout(Duration::from_secs(10));
+/// let registration = Registration::new_with_config("https://botsin.space", &config).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HttpConfig {
+    proxies: Vec<Proxy>,
+    #[cfg(any(feature = "default", feature = "rustls-tls"))]
+    root_certificates: Vec<Certificate>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    pool_idle_timeout: Option<Option<Duration>>,
+    pool_max_idle_per_host: Option<usize>,
+    #[cfg(feature = "socks")]
+    socks5_proxy: Option<String>,
+    pub(crate) allow_plaintext: bool,
+}
+
+impl HttpConfig {
+    /// Send requests through a proxy. Proxies are tried in the order they
+    /// were added, and the first which matches a request is used.
+    pub fn proxy(&mut self, proxy: Proxy) -> &mut Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Send all requests through a SOCKS5 proxy at `address`, such as
+    /// `127.0.0.1:1080`, which also resolves the hosts they're sent to.
+    ///
+    /// In order to use this method, set the "socks" feature in your
+    /// Cargo.toml.
+    #[cfg(feature = "socks")]
+    pub fn socks5_proxy(&mut self, address: impl Into<String>) -> &mut Self {
+        self.socks5_proxy = Some(address.into());
+        self
+    }
+
+    /// Send all requests through a Tor daemon running on this machine with
+    /// its default SOCKS port, as needed for instances which are only
+    /// reachable as onion services.
+    ///
+    /// ```
+    /// use mastodon_async::{builder::HttpConfig, MastodonUnauthenticated};
+    ///
+    /// let mut config = HttpConfig::default();
+    /// config.tor();
+    /// let client = MastodonUnauthenticated::new_with_config(
+    ///     "mastodonx7p4ehlsfj2atlbbw4qgelafn5ktmt4kmbj3beuo6o2fqd.onion",
+    ///     &config,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(client.base.scheme(), "http");
+    /// ```
+    #[cfg(feature = "socks")]
+    pub fn tor(&mut self) -> &mut Self {
+        self.socks5_proxy("127.0.0.1:9050")
+    }
+
+    /// Allow instances to be contacted over plain `http`, which anyone on
+    /// the way can read the access token from. Onion services and this
+    /// machine can be contacted over `http` without it.
+    pub fn allow_plaintext(&mut self, allow: bool) -> &mut Self {
+        self.allow_plaintext = allow;
+        self
+    }
+
+    /// Trust a certificate authority besides the system's, such as the one
+    /// which signed a self-hosted instance's certificate.
+    ///
+    /// ```no_run
+    /// use mastodon_async::builder::HttpConfig;
+    ///
+    /// let pem = std::fs::read("my-ca.pem").unwrap();
+    /// let mut config = HttpConfig::default();
+    /// config.add_root_certificate(reqwest::Certificate::from_pem(&pem).unwrap());
+    /// ```
+    #[cfg(any(feature = "default", feature = "rustls-tls"))]
+    pub fn add_root_certificate(&mut self, certificate: Certificate) -> &mut Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// How long a request may take altogether, from connecting until the
+    /// body has been read.
+    ///
+    /// This includes streaming connections, which it ends once the time is
+    /// up, so prefer [`MastodonBuilder::timeouts`], which sets timeouts for
+    /// each kind of request.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// How long connecting to the server may take.
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// The `User-Agent` header sent with every request.
+    pub fn user_agent(&mut self, user_agent: impl Into<String>) -> &mut Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// How long an idle connection is kept open for reuse, or `None` to
+    /// keep it open indefinitely.
+    pub fn pool_idle_timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// How many idle connections to each host are kept open for reuse.
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Build an HTTP client with these settings.
+    ///
+    /// ## Errors
+    /// If the user agent isn't a valid header value, or the TLS backend
+    /// can't be set up with the given certificates.
+    pub fn client(&self) -> Result<Client> {
+        let mut builder = Client::builder();
+        #[cfg(feature = "socks")]
+        if let Some(address) = &self.socks5_proxy {
+            // socks5h, so that the proxy resolves onion addresses
+            builder = builder.proxy(Proxy::all(format!("socks5h://{address}"))?);
+        }
+        for proxy in &self.proxies {
+            builder = builder.proxy(proxy.clone());
+        }
+        #[cfg(any(feature = "default", feature = "rustls-tls"))]
+        for certificate in &self.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// How long requests of each kind may take, set with
+/// [`MastodonBuilder::timeouts`]. Anything left unset has no timeout.
+///
+/// Streaming connections never time out, as they're meant to stay open;
+/// see [`EventStreamExt::stall_timeout`](crate::event_stream::EventStreamExt::stall_timeout)
+/// for noticing when one has stopped delivering events.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// How long connecting to the server may take.
+    pub connect: Option<Duration>,
+    /// How long an API request may take altogether, from sending it until
+    /// its response has been read.
+    pub read: Option<Duration>,
+    /// How long uploading media may take altogether, in place of `read`.
+    pub upload: Option<Duration>,
+}
+
+impl Timeouts {
+    /// The timeout for a request with the given method to the given path.
+    pub(crate) fn for_request(&self, method: &str, path: &str) -> Option<Duration> {
+        if path.starts_with("/api/v1/streaming") {
+            None
+        } else if !method.eq_ignore_ascii_case("get")
+            && (path.starts_with("/api/v1/media") || path.starts_with("/api/v2/media"))
+        {
+            self.upload
+        } else {
+            self.read
+        }
+    }
+}
+
+/// Builds a [`Mastodon`] client whose HTTP client is configured with
+/// [`HttpConfig`]'s settings, instead of the defaults
+/// [`Mastodon::from`] uses.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use mastodon_async::prelude::*;
+///
+/// let data = Data {
+///     base: "https://example.com".into(),
+///     ..Default::default()
+/// };
+/// let client = Mastodon::builder(data)
+///     .user_agent("my cool app")
+///     .timeout(Duration::from_secs(30))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct MastodonBuilder {
+    data: Data,
+    http: HttpConfig,
+    timeouts: Timeouts,
+}
+
+impl MastodonBuilder {
+    /// Start building a client for the given credentials.
+    pub fn new(data: Data) -> Self {
+        MastodonBuilder {
+            data,
+            http: HttpConfig::default(),
+            timeouts: Timeouts::default(),
+        }
+    }
+
+    /// Replace all of the HTTP settings at once, e.g. with those used for
+    /// registering the app.
+    pub fn http_config(&mut self, config: HttpConfig) -> &mut Self {
+        self.http = config;
+        self
+    }
+
+    /// See [`HttpConfig::proxy`].
+    pub fn proxy(&mut self, proxy: Proxy) -> &mut Self {
+        self.http.proxy(proxy);
+        self
+    }
+
+    /// See [`HttpConfig::socks5_proxy`].
+    #[cfg(feature = "socks")]
+    pub fn socks5_proxy(&mut self, address: impl Into<String>) -> &mut Self {
+        self.http.socks5_proxy(address);
+        self
+    }
+
+    /// See [`HttpConfig::tor`].
+    #[cfg(feature = "socks")]
+    pub fn tor(&mut self) -> &mut Self {
+        self.http.tor();
+        self
+    }
+
+    /// See [`HttpConfig::allow_plaintext`].
+    pub fn allow_plaintext(&mut self, allow: bool) -> &mut Self {
+        self.http.allow_plaintext(allow);
+        self
+    }
+
+    /// See [`HttpConfig::add_root_certificate`].
+    #[cfg(any(feature = "default", feature = "rustls-tls"))]
+    pub fn add_root_certificate(&mut self, certificate: Certificate) -> &mut Self {
+        self.http.add_root_certificate(certificate);
+        self
+    }
+
+    /// How long requests of each kind may take. Setting the connect timeout
+    /// here is the same as [`MastodonBuilder::connect_timeout`].
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use mastodon_async::{builder::Timeouts, prelude::*};
+    ///
+    /// let data = Data {
+    ///     base: "https://example.com".into(),
+    ///     ..Default::default()
+    /// };
+    /// let client = Mastodon::builder(data)
+    ///     .timeouts(Timeouts {
+    ///         connect: Some(Duration::from_secs(5)),
+    ///         read: Some(Duration::from_secs(15)),
+    ///         upload: Some(Duration::from_secs(300)),
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn timeouts(&mut self, timeouts: Timeouts) -> &mut Self {
+        if let Some(connect) = timeouts.connect {
+            self.http.connect_timeout(connect);
+        }
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// See [`HttpConfig::timeout`].
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.http.timeout(timeout);
+        self
+    }
+
+    /// See [`HttpConfig::connect_timeout`].
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.http.connect_timeout(timeout);
+        self
+    }
+
+    /// See [`HttpConfig::user_agent`].
+    pub fn user_agent(&mut self, user_agent: impl Into<String>) -> &mut Self {
+        self.http.user_agent(user_agent);
+        self
+    }
+
+    /// See [`HttpConfig::pool_idle_timeout`].
+    pub fn pool_idle_timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.http.pool_idle_timeout(timeout);
+        self
+    }
+
+    /// See [`HttpConfig::pool_max_idle_per_host`].
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.http.pool_max_idle_per_host(max);
+        self
+    }
+
+    /// Build the client.
+    ///
+    /// ## Errors
+    /// If the instance's URL is plain `http` and that isn't allowed, as for
+    /// [`MastodonUnauthenticated::new`](crate::MastodonUnauthenticated::new),
+    /// or if the HTTP client can't be built. See [`HttpConfig::client`].
+    pub fn build(&self) -> Result<Mastodon> {
+        parse_base(&self.data.base, self.http.allow_plaintext)?;
+        let client = Mastodon::new(self.http.client()?, self.data.clone());
+        Ok(client.with_timeouts(self.timeouts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::Server, Error};
+
+    #[test]
+    fn test_build() {
+        let data = Data {
+            base: "https://example.com".into(),
+            ..Default::default()
+        };
+        let client = MastodonBuilder::new(data.clone())
+            .proxy(Proxy::all("http://localhost:8080").expect("proxy"))
+            .connect_timeout(Duration::from_secs(5))
+            .pool_idle_timeout(None)
+            .pool_max_idle_per_host(2)
+            .user_agent("test")
+            .build()
+            .expect("client");
+        assert_eq!(client.data, data);
+
+        let result = MastodonBuilder::new(data).user_agent("not\nvalid").build();
+        assert!(matches!(result, Err(Error::Http(_))));
+
+        let data = Data {
+            base: "http://example.com".into(),
+            ..Default::default()
+        };
+        assert!(MastodonBuilder::new(data.clone()).build().is_err());
+        assert!(MastodonBuilder::new(data)
+            .allow_plaintext(true)
+            .build()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_timeouts() {
+        let read = Duration::from_secs(15);
+        let upload = Duration::from_secs(300);
+        let server = Server::with_body("{}");
+        let data = Data {
+            base: "https://example.com".into(),
+            ..Default::default()
+        };
+        let client = MastodonBuilder::new(data)
+            .timeouts(Timeouts {
+                connect: None,
+                read: Some(read),
+                upload: Some(upload),
+            })
+            .build()
+            .expect("client")
+            .with_http_send(server.clone());
+        let _ = client.verify_credentials().await;
+        let _ = client
+            .media_from_bytes(vec![0], "a.png", None, None, None)
+            .await;
+        let _ = client.stream_user().await;
+        let timeouts: Vec<_> = server
+            .requests()
+            .iter()
+            .map(|request| request.timeout().copied())
+            .collect();
+        assert_eq!(timeouts, [Some(read), Some(upload), None]);
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt::Debug;
+
+use crate::entities::prelude::*;
+
+/// Callbacks invoked after the client successfully changes something on the
+/// server, to keep a local store of statuses and accounts consistent without
+/// wrapping every call site. Set it with
+/// [`Mastodon::with_cache_sync`](crate::Mastodon::with_cache_sync).
+///
+/// Every method does nothing by default, so implement only those you need.
+/// They're called before the method which made the change returns, so they
+/// should be quick, e.g. sending the change to a task which writes to the
+/// store.
+///
+/// ```
+/// use std::sync::Mutex;
+/// use mastodon_async::{cache_sync::CacheSync, prelude::*};
+///
+/// #[derive(Debug, Default)]
+/// struct Favourites(Mutex<Vec<StatusId>>);
+///
+/// impl CacheSync for Favourites {
+///     fn favourited(&self, status: &Status) {
+///         self.0.lock().unwrap().push(status.id.clone());
+///     }
+///
+///     fn unfavourited(&self, status: &Status) {
+///         self.0.lock().unwrap().retain(|id| *id != status.id);
+///     }
+/// }
+///
+/// let client = Mastodon::from(Data::default()).with_cache_sync(Favourites::default());
+/// ```
+pub trait CacheSync: Debug + Send + Sync {
+    /// The client account favourited a status.
+    fn favourited(&self, status: &Status) {
+        let _ = status;
+    }
+
+    /// The client account removed a status from its favourites.
+    fn unfavourited(&self, status: &Status) {
+        let _ = status;
+    }
+
+    /// The client account boosted a status. `status` is the new reblog,
+    /// wrapping the original status.
+    fn reblogged(&self, status: &Status) {
+        let _ = status;
+    }
+
+    /// The client account undid a boost. `status` is the original status.
+    fn unreblogged(&self, status: &Status) {
+        let _ = status;
+    }
+
+    /// The client account bookmarked a status.
+    fn bookmarked(&self, status: &Status) {
+        let _ = status;
+    }
+
+    /// The client account removed a status from its bookmarks.
+    fn unbookmarked(&self, status: &Status) {
+        let _ = status;
+    }
+
+    /// The client account followed, or requested to follow, an account.
+    fn followed(&self, relationship: &Relationship) {
+        let _ = relationship;
+    }
+
+    /// The client account unfollowed an account.
+    fn unfollowed(&self, relationship: &Relationship) {
+        let _ = relationship;
+    }
+
+    /// One of the client account's statuses was deleted.
+    fn status_deleted(&self, id: &StatusId) {
+        let _ = id;
+    }
+}
@@ -0,0 +1,216 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// A Mastodon version number, as advertised by an instance.
+///
+/// Only the leading numeric components are considered, so suffixes such as
+/// `+glitch` or `-beta.1` are ignored.
+///
+/// ```
+/// use mastodon_async::capabilities::Version;
+///
+/// let version: Version = "4.3.0+glitch".parse().unwrap();
+/// assert_eq!(version, Version::new(4, 3, 0));
+/// assert!(version >= Version::new(4, 2, 10));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Version {
+    /// The major version number.
+    pub major: u32,
+    /// The minor version number.
+    pub minor: u32,
+    /// The patch version number.
+    pub patch: u32,
+}
+
+impl Version {
+    /// Construct a version from its components.
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Version {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl FromStr for Version {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let numeric = s
+            .trim()
+            .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .next()
+            .unwrap_or_default();
+        let mut parts = numeric.split('.').map(str::parse::<u32>);
+        let mut next = || parts.next().transpose().ok().flatten();
+        let major = next().ok_or_else(|| crate::format_err!("invalid version {s:?}"))?;
+        Ok(Version::new(
+            major,
+            next().unwrap_or(0),
+            next().unwrap_or(0),
+        ))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// What the connected server is known to support.
+///
+/// Retrieve this with [`Mastodon::capabilities`](crate::Mastodon::capabilities);
+/// it is fetched once per client and cached.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// The raw version string advertised by the server.
+    pub version_string: String,
+    /// The parsed Mastodon version, if the version string could be parsed.
+    pub version: Option<Version>,
+}
+
+impl Capabilities {
+    /// Determine capabilities from the version string an instance reports.
+    pub fn from_version_string(version_string: impl Into<String>) -> Self {
+        let version_string = version_string.into();
+        Capabilities {
+            version: version_string.parse().ok(),
+            version_string,
+        }
+    }
+
+    /// Whether the server is known to run at least the given version.
+    pub fn at_least(&self, version: Version) -> bool {
+        self.version.is_some_and(|it| it >= version)
+    }
+
+    /// Whether multiple statuses can be fetched at once with
+    /// `GET /api/v1/statuses?id[]=...` (Mastodon 4.3+).
+    pub fn batch_statuses(&self) -> bool {
+        self.at_least(Version::new(4, 3, 0))
+    }
+
+    /// Whether statuses can quote other statuses, and quotes can be listed
+    /// and revoked (Mastodon 4.5+).
+    pub fn quotes(&self) -> bool {
+        self.at_least(Version::new(4, 5, 0))
+    }
+
+    /// Whether account aliases can be managed, and the account moved, through
+    /// the API. Pleroma and Akkoma allow it; Mastodon only allows it through
+    /// its settings pages.
+    pub fn account_migration(&self) -> bool {
+        self.is_pleroma()
+    }
+
+    /// Whether statuses can be reacted to with emoji. Mastodon only allows
+    /// reactions to announcements; glitch-soc, Pleroma, Akkoma and Firefish,
+    /// among others, allow them on statuses too.
+    pub fn status_reactions(&self) -> bool {
+        self.is_pleroma() || self.runs_any(&["glitch", "firefish", "iceshrimp", "fedibird"])
+    }
+
+    /// Whether the server runs Pleroma or a fork of it, with its own routes
+    /// for the features it adds.
+    pub(crate) fn is_pleroma(&self) -> bool {
+        self.runs_any(&["pleroma", "akkoma"])
+    }
+
+    /// Whether the version string names any of the given software.
+    fn runs_any(&self, names: &[&str]) -> bool {
+        let version = self.version_string.to_ascii_lowercase();
+        names.iter().any(|name| version.contains(name))
+    }
+
+    /// Fail with [`Error::Unsupported`](crate::Error::Unsupported) unless
+    /// `supported`, which should be one of the checks above.
+    pub(crate) fn require(&self, supported: bool, feature: &'static str) -> crate::Result<()> {
+        if supported {
+            Ok(())
+        } else {
+            Err(crate::Error::Unsupported {
+                feature,
+                version: self.version_string.clone(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            "4.2.10".parse::<Version>().expect("parse"),
+            Version::new(4, 2, 10)
+        );
+        assert_eq!(
+            "4.3.0-beta.1".parse::<Version>().expect("parse"),
+            Version::new(4, 3, 0)
+        );
+        assert_eq!(
+            "2.7.2 (compatible; Pleroma 2.5.0)"
+                .parse::<Version>()
+                .expect("parse"),
+            Version::new(2, 7, 2)
+        );
+        assert_eq!(
+            "4".parse::<Version>().expect("parse"),
+            Version::new(4, 0, 0)
+        );
+        assert!("unknown".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn test_batch_statuses() {
+        assert!(Capabilities::from_version_string("4.3.1").batch_statuses());
+        assert!(!Capabilities::from_version_string("4.2.12").batch_statuses());
+        assert!(!Capabilities::from_version_string("garbage").batch_statuses());
+    }
+
+    #[test]
+    fn test_quotes() {
+        assert!(Capabilities::from_version_string("4.5.0").quotes());
+        let capabilities = Capabilities::from_version_string("4.4.3");
+        assert!(!capabilities.quotes());
+        let err = capabilities
+            .require(capabilities.quotes(), "quote posts")
+            .unwrap_err();
+        assert!(
+            matches!(err, crate::Error::Unsupported { feature: "quote posts", version } if version == "4.4.3")
+        );
+    }
+
+    #[test]
+    fn test_account_migration() {
+        assert!(
+            Capabilities::from_version_string("2.7.2 (compatible; Pleroma 2.5.0)")
+                .account_migration()
+        );
+        assert!(
+            Capabilities::from_version_string("2.7.2 (compatible; Akkoma 3.10.4)")
+                .account_migration()
+        );
+        assert!(!Capabilities::from_version_string("4.3.1").account_migration());
+    }
+
+    #[test]
+    fn test_status_reactions() {
+        assert!(Capabilities::from_version_string("4.3.0+glitch").status_reactions());
+        assert!(
+            Capabilities::from_version_string("2.7.2 (compatible; Akkoma 3.10.4)")
+                .status_reactions()
+        );
+        assert!(
+            Capabilities::from_version_string("3.0.0 (compatible; Firefish 1.0.4)")
+                .status_reactions()
+        );
+        assert!(!Capabilities::from_version_string("4.3.1").status_reactions());
+    }
+}
@@ -1,6 +1,9 @@
 use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::entities::auth::scopes::Scopes;
 
 /// Raw data about mastodon app. Save `Data` using `serde` to prevent needing
 /// to authenticate on every run.
@@ -16,4 +19,19 @@ pub struct Data {
     pub redirect: Cow<'static, str>,
     /// The client's access token.
     pub token: Cow<'static, str>,
+    /// The token used to get a new access token once `token` expires, if
+    /// the server issued one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<Cow<'static, str>>,
+    /// When `token` expires, if it does.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "time::serde::timestamp::option"
+    )]
+    pub expires_at: Option<OffsetDateTime>,
+    /// The scopes granted to `token`, if the server said which when it
+    /// issued it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Scopes>,
 }
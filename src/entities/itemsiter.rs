@@ -28,6 +28,7 @@ pub(crate) struct ItemsIter<T: Clone + for<'de> Deserialize<'de> + Serialize> {
     buffer: Vec<T>,
     cur_idx: usize,
     use_initial: bool,
+    remaining: Option<usize>,
 }
 
 impl<'a, T: Clone + for<'de> Deserialize<'de> + Serialize> ItemsIter<T> {
@@ -37,9 +38,16 @@ impl<'a, T: Clone + for<'de> Deserialize<'de> + Serialize> ItemsIter<T> {
             buffer: vec![],
             cur_idx: 0,
             use_initial: true,
+            remaining: None,
         }
     }
 
+    /// Stop after yielding `limit` items, without fetching another page.
+    pub(crate) fn limit(mut self, limit: usize) -> Self {
+        self.remaining = Some(limit);
+        self
+    }
+
     fn need_next_page(&self) -> bool {
         if self.buffer.is_empty() || self.cur_idx == self.buffer.len() {
             debug!(idx = self.cur_idx, buffer_len = self.buffer.len(); "next page needed");
@@ -70,6 +78,13 @@ impl<'a, T: Clone + for<'de> Deserialize<'de> + Serialize> ItemsIter<T> {
 
     pub(crate) fn stream(self) -> impl Stream<Item = T> {
         unfold(self, |mut this| async move {
+            if let Some(remaining) = this.remaining.as_mut() {
+                if *remaining == 0 {
+                    debug!("yielded as many items as requested");
+                    return None;
+                }
+                *remaining -= 1;
+            }
             if this.use_initial {
                 let idx = this.cur_idx;
                 if this.page.initial_items.is_empty() || idx == this.page.initial_items.len() {
@@ -1,20 +1,30 @@
 use std::string::FromUtf8Error;
-use std::{error, fmt, io::Error as IoError, num::TryFromIntError};
+use std::{
+    collections::HashMap, error, fmt, io::Error as IoError, num::TryFromIntError, time::Duration,
+};
 
 use derive_builder::UninitializedFieldError;
 use derive_is_enum_variant::is_enum_variant;
 #[cfg(feature = "env")]
 use envy::Error as EnvyError;
+#[cfg(feature = "keyring")]
+use keyring::Error as KeyringError;
 use reqwest::{header::ToStrError as HeaderStrError, Error as HttpError, StatusCode};
+#[cfg(feature = "store-sqlite")]
+use rusqlite::Error as SqliteError;
 use serde::Deserialize;
 use serde_json::Error as SerdeError;
 use serde_urlencoded::ser::Error as UrlEncodedError;
+#[cfg(feature = "websocket")]
+use tokio_tungstenite::tungstenite::Error as WebSocketError;
 #[cfg(feature = "toml")]
 use tomlcrate::de::Error as TomlDeError;
 #[cfg(feature = "toml")]
 use tomlcrate::ser::Error as TomlSerError;
 use url::ParseError as UrlError;
 
+use crate::pacing::RetryAfter;
+
 /// Convience type over `std::result::Result` with `Error` as the error type.
 pub type Result<T> = ::std::result::Result<T, Error>;
 
@@ -28,7 +38,9 @@ pub enum Error {
         /// The response status.
         status: StatusCode,
         /// The JSON-decoded error response from the server.
-        response: ApiError,
+        response: Box<ApiError>,
+        /// The value of the `Retry-After` header, if the server sent one.
+        retry_after: Option<RetryAfter>,
     },
     /// Error deserialising to json. Typically represents a breaking change in
     /// the Mastodon API
@@ -55,6 +67,9 @@ pub enum Error {
     /// Missing Access Token.
     #[error("Missing Access Token.")]
     AccessTokenRequired,
+    /// Missing Refresh Token.
+    #[error("Missing Refresh Token.")]
+    RefreshTokenRequired,
     /// Error serializing to toml
     #[cfg(feature = "toml")]
     #[error("Error serializing to toml")]
@@ -102,13 +117,176 @@ pub enum Error {
     #[error(transparent)]
     FromUtf8(#[from] FromUtf8Error),
     /// Error constructing type from its builder
-    #[error(transparent)]
+    #[error("the required field wasn't set before calling build(): {0}")]
     Builder(#[from] UninitializedFieldError),
+    /// Error from the WebSocket connection used for streaming
+    #[cfg(feature = "websocket")]
+    #[error("WebSocket error")]
+    WebSocket(#[from] Box<WebSocketError>),
+    /// Error from the SQLite database of a [`store::Store`](crate::store::Store)
+    #[cfg(feature = "store-sqlite")]
+    #[error("SQLite error")]
+    Sqlite(#[from] SqliteError),
+    /// A lossless [`stream_manager`](crate::stream_manager) subscriber
+    /// didn't keep up, and its queue of events filled up.
+    #[error("subscriber fell behind by more than {capacity} events")]
+    SubscriberOverflow {
+        /// The size of the subscriber's queue
+        capacity: usize,
+    },
+    /// Nothing, not even a heartbeat, arrived on a streaming connection for
+    /// longer than the timeout set with
+    /// [`EventStreamExt::stall_timeout`](crate::event_stream::EventStreamExt::stall_timeout).
+    #[error("no data arrived on the stream for {timeout:?}")]
+    StreamStalled {
+        /// How long the stream was given to produce something
+        timeout: Duration,
+    },
+    /// A request needs an OAuth scope which wasn't among those granted to
+    /// the client with
+    /// [`Mastodon::with_granted_scopes`](crate::Mastodon::with_granted_scopes)
+    /// or [`Mastodon::with_scope_guard`](crate::Mastodon::with_scope_guard).
+    /// It is returned before the request is sent.
+    #[error("{method} {path} requires the {required:?} scope, which wasn't granted")]
+    MissingScope {
+        /// The scope the request needs
+        required: &'static str,
+        /// The HTTP method of the request
+        method: String,
+        /// The path of the request
+        path: String,
+    },
+    /// The authenticated account's role doesn't grant any of the permissions
+    /// the admin API needs, so [`Mastodon::admin`](crate::Mastodon::admin)
+    /// refused to make an [`AdminClient`](crate::admin::AdminClient).
+    #[error("the account's role ({role:?}) doesn't grant access to the admin API")]
+    NotAdmin {
+        /// The name of the account's role, if it has one
+        role: Option<String>,
+    },
+    /// The server doesn't run a version which supports a feature the
+    /// request needs, according to
+    /// [`Mastodon::capabilities`](crate::Mastodon::capabilities). It is
+    /// returned before the request is sent.
+    #[error("{feature} isn't supported by the server (version {version:?})")]
+    Unsupported {
+        /// The feature the request needs
+        feature: &'static str,
+        /// The version string the server advertises
+        version: String,
+    },
+    /// An encrypted file couldn't be decrypted, because the passphrase was
+    /// wrong, or the file isn't one written by
+    /// [`helpers::encrypted`](crate::helpers::encrypted), or has been
+    /// tampered with.
+    #[cfg(feature = "encrypted-file")]
+    #[error("couldn't decrypt the file: the passphrase is wrong or the file is corrupt")]
+    Decryption,
+    /// Error from the OS secret store used by
+    /// [`helpers::keyring`](crate::helpers::keyring)
+    #[cfg(feature = "keyring")]
+    #[error("error from the OS secret store")]
+    Keyring(#[from] KeyringError),
     /// Other errors
     #[error("other error: {0:?}")]
     Other(String),
 }
 
+impl Error {
+    /// The HTTP status of the response, if this is an error from the API.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Api { status, .. } => Some(*status),
+            Error::Http(err) => err.status(),
+            _ => None,
+        }
+    }
+
+    /// The error returned by the API, if any.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Error::Api { response, .. } => Some(response.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// The name of the field which wasn't set, if a builder failed because a
+    /// required field was missing.
+    ///
+    /// ```
+    /// use mastodon_async::{entities::forms::Application, Error};
+    ///
+    /// let err: Error = Application::builder().build().unwrap_err().into();
+    /// assert_eq!(err.missing_field(), Some("client_name"));
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "the required field wasn't set before calling build(): \
+    ///      Field not initialized: client_name"
+    /// );
+    /// ```
+    pub fn missing_field(&self) -> Option<&'static str> {
+        match self {
+            Error::Builder(err) => Some(err.field_name()),
+            Error::Entities(err) => err.missing_field(),
+            _ => None,
+        }
+    }
+
+    /// Whether the server rejected the request's access token, or it had
+    /// none when it was needed.
+    pub fn is_unauthorized(&self) -> bool {
+        self.status() == Some(StatusCode::UNAUTHORIZED)
+    }
+
+    /// Whether the server rejected the request for exceeding its rate
+    /// limit.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// Whether the request may succeed if it's sent again later: the
+    /// connection failed, timed out or stalled, the server was overloaded,
+    /// or it failed with a server error other than `501 Not Implemented`.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::Http(err) if err.is_connect() || err.is_timeout() => true,
+            Error::StreamStalled { .. } => true,
+            _ => self.status().is_some_and(crate::mastodon::is_transient),
+        }
+    }
+
+    /// How long the server asked to wait before trying again, if it did.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use mastodon_async::{pacing::RetryAfter, Error};
+    ///
+    /// let err = Error::Api {
+    ///     status: reqwest::StatusCode::TOO_MANY_REQUESTS,
+    ///     response: serde_json::from_str(r#"{"error": "Too many requests"}"#).unwrap(),
+    ///     retry_after: Some(RetryAfter::Delay(Duration::from_secs(30))),
+    /// };
+    /// assert!(err.is_rate_limited());
+    /// assert_eq!(err.retry_after(), Some(Duration::from_secs(30)));
+    /// ```
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::Api {
+                retry_after: Some(retry_after),
+                ..
+            } => Some(retry_after.duration()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "websocket")]
+impl From<WebSocketError> for Error {
+    fn from(err: WebSocketError) -> Self {
+        Error::WebSocket(Box::new(err))
+    }
+}
+
 /// Error returned from the Mastodon API.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ApiError {
@@ -116,6 +294,42 @@ pub struct ApiError {
     pub error: String,
     /// A longer description of the error, mainly provided with the OAuth API.
     pub error_description: Option<String>,
+    /// The reasons each invalid field was rejected, returned along with a
+    /// 422 status by Mastodon 4 and later.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<HashMap<String, Vec<ValidationError>>>,
+}
+
+impl ApiError {
+    /// The reasons the given field was rejected, if it was.
+    ///
+    /// ```
+    /// use mastodon_async::ApiError;
+    ///
+    /// let error: ApiError = serde_json::from_str(r#"{
+    ///     "error": "Validation failed: Username has already been taken",
+    ///     "details": {
+    ///         "username": [{"error": "ERR_TAKEN", "description": "has already been taken"}]
+    ///     }
+    /// }"#).unwrap();
+    /// assert_eq!(error.field_errors("username")[0].error, "ERR_TAKEN");
+    /// assert!(error.field_errors("email").is_empty());
+    /// ```
+    pub fn field_errors(&self, field: &str) -> &[ValidationError] {
+        self.details
+            .as_ref()
+            .and_then(|details| details.get(field))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Why a field of a request was rejected.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ValidationError {
+    /// A machine-readable code, such as `ERR_BLANK` or `ERR_TAKEN`.
+    pub error: String,
+    /// A human-readable description of the problem.
+    pub description: String,
 }
 
 impl fmt::Display for ApiError {
@@ -185,6 +399,54 @@ mod tests {
         assert_is!(err, Error::Url(..));
     }
 
+    #[test]
+    fn test_api_error_helpers() {
+        let err = Error::Api {
+            status: StatusCode::UNAUTHORIZED,
+            response: serde_json::from_str(
+                r#"{"error": "invalid_token", "error_description": "The access token is invalid"}"#,
+            )
+            .unwrap(),
+            retry_after: None,
+        };
+        assert!(err.is_unauthorized());
+        assert!(!err.is_rate_limited());
+        assert!(!err.is_transient());
+        assert_eq!(err.retry_after(), None);
+        let response = err.api_error().unwrap();
+        assert_eq!(response.error, "invalid_token");
+        assert!(response.details.is_none());
+        assert!(!Error::AccessTokenRequired.is_unauthorized());
+        assert!(!Error::AccessTokenRequired.is_transient());
+        let err = Error::Api {
+            status: StatusCode::BAD_GATEWAY,
+            response: serde_json::from_str(r#"{"error": "Bad Gateway"}"#).unwrap(),
+            retry_after: None,
+        };
+        assert!(err.is_transient());
+    }
+
+    #[test]
+    fn test_validation_details() {
+        let response: ApiError = serde_json::from_str(
+            r#"{
+                "error": "Validation failed: Email can't be blank, Agreement must be accepted",
+                "details": {
+                    "email": [{"error": "ERR_BLANK", "description": "can't be blank"}],
+                    "agreement": [{"error": "ERR_ACCEPTED", "description": "must be accepted"}]
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.field_errors("agreement"),
+            [ValidationError {
+                error: "ERR_ACCEPTED".to_string(),
+                description: "must be accepted".to_string(),
+            }]
+        );
+    }
+
     #[cfg(feature = "toml")]
     #[test]
     fn from_toml_de_error() {
@@ -1,32 +1,61 @@
-use std::io;
+use std::{
+    fmt,
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 
-use crate::{errors::Result, prelude::*, Error};
-use futures::{stream::try_unfold, TryStream, TryStreamExt};
-use log::{debug, error, info, trace};
+use crate::{
+    errors::Result,
+    page::{is_reblog, is_reply},
+    prelude::*,
+    Error, Language,
+};
+use futures::{
+    future, ready,
+    stream::{try_unfold, TryFilter},
+    Stream, TryStream, TryStreamExt,
+};
+use log::{debug, error, info, trace, warn};
 use reqwest::Response;
 use tokio::io::AsyncBufReadExt;
 use tokio_util::io::StreamReader;
 
+/// Streaming events over a WebSocket connection
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
 /// Return a stream of events from the given response by parsing Server-Sent
 /// Events as they come in.
 ///
+/// The stream holds its own handle to the client, so it doesn't borrow
+/// `client` and can be moved onto another task.
+///
 /// See <https://docs.joinmastodon.org/methods/streaming/> for more info
 pub fn event_stream(
     response: Response,
     location: String,
     client: &Mastodon,
-) -> impl TryStream<Ok = (Event, Mastodon), Error = Error> + '_ {
+) -> impl TryStream<Ok = (Event, Mastodon), Error = Error> + Send + 'static {
     let stream = StreamReader::new(response.bytes_stream().map_err(|err| {
         error!(err:? = err; "error reading stream");
         io::Error::new(io::ErrorKind::BrokenPipe, format!("{err:?}"))
     }));
     let lines_iter = stream.lines();
+    let client = client.clone();
     try_unfold((lines_iter, location, client), |mut this| async move {
-        let (ref mut lines_iter, ref location, client) = this;
+        let (ref mut lines_iter, ref location, ref client) = this;
         let mut lines = vec![];
         while let Some(line) = lines_iter.next_line().await? {
             debug!(message = line, location = &location; "received message");
             let line = line.trim().to_string();
+            // comments between events are keepalives
+            if line.starts_with(':') && lines.is_empty() {
+                trace!(location = location; "received heartbeat");
+                return Ok(Some(((Event::Heartbeat, client.clone()), this)));
+            }
             if line.starts_with(':') || line.is_empty() {
                 continue;
             }
@@ -43,6 +72,400 @@ pub fn event_stream(
     })
 }
 
+/// How [`reconnecting`] waits between attempts to re-establish a dropped
+/// streaming connection, and how [`Mastodon::with_retries`] waits between
+/// attempts at a failed request.
+///
+/// The delay starts at `initial` and is multiplied by `multiplier` after each
+/// consecutive failure, up to `max`. A random jitter of up to half the delay
+/// is subtracted, so that many clients don't reconnect in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backoff {
+    /// The delay before the first reconnection attempt.
+    pub initial: Duration,
+    /// The longest delay between reconnection attempts.
+    pub max: Duration,
+    /// The factor by which the delay grows after each failed attempt.
+    pub multiplier: f64,
+    /// How many consecutive attempts may fail before giving up, or `None` to
+    /// retry forever.
+    pub max_retries: Option<u32>,
+}
+
+impl Default for Backoff {
+    /// Start at one second, doubling up to five minutes, forever.
+    fn default() -> Self {
+        Backoff {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(5 * 60),
+            multiplier: 2.0,
+            max_retries: None,
+        }
+    }
+}
+
+impl Backoff {
+    /// The delay before the given (zero-based) attempt, before jitter.
+    fn base_delay(&self, attempt: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1.0)
+            .powi(attempt.min(i32::MAX as u32) as i32);
+        Duration::try_from_secs_f64(self.initial.as_secs_f64() * factor)
+            .unwrap_or(self.max)
+            .min(self.max)
+    }
+
+    /// The delay before the given (zero-based) attempt, with jitter applied.
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay(attempt);
+        delay - delay.mul_f64(fastrand::f64() / 2.0)
+    }
+
+    pub(crate) fn exhausted(&self, failures: u32) -> bool {
+        self.max_retries.is_some_and(|max| failures > max)
+    }
+}
+
+type BoxedEvents<'a> = Pin<Box<dyn Stream<Item = Result<(Event, Mastodon)>> + Send + 'a>>;
+
+struct Reconnecting<'a, F> {
+    client: Mastodon,
+    connect: F,
+    backoff: Backoff,
+    current: Option<BoxedEvents<'a>>,
+    failures: u32,
+    connected: bool,
+}
+
+/// Wrap a streaming connection so that it is transparently re-established,
+/// with exponential backoff, whenever it ends or fails.
+///
+/// `connect` is called to open the initial connection and each subsequent
+/// one. After every successful reconnection an [`Event::ConnectionReset`] is
+/// yielded, since events may have been missed in the meantime. If
+/// `backoff.max_retries` consecutive attempts fail, the stream ends with the
+/// last error.
+///
+/// The stream doesn't borrow `client`, so if `connect` owns everything it
+/// uses, such as a clone of the client, the stream can be spawned onto
+/// another task.
+///
+/// ```no_run
+/// use futures_util::TryStreamExt;
+/// use mastodon_async::{
+///     entities::event::Event,
+///     event_stream::{reconnecting, Backoff},
+///     prelude::*,
+/// };
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let backoff = Backoff {
+///         max_retries: Some(10),
+///         ..Default::default()
+///     };
+///     reconnecting(&client, backoff, || client.stream_user())
+///         .try_for_each(|(event, _client)| async move {
+///             if let Event::ConnectionReset = event {
+///                 // catch up on anything which was missed
+///             }
+///             Ok(())
+///         })
+///         .await
+///         .unwrap();
+/// });
+/// ```
+pub fn reconnecting<'a, F, Fut, S>(
+    client: &Mastodon,
+    backoff: Backoff,
+    connect: F,
+) -> impl TryStream<Ok = (Event, Mastodon), Error = Error> + 'a
+where
+    F: FnMut() -> Fut + 'a,
+    Fut: Future<Output = Result<S>> + 'a,
+    S: TryStream<Ok = (Event, Mastodon), Error = Error> + Send + 'a,
+{
+    let state = Reconnecting {
+        client: client.clone(),
+        connect,
+        backoff,
+        current: None,
+        failures: 0,
+        connected: false,
+    };
+    try_unfold(state, |mut this| async move {
+        loop {
+            let Some(current) = this.current.as_mut() else {
+                if this.failures > 0 {
+                    let delay = this.backoff.delay(this.failures - 1);
+                    debug!(delay:? = delay, failures = this.failures; "waiting to reconnect");
+                    tokio::time::sleep(delay).await;
+                }
+                match (this.connect)().await {
+                    Ok(stream) => {
+                        this.current = Some(Box::pin(stream.into_stream()));
+                        if this.connected {
+                            info!(failures = this.failures; "reconnected to streaming API");
+                            let client = this.client.clone();
+                            return Ok(Some(((Event::ConnectionReset, client), this)));
+                        }
+                        this.connected = true;
+                    }
+                    Err(err) => {
+                        this.failures += 1;
+                        warn!(err:? = err, failures = this.failures; "failed to connect to streaming API");
+                        if this.backoff.exhausted(this.failures) {
+                            return Err(err);
+                        }
+                    }
+                }
+                continue;
+            };
+            match current.try_next().await {
+                Ok(Some(item)) => {
+                    this.failures = 0;
+                    return Ok(Some((item, this)));
+                }
+                Ok(None) => {
+                    warn!(failures = this.failures; "streaming connection closed");
+                    this.current = None;
+                    this.failures += 1;
+                    if this.backoff.exhausted(this.failures) {
+                        return Ok(None);
+                    }
+                }
+                Err(err) => {
+                    warn!(err:? = err, failures = this.failures; "streaming connection failed");
+                    this.current = None;
+                    this.failures += 1;
+                    if this.backoff.exhausted(this.failures) {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Combinators for streams of events, such as those returned by
+/// [`Mastodon::stream_public`](crate::Mastodon::stream_public).
+pub trait EventStreamExt: TryStream<Ok = (Event, Mastodon), Error = Error> + Sized {
+    /// Skip new and edited statuses which aren't in one of the given
+    /// languages, or whose language the server didn't detect. Other events
+    /// are passed through.
+    ///
+    /// ```no_run
+    /// use futures_util::TryStreamExt;
+    /// use mastodon_async::{event_stream::EventStreamExt, prelude::*, Language};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     client
+    ///         .stream_hashtag("rustlang")
+    ///         .await
+    ///         .unwrap()
+    ///         .in_languages(&[Language::Eng, Language::Deu])
+    ///         .try_for_each(|(event, _client)| async move {
+    ///             // only English and German statuses get here
+    ///             Ok(())
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    fn in_languages(
+        self,
+        languages: &[Language],
+    ) -> InLanguages<Self, fn(&Status) -> Option<Language>> {
+        let undetected: fn(&Status) -> Option<Language> = |_| None;
+        self.in_languages_or_detect(languages, undetected)
+    }
+
+    /// Like [`EventStreamExt::in_languages`], but calls `detect` to find the
+    /// language of statuses the server didn't detect one for.
+    fn in_languages_or_detect<F>(self, languages: &[Language], detect: F) -> InLanguages<Self, F>
+    where
+        F: FnMut(&Status) -> Option<Language>,
+    {
+        InLanguages {
+            stream: Box::pin(self),
+            languages: languages.to_vec(),
+            detect,
+        }
+    }
+
+    /// End the stream with [`Error::StreamStalled`] if nothing arrives on it
+    /// for longer than `timeout`. Mastodon sends an [`Event::Heartbeat`]
+    /// every few seconds, so a stream which stays silent for much longer
+    /// than that has most likely lost its connection. Wrapped in
+    /// [`reconnecting`], a stalled stream is replaced with a new connection.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use futures_util::TryStreamExt;
+    /// use mastodon_async::{
+    ///     event_stream::{reconnecting, Backoff, EventStreamExt},
+    ///     prelude::*,
+    /// };
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     reconnecting(&client, Backoff::default(), || async {
+    ///         let stream = client.stream_user().await?;
+    ///         Ok(stream.stall_timeout(Duration::from_secs(60)))
+    ///     })
+    ///     .try_for_each(|(event, _client)| async move { Ok(()) })
+    ///     .await
+    ///     .unwrap();
+    /// });
+    /// ```
+    fn stall_timeout(self, timeout: Duration) -> StallTimeout<Self> {
+        StallTimeout {
+            stream: Box::pin(self),
+            timeout,
+            deadline: Box::pin(tokio::time::sleep(timeout)),
+            stalled: false,
+        }
+    }
+
+    /// Skip new and edited statuses which are reblogs. Other events are
+    /// passed through.
+    ///
+    /// ```no_run
+    /// use futures_util::TryStreamExt;
+    /// use mastodon_async::{event_stream::EventStreamExt, prelude::*};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     client
+    ///         .stream_user()
+    ///         .await
+    ///         .unwrap()
+    ///         .without_reblogs()
+    ///         .without_replies()
+    ///         .try_for_each(|(event, _client)| async move {
+    ///             // only original posts, and threads continued by their
+    ///             // authors, get here
+    ///             Ok(())
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    fn without_reblogs(self) -> SkipEvents<Self> {
+        let keep: fn(&(Event, Mastodon)) -> future::Ready<bool> =
+            |(event, _client)| future::ready(!event_status(event).is_some_and(is_reblog));
+        self.try_filter(keep)
+    }
+
+    /// Skip new and edited statuses which reply to other accounts, as
+    /// [`StatusStreamExt::without_replies`](crate::page::StatusStreamExt::without_replies)
+    /// does. Other events are passed through.
+    fn without_replies(self) -> SkipEvents<Self> {
+        let keep: fn(&(Event, Mastodon)) -> future::Ready<bool> =
+            |(event, _client)| future::ready(!event_status(event).is_some_and(is_reply));
+        self.try_filter(keep)
+    }
+}
+
+impl<S: TryStream<Ok = (Event, Mastodon), Error = Error>> EventStreamExt for S {}
+
+/// The status a new or edited status event carries.
+fn event_status(event: &Event) -> Option<&Status> {
+    match event {
+        Event::Update(status) | Event::StatusUpdate(status) => Some(status),
+        _ => None,
+    }
+}
+
+/// Stream for [`EventStreamExt::without_reblogs`] and
+/// [`EventStreamExt::without_replies`].
+pub type SkipEvents<S> =
+    TryFilter<S, future::Ready<bool>, fn(&(Event, Mastodon)) -> future::Ready<bool>>;
+
+/// Stream for [`EventStreamExt::stall_timeout`].
+pub struct StallTimeout<S> {
+    stream: Pin<Box<S>>,
+    timeout: Duration,
+    deadline: Pin<Box<tokio::time::Sleep>>,
+    stalled: bool,
+}
+
+impl<S> fmt::Debug for StallTimeout<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StallTimeout")
+            .field("timeout", &self.timeout)
+            .field("stalled", &self.stalled)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Stream for StallTimeout<S>
+where
+    S: TryStream<Ok = (Event, Mastodon), Error = Error>,
+{
+    type Item = Result<(Event, Mastodon)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.stalled {
+            return Poll::Ready(None);
+        }
+        if let Poll::Ready(item) = this.stream.as_mut().try_poll_next(cx) {
+            let deadline = tokio::time::Instant::now() + this.timeout;
+            this.deadline.as_mut().reset(deadline);
+            return Poll::Ready(item);
+        }
+        ready!(this.deadline.as_mut().poll(cx));
+        warn!(timeout:? = this.timeout; "streaming connection stalled");
+        this.stalled = true;
+        Poll::Ready(Some(Err(Error::StreamStalled {
+            timeout: this.timeout,
+        })))
+    }
+}
+
+/// Stream for [`EventStreamExt::in_languages`].
+pub struct InLanguages<S, F> {
+    stream: Pin<Box<S>>,
+    languages: Vec<Language>,
+    detect: F,
+}
+
+impl<S, F> fmt::Debug for InLanguages<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InLanguages")
+            .field("languages", &self.languages)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, F> Stream for InLanguages<S, F>
+where
+    S: TryStream<Ok = (Event, Mastodon), Error = Error>,
+    F: FnMut(&Status) -> Option<Language> + Unpin,
+{
+    type Item = Result<(Event, Mastodon)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let Some((event, client)) = ready!(this.stream.as_mut().try_poll_next(cx)?) else {
+                return Poll::Ready(None);
+            };
+            if let Event::Update(status) | Event::StatusUpdate(status) = &event {
+                let language = status.language.or_else(|| (this.detect)(status));
+                if !language.is_some_and(|language| this.languages.contains(&language)) {
+                    trace!(id = status.id.as_ref(), language:? = language; "skipping status");
+                    continue;
+                }
+            }
+            return Poll::Ready(Some(Ok((event, client))));
+        }
+    }
+}
+
 pub(crate) fn make_event(lines: &[String]) -> Result<Event> {
     let event;
     let data;
@@ -82,7 +505,250 @@ pub(crate) fn make_event(lines: &[String]) -> Result<Event> {
                 data.ok_or_else(|| Error::Other("Missing `data` line for delete".to_string()))?;
             Event::Delete(data)
         }
+        "status.update" => {
+            let data = data
+                .ok_or_else(|| Error::Other("Missing `data` line for status.update".to_string()))?;
+            let status = serde_json::from_str::<Status>(&data)?;
+            Event::StatusUpdate(status)
+        }
         "filters_changed" => Event::FiltersChanged,
-        _ => return Err(Error::Other(format!("Unknown event `{event}`"))),
+        "conversation" => {
+            let data = data
+                .ok_or_else(|| Error::Other("Missing `data` line for conversation".to_string()))?;
+            Event::Conversation(serde_json::from_str(&data)?)
+        }
+        "announcement" => {
+            let data = data
+                .ok_or_else(|| Error::Other("Missing `data` line for announcement".to_string()))?;
+            Event::Announcement(serde_json::from_str(&data)?)
+        }
+        "announcement.reaction" => {
+            let data = data.ok_or_else(|| {
+                Error::Other("Missing `data` line for announcement.reaction".to_string())
+            })?;
+            Event::AnnouncementReaction(serde_json::from_str(&data)?)
+        }
+        "announcement.delete" => {
+            let data = data.ok_or_else(|| {
+                Error::Other("Missing `data` line for announcement.delete".to_string())
+            })?;
+            Event::AnnouncementDelete(AnnouncementId::new(data))
+        }
+        // wait for the data line, which every event has, before passing an
+        // unknown one on
+        _ => Event::Unknown {
+            payload: data
+                .ok_or_else(|| Error::Other(format!("Missing `data` line for {event}")))?,
+            event: event.to_string(),
+        },
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, StreamExt};
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn test_backoff_delay() {
+        let backoff = Backoff {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(10),
+            multiplier: 2.0,
+            max_retries: Some(3),
+        };
+        assert_eq!(backoff.base_delay(0), Duration::from_secs(1));
+        assert_eq!(backoff.base_delay(2), Duration::from_secs(4));
+        assert_eq!(backoff.base_delay(10), Duration::from_secs(10));
+        assert_eq!(backoff.base_delay(u32::MAX), Duration::from_secs(10));
+        for _ in 0..100 {
+            let delay = backoff.delay(2);
+            assert!(delay <= Duration::from_secs(4));
+            assert!(delay >= Duration::from_secs(2));
+        }
+        assert!(!backoff.exhausted(3));
+        assert!(backoff.exhausted(4));
+        assert!(!Backoff::default().exhausted(u32::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_reconnecting() {
+        let client = Mastodon::from(Data::default());
+        let backoff = Backoff {
+            initial: Duration::from_millis(1),
+            max: Duration::from_millis(1),
+            max_retries: Some(1),
+            ..Default::default()
+        };
+        let mut attempts = 0;
+        let events: Vec<_> = reconnecting(&client, backoff, || {
+            attempts += 1;
+            let attempt = attempts;
+            let client = client.clone();
+            async move {
+                if attempt > 2 {
+                    return Err(Error::Other("refused".to_string()));
+                }
+                let event = Event::Delete(attempt.to_string());
+                Ok(stream::iter([Ok((event, client))]))
+            }
+        })
+        .into_stream()
+        .collect()
+        .await;
+        let events: Vec<_> = events
+            .into_iter()
+            .map(|event| match event {
+                Ok((Event::Delete(id), _)) => id,
+                Ok((Event::ConnectionReset, _)) => "reset".to_string(),
+                Ok((other, _)) => panic!("unexpected event {other:?}"),
+                Err(err) => err.to_string(),
+            })
+            .collect();
+        assert_eq!(events, ["1", "reset", "2", "other error: \"refused\""]);
+    }
+
+    #[test]
+    fn test_streams_can_be_spawned() {
+        fn assert_spawnable<T: Send + 'static>(_: &T) {}
+
+        // never polled: this only checks the streams' types
+        let opened = async {
+            let client = Mastodon::from(Data::default());
+            assert_spawnable(&client.stream_user().await?);
+            assert_spawnable(&client.stream_hashtag("bots").await?);
+            let connect = client.clone();
+            assert_spawnable(&reconnecting(&client, Backoff::default(), move || {
+                let client = connect.clone();
+                async move { client.stream_notifications().await }
+            }));
+            Ok::<_, Error>(())
+        };
+        drop(opened);
+    }
+
+    #[test]
+    fn test_make_event() {
+        let lines = |lines: &[&str]| lines.iter().map(|it| it.to_string()).collect::<Vec<_>>();
+        let event = make_event(&lines(&["event: announcement.delete", "data: 8"])).expect("delete");
+        assert!(matches!(event, Event::AnnouncementDelete(id) if id == AnnouncementId::new("8")));
+        let event = make_event(&lines(&[
+            "event: announcement.reaction",
+            r#"data: {"name":"bongoCat","count":10,"announcement_id":"8"}"#,
+        ]))
+        .expect("reaction");
+        assert!(matches!(event, Event::AnnouncementReaction(reaction) if reaction.count == 10));
+        // unknown events are passed on once their data arrives
+        assert!(make_event(&lines(&["event: encrypted_message"])).is_err());
+        let event = make_event(&lines(&["event: encrypted_message", "data: {}"])).expect("unknown");
+        let Event::Unknown { event, payload } = event else {
+            panic!("unexpected event {event:?}");
+        };
+        assert_eq!(
+            (event.as_str(), payload.as_str()),
+            ("encrypted_message", "{}")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_heartbeats() {
+        let client = Mastodon::from(Data::default());
+        let body = ":)\n\nevent: delete\ndata: 1\n\n:thump\n";
+        let response = http::Response::new(body).into();
+        let events: Vec<_> = event_stream(response, "test".to_string(), &client)
+            .map_ok(|(event, _client)| match event {
+                Event::Heartbeat => "heartbeat".to_string(),
+                Event::Delete(id) => id,
+                other => panic!("unexpected event {other:?}"),
+            })
+            .try_collect()
+            .await
+            .expect("events");
+        assert_eq!(events, ["heartbeat", "1", "heartbeat"]);
+    }
+
+    #[tokio::test]
+    async fn test_stall_timeout() {
+        let client = Mastodon::from(Data::default());
+        let events: Vec<_> = stream::iter([Ok((Event::Heartbeat, client))])
+            .chain(stream::pending())
+            .stall_timeout(Duration::from_millis(10))
+            .collect()
+            .await;
+        assert_eq!(events.len(), 2);
+        assert!(events[0].is_ok());
+        assert!(matches!(
+            events[1],
+            Err(Error::StreamStalled { timeout }) if timeout == Duration::from_millis(10)
+        ));
+    }
+
+    fn status(id: &str, language: Option<&str>) -> Status {
+        let mut status = fixtures::status_json(id);
+        status["language"] = language.into();
+        fixtures::from_json(status)
+    }
+
+    #[tokio::test]
+    async fn test_in_languages() {
+        let client = Mastodon::from(Data::default());
+        let events = || {
+            stream::iter([
+                Event::Update(status("1", Some("en"))),
+                Event::Update(status("2", Some("fr"))),
+                Event::StatusUpdate(status("3", None)),
+                Event::Delete("4".to_string()),
+            ])
+            .map(|event| Ok::<_, Error>((event, client.clone())))
+        };
+        let ids = |events: Vec<Result<(Event, Mastodon)>>| -> Vec<String> {
+            events
+                .into_iter()
+                .map(|event| match event.expect("event").0 {
+                    Event::Update(status) | Event::StatusUpdate(status) => status.id.to_string(),
+                    Event::Delete(id) => id,
+                    other => panic!("unexpected event {other:?}"),
+                })
+                .collect()
+        };
+        let english = events().in_languages(&[Language::Eng]).collect().await;
+        assert_eq!(ids(english), ["1", "4"]);
+        let detected = events()
+            .in_languages_or_detect(&[Language::Eng], |_| Some(Language::Eng))
+            .collect()
+            .await;
+        assert_eq!(ids(detected), ["1", "3", "4"]);
+    }
+
+    #[tokio::test]
+    async fn test_without_reblogs_and_replies() {
+        let client = Mastodon::from(Data::default());
+        let original = status("1", Some("en"));
+        let mut reblog = status("2", Some("en"));
+        reblog.reblog = Some(Box::new(original.clone()));
+        let mut reply = status("3", Some("en"));
+        reply.in_reply_to_id = Some(StatusId::new("100"));
+        reply.in_reply_to_account_id = Some(AccountId::new("2"));
+        let events = stream::iter([
+            Event::Update(original),
+            Event::Update(reblog),
+            Event::StatusUpdate(reply),
+            Event::Delete("4".to_string()),
+        ])
+        .map(|event| Ok::<_, Error>((event, client.clone())));
+        let events: Vec<_> = events
+            .without_reblogs()
+            .without_replies()
+            .map_ok(|(event, _client)| match event {
+                Event::Update(status) | Event::StatusUpdate(status) => status.id.to_string(),
+                Event::Delete(id) => id,
+                other => panic!("unexpected event {other:?}"),
+            })
+            .try_collect()
+            .await
+            .expect("events");
+        assert_eq!(events, ["1", "4"]);
+    }
+}
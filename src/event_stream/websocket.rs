@@ -0,0 +1,230 @@
+use futures::{stream::try_unfold, SinkExt, StreamExt, TryStream};
+use log::{debug, info, trace};
+use serde::Serialize;
+use tokio::{net::TcpStream, sync::mpsc};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+pub use tokio_tungstenite::tungstenite::Message;
+
+use super::make_event;
+use crate::{errors::Result, format_err, prelude::*, Error};
+
+/// A timeline which can be subscribed to over a WebSocket connection.
+///
+/// See <https://docs.joinmastodon.org/methods/streaming/#streams> for more
+/// info
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "stream")]
+pub enum Timeline {
+    /// Events relevant to the authorized user, i.e. home timeline &
+    /// notifications
+    #[serde(rename = "user")]
+    User,
+    /// Notifications for the current user.
+    #[serde(rename = "user:notification")]
+    UserNotification,
+    /// All public posts known to the server.
+    #[serde(rename = "public")]
+    Public,
+    /// All public posts known to the server, filtered for media attachments.
+    #[serde(rename = "public:media")]
+    PublicMedia,
+    /// All public posts originating from this server.
+    #[serde(rename = "public:local")]
+    PublicLocal,
+    /// All public posts originating from this server, filtered for media
+    /// attachments.
+    #[serde(rename = "public:local:media")]
+    PublicLocalMedia,
+    /// All public posts originating from other servers.
+    #[serde(rename = "public:remote")]
+    PublicRemote,
+    /// All public posts originating from other servers, filtered for media
+    /// attachments.
+    #[serde(rename = "public:remote:media")]
+    PublicRemoteMedia,
+    /// All public posts using a certain hashtag.
+    #[serde(rename = "hashtag")]
+    Hashtag {
+        /// The hashtag, without the leading `#`
+        tag: String,
+    },
+    /// All public posts using a certain hashtag, originating from this
+    /// server.
+    #[serde(rename = "hashtag:local")]
+    HashtagLocal {
+        /// The hashtag, without the leading `#`
+        tag: String,
+    },
+    /// Updates to a specific list.
+    #[serde(rename = "list")]
+    List {
+        /// The list to stream
+        list: ListId,
+    },
+    /// Updates to direct conversations.
+    #[serde(rename = "direct")]
+    Direct,
+}
+
+#[derive(Serialize)]
+struct Frame<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(flatten)]
+    timeline: &'a Timeline,
+}
+
+impl Frame<'_> {
+    fn message(&self) -> Result<Message> {
+        Ok(Message::Text(serde_json::to_string(self)?))
+    }
+}
+
+/// A handle for changing which timelines are multiplexed over a WebSocket
+/// connection opened with
+/// [`Mastodon::stream_websocket`](crate::Mastodon::stream_websocket).
+///
+/// Subscription changes are sent the next time the associated event stream
+/// is polled.
+#[derive(Debug, Clone)]
+pub struct Subscriptions(mpsc::UnboundedSender<Message>);
+
+impl Subscriptions {
+    /// Start receiving events from the given timeline.
+    pub fn subscribe(&self, timeline: &Timeline) -> Result<()> {
+        self.send(Frame {
+            kind: "subscribe",
+            timeline,
+        })
+    }
+
+    /// Stop receiving events from the given timeline.
+    pub fn unsubscribe(&self, timeline: &Timeline) -> Result<()> {
+        self.send(Frame {
+            kind: "unsubscribe",
+            timeline,
+        })
+    }
+
+    fn send(&self, frame: Frame<'_>) -> Result<()> {
+        self.0
+            .send(frame.message()?)
+            .map_err(|_| format_err!("WebSocket event stream was dropped"))
+    }
+}
+
+struct State {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    frames: mpsc::UnboundedReceiver<Message>,
+    accepting_frames: bool,
+    location: String,
+    client: Mastodon,
+}
+
+/// Return a handle for managing subscriptions, along with a stream of events
+/// from every subscribed timeline, parsed from the JSON messages received
+/// over the given WebSocket connection.
+///
+/// See <https://docs.joinmastodon.org/methods/streaming/#websocket> for more
+/// info
+pub fn websocket_event_stream(
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    location: String,
+    client: &Mastodon,
+) -> (
+    Subscriptions,
+    impl TryStream<Ok = (Event, Mastodon), Error = Error> + Send + 'static,
+) {
+    let (sender, frames) = mpsc::unbounded_channel();
+    let state = State {
+        socket,
+        frames,
+        accepting_frames: true,
+        location,
+        client: client.clone(),
+    };
+    let stream = try_unfold(state, |mut this| async move {
+        loop {
+            let message = tokio::select! {
+                frame = this.frames.recv(), if this.accepting_frames => {
+                    match frame {
+                        Some(frame) => {
+                            debug!(frame:? = frame, location = &this.location; "sending frame");
+                            this.socket.send(frame).await?;
+                        }
+                        None => this.accepting_frames = false,
+                    }
+                    continue;
+                }
+                message = this.socket.next() => message,
+            };
+            let Some(message) = message else {
+                return Ok(None);
+            };
+            match message? {
+                Message::Text(text) => {
+                    debug!(message = text, location = &this.location; "received message");
+                    match make_event(&[text]) {
+                        Ok(event) => {
+                            info!(event:serde = event, location = &this.location; "received event");
+                            return Ok(Some(((event, this.client.clone()), this)));
+                        }
+                        Err(err) => trace!(err:? = err; "skipping unrecognized message"),
+                    }
+                }
+                Message::Close(frame) => {
+                    debug!(frame:? = frame, location = &this.location; "connection closed");
+                    return Ok(None);
+                }
+                _ => {}
+            }
+        }
+    });
+    (Subscriptions(sender), stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(kind: &'static str, timeline: &Timeline) -> String {
+        match (Frame { kind, timeline }).message().expect("serialize") {
+            Message::Text(text) => text,
+            other => panic!("unexpected message {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_subscribe_frames() {
+        assert_eq!(
+            frame("subscribe", &Timeline::PublicLocalMedia),
+            r#"{"type":"subscribe","stream":"public:local:media"}"#
+        );
+        assert_eq!(
+            frame(
+                "unsubscribe",
+                &Timeline::Hashtag {
+                    tag: "bots".to_string()
+                }
+            ),
+            r#"{"type":"unsubscribe","stream":"hashtag","tag":"bots"}"#
+        );
+        assert_eq!(
+            frame(
+                "subscribe",
+                &Timeline::List {
+                    list: ListId::new("12345")
+                }
+            ),
+            r#"{"type":"subscribe","stream":"list","list":"12345"}"#
+        );
+    }
+
+    #[test]
+    fn test_make_event_from_websocket_message() {
+        let message = r#"{"stream":["user"],"event":"delete","payload":"123"}"#;
+        let event = make_event(&[message.to_string()]).expect("parse");
+        assert!(matches!(event, Event::Delete(id) if id == "123"));
+    }
+}
@@ -0,0 +1,191 @@
+//! Entities shared by the tests, as the API would return them, and a
+//! stand-in server to return them from.
+// some are only used by tests of optional features
+#![allow(dead_code)]
+
+use std::{
+    fmt::{self, Debug},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use reqwest::{Client, Request, Response};
+use serde_json::{json, Value};
+
+use crate::{entities::prelude::*, http_send::HttpSend, Data, Mastodon, Result};
+
+/// An account, as JSON. A remote `acct` has its username taken from it.
+pub(crate) fn account_json(id: &str, acct: &str) -> Value {
+    let username = acct.split('@').next().unwrap_or(acct);
+    json!({
+        "id": id,
+        "username": username,
+        "acct": acct,
+        "display_name": username,
+        "locked": false,
+        "bot": false,
+        "created_at": "2016-03-16T14:34:26.392Z",
+        "note": "",
+        "url": format!("https://example.com/@{username}"),
+        "avatar": "https://example.com/avatar.jpg",
+        "avatar_static": "https://example.com/avatar.jpg",
+        "header": "https://example.com/header.png",
+        "header_static": "https://example.com/header.png",
+        "followers_count": 0,
+        "following_count": 0,
+        "statuses_count": 1,
+        "last_status_at": null,
+        "emojis": [],
+        "fields": []
+    })
+}
+
+/// An account.
+pub(crate) fn account(id: &str, acct: &str) -> Account {
+    serde_json::from_value(account_json(id, acct)).expect("deserialize account")
+}
+
+/// A public status in English by account `1`, `Gargron`, with the content
+/// `<p>status {id}</p>`, as JSON.
+pub(crate) fn status_json(id: &str) -> Value {
+    json!({
+        "id": id,
+        "created_at": "2019-12-08T03:48:33.901Z",
+        "in_reply_to_id": null,
+        "in_reply_to_account_id": null,
+        "sensitive": false,
+        "spoiler_text": "",
+        "visibility": "public",
+        "language": "en",
+        "uri": format!("https://example.com/users/Gargron/statuses/{id}"),
+        "url": format!("https://example.com/@Gargron/{id}"),
+        "replies_count": 0,
+        "reblogs_count": 0,
+        "favourites_count": 0,
+        "content": format!("<p>status {id}</p>"),
+        "reblog": null,
+        "application": null,
+        "account": account_json("1", "Gargron"),
+        "media_attachments": [],
+        "mentions": [],
+        "tags": [],
+        "emojis": [],
+        "card": null,
+        "poll": null
+    })
+}
+
+/// A status, as described for [`status_json`].
+pub(crate) fn status(id: &str) -> Status {
+    from_json(status_json(id))
+}
+
+/// The user's relationship with an account, which isn't any relationship
+/// at all, as JSON.
+pub(crate) fn relationship_json(id: &str) -> Value {
+    json!({
+        "id": id,
+        "following": false,
+        "showing_reblogs": false,
+        "notifying": false,
+        "followed_by": false,
+        "blocking": false,
+        "blocked_by": false,
+        "muting": false,
+        "muting_notifications": false,
+        "requested": false,
+        "domain_blocking": false,
+        "endorsed": false,
+        "note": ""
+    })
+}
+
+/// An entity from JSON, e.g. a fixture with some fields changed.
+pub(crate) fn from_json<T: serde::de::DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).expect("deserialize fixture")
+}
+
+/// How a [`Server`] answers a request.
+type Respond = dyn Fn(&Request) -> http::Response<String> + Send + Sync;
+
+/// A stand-in for a server, which answers each request with what `respond`
+/// makes of it, and records the requests it was sent.
+pub(crate) struct Server {
+    respond: Box<Respond>,
+    requests: Mutex<Vec<Request>>,
+}
+
+impl Server {
+    /// A server answering requests with `respond`.
+    pub(crate) fn new(
+        respond: impl Fn(&Request) -> http::Response<String> + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        Arc::new(Server {
+            respond: Box::new(respond),
+            requests: Mutex::default(),
+        })
+    }
+
+    /// A server answering every request with `body`.
+    pub(crate) fn with_body(body: impl Into<String>) -> Arc<Self> {
+        let body = body.into();
+        Server::new(move |_| http::Response::new(body.clone()))
+    }
+
+    /// The requests made so far.
+    pub(crate) fn requests(&self) -> MutexGuard<'_, Vec<Request>> {
+        self.requests.lock().unwrap()
+    }
+
+    /// The method and path, with the query string, of each request made so
+    /// far, e.g. `GET /api/v1/follow_requests?max_id=1`.
+    pub(crate) fn targets(&self) -> Vec<String> {
+        self.requests().iter().map(target).collect()
+    }
+
+    /// The body of each request made so far.
+    pub(crate) fn bodies(&self) -> Vec<String> {
+        self.requests().iter().map(body).collect()
+    }
+}
+
+impl Debug for Server {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Server")
+            .field("requests", &self.requests)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl HttpSend for Server {
+    async fn send(&self, _client: &Client, request: Request) -> Result<Response> {
+        let response = (self.respond)(&request);
+        self.requests().push(request);
+        Ok(response.into())
+    }
+}
+
+/// The method and path, with the query string, of `request`.
+pub(crate) fn target(request: &Request) -> String {
+    let url = request.url();
+    format!("{} {}", request.method(), &url[url::Position::BeforePath..])
+}
+
+/// The body of `request`, or nothing if it's streamed.
+pub(crate) fn body(request: &Request) -> String {
+    request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .map(|body| String::from_utf8_lossy(body).into_owned())
+        .unwrap_or_default()
+}
+
+/// A client of `https://example.com` which sends its requests to `server`.
+pub(crate) fn client(server: &Arc<Server>) -> Mastodon {
+    let data = Data {
+        base: "https://example.com".into(),
+        ..Default::default()
+    };
+    Mastodon::from(data).with_http_send(server.clone())
+}
@@ -0,0 +1,191 @@
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{Data, Mastodon, Result};
+
+/// Credentials for several accounts, keyed by `acct@domain`, which can be
+/// saved to and loaded from a single file.
+///
+/// ```
+/// use mastodon_async::{helpers::accounts::AccountStore, prelude::*};
+///
+/// let mut store = AccountStore::new();
+/// store.add("alice@example.com", Data::default());
+/// store.add("bob@example.org", Data::default());
+/// assert_eq!(store.list().collect::<Vec<_>>(), ["alice@example.com", "bob@example.org"]);
+///
+/// let client = store.client("alice@example.com").unwrap();
+/// store.remove("bob@example.org");
+/// assert_eq!(store.len(), 1);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct AccountStore {
+    accounts: BTreeMap<String, Data>,
+}
+
+impl AccountStore {
+    /// An empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a store from a file.
+    ///
+    /// Files ending in `.toml` are read as toml when the "toml" feature is
+    /// enabled, anything else as json.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut buffer = String::new();
+        File::open(path)?.read_to_string(&mut buffer)?;
+        #[cfg(feature = "toml")]
+        if is_toml(path) {
+            return Ok(tomlcrate::from_str(&buffer)?);
+        }
+        Ok(serde_json::from_str(&buffer)?)
+    }
+
+    /// Save the store to a file, replacing its contents, in the same format
+    /// [`AccountStore::from_file`] would read it in.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        #[cfg(feature = "toml")]
+        let contents = if is_toml(path) {
+            tomlcrate::to_string_pretty(self)?
+        } else {
+            serde_json::to_string_pretty(self)?
+        };
+        #[cfg(not(feature = "toml"))]
+        let contents = serde_json::to_string_pretty(self)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+        file.write_all(contents.as_bytes())?;
+        Ok(())
+    }
+
+    /// Add an account's credentials, returning those it replaced, if any.
+    pub fn add(&mut self, acct: impl Into<String>, data: Data) -> Option<Data> {
+        self.accounts.insert(acct.into(), data)
+    }
+
+    /// Add the credentials of a client, keyed by the `acct@domain` of the
+    /// account they belong to, which is fetched from the server. Returns
+    /// the key.
+    pub async fn add_client(&mut self, client: &Mastodon) -> Result<String> {
+        let account = client.verify_credentials().await?;
+        let acct = if account.acct.contains('@') {
+            account.acct
+        } else {
+            let base = Url::parse(&client.data.base)?;
+            format!("{}@{}", account.acct, base.host_str().unwrap_or_default())
+        };
+        self.add(acct.clone(), client.current_data().await);
+        Ok(acct)
+    }
+
+    /// Remove an account's credentials, returning them if it was present.
+    pub fn remove(&mut self, acct: &str) -> Option<Data> {
+        self.accounts.remove(acct)
+    }
+
+    /// The `acct@domain` of every account in the store, in order.
+    pub fn list(&self) -> impl Iterator<Item = &str> {
+        self.accounts.keys().map(String::as_str)
+    }
+
+    /// An account's credentials.
+    pub fn get(&self, acct: &str) -> Option<&Data> {
+        self.accounts.get(acct)
+    }
+
+    /// A client authenticated as the given account.
+    pub fn client(&self, acct: &str) -> Option<Mastodon> {
+        self.get(acct).cloned().map(Mastodon::from)
+    }
+
+    /// The number of accounts in the store.
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    /// Whether the store has no accounts.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
+
+#[cfg(feature = "toml")]
+fn is_toml(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn store() -> AccountStore {
+        let mut store = AccountStore::new();
+        store.add(
+            "alice@example.com",
+            Data {
+                base: "https://example.com".into(),
+                token: "alice".into(),
+                ..Default::default()
+            },
+        );
+        store.add(
+            "bob@example.org",
+            Data {
+                base: "https://example.org".into(),
+                token: "bob".into(),
+                ..Default::default()
+            },
+        );
+        store
+    }
+
+    #[test]
+    fn test_add_remove() {
+        let mut store = store();
+        assert_eq!(store.get("alice@example.com").unwrap().token, "alice");
+        let replaced = store.add("alice@example.com", Data::default()).unwrap();
+        assert_eq!(replaced.token, "alice");
+        assert_eq!(store.remove("bob@example.org").unwrap().token, "bob");
+        assert!(store.remove("bob@example.org").is_none());
+        assert_eq!(store.list().collect::<Vec<_>>(), ["alice@example.com"]);
+        assert!(store.client("bob@example.org").is_none());
+    }
+
+    #[test]
+    fn test_json_file() {
+        let dir = tempdir().expect("Couldn't create tempdir");
+        let path = dir.path().join("accounts.json");
+        let store = store();
+        store.to_file(&path).expect("Couldn't write store");
+        assert_eq!(AccountStore::from_file(&path).unwrap(), store);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_file() {
+        let dir = tempdir().expect("Couldn't create tempdir");
+        let path = dir.path().join("accounts.toml");
+        let store = store();
+        store.to_file(&path).expect("Couldn't write store");
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("[\"alice@example.com\"]"));
+        assert_eq!(AccountStore::from_file(&path).unwrap(), store);
+    }
+}
@@ -0,0 +1,141 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::Result;
+
+const FNV_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+/// Serialize a form to JSON with the keys of every object sorted, so that
+/// equal forms always serialize the same way, whatever order their fields
+/// are declared in.
+///
+/// ```
+/// use mastodon_async::helpers::canonical;
+/// use serde_json::json;
+///
+/// let form = json!({"status": "hello", "media_ids": ["2", "1"], "poll": {"multiple": false, "expires_in": 60}});
+/// assert_eq!(
+///     canonical::to_string(&form).unwrap(),
+///     r#"{"media_ids":["2","1"],"poll":{"expires_in":60,"multiple":false},"status":"hello"}"#
+/// );
+/// ```
+pub fn to_string<T: Serialize + ?Sized>(form: &T) -> Result<String> {
+    let value = serde_json::to_value(form)?;
+    let mut canonical = String::new();
+    write(&value, &mut canonical);
+    Ok(canonical)
+}
+
+/// A key identifying a form by its content, e.g. to deduplicate repeated
+/// identical requests, or as an `Idempotency-Key` header.
+///
+/// The key is a 128-bit FNV-1a hash of [`to_string`], as 32 hex digits. It
+/// is the same on every platform and in every version of this crate, but it
+/// isn't a cryptographic hash, so don't rely on it where forms could be
+/// crafted to collide. [`Caching`](crate::response_cache::Caching) keys
+/// the responses it stores with it.
+///
+/// The `Idempotency-Key` which [`Mastodon::with_retries`] gives a request
+/// is random instead, as the server would otherwise treat a deliberate
+/// repeat of a post as a retry of the first.
+///
+/// [`Mastodon::with_retries`]: crate::Mastodon::with_retries
+///
+/// ```
+/// use mastodon_async::{helpers::canonical, prelude::*};
+///
+/// let first = NewStatusBuilder::default().status("hello").build().unwrap();
+/// let second = NewStatusBuilder::default().status("hello").build().unwrap();
+/// let other = NewStatusBuilder::default().status("goodbye").build().unwrap();
+/// assert_eq!(canonical::key(&first).unwrap(), canonical::key(&second).unwrap());
+/// assert_ne!(canonical::key(&first).unwrap(), canonical::key(&other).unwrap());
+/// ```
+pub fn key<T: Serialize + ?Sized>(form: &T) -> Result<String> {
+    let hash = to_string(form)?
+        .bytes()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u128::from(byte)).wrapping_mul(FNV_PRIME)
+        });
+    Ok(format!("{hash:032x}"))
+}
+
+fn write(value: &Value, canonical: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            canonical.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    canonical.push(',');
+                }
+                // keys are escaped just like string values
+                canonical.push_str(&Value::from(key.as_str()).to_string());
+                canonical.push(':');
+                write(value, canonical);
+            }
+            canonical.push('}');
+        }
+        Value::Array(items) => {
+            canonical.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    canonical.push(',');
+                }
+                write(item, canonical);
+            }
+            canonical.push(']');
+        }
+        scalar => canonical.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Forwards {
+        a: u8,
+        b: &'static str,
+    }
+
+    #[derive(Serialize)]
+    struct Backwards {
+        b: &'static str,
+        a: u8,
+    }
+
+    #[test]
+    fn test_field_order() {
+        let forwards = Forwards {
+            a: 1,
+            b: "\"quoted\"",
+        };
+        let backwards = Backwards {
+            b: "\"quoted\"",
+            a: 1,
+        };
+        assert_eq!(
+            to_string(&forwards).expect("serialize"),
+            r#"{"a":1,"b":"\"quoted\""}"#
+        );
+        assert_eq!(
+            to_string(&forwards).expect("serialize"),
+            to_string(&backwards).expect("serialize")
+        );
+        assert_eq!(key(&forwards).expect("key"), key(&backwards).expect("key"));
+    }
+
+    #[test]
+    fn test_key_is_stable() {
+        assert_eq!(key("").expect("key"), "088094af1dab1be95aa07330555adc6d");
+        assert_eq!(
+            key(&Forwards { a: 1, b: "x" }).expect("key"),
+            "df7339ff18e3c72e3ce9ccf9042a3975"
+        );
+    }
+}
@@ -1,5 +1,14 @@
 use std::io::{self, BufRead, Write};
 
+#[cfg(feature = "localhost-redirect")]
+use log::warn;
+#[cfg(feature = "localhost-redirect")]
+use tokio::net::{TcpListener, TcpStream};
+#[cfg(feature = "localhost-redirect")]
+use url::Url;
+
+#[cfg(feature = "localhost-redirect")]
+use crate::Registration;
 use crate::{errors::Result, registration::Registered, Mastodon};
 
 /// Finishes the authentication process for the given `Registered` object,
@@ -27,6 +36,146 @@ pub async fn authenticate(registration: Registered) -> Result<Mastodon> {
     registration.complete(code).await
 }
 
+/// Finishes registering the app and authenticating, by opening the
+/// authorization page in the browser and capturing the authorization code
+/// with a local HTTP server, so the user doesn't have to paste it.
+///
+/// The server listens on `127.0.0.1:port`, which is registered as the app's
+/// redirect URI. Pass `0` to let the OS pick a free port. The link is also
+/// printed, in case the browser can't be opened.
+///
+/// In order to use this function, set the "localhost-redirect" feature in
+/// your Cargo.toml:
+///
+/// ```toml,ignore
+/// [dependencies.mastodon-async]
+/// version = "0.22"
+/// features = ["localhost-redirect"]
+/// ```
+///
+/// ```no_run
+/// use mastodon_async::{helpers::cli, prelude::*};
+///
+/// tokio_test::block_on(async {
+///     let mut registration = Registration::new("https://botsin.space").unwrap();
+///     registration.client_name("mastodon-async-example");
+///     let mastodon = cli::authenticate_in_browser(&mut registration, 0).await.unwrap();
+///     println!("{:?}", mastodon.verify_credentials().await.unwrap());
+/// });
+/// ```
+#[cfg(feature = "localhost-redirect")]
+pub async fn authenticate_in_browser(
+    registration: &mut Registration,
+    port: u16,
+) -> Result<Mastodon> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    let port = listener.local_addr()?.port();
+    let registered = registration
+        .redirect_uris(format!("http://127.0.0.1:{port}/"))
+        .build()
+        .await?;
+    let url = registered.authorize_url()?;
+
+    println!("Authorize the app in your browser: {url}");
+    if let Err(err) = webbrowser::open(&url) {
+        warn!(err:? = err; "couldn't open the browser");
+    }
+
+    let code = wait_for_code(&listener).await?;
+    registered.complete(code).await
+}
+
+/// Accept connections until one of them is the redirect back from the
+/// authorization page, and return its code.
+///
+/// Connections are answered concurrently, as a browser may open some
+/// without sending a request on them, e.g. to preconnect.
+#[cfg(feature = "localhost-redirect")]
+async fn wait_for_code(listener: &TcpListener) -> Result<String> {
+    use futures::stream::{FuturesUnordered, StreamExt};
+
+    let mut connections = FuturesUnordered::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                connections.push(answer(stream));
+            }
+            Some(answered) = connections.next() => match answered {
+                Ok(Some(result)) => return result,
+                Ok(None) => {}
+                Err(err) => warn!(err:? = err; "couldn't answer a request to the redirect URI"),
+            },
+        }
+    }
+}
+
+/// Answer a request to the redirect URI, returning the code, or the error,
+/// if it is the redirect back from the authorization page.
+#[cfg(feature = "localhost-redirect")]
+async fn answer(mut stream: TcpStream) -> Result<Option<Result<String>>> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let mut reader = BufReader::new(&mut stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    // read the rest of the headers, so that closing the connection doesn't
+    // reset it before the browser gets the response
+    let mut header = String::new();
+    while reader.read_line(&mut header).await? > 2 {
+        header.clear();
+    }
+    let (status, body, result) = match parse_redirect(&request_line) {
+        Some(Ok(code)) => (
+            "200 OK",
+            "Authorized! You can close this window now.",
+            Some(Ok(code)),
+        ),
+        Some(Err(err)) => (
+            "400 Bad Request",
+            "Authorization failed. You can close this window now.",
+            Some(Err(err)),
+        ),
+        // e.g. the browser asking for a favicon
+        None => ("404 Not Found", "Not found.", None),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(result)
+}
+
+/// The authorization code, or the error the server redirected with, from the
+/// first line of an HTTP request. `None` if the request isn't a redirect from
+/// the authorization page.
+#[cfg(feature = "localhost-redirect")]
+fn parse_redirect(request_line: &str) -> Option<Result<String>> {
+    let mut parts = request_line.split_whitespace();
+    if parts.next() != Some("GET") {
+        return None;
+    }
+    let url = Url::parse(&format!("http://127.0.0.1{}", parts.next()?)).ok()?;
+    let mut code = None;
+    let mut error = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "code" => code = Some(value.into_owned()),
+            "error_description" => error = Some(value.into_owned()),
+            "error" if error.is_none() => error = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+    match (code, error) {
+        (_, Some(error)) => Some(Err(crate::format_err!("authorization failed: {error}"))),
+        (Some(code), None) => Some(Ok(code)),
+        (None, None) => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,4 +190,53 @@ mod tests {
         };
         assert_send_sync(no_run());
     }
+
+    #[cfg(feature = "localhost-redirect")]
+    #[test]
+    fn test_parse_redirect() {
+        assert_eq!(
+            parse_redirect("GET /?code=abc123 HTTP/1.1\r\n")
+                .expect("redirect")
+                .expect("code"),
+            "abc123"
+        );
+        assert!(parse_redirect(
+            "GET /?error=access_denied&error_description=The+resource+owner+denied HTTP/1.1\r\n"
+        )
+        .expect("redirect")
+        .is_err());
+        assert!(parse_redirect("GET /favicon.ico HTTP/1.1\r\n").is_none());
+        assert!(parse_redirect("POST /?code=abc123 HTTP/1.1\r\n").is_none());
+    }
+
+    #[cfg(feature = "localhost-redirect")]
+    #[tokio::test]
+    async fn test_wait_for_code() {
+        use std::time::Duration;
+
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.expect("bind");
+        let addr = listener.local_addr().expect("address");
+        // a preconnect, which never sends a request
+        let _idle = TcpStream::connect(addr).await.expect("connect");
+        let browser = tokio::spawn(async move {
+            for path in ["/favicon.ico", "/?code=abc123"] {
+                let mut stream = TcpStream::connect(addr).await.expect("connect");
+                stream
+                    .write_all(format!("GET {path} HTTP/1.1\r\nHost: {addr}\r\n\r\n").as_bytes())
+                    .await
+                    .expect("write");
+                let mut response = String::new();
+                stream.read_to_string(&mut response).await.expect("read");
+                assert!(response.starts_with("HTTP/1.1 "));
+            }
+        });
+        let code = tokio::time::timeout(Duration::from_secs(5), wait_for_code(&listener))
+            .await
+            .expect("not stalled by the idle connection")
+            .expect("code");
+        assert_eq!(code, "abc123");
+        browser.await.expect("browser");
+    }
 }
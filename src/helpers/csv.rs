@@ -0,0 +1,537 @@
+//! Importing and exporting the CSV files Mastodon's settings pages use for
+//! moving followed, blocked and muted accounts, and blocked domains, between
+//! servers.
+//!
+//! Each export fetches every page of the list, and each import handles one
+//! row at a time, searching for the account first if the row is one, so a
+//! client set up with [`Mastodon::with_pacer`] spreads a large import out
+//! rather than running into the rate limit. Accounts the new server hasn't
+//! seen yet are looked up with WebFinger. Rows which fail to import don't
+//! stop the rest; they are returned in the [`ImportReport`].
+//!
+//! ```no_run
+//! use mastodon_async::{helpers::csv, pacing::Pacer, prelude::*};
+//!
+//! tokio_test::block_on(async {
+//!     let old = Mastodon::from(Data::default());
+//!     let new = Mastodon::from(Data::default()).with_pacer(Pacer::default());
+//!     let blocks = csv::export_blocks(&old).await.unwrap();
+//!     std::fs::write("blocked_accounts.csv", csv::write_accounts(&blocks)).unwrap();
+//!
+//!     let blocks = csv::parse_accounts(&std::fs::read_to_string("blocked_accounts.csv").unwrap());
+//!     let report = csv::import_blocks(&new, &blocks).await;
+//!     for (acct, err) in report.failed {
+//!         eprintln!("couldn't block {acct}: {err}");
+//!     }
+//! });
+//! ```
+
+use isolang::Language;
+use url::Url;
+
+use crate::{
+    entities::prelude::*,
+    errors::{Error, Result},
+    format_err, Mastodon,
+};
+
+/// The most accounts the server describes relationships with at once.
+const RELATIONSHIPS_BATCH: usize = 40;
+
+const FOLLOWING_HEADER: [&str; 4] = [
+    "Account address",
+    "Show boosts",
+    "Notify on new posts",
+    "Languages",
+];
+const MUTES_HEADER: [&str; 2] = ["Account address", "Hide notifications"];
+
+/// A row of the followed accounts CSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowedAccount {
+    /// The account's address, such as `user@example.com`.
+    pub acct: String,
+    /// Whether the account's boosts are shown in the home timeline.
+    pub show_reblogs: bool,
+    /// Whether to be notified when the account posts.
+    pub notify: bool,
+    /// The languages to show the account's statuses in; all of them if
+    /// empty.
+    pub languages: Vec<Language>,
+}
+
+/// A row of the muted accounts CSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutedAccount {
+    /// The account's address, such as `user@example.com`.
+    pub acct: String,
+    /// Whether notifications from the account are muted as well.
+    pub hide_notifications: bool,
+}
+
+/// What came of an import.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// How many rows were imported.
+    pub imported: usize,
+    /// The rows which couldn't be imported, by account address or domain,
+    /// and why.
+    pub failed: Vec<(String, Error)>,
+}
+
+/// Parse a followed accounts CSV, with or without its header.
+pub fn parse_following(csv: &str) -> Result<Vec<FollowedAccount>> {
+    rows(csv, FOLLOWING_HEADER[0])
+        .map(|row| {
+            Ok(FollowedAccount {
+                acct: row[0].clone(),
+                show_reblogs: parse_bool(row.get(1), true)?,
+                notify: parse_bool(row.get(2), false)?,
+                languages: row
+                    .get(3)
+                    .map(|it| parse_languages(it))
+                    .transpose()?
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Write a followed accounts CSV, with its header.
+pub fn write_following(accounts: &[FollowedAccount]) -> String {
+    let rows = accounts.iter().map(|account| {
+        let languages: Vec<_> = account.languages.iter().map(language_code).collect();
+        vec![
+            account.acct.clone(),
+            account.show_reblogs.to_string(),
+            account.notify.to_string(),
+            languages.join(", "),
+        ]
+    });
+    write_rows(Some(&FOLLOWING_HEADER[..]), rows)
+}
+
+/// Parse a muted accounts CSV, with or without its header.
+pub fn parse_mutes(csv: &str) -> Result<Vec<MutedAccount>> {
+    rows(csv, MUTES_HEADER[0])
+        .map(|row| {
+            Ok(MutedAccount {
+                acct: row[0].clone(),
+                hide_notifications: parse_bool(row.get(1), true)?,
+            })
+        })
+        .collect()
+}
+
+/// Write a muted accounts CSV, with its header.
+pub fn write_mutes(accounts: &[MutedAccount]) -> String {
+    let rows = accounts
+        .iter()
+        .map(|account| vec![account.acct.clone(), account.hide_notifications.to_string()]);
+    write_rows(Some(&MUTES_HEADER[..]), rows)
+}
+
+/// Parse a blocked accounts CSV: one account address per row.
+pub fn parse_accounts(csv: &str) -> Vec<String> {
+    rows(csv, FOLLOWING_HEADER[0])
+        .map(|row| row[0].clone())
+        .collect()
+}
+
+/// Write a blocked accounts CSV.
+pub fn write_accounts(accts: &[String]) -> String {
+    write_rows(None, accts.iter().map(|acct| vec![acct.clone()]))
+}
+
+/// Parse a blocked domains CSV: one domain per row.
+pub fn parse_domains(csv: &str) -> Vec<String> {
+    rows(csv, "#domain").map(|row| row[0].clone()).collect()
+}
+
+/// Write a blocked domains CSV.
+pub fn write_domains(domains: &[String]) -> String {
+    write_rows(None, domains.iter().map(|domain| vec![domain.clone()]))
+}
+
+/// The accounts the user follows, and how.
+pub async fn export_following(client: &Mastodon) -> Result<Vec<FollowedAccount>> {
+    let me = client.verify_credentials().await?;
+    let accounts = client
+        .following_with_limit(&me.id, 80)
+        .await?
+        .fetch_all_bounded(usize::MAX, usize::MAX)
+        .await?;
+    let mut following = Vec::with_capacity(accounts.len());
+    for batch in accounts.chunks(RELATIONSHIPS_BATCH) {
+        let ids: Vec<_> = batch.iter().map(|account| &account.id).collect();
+        let relationships = client.relationships(&ids).await?.initial_items;
+        for account in batch {
+            let relationship = relationships
+                .iter()
+                .find(|it| it.id.as_ref() == account.id.as_ref());
+            following.push(FollowedAccount {
+                acct: full_acct(client, account),
+                show_reblogs: relationship.map_or(true, |it| it.showing_reblogs),
+                notify: relationship.is_some_and(|it| it.notifying),
+                languages: relationship
+                    .map(|it| it.languages.clone())
+                    .unwrap_or_default(),
+            });
+        }
+    }
+    Ok(following)
+}
+
+/// The addresses of the accounts the user blocks.
+pub async fn export_blocks(client: &Mastodon) -> Result<Vec<String>> {
+    let accounts = client
+        .blocks()
+        .await?
+        .fetch_all_bounded(usize::MAX, usize::MAX)
+        .await?;
+    Ok(accounts
+        .iter()
+        .map(|account| full_acct(client, account))
+        .collect())
+}
+
+/// The accounts the user mutes, and whether their notifications are muted
+/// too.
+pub async fn export_mutes(client: &Mastodon) -> Result<Vec<MutedAccount>> {
+    let accounts = client
+        .mutes()
+        .await?
+        .fetch_all_bounded(usize::MAX, usize::MAX)
+        .await?;
+    let mut mutes = Vec::with_capacity(accounts.len());
+    for batch in accounts.chunks(RELATIONSHIPS_BATCH) {
+        let ids: Vec<_> = batch.iter().map(|account| &account.id).collect();
+        let relationships = client.relationships(&ids).await?.initial_items;
+        for account in batch {
+            let relationship = relationships
+                .iter()
+                .find(|it| it.id.as_ref() == account.id.as_ref());
+            mutes.push(MutedAccount {
+                acct: full_acct(client, account),
+                hide_notifications: relationship.map_or(true, |it| it.muting_notifications),
+            });
+        }
+    }
+    Ok(mutes)
+}
+
+/// The domains the user blocks.
+pub async fn export_domain_blocks(client: &Mastodon) -> Result<Vec<String>> {
+    client
+        .domain_blocks()
+        .await?
+        .fetch_all_bounded(usize::MAX, usize::MAX)
+        .await
+}
+
+/// Follow each account, as it was followed before.
+pub async fn import_following(client: &Mastodon, accounts: &[FollowedAccount]) -> ImportReport {
+    let mut report = ImportReport::default();
+    for account in accounts {
+        let result = async {
+            let id = resolve_account(client, &account.acct).await?;
+            let mut options = forms::account::FollowOptions::builder();
+            options.reblogs(account.show_reblogs).notify(account.notify);
+            if !account.languages.is_empty() {
+                options.languages(account.languages.clone());
+            }
+            client.follow_with_options(&id, options.build()?).await
+        };
+        report.record(&account.acct, result.await);
+    }
+    report
+}
+
+/// Block each account.
+pub async fn import_blocks(client: &Mastodon, accts: &[String]) -> ImportReport {
+    let mut report = ImportReport::default();
+    for acct in accts {
+        let result = async {
+            let id = resolve_account(client, acct).await?;
+            client.block(&id).await
+        };
+        report.record(acct, result.await);
+    }
+    report
+}
+
+/// Mute each account.
+pub async fn import_mutes(client: &Mastodon, accounts: &[MutedAccount]) -> ImportReport {
+    let mut report = ImportReport::default();
+    for account in accounts {
+        let result = async {
+            let id = resolve_account(client, &account.acct).await?;
+            let options = forms::account::MuteOptions::builder()
+                .notifications(account.hide_notifications)
+                .build()?;
+            client.mute(&id, options).await
+        };
+        report.record(&account.acct, result.await);
+    }
+    report
+}
+
+/// Block each domain.
+pub async fn import_domain_blocks(client: &Mastodon, domains: &[String]) -> ImportReport {
+    let mut report = ImportReport::default();
+    for domain in domains {
+        let result = client.block_domain(domain.clone()).await;
+        report.record(domain, result);
+    }
+    report
+}
+
+impl ImportReport {
+    fn record<T>(&mut self, row: &str, result: Result<T>) {
+        match result {
+            Ok(_) => self.imported += 1,
+            Err(err) => self.failed.push((row.to_string(), err)),
+        }
+    }
+}
+
+/// The ID of the account with the given address. It's searched for, so that
+/// the server looks up accounts it hasn't seen yet with WebFinger, as it
+/// won't have seen most of those moved from another server.
+async fn resolve_account(client: &Mastodon, acct: &str) -> Result<AccountId> {
+    let acct = acct.trim_start_matches('@');
+    let search = forms::account::Search::builder(acct)
+        .resolve(true)
+        .limit(1u64)
+        .build()?;
+    if let Ok(found) = client.search_accounts_with_options(search).await {
+        let found = found.initial_items.into_iter().find(|account| {
+            account.acct.eq_ignore_ascii_case(acct)
+                || full_acct(client, account).eq_ignore_ascii_case(acct)
+        });
+        if let Some(account) = found {
+            return Ok(account.id);
+        }
+    }
+    Ok(client.lookup_account(acct).await?.id)
+}
+
+/// The account's address, with the domain of the client's server added for
+/// local accounts, so that the address still works from another server.
+fn full_acct(client: &Mastodon, account: &Account) -> String {
+    if account.acct.contains('@') {
+        return account.acct.clone();
+    }
+    let domain = Url::parse(&client.data.base)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string));
+    match domain {
+        Some(domain) => format!("{}@{domain}", account.acct),
+        None => account.acct.clone(),
+    }
+}
+
+/// Mastodon writes the two-letter code where there is one.
+fn language_code(language: &Language) -> &'static str {
+    language.to_639_1().unwrap_or_else(|| language.to_639_3())
+}
+
+fn parse_languages(field: &str) -> Result<Vec<Language>> {
+    field
+        .split(',')
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+        .map(|code| {
+            Language::from_639_1(code)
+                .or_else(|| Language::from_639_3(code))
+                .ok_or_else(|| format_err!("unrecognized language code {code:?}"))
+        })
+        .collect()
+}
+
+fn parse_bool(field: Option<&String>, default: bool) -> Result<bool> {
+    match field.map(|it| it.trim()) {
+        None | Some("") => Ok(default),
+        Some(field) if field.eq_ignore_ascii_case("true") => Ok(true),
+        Some(field) if field.eq_ignore_ascii_case("false") => Ok(false),
+        Some(field) => Err(format_err!("expected true or false, got {field:?}")),
+    }
+}
+
+/// The non-blank rows of a CSV file, skipping a header row which starts with
+/// `header`.
+fn rows<'a>(csv: &str, header: &'a str) -> impl Iterator<Item = Vec<String>> + 'a {
+    parse_rows(csv)
+        .into_iter()
+        .enumerate()
+        .filter(move |(n, row)| *n != 0 || !row[0].eq_ignore_ascii_case(header))
+        .map(|(_, row)| row)
+}
+
+fn parse_rows(csv: &str) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut field).trim().to_string()),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field).trim().to_string());
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field.trim().to_string());
+        rows.push(row);
+    }
+    rows.retain(|row| !row[0].is_empty());
+    rows
+}
+
+fn write_rows(header: Option<&[&str]>, rows: impl Iterator<Item = Vec<String>>) -> String {
+    let mut csv = String::new();
+    if let Some(header) = header {
+        csv.push_str(&header.join(","));
+        csv.push('\n');
+    }
+    for row in rows {
+        let fields: Vec<_> = row.iter().map(|field| quote(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::fixtures::{self, Server};
+
+    #[test]
+    fn test_following_round_trip() {
+        let csv = "Account address,Show boosts,Notify on new posts,Languages\n\
+                   alice@example.com,true,false,\n\
+                   bob@example.org,false,true,\"en, de\"\n";
+        let following = parse_following(csv).unwrap();
+        assert_eq!(
+            following,
+            [
+                FollowedAccount {
+                    acct: "alice@example.com".to_string(),
+                    show_reblogs: true,
+                    notify: false,
+                    languages: vec![],
+                },
+                FollowedAccount {
+                    acct: "bob@example.org".to_string(),
+                    show_reblogs: false,
+                    notify: true,
+                    languages: vec![Language::Eng, Language::Deu],
+                },
+            ]
+        );
+        assert_eq!(write_following(&following), csv);
+    }
+
+    #[test]
+    fn test_parse_without_header() {
+        let mutes = parse_mutes("alice@example.com\r\n\r\nbob@example.org,false\r\n").unwrap();
+        assert_eq!(
+            mutes,
+            [
+                MutedAccount {
+                    acct: "alice@example.com".to_string(),
+                    hide_notifications: true,
+                },
+                MutedAccount {
+                    acct: "bob@example.org".to_string(),
+                    hide_notifications: false,
+                },
+            ]
+        );
+        assert_eq!(
+            parse_domains("#domain\nexample.com\nexample.org"),
+            ["example.com", "example.org"]
+        );
+        assert!(parse_mutes("alice@example.com,maybe").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_domain_blocks() {
+        // blocks every domain but one
+        let server = Server::new(|request| {
+            if fixtures::body(request).contains("bad.example") {
+                let body = r#"{"error":"Validation failed"}"#.to_string();
+                http::Response::builder().status(422).body(body).unwrap()
+            } else {
+                http::Response::new("{}".to_string())
+            }
+        });
+        let client = fixtures::client(&server);
+        let domains = parse_domains("spam.example\nbad.example\nworse.example\n");
+        let report = import_domain_blocks(&client, &domains).await;
+        assert_eq!(report.imported, 2);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "bad.example");
+        assert_eq!(
+            server.bodies(),
+            [
+                r#"{"domain":"spam.example"}"#,
+                r#"{"domain":"bad.example"}"#,
+                r#"{"domain":"worse.example"}"#,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_blocks() {
+        // the server knows about alice only once it has searched for her
+        let server = Server::new(|request| {
+            let body = match fixtures::target(request).as_str() {
+                "GET /api/v1/accounts/search?q=alice%40remote.example&limit=1&resolve=true" => {
+                    json!([fixtures::account_json("1", "alice@remote.example")])
+                }
+                "GET /api/v1/accounts/lookup?acct=bob" => fixtures::account_json("2", "bob"),
+                target if target.starts_with("GET /api/v1/accounts/search") => json!([]),
+                target if target.starts_with("GET ") => {
+                    let body = r#"{"error":"Record not found"}"#.to_string();
+                    return http::Response::builder().status(404).body(body).unwrap();
+                }
+                _ => fixtures::relationship_json("1"),
+            };
+            http::Response::new(body.to_string())
+        });
+        let client = fixtures::client(&server);
+        let accts = ["@alice@remote.example".to_string(), "bob".to_string()];
+        let report = import_blocks(&client, &accts).await;
+        assert!(report.failed.is_empty(), "{:?}", report.failed);
+        assert_eq!(report.imported, 2);
+        let targets = server.targets();
+        assert_eq!(targets[1], "POST /api/v1/accounts/1/block");
+        assert_eq!(targets[4], "POST /api/v1/accounts/2/block");
+    }
+}
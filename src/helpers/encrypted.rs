@@ -0,0 +1,118 @@
+use std::{fs::OpenOptions, io::Write, path::Path};
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+use crate::{format_err, Error, Result};
+
+/// Marks the start of a file written by this module, along with the version
+/// of its format.
+const MAGIC: &[u8] = b"mastodon-async encrypted v1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Derive the key for the given passphrase and salt with Argon2id.
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| format_err!("couldn't derive a key from the passphrase: {err}"))?;
+    Ok(key)
+}
+
+/// Encrypt `plaintext` with ChaCha20-Poly1305, using a key derived from
+/// `passphrase`.
+///
+/// The result holds everything [`decrypt`] needs other than the
+/// passphrase: the random salt the key was derived with and the random
+/// nonce, followed by the ciphertext.
+///
+/// ```
+/// use mastodon_async::helpers::encrypted;
+///
+/// let sealed = encrypted::encrypt(b"secret token", "correct horse").unwrap();
+/// assert_eq!(encrypted::decrypt(&sealed, "correct horse").unwrap(), b"secret token");
+/// assert!(encrypted::decrypt(&sealed, "battery staple").is_err());
+/// ```
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt)?;
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(&key)
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| format_err!("couldn't encrypt the data"))?;
+    let mut sealed = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    sealed.extend_from_slice(MAGIC);
+    sealed.extend_from_slice(&salt);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Decrypt data encrypted by [`encrypt`] with the same passphrase.
+///
+/// Returns [`Error::Decryption`] if the passphrase is wrong, or the data
+/// wasn't written by [`encrypt`] or has been altered since.
+pub fn decrypt(sealed: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let rest = sealed.strip_prefix(MAGIC).ok_or(Error::Decryption)?;
+    if rest.len() < SALT_LEN + NONCE_LEN {
+        return Err(Error::Decryption);
+    }
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let key = derive_key(passphrase, salt)?;
+    ChaCha20Poly1305::new(&key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| Error::Decryption)
+}
+
+/// Encrypt `plaintext` and write it to the file at `path`, replacing its
+/// contents. On unix, a new file is only readable by its owner.
+pub(crate) fn to_file(plaintext: &[u8], path: &Path, passphrase: &str) -> Result<()> {
+    let sealed = encrypt(plaintext, passphrase)?;
+    let mut options = OpenOptions::new();
+    options.create(true).write(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(&sealed)?;
+    Ok(())
+}
+
+/// Read the file at `path` and decrypt its contents.
+pub(crate) fn from_file(path: &Path, passphrase: &str) -> Result<Vec<u8>> {
+    decrypt(&std::fs::read(path)?, passphrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let sealed = encrypt(b"token", "passphrase").expect("encrypt");
+        assert!(sealed.starts_with(MAGIC));
+        assert!(!sealed.windows(5).any(|it| it == b"token"));
+        assert_eq!(decrypt(&sealed, "passphrase").expect("decrypt"), b"token");
+        // a fresh salt and nonce every time
+        assert_ne!(encrypt(b"token", "passphrase").expect("encrypt"), sealed);
+    }
+
+    #[test]
+    fn test_decrypt_rejects() {
+        let sealed = encrypt(b"token", "passphrase").expect("encrypt");
+        assert!(decrypt(&sealed, "wrong").unwrap_err().is_decryption());
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&tampered, "passphrase")
+            .unwrap_err()
+            .is_decryption());
+        assert!(decrypt(b"token", "passphrase").unwrap_err().is_decryption());
+        assert!(decrypt(&sealed[..MAGIC.len() + 4], "passphrase")
+            .unwrap_err()
+            .is_decryption());
+    }
+}
@@ -59,6 +59,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                ..Default::default()
             }
         );
     }
@@ -74,6 +75,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                ..Default::default()
             }
         );
     }
@@ -6,6 +6,8 @@ use std::{
 
 use serde_json;
 
+#[cfg(feature = "encrypted-file")]
+use super::encrypted;
 use crate::{Data, Result};
 
 /// Attempts to deserialize a Data struct from a string
@@ -73,6 +75,40 @@ pub fn to_file_with_options<P: AsRef<Path>>(
     Ok(())
 }
 
+/// Attempts to deserialize a Data struct from a file written by
+/// [`to_encrypted_file`], with the passphrase returned by `passphrase`
+///
+/// ```no_run
+/// use mastodon_async::{helpers::json, Mastodon};
+///
+/// let data = json::from_encrypted_file("mastodon-data.json.enc", || {
+///     Ok(std::env::var("MASTODON_PASSPHRASE").expect("passphrase not set"))
+/// })
+/// .unwrap();
+/// let client = Mastodon::from(data);
+/// ```
+#[cfg(feature = "encrypted-file")]
+pub fn from_encrypted_file<P: AsRef<Path>>(
+    path: P,
+    passphrase: impl FnOnce() -> Result<String>,
+) -> Result<Data> {
+    from_slice(&encrypted::from_file(path.as_ref(), &passphrase()?)?)
+}
+
+/// Attempts to serialize a Data struct to a file, encrypted with the
+/// passphrase returned by `passphrase`. See [`encrypted`] for how.
+///
+/// The file's contents are replaced. A new file is only readable by its
+/// owner on unix.
+#[cfg(feature = "encrypted-file")]
+pub fn to_encrypted_file<P: AsRef<Path>>(
+    data: &Data,
+    path: P,
+    passphrase: impl FnOnce() -> Result<String>,
+) -> Result<()> {
+    encrypted::to_file(&to_vec(data)?, path.as_ref(), &passphrase()?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,6 +138,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                ..Default::default()
             }
         );
     }
@@ -117,6 +154,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                ..Default::default()
             }
         );
     }
@@ -133,6 +171,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                ..Default::default()
             }
         );
     }
@@ -149,6 +188,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                ..Default::default()
             }
         );
     }
@@ -160,6 +200,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            ..Default::default()
         };
         let s = to_string(&data).expect("Couldn't serialize Data");
         let desered = from_str(&s).expect("Couldn't deserialize Data");
@@ -173,6 +214,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            ..Default::default()
         };
         let v = to_vec(&data).expect("Couldn't write to vec");
         let desered = from_slice(&v).expect("Couldn't deserialize data");
@@ -186,6 +228,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            ..Default::default()
         };
         let mut buffer = Vec::new();
         to_writer(&data, &mut buffer).expect("Couldn't write to writer");
@@ -201,6 +244,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            ..Default::default()
         };
         let tempdir = tempdir().expect("Couldn't create tempdir");
         let filename = tempdir.path().join("mastodon-data.json");
@@ -216,6 +260,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            ..Default::default()
         };
         let file = NamedTempFile::new().expect("Couldn't create tempfile");
         let mut options = OpenOptions::new();
@@ -224,4 +269,25 @@ mod tests {
         let desered = from_file(file.path()).expect("Couldn't deserialize Data");
         assert_eq!(data, desered);
     }
+
+    #[cfg(feature = "encrypted-file")]
+    #[test]
+    fn test_encrypted_file() {
+        let data = Data {
+            base: "https://example.com".into(),
+            token: "fedc5678".into(),
+            ..Default::default()
+        };
+        let tempdir = tempdir().expect("Couldn't create tempdir");
+        let filename = tempdir.path().join("mastodon-data.json.enc");
+        to_encrypted_file(&data, &filename, || Ok("hunter2".to_string()))
+            .expect("Couldn't write to file");
+        let contents = std::fs::read(&filename).expect("Couldn't read file");
+        assert!(!contents.windows(8).any(|it| it == b"fedc5678"));
+        let desered = from_encrypted_file(&filename, || Ok("hunter2".to_string()))
+            .expect("Couldn't deserialize Data");
+        assert_eq!(data, desered);
+        let err = from_encrypted_file(&filename, || Ok("hunter3".to_string())).unwrap_err();
+        assert!(err.is_decryption());
+    }
 }
@@ -0,0 +1,70 @@
+//! The credentials are kept in the Secret Service on Linux, the Keychain
+//! on macOS and the Credential Manager on Windows, instead of in a file
+//! anyone who can read it can take the access token from. Each is stored
+//! as JSON in an entry identified by a service name, such as the name of
+//! the app, and a user name, such as the account's address.
+//!
+//! ```no_run
+//! use mastodon_async::{helpers::keyring, Mastodon};
+//!
+//! let data = keyring::from_keyring("my-app", "alice@example.social").unwrap();
+//! let client = Mastodon::from(data);
+//! ```
+
+pub use keyring::Entry;
+
+use crate::{Data, Result};
+
+/// Attempts to deserialize a Data struct from the entry for `user` of
+/// `service`
+pub fn from_keyring(service: &str, user: &str) -> Result<Data> {
+    from_entry(&Entry::new(service, user)?)
+}
+
+/// Attempts to deserialize a Data struct from a keyring entry
+pub fn from_entry(entry: &Entry) -> Result<Data> {
+    Ok(serde_json::from_str(&entry.get_password()?)?)
+}
+
+/// Attempts to serialize a Data struct to the entry for `user` of
+/// `service`, replacing what was stored there
+pub fn to_keyring(data: &Data, service: &str, user: &str) -> Result<()> {
+    to_entry(data, &Entry::new(service, user)?)
+}
+
+/// Attempts to serialize a Data struct to a keyring entry, replacing what
+/// was stored there
+pub fn to_entry(data: &Data, entry: &Entry) -> Result<()> {
+    entry.set_password(&serde_json::to_string(data)?)?;
+    Ok(())
+}
+
+/// Removes the entry for `user` of `service`, e.g. once its token has been
+/// revoked
+pub fn delete(service: &str, user: &str) -> Result<()> {
+    Entry::new(service, user)?.delete_credential()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_round_trip() {
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+        let data = Data {
+            base: "https://example.com".into(),
+            client_id: "adbc01234".into(),
+            client_secret: "0987dcba".into(),
+            redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
+            token: "fedc5678".into(),
+            ..Default::default()
+        };
+        let entry = Entry::new("mastodon-async", "alice@example.com").expect("entry");
+        assert!(from_entry(&entry).is_err());
+        to_entry(&data, &entry).expect("Couldn't store Data");
+        let stored = from_entry(&entry).expect("Couldn't retrieve Data");
+        assert_eq!(stored, data);
+    }
+}
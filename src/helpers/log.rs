@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 /// Serializable form of reqwest's Status type.
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
@@ -48,3 +49,60 @@ impl<'h> From<&'h reqwest::Response> for Headers<'h> {
         Headers(value.headers())
     }
 }
+
+/// Query parameters whose values are credentials.
+const SECRETS: &[&str] = &[
+    "access_token",
+    "client_secret",
+    "code",
+    "password",
+    "refresh_token",
+    "token",
+];
+
+/// Paths whose responses hold credentials: the access token from the
+/// token exchange and from creating an account, and the client secret from
+/// registering an app.
+const SECRET_RESPONSES: &[&str] = &["/oauth/token", "/api/v1/accounts", "/api/v1/apps"];
+
+/// Whether the body of a response from `url` holds credentials, so it
+/// mustn't be logged.
+pub(crate) fn has_secret_body(url: &Url) -> bool {
+    SECRET_RESPONSES.contains(&url.path().trim_end_matches('/'))
+}
+
+/// The URL, with the values of any credentials in its query string
+/// replaced, for logging.
+///
+/// ```
+/// use mastodon_async::helpers::log::redact_url;
+///
+/// assert_eq!(
+///     redact_url("https://example.com/oauth/token?client_id=app&client_secret=s3cr3t"),
+///     "https://example.com/oauth/token?client_id=app&client_secret=REDACTED",
+/// );
+/// ```
+pub fn redact_url(url: &str) -> String {
+    let Ok(mut parsed) = Url::parse(url) else {
+        return url.to_string();
+    };
+    if !parsed
+        .query_pairs()
+        .any(|(key, _)| SECRETS.contains(&&*key))
+    {
+        return url.to_string();
+    }
+    let pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if SECRETS.contains(&&*key) {
+                "REDACTED".to_string()
+            } else {
+                value.into_owned()
+            };
+            (key.into_owned(), value)
+        })
+        .collect();
+    parsed.query_pairs_mut().clear().extend_pairs(pairs);
+    parsed.to_string()
+}
@@ -34,10 +34,49 @@ pub mod json;
 /// ```
 pub mod env;
 
+#[cfg(feature = "encrypted-file")]
+/// Encrypting credentials at rest with a passphrase
+///
+/// In order to use this module, set the "encrypted-file" feature in your
+/// Cargo.toml:
+///
+/// ```toml,ignore
+/// [dependencies.mastodon-async]
+/// version = "0.22"
+/// features = ["encrypted-file"]
+/// ```
+pub mod encrypted;
+
+#[cfg(feature = "keyring")]
+/// Helpers for storing a `Data` struct in the OS secret store
+///
+/// In order to use this module, set the "keyring" feature in your
+/// Cargo.toml:
+///
+/// ```toml,ignore
+/// [dependencies.mastodon-async]
+/// version = "0.22"
+/// features = ["keyring"]
+/// ```
+///
+/// It isn't part of the "all" feature, as on Linux it needs the libdbus
+/// development files, e.g. the `libdbus-1-dev` package, to build.
+pub mod keyring;
+
+/// Credentials for several accounts in one file
+pub mod accounts;
+/// Deterministic serialization of request forms, for cache keys and
+/// deduplicating identical requests
+pub mod canonical;
 /// Helpers for working with the command line
 pub mod cli;
+/// Importing and exporting blocks, mutes and follows as Mastodon's CSV files
+pub mod csv;
 /// Helpers for serializing data for logging
 pub mod log;
 /// Adapter for reading JSON data from a response with better logging and a
 /// fail-safe timeout.
 pub mod read_response;
+/// Counting the characters of statuses as the server does, to check them
+/// against its limits before posting
+pub use mastodon_async_entities::text;
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use crate::{errors::Result, Error};
+use crate::{errors::Result, pacing::RetryAfter, Error};
 use futures::pin_mut;
 use futures_util::StreamExt;
 use log::{debug, trace, warn};
@@ -19,8 +19,10 @@ where
     T: for<'de> Deserialize<'de> + Serialize,
 {
     let mut bytes = vec![];
-    let url = response.url().clone();
+    let url = crate::helpers::log::redact_url(response.url().as_str());
+    let secret = crate::helpers::log::has_secret_body(response.url());
     let status = response.status();
+    let retry_after = RetryAfter::from_headers(response.headers());
     trace!(status:serde = crate::helpers::log::Status::from(&response), headers:serde = crate::helpers::log::Headers::from(&response); "attempting to stream response");
     let stream = response.bytes_stream();
     pin_mut!(stream);
@@ -34,6 +36,13 @@ where
             let data = data?;
             // as of here, we did not hit an error while reading the body
             bytes.extend_from_slice(&data);
+            if secret {
+                debug!(
+                    url = url.as_str(), bytes_received_so_far = bytes.len();
+                    "data chunk received"
+                );
+                continue;
+            }
             debug!(
                 data = String::from_utf8_lossy(&data), url = url.as_str(),
                 bytes_received_so_far = bytes.len();
@@ -50,24 +59,96 @@ where
     }
     // done growing the vec, let's just do this once.
     let bytes = bytes.as_slice();
-    trace!(
-        url = url.as_str(),
-        data = String::from_utf8_lossy(bytes);
-        "parsing response"
-    );
+    if secret {
+        trace!(url = url.as_str(); "parsing response");
+    } else {
+        trace!(
+            url = url.as_str(),
+            data = String::from_utf8_lossy(bytes);
+            "parsing response"
+        );
+    }
     if status.is_success() {
         // the the response should deserialize to T
-        let result = serde_json::from_slice(bytes)?;
-        debug!(
+        let result = parse_success(bytes)?;
+        if secret {
+            debug!(url = url.as_str(); "result parsed successfully");
+        } else {
+            debug!(
                 url = url.as_str(),
-            result:serde = result;
-            "result parsed successfully"
-        );
+                result:serde = result;
+                "result parsed successfully"
+            );
+        }
         Ok(result)
     } else {
         // we've received an error message, let's deserialize that instead.
         let response = serde_json::from_slice(bytes)?;
         debug!(status:? = status, response:serde = response; "error received from API");
-        Err(Error::Api { status, response })
+        Err(Error::Api {
+            status,
+            response,
+            retry_after,
+        })
+    }
+}
+
+/// Deserialize the body of a successful response. Endpoints with nothing to
+/// return answer with `()`, which servers send as `{}` or as an empty body.
+fn parse_success<T>(bytes: &[u8]) -> serde_json::Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    match serde_json::from_slice(bytes) {
+        Ok(result) => Ok(result),
+        Err(err) if is_empty_body(bytes) => {
+            serde_json::from_value(serde_json::Value::Null).map_err(|_| err)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Whether a response body is blank or an empty JSON object.
+fn is_empty_body(bytes: &[u8]) -> bool {
+    bytes.iter().all(u8::is_ascii_whitespace)
+        || serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(bytes)
+            .is_ok_and(|object| object.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unit() {
+        for body in ["", " \n", "{}", "{ }", "null"] {
+            parse_success::<()>(body.as_bytes()).expect(body);
+        }
+        assert!(parse_success::<()>(br#"{"error": "oops"}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_other() {
+        assert_eq!(parse_success::<Vec<u8>>(b"[1]").expect("parse"), [1]);
+        assert!(parse_success::<Vec<u8>>(b"").is_err());
+        assert!(parse_success::<Vec<u8>>(b"{}").is_err());
+        assert_eq!(parse_success::<Option<u8>>(b"").expect("parse"), None);
+    }
+
+    #[test]
+    fn test_secret_body() {
+        use crate::helpers::log::has_secret_body;
+        use url::Url;
+
+        let url = |url| Url::parse(url).expect("url");
+        assert!(has_secret_body(&url("https://example.com/oauth/token")));
+        assert!(has_secret_body(&url("https://example.com/api/v1/apps")));
+        assert!(has_secret_body(&url("https://example.com/api/v1/accounts")));
+        assert!(!has_secret_body(&url(
+            "https://example.com/api/v1/accounts/1/statuses"
+        )));
+        assert!(!has_secret_body(&url(
+            "https://example.com/api/v1/timelines/home"
+        )));
     }
 }
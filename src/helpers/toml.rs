@@ -6,7 +6,9 @@ use std::{
 
 use tomlcrate;
 
-use crate::{Data, Result};
+#[cfg(feature = "encrypted-file")]
+use super::encrypted;
+use crate::{entities::status::CwPolicy, Data, Result};
 
 /// Attempts to deserialize a Data struct from a string
 pub fn from_str(s: &str) -> Result<Data> {
@@ -73,6 +75,68 @@ pub fn to_file_with_options<P: AsRef<Path>>(
     Ok(())
 }
 
+/// Attempts to deserialize a Data struct from a file written by
+/// [`to_encrypted_file`], with the passphrase returned by `passphrase`
+///
+/// ```no_run
+/// use mastodon_async::{helpers::toml, Mastodon};
+///
+/// let data = toml::from_encrypted_file("mastodon-data.toml.enc", || {
+///     Ok(std::env::var("MASTODON_PASSPHRASE").expect("passphrase not set"))
+/// })
+/// .unwrap();
+/// let client = Mastodon::from(data);
+/// ```
+#[cfg(feature = "encrypted-file")]
+pub fn from_encrypted_file<P: AsRef<Path>>(
+    path: P,
+    passphrase: impl FnOnce() -> Result<String>,
+) -> Result<Data> {
+    from_slice(&encrypted::from_file(path.as_ref(), &passphrase()?)?)
+}
+
+/// Attempts to serialize a Data struct to a file, encrypted with the
+/// passphrase returned by `passphrase`. See [`encrypted`] for how.
+///
+/// The file's contents are replaced. A new file is only readable by its
+/// owner on unix.
+#[cfg(feature = "encrypted-file")]
+pub fn to_encrypted_file<P: AsRef<Path>>(
+    data: &Data,
+    path: P,
+    passphrase: impl FnOnce() -> Result<String>,
+) -> Result<()> {
+    encrypted::to_file(&to_vec(data)?, path.as_ref(), &passphrase()?)
+}
+
+/// Attempts to deserialize a content warning policy from a string
+///
+/// ```
+/// use mastodon_async::{helpers::toml, prelude::*};
+///
+/// let policy = toml::cw_policy_from_str(r#"
+///     [[rules]]
+///     keyword = "election"
+///     spoiler_text = "politics"
+/// "#).unwrap();
+/// let status = NewStatusBuilder::default()
+///     .status("election day!")
+///     .apply_policy(&policy)
+///     .build()
+///     .unwrap();
+/// assert_eq!(status.spoiler_text.as_deref(), Some("politics"));
+/// ```
+pub fn cw_policy_from_str(s: &str) -> Result<CwPolicy> {
+    Ok(tomlcrate::from_str(s)?)
+}
+
+/// Attempts to deserialize a content warning policy from a file
+pub fn cw_policy_from_file<P: AsRef<Path>>(path: P) -> Result<CwPolicy> {
+    let mut buffer = String::new();
+    File::open(path.as_ref())?.read_to_string(&mut buffer)?;
+    cw_policy_from_str(&buffer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +164,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                ..Default::default()
             }
         );
     }
@@ -115,6 +180,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                ..Default::default()
             }
         );
     }
@@ -131,6 +197,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                ..Default::default()
             }
         );
     }
@@ -147,6 +214,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                ..Default::default()
             }
         );
     }
@@ -158,6 +226,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            ..Default::default()
         };
         let s = to_string(&data).expect("Couldn't serialize Data");
         let desered = from_str(&s).expect("Couldn't deserialize Data");
@@ -171,6 +240,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            ..Default::default()
         };
         let v = to_vec(&data).expect("Couldn't write to vec");
         let desered = from_slice(&v).expect("Couldn't deserialize data");
@@ -184,6 +254,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            ..Default::default()
         };
         let mut buffer = Vec::new();
         to_writer(&data, &mut buffer).expect("Couldn't write to writer");
@@ -199,6 +270,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            ..Default::default()
         };
         let tempdir = tempdir().expect("Couldn't create tempdir");
         let filename = tempdir.path().join("mastodon-data.toml");
@@ -207,6 +279,42 @@ mod tests {
         assert_eq!(data, desered);
     }
     #[test]
+    fn test_cw_policy_from_file() {
+        use crate::entities::status::content_warning::Matcher;
+
+        let mut policyfile = NamedTempFile::new().expect("Couldn't create tempfile");
+        write!(
+            &mut policyfile,
+            "{}",
+            indoc!(
+                r#"
+                    [[rules]]
+                    keyword = "election"
+                    spoiler_text = "politics"
+
+                    [[rules]]
+                    regex = '\bspiders?\b'
+                    spoiler_text = "spiders"
+                    sensitive = true
+                "#
+            )
+        )
+        .expect("Couldn't write policy to file");
+        let policy = cw_policy_from_file(policyfile.path()).expect("Couldn't deserialize policy");
+        assert_eq!(policy.rules.len(), 2);
+        assert_eq!(
+            policy.rules[0].matcher,
+            Matcher::Keyword("election".to_string())
+        );
+        assert!(policy.rules[1].sensitive);
+        assert_eq!(
+            policy
+                .spoiler_text_for("spiders voting in the election")
+                .as_deref(),
+            Some("politics, spiders")
+        );
+    }
+    #[test]
     fn test_to_file_with_options() {
         let data = Data {
             base: "https://example.com".into(),
@@ -214,6 +322,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            ..Default::default()
         };
         let file = NamedTempFile::new().expect("Couldn't create tempfile");
         let mut options = OpenOptions::new();
@@ -222,4 +331,25 @@ mod tests {
         let desered = from_file(file.path()).expect("Couldn't deserialize Data");
         assert_eq!(data, desered);
     }
+
+    #[cfg(feature = "encrypted-file")]
+    #[test]
+    fn test_encrypted_file() {
+        let data = Data {
+            base: "https://example.com".into(),
+            token: "fedc5678".into(),
+            ..Default::default()
+        };
+        let tempdir = tempdir().expect("Couldn't create tempdir");
+        let filename = tempdir.path().join("mastodon-data.toml.enc");
+        to_encrypted_file(&data, &filename, || Ok("hunter2".to_string()))
+            .expect("Couldn't write to file");
+        let contents = std::fs::read(&filename).expect("Couldn't read file");
+        assert!(!contents.windows(8).any(|it| it == b"fedc5678"));
+        let desered = from_encrypted_file(&filename, || Ok("hunter2".to_string()))
+            .expect("Couldn't deserialize Data");
+        assert_eq!(data, desered);
+        let err = from_encrypted_file(&filename, || Ok("hunter3".to_string())).unwrap_err();
+        assert!(err.is_decryption());
+    }
 }
@@ -0,0 +1,90 @@
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use reqwest::{Client, Request, Response};
+
+use crate::errors::Result;
+
+/// Sends the HTTP requests a client makes, so that they can be proxied,
+/// signed, cached or answered without a server. Set it with
+/// [`Mastodon::with_http_send`](crate::Mastodon::with_http_send).
+///
+/// Each request arrives fully built, authenticated and paced, along with the
+/// `reqwest::Client` it was built with. Rate limiting, retries and token
+/// refreshes happen around it, so an implementation only has to deliver the
+/// request and return the response. Wrap [`HttpSender`] to change requests
+/// on their way out, or build a [`Response`] from an `http::Response` to
+/// answer them without a server.
+///
+/// ```no_run
+/// use async_trait::async_trait;
+/// use mastodon_async::{
+///     http_send::{HttpSend, HttpSender},
+///     prelude::*,
+///     Result,
+/// };
+/// use reqwest::{Client, Request, Response};
+///
+/// #[derive(Debug)]
+/// struct Tagged(HttpSender);
+///
+/// #[async_trait]
+/// impl HttpSend for Tagged {
+///     async fn send(&self, client: &Client, mut request: Request) -> Result<Response> {
+///         request
+///             .headers_mut()
+///             .insert("X-Request-Source", "my-bot".parse().unwrap());
+///         self.0.send(client, request).await
+///     }
+/// }
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default()).with_http_send(Tagged(HttpSender));
+///     client.get_home_timeline().await.unwrap();
+/// });
+/// ```
+#[async_trait]
+pub trait HttpSend: Debug + Send + Sync {
+    /// Send `request` and return the server's response.
+    async fn send(&self, client: &Client, request: Request) -> Result<Response>;
+}
+
+/// Sends requests with the `reqwest::Client` they were built with. This is
+/// what clients use unless told otherwise.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HttpSender;
+
+#[async_trait]
+impl HttpSend for HttpSender {
+    async fn send(&self, client: &Client, request: Request) -> Result<Response> {
+        Ok(client.execute(request).await?)
+    }
+}
+
+/// Lets a sender be shared with, and inspected by, other code, e.g. a test
+/// checking the requests it was given.
+#[async_trait]
+impl<T: HttpSend + ?Sized> HttpSend for Arc<T> {
+    async fn send(&self, client: &Client, request: Request) -> Result<Response> {
+        (**self).send(client, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entities::prelude::*,
+        fixtures::{self, Server},
+    };
+
+    #[tokio::test]
+    async fn test_with_http_send() {
+        let server = Server::with_body("{}");
+        let client = fixtures::client(&server);
+        client
+            .delete_status(&StatusId::new("1"))
+            .await
+            .expect("delete status");
+        assert_eq!(server.targets(), ["DELETE /api/v1/statuses/1"]);
+    }
+}
@@ -8,6 +8,7 @@
 //!
 //! tokio_test::block_on(async {
 //!     let registration = Registration::new("https://botsin.space")
+//!         .unwrap()
 //!         .client_name("mastodon-async_test")
 //!         .build()
 //!         .await
@@ -45,7 +46,15 @@
 //!             Event::Update(ref status) => { /* .. */ },
 //!             Event::Notification(ref notification) => { /* .. */ },
 //!             Event::Delete(ref id) => { /* .. */ },
+//!             Event::StatusUpdate(ref status) => { /* .. */ },
 //!             Event::FiltersChanged => { /* .. */ },
+//!             Event::Conversation(ref conversation) => { /* .. */ },
+//!             Event::Announcement(ref announcement) => { /* .. */ },
+//!             Event::AnnouncementReaction(ref reaction) => { /* .. */ },
+//!             Event::AnnouncementDelete(ref id) => { /* .. */ },
+//!             Event::ConnectionReset => { /* .. */ },
+//!             Event::Heartbeat => { /* .. */ },
+//!             Event::Unknown { .. } => { /* .. */ },
 //!         }
 //!         Ok(())
 //!     }).await.unwrap();
@@ -87,17 +96,52 @@ extern crate indoc;
 
 use page::Page;
 
+pub use builder::MastodonBuilder;
 pub use data::Data;
-pub use errors::{ApiError, Error, Result};
+pub use errors::{ApiError, Error, Result, ValidationError};
 pub use isolang::Language;
-pub use mastodon::{Mastodon, MastodonUnauthenticated};
-// pub use mastodon_client::{MastodonClient, MastodonUnauthenticated};
+pub use mastodon::{AccountOverview, Mastodon, MastodonUnauthenticated};
 pub use mastodon_async_entities::{
     status::NewStatus, status::NewStatusBuilder, visibility::Visibility,
 };
+pub use mastodon_client::MastodonClient;
 pub use registration::Registration;
 pub use requests::{AddPushRequest, StatusesRequest, UpdatePushRequest};
 
+/// Moderating the server through the admin API
+pub mod admin;
+/// Reasoning about who can see a status
+pub mod audience;
+#[cfg(feature = "blocking")]
+/// A client which blocks instead of being async
+///
+/// In order to use this module, set the "blocking" feature in your
+/// Cargo.toml:
+///
+/// ```toml,ignore
+/// [dependencies.mastodon-async]
+/// version = "0.22"
+/// features = ["blocking"]
+/// ```
+pub mod blocking;
+#[cfg(feature = "bot")]
+/// The skeleton of a bot which responds to commands
+///
+/// In order to use this module, set the "bot" feature in your Cargo.toml:
+///
+/// ```toml,ignore
+/// [dependencies.mastodon-async]
+/// version = "0.22"
+/// features = ["bot"]
+/// ```
+pub mod bot;
+/// Configuring the HTTP client: proxies, certificates, timeouts and the user
+/// agent
+pub mod builder;
+/// Keeping local stores consistent with changes made through the client
+pub mod cache_sync;
+/// Detecting what the connected server supports
+pub mod capabilities;
 /// Contains the struct that holds the client auth data
 pub mod data;
 /// Entities returned from the API
@@ -108,15 +152,85 @@ pub mod errors;
 pub mod event_stream;
 /// Collection of helpers for serializing/deserializing `Data` objects
 pub mod helpers;
+/// Customising how requests are sent
+pub mod http_send;
+/// Observing the requests the client makes, e.g. to export metrics
+pub mod metrics;
+/// Posting statuses which were queued while offline
+pub mod outbox;
+/// Pacing requests to cooperate with the server's rate limits.
+pub mod pacing;
 /// Handling multiple pages of entities.
 pub mod page;
+#[cfg(feature = "pleroma")]
+/// The extensions Pleroma and Akkoma make to the Mastodon API, such as emoji
+/// reactions and chats
+///
+/// In order to use this module, set the "pleroma" feature in your
+/// Cargo.toml:
+///
+/// ```toml,ignore
+/// [dependencies.mastodon-async]
+/// version = "0.22"
+/// features = ["pleroma"]
+/// ```
+pub mod pleroma;
+#[cfg(feature = "raw-json")]
+/// Reading responses as raw JSON, for servers which send entities this crate
+/// can't deserialize
+///
+/// In order to use this module, set the "raw-json" feature in your
+/// Cargo.toml:
+///
+/// ```toml,ignore
+/// [dependencies.mastodon-async]
+/// version = "0.22"
+/// features = ["raw-json"]
+/// ```
+pub mod raw;
 /// Registering your app.
 pub mod registration;
 /// Requests
 pub mod requests;
+/// Revalidating cached responses instead of downloading them again
+pub mod response_cache;
+/// Introspecting which API routes are implemented.
+pub mod routes;
+#[cfg(feature = "store-sqlite")]
+/// Persisting fetched entities in a SQLite database
+///
+/// In order to use this module, set the "store-sqlite" feature in your
+/// Cargo.toml:
+///
+/// ```toml,ignore
+/// [dependencies.mastodon-async]
+/// version = "0.22"
+/// features = ["store-sqlite"]
+/// ```
+pub mod store;
+/// Fanning events out to multiple consumers
+pub mod stream_manager;
+#[cfg(feature = "testing")]
+/// A stand-in client for testing code without a server
+///
+/// In order to use this module, set the "testing" feature in your
+/// Cargo.toml, usually only for tests:
+///
+/// ```toml,ignore
+/// [dev-dependencies.mastodon-async]
+/// version = "0.22"
+/// features = ["testing"]
+/// ```
+pub mod testing;
+/// Merging statuses from several sources into one timeline
+pub mod timeline;
+/// Uploading media with progress reporting
+pub mod upload;
 
 #[macro_use]
 mod macros;
+#[cfg(test)]
+mod fixtures;
 /// How much time to wait before checking an endpoint again.
 pub mod polling_time;
 /// Automatically import the things you need
@@ -130,6 +244,8 @@ pub mod prelude {
 }
 /// The mastodon client
 pub mod mastodon;
+/// The most common requests as a trait, for mocking
+mod mastodon_client;
 
 /// Legacy aliases. TODO remove for 2.0
 pub mod status_builder {
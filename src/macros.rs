@@ -3,7 +3,7 @@ macro_rules! methods {
         $(
             #[allow(dead_code)]
             #[doc=concat!("Make a ", stringify!($method), " API request, and deserialize the result into T")]
-            async fn $method<T: for<'de> serde::Deserialize<'de> + serde::Serialize>(&self, url: impl AsRef<str>) -> Result<T>
+            pub(crate) async fn $method<T: for<'de> serde::Deserialize<'de> + serde::Serialize>(&self, url: impl AsRef<str>) -> Result<T>
             {
                 let call_id = uuid::Uuid::new_v4();
                 self.$method_with_call_id(url, call_id).await
@@ -14,14 +14,14 @@ macro_rules! methods {
                     "Make a ", stringify!($method), " API request, and deserialize the result into T.\n\n",
                     "Logging will use the provided UUID, rather than generating one before making the request.",
             )]
-            async fn $method_with_call_id<T: for<'de> serde::Deserialize<'de> + serde::Serialize>(&self, url: impl AsRef<str>, call_id: Uuid) -> Result<T>
+            pub(crate) async fn $method_with_call_id<T: for<'de> serde::Deserialize<'de> + serde::Serialize>(&self, url: impl AsRef<str>, call_id: Uuid) -> Result<T>
             {
 
                 use log::debug;
 
                 let url = url.as_ref();
                 debug!(url = url, method = stringify!($method), call_id:? = call_id; "making API request");
-                let response = self.authenticated(self.client.$method(url)).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.$method(url)).header("Accept", "application/json")).await?;
                 read_response(response).await
             }
          )+
@@ -29,8 +29,12 @@ macro_rules! methods {
 }
 
 macro_rules! paged_routes {
+    // public, unless a visibility is given in brackets first
+    (($($spec:tt)*) $($rest:tt)*) => {
+        paged_routes!{[pub] ($($spec)*) $($rest)*}
+    };
 
-    (($method:ident) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+    ([$vis:vis] ($method:ident) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
         doc_comment! {
             concat!(
             "Equivalent to `", stringify!($method), " /api/v1/",
@@ -44,29 +48,57 @@ macro_rules! paged_routes {
             "client.", stringify!($name), "();\n",
             "```"
             ),
-            pub async fn $name(&self) -> Result<Page<$ret>> {
+            $vis async fn $name(&self) -> Result<Page<$ret>> {
                 use log::debug;
                 let url = self.route(concat!("/api/v1/", $url));
                 let call_id = uuid::Uuid::new_v4();
                 debug!(url = url, method = stringify!($method), call_id:? = call_id; "making API request");
-                let response = self.authenticated(self.client.$method(&url)).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.$method(&url)).header("Accept", "application/json")).await?;
 
                 Page::new(self.clone(), response, call_id).await
             }
 
         }
 
-        paged_routes!{$($rest)*}
+        paged_routes!{[$vis] $($rest)*}
+    };
+
+    ([$vis:vis] ($method:ident<-$typ:ty) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+        doc_comment! {
+            concat!(
+                "Equivalent to `", stringify!($method), " /api/v1/",
+                $url,
+                "`\n# Errors\nIf `access_token` is not set."
+            ),
+            $vis async fn $name(&self, form: $typ) -> Result<Page<$ret>> {
+                use log::debug;
+
+                let call_id = uuid::Uuid::new_v4();
+                let form_data = serde_urlencoded::to_string(&form)?;
+                let url = self.route(format!(concat!("/api/v1/", $url, "?{}"), form_data));
+
+                debug!(
+                    url = url, method = stringify!($method),
+                    call_id:? = call_id, form_data:serde = &form;
+                    "making API request"
+                );
+                let response = self.send(self.authenticated(self.client.$method(&url)).header("Accept", "application/json")).await?;
+
+                Page::new(self.clone(), response, call_id).await
+            }
+        }
+
+        paged_routes!{[$vis] $($rest)*}
     };
 
-    ((get ($($(#[$m:meta])* $param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+    ([$vis:vis] (get ($($(#[$m:meta])* $param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
         doc_comment! {
             concat!(
                 "Equivalent to `get /api/v1/",
                 $url,
                 "`\n# Errors\nIf `access_token` is not set."
             ),
-            pub async fn $name<'a>(&self, $($param: $typ,)*) -> Result<Page<$ret>> {
+            $vis async fn $name<'a>(&self, $($param: $typ,)*) -> Result<Page<$ret>> {
                 use serde_urlencoded;
                 use log::debug;
 
@@ -99,16 +131,16 @@ macro_rules! paged_routes {
 
                 debug!(url = url, method = "get", call_id:? = call_id; "making API request");
 
-                let response = self.authenticated(self.client.get(&url)).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.get(&url)).header("Accept", "application/json")).await?;
 
                 Page::new(self.clone(), response, call_id).await
             }
         }
 
-        paged_routes!{$($rest)*}
+        paged_routes!{[$vis] $($rest)*}
     };
 
-    () => {}
+    ([$vis:vis]) => {}
 }
 
 macro_rules! route_v2 {
@@ -172,7 +204,7 @@ macro_rules! route_v2 {
 
                 let form_data = Form::new()
                     $(
-                        .part(stringify!($param), Self::get_form_part($param)?)
+                        .part(stringify!($param), Self::get_form_part($param).await?)
                      )*;
 
                 let form_data = if let Some(description) = description {
@@ -187,11 +219,10 @@ macro_rules! route_v2 {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.post(url))
+                let request = self.authenticated(self.client.post(url))
                     .multipart(form_data)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
@@ -216,7 +247,7 @@ macro_rules! route_v2 {
 
                 let form_data = Form::new()
                     $(
-                        .part(stringify!($param), Self::get_form_part($param)?)
+                        .part(stringify!($param), Self::get_form_part($param).await?)
                      )*;
 
                 let url = &self.route(concat!("/api/v2/", $url));
@@ -227,11 +258,10 @@ macro_rules! route_v2 {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.post(url))
+                let request = self.authenticated(self.client.post(url))
                     .multipart(form_data)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
@@ -285,10 +315,9 @@ macro_rules! route_v2 {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.$method(url))
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                let request = self.authenticated(self.client.$method(url))
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
@@ -300,14 +329,18 @@ macro_rules! route_v2 {
 }
 
 macro_rules! route {
+    // public, unless a visibility is given in brackets first
+    (($($spec:tt)*) $($rest:tt)*) => {
+        route!{[pub] ($($spec)*) $($rest)*}
+    };
 
-    ((post multipart ($($param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+    ([$vis:vis] (post multipart ($($param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
         doc_comment! {
             concat!(
                 "Equivalent to `post /api/v1/",
                 $url,
                 "`\n# Errors\nIf `access_token` is not set."),
-            pub async fn $name(&self, $($param: $typ,)*) -> Result<$ret> {
+            $vis async fn $name(&self, $($param: $typ,)*) -> Result<$ret> {
                 use reqwest::multipart::Form;
                 use log::debug;
                 use uuid::Uuid;
@@ -317,7 +350,7 @@ macro_rules! route {
 
                 let form_data = Form::new()
                     $(
-                        .part(stringify!($param), Self::get_form_part($param)?)
+                        .part(stringify!($param), Self::get_form_part($param).await?)
                      )*;
 
                 let url = &self.route(concat!("/api/v1/", $url));
@@ -328,27 +361,26 @@ macro_rules! route {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.post(url))
+                let request = self.authenticated(self.client.post(url))
                     .multipart(form_data)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
         }
 
-        route!{$($rest)*}
+        route!{[$vis] $($rest)*}
     };
 
-    ((post multipart with description ($($param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+    ([$vis:vis] (post multipart with description ($($param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
         doc_comment! {
             concat!(
                 "Equivalent to `post /api/v1/",
                 $url,
                 "`, with a description/alt-text.",
                 "\n# Errors\nIf `access_token` is not set."),
-            pub async fn $name(&self $(, $param: $typ)*, description: Option<String>) -> Result<$ret> {
+            $vis async fn $name(&self $(, $param: $typ)*, description: Option<String>) -> Result<$ret> {
                 use reqwest::multipart::Form;
                 use log::debug;
                 use uuid::Uuid;
@@ -358,7 +390,7 @@ macro_rules! route {
 
                 let form_data = Form::new()
                     $(
-                        .part(stringify!($param), Self::get_form_part($param)?)
+                        .part(stringify!($param), Self::get_form_part($param).await?)
                      )*;
 
                 let form_data = if let Some(description) = description {
@@ -373,25 +405,24 @@ macro_rules! route {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.post(url))
+                let request = self.authenticated(self.client.post(url))
                     .multipart(form_data)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
         }
-        route! { $($rest)* }
+        route!{[$vis] $($rest)*}
     };
-    ((get ($($param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+    ([$vis:vis] (get ($($param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
         doc_comment! {
             concat!(
                 "Equivalent to `get /api/v1/",
                 $url,
                 "`\n# Errors\nIf `access_token` is not set."
             ),
-            pub async fn $name<'a>(&self, $($param: $typ,)*) -> Result<$ret> {
+            $vis async fn $name<'a>(&self, $($param: $typ,)*) -> Result<$ret> {
                 use serde_urlencoded;
                 use log::debug;
                 use uuid::Uuid;
@@ -426,17 +457,17 @@ macro_rules! route {
             }
         }
 
-        route!{$($rest)*}
+        route!{[$vis] $($rest)*}
     };
 
-    (($method:ident ($($param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+    ([$vis:vis] ($method:ident ($($param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
         doc_comment! {
             concat!(
                 "Equivalent to `", stringify!($method), " /api/v1/",
                 $url,
                 "`\n# Errors\nIf `access_token` is not set.",
             ),
-            pub async fn $name(&self, $($param: $typ,)*) -> Result<$ret> {
+            $vis async fn $name(&self, $($param: $typ,)*) -> Result<$ret> {
                 use log::debug;
                 use uuid::Uuid;
 
@@ -455,20 +486,52 @@ macro_rules! route {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.$method(url))
+                let request = self.authenticated(self.client.$method(url))
                     .json(&form_data)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
         }
 
-        route!{$($rest)*}
+        route!{[$vis] $($rest)*}
     };
 
-    (($method:ident) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+    ([$vis:vis] ($method:ident<-$typ:ty) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+        doc_comment! {
+            concat!(
+                "Equivalent to `", stringify!($method), " /api/v1/",
+                $url,
+                "`\n# Errors\nIf `access_token` is not set.",
+            ),
+            $vis async fn $name(&self, form: $typ) -> Result<$ret> {
+                use log::debug;
+                use uuid::Uuid;
+
+                let call_id = Uuid::new_v4();
+
+                let url = &self.route(concat!("/api/v1/", $url));
+                debug!(
+                    url = url.as_str(), method = stringify!($method),
+                    call_id:? = call_id,
+                    form_data:serde = &form;
+                    "making API request"
+                );
+
+                let request = self.authenticated(self.client.$method(url))
+                    .json(&form)
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
+
+                read_response(response).await
+            }
+        }
+
+        route!{[$vis] $($rest)*}
+    };
+
+    ([$vis:vis] ($method:ident) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
         doc_comment! {
             concat!(
                 "Equivalent to `", stringify!($method), " /api/v1/",
@@ -482,42 +545,109 @@ macro_rules! route {
                 "client.", stringify!($name), "();\n",
                 "```"
             ),
-            pub async fn $name(&self) -> Result<$ret> {
+            $vis async fn $name(&self) -> Result<$ret> {
                 self.$method(self.route(concat!("/api/v1/", $url))).await
             }
         }
 
-        route!{$($rest)*}
+        route!{[$vis] $($rest)*}
     };
 
-    () => {}
+    ([$vis:vis]) => {}
 }
 
 macro_rules! route_id {
+    // public, unless a visibility is given in brackets first
+    (($($spec:tt)*) $($rest:tt)*) => {
+        route_id!{[pub] ($($spec)*) $($rest)*}
+    };
+    ([$vis:vis] ($method:ident) $name:ident[$id_type:ty]: $url:expr => $ret:ty, $($rest:tt)*) => {
+        doc_comment! {
+            concat!(
+                "Equivalent to `", stringify!($method), " /api/v1/",
+                $url,
+                "`\n# Errors\nIf `access_token` is not set.",
+                "\n",
+                "```no_run",
+                "use mastodon_async::prelude::*;\n",
+                "let data = Data::default();\n",
+                "let client = Mastodon::from(data);\n",
+                "client.", stringify!($name), "(\"42\");\n",
+                "#   Ok(())\n",
+                "# }\n",
+                "```"
+            ),
+            $vis async fn $name(&self, id: &$id_type) -> Result<$ret> {
+                let segment = crate::mastodon::path_segment(id);
+                self.$method(self.route(&format!(concat!("/api/v1/", $url), segment))).await
+            }
+        }
 
-    ($(($method:ident) $name:ident[$id_type:ty]: $url:expr => $ret:ty,)*) => {
-        $(
-            doc_comment! {
-                concat!(
-                    "Equivalent to `", stringify!($method), " /api/v1/",
-                    $url,
-                    "`\n# Errors\nIf `access_token` is not set.",
-                    "\n",
-                    "```no_run",
-                    "use mastodon_async::prelude::*;\n",
-                    "let data = Data::default();\n",
-                    "let client = Mastodon::from(data);\n",
-                    "client.", stringify!($name), "(\"42\");\n",
-                    "#   Ok(())\n",
-                    "# }\n",
-                    "```"
-                ),
-                pub async fn $name(&self, id: &$id_type) -> Result<$ret> {
-                    self.$method(self.route(&format!(concat!("/api/v1/", $url), id))).await
+        route_id!{[$vis] $($rest)*}
+    };
+    ([$vis:vis] ($method:ident then $hook:ident $($by_id:ident)?) $name:ident[$id_type:ty]: $url:expr => $ret:ty, $($rest:tt)*) => {
+        doc_comment! {
+            concat!(
+                "Equivalent to `", stringify!($method), " /api/v1/",
+                $url,
+                "`\n\nCalls [`CacheSync::", stringify!($hook), "`](crate::cache_sync::CacheSync::",
+                stringify!($hook), ") on success, if set.",
+                "\n# Errors\nIf `access_token` is not set.",
+            ),
+            #[allow(clippy::let_unit_value)]
+            $vis async fn $name(&self, id: &$id_type) -> Result<$ret> {
+                let segment = crate::mastodon::path_segment(id);
+                let url = self.route(&format!(concat!("/api/v1/", $url), segment));
+                let result = self.$method(url).await?;
+                if let Some(cache_sync) = &self.cache_sync {
+                    route_id!(@hook cache_sync, $hook, result, id $(, $by_id)?);
                 }
+                Ok(result)
             }
-         )*
-    }
+        }
+
+        route_id!{[$vis] $($rest)*}
+    };
+    (@hook $cache_sync:ident, $hook:ident, $result:ident, $id:ident) => {
+        $cache_sync.$hook(&$result)
+    };
+    (@hook $cache_sync:ident, $hook:ident, $result:ident, $id:ident, by_id) => {
+        $cache_sync.$hook($id)
+    };
+    ([$vis:vis] ($method:ident<-$typ:ty) $name:ident[$id_type:ty]: $url:expr => $ret:ty, $($rest:tt)*) => {
+        doc_comment! {
+            concat!(
+                "Equivalent to `", stringify!($method), " /api/v1/",
+                $url,
+                "`\n# Errors\nIf `access_token` is not set.",
+            ),
+            $vis async fn $name(&self, id: &$id_type, form: $typ) -> Result<$ret> {
+                use log::debug;
+                use uuid::Uuid;
+
+                let call_id = Uuid::new_v4();
+
+                let segment = crate::mastodon::path_segment(id);
+                let url = &self.route(&format!(concat!("/api/v1/", $url), segment));
+                debug!(
+                    url = url.as_str(), method = stringify!($method),
+                    call_id:? = call_id,
+                    form_data:serde = &form;
+                    "making API request"
+                );
+
+                let request = self.authenticated(self.client.$method(url))
+                    .json(&form)
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
+
+                read_response(response).await
+            }
+        }
+
+        route_id!{[$vis] $($rest)*}
+    };
+    ([$vis:vis]) => {};
 }
 
 macro_rules! route_v2_id {
@@ -538,7 +668,8 @@ macro_rules! route_v2_id {
                 "```"
             ),
             pub async fn $name(&self, id: &$id_type) -> Result<$ret> {
-                self.$method(self.route(&format!(concat!("/api/v2/", $url), id))).await
+                let segment = crate::mastodon::path_segment(id);
+                self.$method(self.route(&format!(concat!("/api/v2/", $url), segment))).await
             }
         }
 
@@ -559,7 +690,8 @@ macro_rules! route_v2_id {
 
                 let form_data = serde_urlencoded::to_string(&form)?;
 
-                let url = &self.route(format!("/api/v2/{}?{form_data}", format!($url, id)));
+                let segment = crate::mastodon::path_segment(&id);
+                let url = &self.route(format!("/api/v2/{}?{form_data}", format!($url, segment)));
                 debug!(
                     url = url.as_str(), method = stringify!($method),
                     call_id:? = call_id,
@@ -567,10 +699,9 @@ macro_rules! route_v2_id {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.$method(url))
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                let request = self.authenticated(self.client.$method(url))
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
@@ -583,7 +714,7 @@ macro_rules! route_v2_id {
 
 macro_rules! paged_routes_with_id {
 
-    (($method:ident) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+    (($method:ident) $name:ident[$id_type:ty]: $url:expr => $ret:ty, $($rest:tt)*) => {
         doc_comment! {
             concat!(
                 "Equivalent to `", stringify!($method), " /api/v1/",
@@ -594,18 +725,62 @@ macro_rules! paged_routes_with_id {
                 "use mastodon_async::prelude::*;\n",
                 "let data = Data::default();",
                 "let client = Mastodon::from(data);\n",
-                "client.", stringify!($name), "(\"some-id\");\n",
+                "client.", stringify!($name), "(&", stringify!($id_type), "::new(\"some-id\"));\n",
                 "```"
             ),
-            pub async fn $name(&self, id: impl AsRef<str>) -> Result<Page<$ret>> {
+            pub async fn $name(&self, id: &$id_type) -> Result<Page<$ret>> {
                 use log::debug;
                 use uuid::Uuid;
 
                 let call_id = Uuid::new_v4();
-                let url = self.route(&format!(concat!("/api/v1/", $url), id.as_ref()));
+                let segment = crate::mastodon::path_segment(id);
+                let url = self.route(&format!(concat!("/api/v1/", $url), segment));
 
                 debug!(url = url, method = stringify!($method), call_id:? = call_id; "making API request");
-                let response = self.authenticated(self.client.$method(&url)).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.$method(&url)).header("Accept", "application/json")).await?;
+                Page::new(self.clone(), response, call_id).await
+            }
+        }
+
+        paged_routes_with_id!{$($rest)*}
+    };
+
+    ((get ($($(#[$m:meta])* $param:ident: $typ:ty,)*)) $name:ident[$id_type:ty]: $url:expr => $ret:ty, $($rest:tt)*) => {
+        doc_comment! {
+            concat!(
+                "Equivalent to `get /api/v1/",
+                $url,
+                "`\n# Errors\nIf `access_token` is not set."
+            ),
+            pub async fn $name(&self, id: &$id_type, $($param: $typ,)*) -> Result<Page<$ret>> {
+                use log::debug;
+                use uuid::Uuid;
+
+                let call_id = Uuid::new_v4();
+
+                #[derive(Serialize)]
+                struct Data {
+                    $(
+                        $(
+                        #[$m]
+                        )*
+                        $param: $typ,
+                    )*
+                }
+
+                #[allow(clippy::redundant_field_names)]
+                let qs_data = Data {
+                    $(
+                            $param: $param,
+                    )*
+                };
+
+                let qs = serde_urlencoded::to_string(&qs_data)?;
+                let segment = crate::mastodon::path_segment(id);
+                let url = self.route(&format!(concat!("/api/v1/", $url, "?{}"), segment, &qs));
+
+                debug!(url = url, method = "get", call_id:? = call_id; "making API request");
+                let response = self.send(self.authenticated(self.client.get(&url)).header("Accept", "application/json")).await?;
                 Page::new(self.clone(), response, call_id).await
             }
         }
@@ -638,16 +813,24 @@ tokio_test::block_on(async {
             Event::Update(ref status) => { /* .. */ },
             Event::Notification(ref notification) => { /* .. */ },
             Event::Delete(ref id) => { /* .. */ },
+            Event::StatusUpdate(ref status) => { /* .. */ },
             Event::FiltersChanged => { /* .. */ },
+            Event::Conversation(ref conversation) => { /* .. */ },
+            Event::Announcement(ref announcement) => { /* .. */ },
+            Event::AnnouncementReaction(ref reaction) => { /* .. */ },
+            Event::AnnouncementDelete(ref id) => { /* .. */ },
+            Event::ConnectionReset => { /* .. */ },
+            Event::Heartbeat => { /* .. */ },
+            Event::Unknown { .. } => { /* .. */ },
         }
         Ok(())
     }).await.unwrap();
 });"
             ),
-            pub async fn $fn_name(&self) -> Result<impl TryStream<Ok=(Event, Mastodon), Error=Error> + '_> {
+            pub async fn $fn_name(&self) -> Result<impl TryStream<Ok=(Event, Mastodon), Error=Error> + Send + 'static> {
                 use $crate::event_stream::event_stream;
                 let url = self.route(&format!("/api/v1/streaming/{}", $stream));
-                let response = self.authenticated(self.client.get(&url)).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.get(&url)).header("Accept", "application/json")).await?;
                 debug!(
                     status:serde = crate::helpers::log::Status::from(&response), url = &url,
                     headers:serde = crate::helpers::log::Headers::from(&response);
@@ -657,8 +840,9 @@ tokio_test::block_on(async {
                 if status.is_success() {
                      Ok(event_stream(response, url, self))
                 } else {
+                    let retry_after = $crate::pacing::RetryAfter::from_headers(response.headers());
                     let response = response.json().await?;
-                    Err(Error::Api{ status, response })
+                    Err(Error::Api{ status, response, retry_after })
                 }
             }
         }
@@ -687,18 +871,26 @@ tokio_test::block_on(async {
             Event::Update(ref status) => { /* .. */ },
             Event::Notification(ref notification) => { /* .. */ },
             Event::Delete(ref id) => { /* .. */ },
+            Event::StatusUpdate(ref status) => { /* .. */ },
             Event::FiltersChanged => { /* .. */ },
+            Event::Conversation(ref conversation) => { /* .. */ },
+            Event::Announcement(ref announcement) => { /* .. */ },
+            Event::AnnouncementReaction(ref reaction) => { /* .. */ },
+            Event::AnnouncementDelete(ref id) => { /* .. */ },
+            Event::ConnectionReset => { /* .. */ },
+            Event::Heartbeat => { /* .. */ },
+            Event::Unknown { .. } => { /* .. */ },
         }
         Ok(())
     }).await.unwrap();
 });"
             ),
-            pub async fn $fn_name(&self, $param: $param_type) -> Result<impl TryStream<Ok=(Event, Mastodon), Error=Error> + '_> {
+            pub async fn $fn_name(&self, $param: $param_type) -> Result<impl TryStream<Ok=(Event, Mastodon), Error=Error> + Send + 'static> {
                 use $crate::event_stream::event_stream;
                 let mut url: Url = self.route(concat!("/api/v1/streaming/", $stream)).parse()?;
                 url.query_pairs_mut().append_pair(stringify!($param), $param.as_ref());
                 let url = url.to_string();
-                let response = self.authenticated(self.client.get(url.as_str())).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.get(url.as_str())).header("Accept", "application/json")).await?;
                 debug!(
                     status:serde = crate::helpers::log::Status::from(&response), url:? = url,
                     headers:serde = crate::helpers::log::Headers::from(&response);
@@ -708,8 +900,9 @@ tokio_test::block_on(async {
                 if status.is_success() {
                      Ok(event_stream(response, url, self))
                 } else {
+                    let retry_after = $crate::pacing::RetryAfter::from_headers(response.headers());
                     let response = response.json().await?;
-                    Err(Error::Api{ status, response })
+                    Err(Error::Api{ status, response, retry_after })
                 }
             }
         }
@@ -736,20 +929,28 @@ tokio_test::block_on(async {
             Event::Update(ref status) => { /* .. */ },
             Event::Notification(ref notification) => { /* .. */ },
             Event::Delete(ref id) => { /* .. */ },
+            Event::StatusUpdate(ref status) => { /* .. */ },
             Event::FiltersChanged => { /* .. */ },
+            Event::Conversation(ref conversation) => { /* .. */ },
+            Event::Announcement(ref announcement) => { /* .. */ },
+            Event::AnnouncementReaction(ref reaction) => { /* .. */ },
+            Event::AnnouncementDelete(ref id) => { /* .. */ },
+            Event::ConnectionReset => { /* .. */ },
+            Event::Heartbeat => { /* .. */ },
+            Event::Unknown { .. } => { /* .. */ },
         }
         Ok(())
     }).await.unwrap();
 });"
             ),
-            pub async fn $fn_name(&self, $param: bool) -> Result<impl TryStream<Ok=(Event, Mastodon), Error=Error> + '_> {
+            pub async fn $fn_name(&self, $param: bool) -> Result<impl TryStream<Ok=(Event, Mastodon), Error=Error> + Send + 'static> {
                 use $crate::event_stream::event_stream;
                 let mut url: Url = self.route(concat!("/api/v1/streaming/", $stream)).parse()?;
                 if $param {
                     url.query_pairs_mut().append_key_only(stringify!($param));
                 }
                 let url = url.to_string();
-                let response = self.authenticated(self.client.get(url.as_str())).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.get(url.as_str())).header("Accept", "application/json")).await?;
                 debug!(
                     status:serde = crate::helpers::log::Status::from(&response), url:? = url,
                     headers:serde = crate::helpers::log::Headers::from(&response);
@@ -759,8 +960,9 @@ tokio_test::block_on(async {
                 if status.is_success() {
                      Ok(event_stream(response, url, self))
                 } else {
+                    let retry_after = $crate::pacing::RetryAfter::from_headers(response.headers());
                     let response = response.json().await?;
-                    Err(Error::Api{ status, response })
+                    Err(Error::Api{ status, response, retry_after })
                 }
             }
         }
@@ -768,3 +970,111 @@ tokio_test::block_on(async {
     };
     () => {}
 }
+
+macro_rules! route_metadata {
+    ($prefix:literal, $paged:literal, [$($acc:tt)*]) => {
+        &[$($acc)*]
+    };
+
+    ($prefix:literal, $paged:literal, [$($acc:tt)*] ($method:ident $($spec:tt)*) $name:ident $([$id_type:ty])?: $url:expr => $ret:ty, $($rest:tt)*) => {
+        route_metadata!($prefix, $paged, [
+            $($acc)*
+            $crate::routes::Route {
+                name: stringify!($name),
+                method: stringify!($method),
+                path: concat!($prefix, $url),
+                returns: stringify!($ret),
+                paged: $paged,
+            },
+        ] $($rest)*)
+    };
+
+    ($prefix:literal, $paged:literal, [$($acc:tt)*] $desc:literal $name:ident $(($($param:tt)*))?@$stream:literal, $($rest:tt)*) => {
+        route_metadata!($prefix, $paged, [
+            $($acc)*
+            $crate::routes::Route {
+                name: stringify!($name),
+                method: "get",
+                path: concat!($prefix, $stream),
+                returns: "Event",
+                paged: $paged,
+            },
+        ] $($rest)*)
+    };
+
+    (@hand_written [$($acc:tt)*]) => {
+        &[$($acc)*]
+    };
+
+    (
+        @hand_written [$($acc:tt)*]
+        #[route($method:ident $path:literal => $ret:ty $(, $paged:ident)?)]
+        $(#[$attr:meta])*
+        $vis:vis async fn $name:ident
+        $(<$($lt:lifetime $(: $bound:lifetime)?),* $(,)?>)?
+        ($($args:tt)*) -> $out:ty $body:block
+        $($rest:tt)*
+    ) => {
+        route_metadata!(@hand_written [
+            $($acc)*
+            $crate::routes::Route {
+                name: stringify!($name),
+                method: stringify!($method),
+                path: $path,
+                returns: stringify!($ret),
+                paged: route_metadata!(@paged $($paged)?),
+            },
+        ] $($rest)*)
+    };
+
+    // helpers aren't routes
+    (@hand_written [$($acc:tt)*] $skipped:item $($rest:tt)*) => {
+        route_metadata!(@hand_written [$($acc)*] $($rest)*)
+    };
+
+    (@paged) => { false };
+    (@paged paged) => { true };
+}
+
+/// Methods written out in full, each endpoint marked with
+/// `#[route(method "path" => Returns)]`, or `#[route(... , paged)]` if it
+/// returns a [`Page`](crate::page::Page), and helpers left unmarked.
+macro_rules! hand_written {
+    (
+        #[route($($spec:tt)*)]
+        $(#[$attr:meta])*
+        $vis:vis async fn $name:ident
+        $(<$($lt:lifetime $(: $bound:lifetime)?),* $(,)?>)?
+        ($($args:tt)*) -> $ret:ty $body:block
+        $($rest:tt)*
+    ) => {
+        $(#[$attr])*
+        $vis async fn $name $(<$($lt $(: $bound)?),*>)? ($($args)*) -> $ret $body
+
+        hand_written! { $($rest)* }
+    };
+    ($item:item $($rest:tt)*) => {
+        $item
+
+        hand_written! { $($rest)* }
+    };
+    () => {};
+}
+
+macro_rules! with_route_metadata {
+    ($family:ident as $routes:ident($prefix:literal, paged: $paged:literal) { $($body:tt)* }) => {
+        $family! { $($body)* }
+
+        const $routes: &'static [$crate::routes::Route] = route_metadata!($prefix, $paged, [] $($body)*);
+    };
+    ($family:ident as $routes:ident($prefix:literal, paged: $paged:literal, $vis:vis) { $($body:tt)* }) => {
+        $family! { [$vis] $($body)* }
+
+        const $routes: &'static [$crate::routes::Route] = route_metadata!($prefix, $paged, [] $($body)*);
+    };
+    (hand_written as $routes:ident { $($body:tt)* }) => {
+        hand_written! { $($body)* }
+
+        const $routes: &'static [$crate::routes::Route] = route_metadata!(@hand_written [] $($body)*);
+    };
+}
@@ -1,25 +1,99 @@
-use std::{borrow::Cow, ops::Deref, path::Path, sync::Arc};
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    fmt::Display,
+    future::Future,
+    ops::Deref,
+    path::Path,
+    sync::{Arc, Mutex, OnceLock},
+};
 
 use crate::{
+    admin::AdminClient,
+    builder::{HttpConfig, Timeouts},
+    cache_sync::CacheSync,
+    capabilities::Capabilities,
     entities::prelude::*,
     errors::{Error, Result},
+    event_stream::Backoff,
     helpers::read_response::read_response,
+    http_send::{HttpSend, HttpSender},
+    metrics::{Metrics, RequestMetrics},
+    pacing::{EndpointFamily, Pacer, RateLimit, RateLimiter, RetryAfter},
     polling_time::PollingTime,
-    AddPushRequest, Data, NewStatus, Page, StatusesRequest, UpdatePushRequest,
+    registration::AccessToken,
+    response_cache::{CacheStore, Caching},
+    routes::{self, Route},
+    upload::{self, UploadProgress},
+    AddPushRequest, Data, MastodonBuilder, NewStatus, Page, StatusesRequest, UpdatePushRequest,
+};
+use bytes::Bytes;
+use futures::{
+    future::try_join_all,
+    stream::{self, try_unfold},
+    Stream, TryStream, TryStreamExt,
 };
-use futures::TryStream;
-use log::{debug, error, trace};
+use log::{debug, error, trace, warn};
 use mastodon_async_entities::attachment::ProcessedAttachment;
-use reqwest::{multipart::Part, Client, RequestBuilder};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::{multipart::Part, Body, Client, RequestBuilder, Response};
+use time::OffsetDateTime;
+use tokio::io::AsyncRead;
 use url::Url;
 use uuid::Uuid;
 
 /// The Mastodon client is a smart pointer to this struct
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MastodonClient {
     pub(crate) client: Client,
     /// Raw data about your mastodon instance.
     pub data: Data,
+    /// Defaults applied to statuses built with
+    /// [`NewStatusBuilder::with_defaults`](crate::NewStatusBuilder::with_defaults).
+    pub status_defaults: status::new::Defaults,
+    /// What the server supports, once it has been determined.
+    pub(crate) capabilities: OnceLock<Capabilities>,
+    /// Paces requests to stay within the rate limits, if set with
+    /// [`Mastodon::with_rate_limiter`] or [`Mastodon::with_pacer`]. It is
+    /// shared by every clone of the client.
+    pub(crate) rate_limiter: Option<RateLimiter>,
+    /// The rate limit reported with the most recent response.
+    pub(crate) rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    /// How to retry requests which fail for transient reasons, if set with
+    /// [`Mastodon::with_retries`].
+    pub(crate) retry: Option<Backoff>,
+    /// How long requests of each kind may take, if set with
+    /// [`MastodonBuilder::timeouts`].
+    pub(crate) timeouts: Timeouts,
+    /// The scopes requests are checked against before they are sent, if set
+    /// with [`Mastodon::with_granted_scopes`].
+    pub(crate) granted_scopes: Option<Scopes>,
+    /// Notified of changes made through this client, if set with
+    /// [`Mastodon::with_cache_sync`].
+    pub(crate) cache_sync: Option<Arc<dyn CacheSync>>,
+    /// Told about every request, if set with [`Mastodon::with_metrics`].
+    pub(crate) metrics: Option<Arc<dyn Metrics>>,
+    /// The current credentials, if set to refresh them automatically with
+    /// [`Mastodon::with_auto_refresh`].
+    pub(crate) credentials: Option<Arc<tokio::sync::Mutex<Data>>>,
+    /// Sends the requests, [`HttpSender`] unless set with
+    /// [`Mastodon::with_http_send`].
+    pub(crate) http_send: Arc<dyn HttpSend>,
+}
+
+/// A summary of an account and its recent activity, as returned by
+/// [`Mastodon::account_overview`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountOverview {
+    /// The account itself.
+    pub account: Account,
+    /// The statuses the account has pinned to its profile.
+    pub pinned: Vec<Status>,
+    /// The account's most recent statuses.
+    pub recent: Vec<Status>,
+    /// The client account's relationship to the account, if the server
+    /// returned one.
+    pub relationship: Option<Relationship>,
 }
 
 /// Your mastodon application client, handles all requests to and from Mastodon.
@@ -39,6 +113,9 @@ pub struct MastodonUnauthenticated {
 
 impl From<Data> for Mastodon {
     /// Creates a mastodon instance from the data struct.
+    ///
+    /// This can't fail, so `data.base` isn't checked, and may be plain
+    /// `http`. Use [`Mastodon::builder`] to refuse that.
     fn from(data: Data) -> Mastodon {
         Mastodon::new(Client::new(), data)
     }
@@ -46,283 +123,2066 @@ impl From<Data> for Mastodon {
 impl Mastodon {
     methods![get and get_with_call_id, post and post_with_call_id, delete and delete_with_call_id,];
 
-    paged_routes! {
-        (get) favourites: "favourites" => Status,
-        (get) bookmarks: "bookmarks" => Status,
-        (get) blocks: "blocks" => Account,
-        (get) domain_blocks: "domain_blocks" => String,
-        (get) instance_domain_blocks: "instance/domain_blocks" => DomainBlock,
-        (get) follow_requests: "follow_requests" => Account,
-        (get) get_home_timeline: "timelines/home" => Status,
-        (get) get_emojis: "custom_emojis" => CustomEmoji,
-        (get) mutes: "mutes" => Account,
-        (get) notifications: "notifications" => Notification,
-        (get) instance_peers: "instance/peers" => String,
-        (get) instance_activity: "instance/activity" => instance::Activity,
-        (get) instance_rules: "instance/rules" => instance::Rule,
-        (get) reports: "reports" => Report,
-        (get (q: &'a str, #[serde(skip_serializing_if = "Option::is_none")] limit: Option<u64>, following: bool,)) search_accounts: "accounts/search" => Account,
-        (get) get_endorsements: "endorsements" => Account,
-    }
-
-    paged_routes_with_id! {
-        (get) followers: "accounts/{}/followers" => Account,
-        (get) following: "accounts/{}/following" => Account,
-        (get) reblogged_by: "statuses/{}/reblogged_by" => Account,
-        (get) favourited_by: "statuses/{}/favourited_by" => Account,
-    }
-
-    route! {
-        (delete (domain: String,)) unblock_domain: "domain_blocks" => Empty,
-        (get) instance: "instance" => Instance,
-        (get) verify_credentials: "accounts/verify_credentials" => Account,
-        (post (account_id: &str, status_ids: Vec<&str>, comment: String,)) report: "reports" => Report,
-        (post (domain: String,)) block_domain: "domain_blocks" => Empty,
-        (post (id: &str,)) authorize_follow_request: "accounts/follow_requests/authorize" => Empty,
-        (post (id: &str,)) reject_follow_request: "accounts/follow_requests/reject" => Empty,
-        (get  (local: bool,)) get_public_timeline: "timelines/public" => Vec<Status>,
-        (post (uri: Cow<'static, str>,)) follows: "follows" => Account,
-        (post) clear_notifications: "notifications/clear" => Empty,
-        (get) get_push_subscription: "push/subscription" => Subscription,
-        (delete) delete_push_subscription: "push/subscription" => Empty,
-        (get) get_follow_suggestions: "suggestions" => Vec<Account>,
-        (post (app: forms::Application,)) create_app: "apps" => Application,
-        (get) verify_app: "apps/verify_credentials" => Application,
-    }
-
-    route_v2! {
-        (get (q: &'a str, resolve: bool,)) search: "search" => SearchResult,
-        (post multipart with description (file: impl AsRef<Path>,)) media: "media" => Attachment,
-        (post multipart with description (file: impl AsRef<Path>, thumbnail: impl AsRef<Path>,)) media_with_thumbnail: "media" => Attachment,
-        (get) filters: "filters" => Vec<Filter>,
-        (post<-forms::filter::Add) add_filter: "filters" => Filter,
-    }
-
-    route_id! {
-        (get) get_account[AccountId]: "accounts/{}" => Account,
-        (post) follow[AccountId]: "accounts/{}/follow" => Relationship,
-        (post) unfollow[AccountId]: "accounts/{}/unfollow" => Relationship,
-        (post) block[AccountId]: "accounts/{}/block" => Relationship,
-        (post) unblock[AccountId]: "accounts/{}/unblock" => Relationship,
-        (get) mute[AccountId]: "accounts/{}/mute" => Relationship,
-        (get) unmute[AccountId]: "accounts/{}/unmute" => Relationship,
-        (get) get_notification[NotificationId]: "notifications/{}" => Notification,
-        (post) dismiss_notification[NotificationId]: "notifications/{}/dismiss" => Empty,
-        (get) get_status[StatusId]: "statuses/{}" => Status,
-        (get) get_context[StatusId]: "statuses/{}/context" => Context,
-        (get) get_card[StatusId]: "statuses/{}/card" => Card,
-        (post) reblog[StatusId]: "statuses/{}/reblog" => Status,
-        (post) unreblog[StatusId]: "statuses/{}/unreblog" => Status,
-        (post) favourite[StatusId]: "statuses/{}/favourite" => Status,
-        (post) unfavourite[StatusId]: "statuses/{}/unfavourite" => Status,
-        (delete) delete_status[StatusId]: "statuses/{}" => Empty,
-        (delete) delete_from_suggestions[AccountId]: "suggestions/{}" => Empty,
-        (post) endorse_user[AccountId]: "accounts/{}/pin" => Relationship,
-        (post) unendorse_user[AccountId]: "accounts/{}/unpin" => Relationship,
-        (get) attachment[AttachmentId]: "media/{}" => Attachment,
-    }
-
-    route_v2_id! {
-        (get) filter[FilterId]: "filters/{}" => Filter,
-        (delete) delete_filter[FilterId]: "filters/{}" => Empty,
-        (put<-forms::filter::Update) update_filter[FilterId]: "filters/{}" => Filter,
-        (get) filter_keywords[FilterId]: "filters/{}/keywords" => Vec<filter::Keyword>,
-        (post<-forms::filter::add::Keyword) add_keyword_to_filter[FilterId]: "filters/{}/keywords" => filter::Keyword,
-        (get) filter_keyword[KeywordId]: "filters/keywords/{}" => filter::Keyword,
-        (put<-forms::filter::add::Keyword) update_filter_keyword[KeywordId]: "filters/keywords/{}" => filter::Keyword,
-        (delete) delete_filter_keyword[KeywordId]: "filters/keywords/{}" => Empty,
-        (get) filter_statuses[FilterId]: "filters/{}/statuses" => Vec<filter::Status>,
-        (post<-forms::filter::Status) add_status_to_filter[FilterId]: "filters/{}/statuses" => filter::Status,
-        (get) filter_status[StatusId]: "filters/statuses/{}" => filter::Status,
-        (delete) disassociate_status_from_filter[StatusId]: "filters/statuses/{}" => Empty,
-    }
-
-    streaming! {
-        "returns events that are relevant to the authorized user, i.e. home timeline & notifications"
-        stream_user@"user",
-        "All public posts known to the server. Analogous to the federated timeline."
-        stream_public@"public",
-        "All public posts known to the server, filtered for media attachments. Analogous to the federated timeline with 'only media' enabled."
-        stream_public_media@"public/media",
-        "All public posts originating from this server."
-        stream_local(flag only_media)@"public/local",
-        "All public posts originating from other servers."
-        stream_remote(flag only_media)@"public/remote",
-        "All public posts using a certain hashtag."
-        stream_hashtag(tag: impl AsRef<str>, like "#bots")@"hashtag",
-        "All public posts using a certain hashtag, originating from this server."
-        stream_local_hashtag(tag: impl AsRef<str>, like "#bots")@"hashtag/local",
-        "Notifications for the current user."
-        stream_notifications@"user/notification",
-        "Updates to a specific list."
-        stream_list(list: impl AsRef<str>, like "12345")@"list",
-        "Updates to direct conversations."
-        stream_direct@"direct",
+    with_route_metadata! {
+        paged_routes as PAGED_ROUTES("/api/v1/", paged: true) {
+            (get) favourites: "favourites" => Status,
+            (get) bookmarks: "bookmarks" => Status,
+            (get) blocks: "blocks" => Account,
+            (get) domain_blocks: "domain_blocks" => String,
+            (get) instance_domain_blocks: "instance/domain_blocks" => DomainBlock,
+            (get) follow_requests: "follow_requests" => Account,
+            (get) get_home_timeline: "timelines/home" => Status,
+            (get<-forms::timeline::Options) get_home_timeline_with_options: "timelines/home" => Status,
+            (get<-forms::timeline::Options) get_public_timeline_with_options: "timelines/public" => Status,
+            (get) get_emojis: "custom_emojis" => CustomEmoji,
+            (get) mutes: "mutes" => Account,
+            (get) notifications: "notifications" => Notification,
+            (get<-forms::notification::Options) notifications_with_options: "notifications" => Notification,
+            (get) notification_requests: "notifications/requests" => notification::Request,
+            (get) instance_peers: "instance/peers" => String,
+            (get) instance_activity: "instance/activity" => instance::Activity,
+            (get) instance_rules: "instance/rules" => instance::Rule,
+            (get) reports: "reports" => Report,
+            (get<-forms::account::Search) search_accounts_with_options: "accounts/search" => Account,
+            (get) get_endorsements: "endorsements" => Account,
+            (get) followed_tags: "followed_tags" => Tag,
+            (get (#[serde(skip_serializing_if = "Option::is_none")] limit: Option<u64>, #[serde(skip_serializing_if = "Option::is_none")] offset: Option<u64>,)) trending_tags: "trends/tags" => Tag,
+            (get (#[serde(skip_serializing_if = "Option::is_none")] limit: Option<u64>, #[serde(skip_serializing_if = "Option::is_none")] offset: Option<u64>,)) trending_statuses: "trends/statuses" => Status,
+            (get (#[serde(skip_serializing_if = "Option::is_none")] limit: Option<u64>, #[serde(skip_serializing_if = "Option::is_none")] offset: Option<u64>,)) trending_links: "trends/links" => TrendsLink,
+        }
+    }
+
+    with_route_metadata! {
+        paged_routes_with_id as PAGED_ROUTES_WITH_ID("/api/v1/", paged: true) {
+            (get) followers[AccountId]: "accounts/{}/followers" => Account,
+            (get) following[AccountId]: "accounts/{}/following" => Account,
+            (get (limit: u64,)) followers_with_limit[AccountId]: "accounts/{}/followers" => Account,
+            (get (limit: u64,)) following_with_limit[AccountId]: "accounts/{}/following" => Account,
+            (get) reblogged_by[StatusId]: "statuses/{}/reblogged_by" => Account,
+            (get) favourited_by[StatusId]: "statuses/{}/favourited_by" => Account,
+        }
+    }
+
+    with_route_metadata! {
+        route as ROUTES("/api/v1/", paged: false) {
+            (delete (domain: String,)) unblock_domain: "domain_blocks" => (),
+            (get) instance: "instance" => Instance,
+            (get) verify_credentials: "accounts/verify_credentials" => Account,
+            (post (account_id: &str, status_ids: Vec<&str>, comment: String,)) report: "reports" => Report,
+            (post<-forms::report::Creation) file_report: "reports" => Report,
+            (post (domain: String,)) block_domain: "domain_blocks" => (),
+            (get  (local: bool,)) get_public_timeline: "timelines/public" => Vec<Status>,
+            (get (acct: &'a str,)) lookup_account: "accounts/lookup" => Account,
+            (post (uri: Cow<'static, str>,)) follows: "follows" => Account,
+            (post) clear_notifications: "notifications/clear" => (),
+            (get) get_push_subscription: "push/subscription" => Subscription,
+            (delete) delete_push_subscription: "push/subscription" => (),
+            (get) get_follow_suggestions: "suggestions" => Vec<Account>,
+            (post (app: forms::Application,)) create_app: "apps" => Application,
+            (get) verify_app: "apps/verify_credentials" => Application,
+            (get) preferences: "preferences" => Preferences,
+        }
+    }
+
+    with_route_metadata! {
+        route_v2 as ROUTES_V2("/api/v2/", paged: false) {
+            (get (q: &'a str, resolve: bool,)) search: "search" => SearchResult,
+            (get<-forms::search::Options) search_with_options: "search" => SearchResult,
+            (get) filters: "filters" => Vec<Filter>,
+            (post<-forms::filter::Add) add_filter: "filters" => Filter,
+            (get) get_notification_policy: "notifications/policy" => notification::Policy,
+            (patch<-forms::notification::Policy) update_notification_policy: "notifications/policy" => notification::Policy,
+            (get) follow_suggestions: "suggestions" => Vec<account::Suggestion>,
+        }
+    }
+
+    with_route_metadata! {
+        route_id as ROUTES_WITH_ID("/api/v1/", paged: false) {
+            (get) get_account[AccountId]: "accounts/{}" => Account,
+            (post then followed) follow[AccountId]: "accounts/{}/follow" => Relationship,
+            (post then unfollowed) unfollow[AccountId]: "accounts/{}/unfollow" => Relationship,
+            (post) block[AccountId]: "accounts/{}/block" => Relationship,
+            (post) unblock[AccountId]: "accounts/{}/unblock" => Relationship,
+            (post<-forms::account::MuteOptions) mute[AccountId]: "accounts/{}/mute" => Relationship,
+            (post) unmute[AccountId]: "accounts/{}/unmute" => Relationship,
+            (get) get_notification[NotificationId]: "notifications/{}" => Notification,
+            (post) authorize_follow_request[AccountId]: "follow_requests/{}/authorize" => Relationship,
+            (post) reject_follow_request[AccountId]: "follow_requests/{}/reject" => Relationship,
+            (post) dismiss_notification[NotificationId]: "notifications/{}/dismiss" => (),
+            (get) get_notification_request[NotificationRequestId]: "notifications/requests/{}" => notification::Request,
+            (post) accept_notification_request[NotificationRequestId]: "notifications/requests/{}/accept" => (),
+            (post) dismiss_notification_request[NotificationRequestId]: "notifications/requests/{}/dismiss" => (),
+            (get) get_status[StatusId]: "statuses/{}" => Status,
+            (get) get_context[StatusId]: "statuses/{}/context" => Context,
+            (get) get_card[StatusId]: "statuses/{}/card" => Card,
+            (post then reblogged) reblog[StatusId]: "statuses/{}/reblog" => Status,
+            (post then unreblogged) unreblog[StatusId]: "statuses/{}/unreblog" => Status,
+            (post then favourited) favourite[StatusId]: "statuses/{}/favourite" => Status,
+            (post then unfavourited) unfavourite[StatusId]: "statuses/{}/unfavourite" => Status,
+            (post then bookmarked) bookmark[StatusId]: "statuses/{}/bookmark" => Status,
+            (post then unbookmarked) unbookmark[StatusId]: "statuses/{}/unbookmark" => Status,
+            (post) mute_status_conversation[StatusId]: "statuses/{}/mute" => Status,
+            (post) unmute_status_conversation[StatusId]: "statuses/{}/unmute" => Status,
+            (delete then status_deleted by_id) delete_status[StatusId]: "statuses/{}" => (),
+            (delete) delete_from_suggestions[AccountId]: "suggestions/{}" => (),
+            (post) endorse_user[AccountId]: "accounts/{}/pin" => Relationship,
+            (post) unendorse_user[AccountId]: "accounts/{}/unpin" => Relationship,
+            (get) attachment[AttachmentId]: "media/{}" => Attachment,
+            (get) get_tag[str]: "tags/{}" => Tag,
+            (post) follow_tag[str]: "tags/{}/follow" => Tag,
+            (post) unfollow_tag[str]: "tags/{}/unfollow" => Tag,
+        }
+    }
+
+    // reached through `AdminClient`, which checks the account's role first
+    with_route_metadata! {
+        paged_routes as ADMIN_PAGED_ROUTES("/api/v1/", paged: true, pub(crate)) {
+            (get) admin_trending_tags: "admin/trends/tags" => crate::entities::admin::Tag,
+            (get) admin_tags: "admin/tags" => crate::entities::admin::Tag,
+            (get) admin_trending_statuses: "admin/trends/statuses" => Status,
+            (get) admin_trending_links: "admin/trends/links" => TrendsLink,
+            (get) admin_email_domain_blocks: "admin/email_domain_blocks" => EmailDomainBlock,
+            (get) admin_ip_blocks: "admin/ip_blocks" => IpBlock,
+            (get) admin_canonical_email_blocks: "admin/canonical_email_blocks" => CanonicalEmailBlock,
+            (get) admin_reports: "admin/reports" => crate::entities::admin::Report,
+        }
+    }
+
+    with_route_metadata! {
+        route as ADMIN_ROUTES("/api/v1/", paged: false, pub(crate)) {
+            (post (domain: String,)) admin_block_email_domain: "admin/email_domain_blocks" => EmailDomainBlock,
+            (post<-forms::admin::ip_block::Add) admin_add_ip_block: "admin/ip_blocks" => IpBlock,
+            (post<-forms::admin::canonical_email_block::Test) admin_test_canonical_email_block: "admin/canonical_email_blocks/test" => Vec<CanonicalEmailBlock>,
+            (post<-forms::admin::canonical_email_block::Add) admin_create_canonical_email_block: "admin/canonical_email_blocks" => CanonicalEmailBlock,
+        }
+    }
+
+    with_route_metadata! {
+        route_id as ADMIN_ROUTES_WITH_ID("/api/v1/", paged: false, pub(crate)) {
+            (get) admin_email_domain_block[EmailDomainBlockId]: "admin/email_domain_blocks/{}" => EmailDomainBlock,
+            (delete) admin_unblock_email_domain[EmailDomainBlockId]: "admin/email_domain_blocks/{}" => (),
+            (get) admin_ip_block[DomainBlockId]: "admin/ip_blocks/{}" => IpBlock,
+            (put<-forms::admin::ip_block::Update) admin_update_ip_block[DomainBlockId]: "admin/ip_blocks/{}" => IpBlock,
+            (delete) admin_delete_ip_block[DomainBlockId]: "admin/ip_blocks/{}" => (),
+            (get) admin_get_canonical_email_block[CanonicalEmailBlockId]: "admin/canonical_email_blocks/{}" => CanonicalEmailBlock,
+            (delete) admin_delete_canonical_email_block[CanonicalEmailBlockId]: "admin/canonical_email_blocks/{}" => (),
+            (get) admin_report[ReportId]: "admin/reports/{}" => crate::entities::admin::Report,
+            (get) admin_get_tag[TagId]: "admin/tags/{}" => crate::entities::admin::Tag,
+            (put<-forms::admin::tag::Update) admin_update_tag[TagId]: "admin/tags/{}" => crate::entities::admin::Tag,
+            (post<-forms::admin::account_action::Action) admin_account_action[AccountId]: "admin/accounts/{}/action" => (),
+        }
+    }
+
+    with_route_metadata! {
+        route_v2_id as ROUTES_V2_WITH_ID("/api/v2/", paged: false) {
+            (get) filter[FilterId]: "filters/{}" => Filter,
+            (delete) delete_filter[FilterId]: "filters/{}" => (),
+            (put<-forms::filter::Update) update_filter[FilterId]: "filters/{}" => Filter,
+            (get) filter_keywords[FilterId]: "filters/{}/keywords" => Vec<filter::Keyword>,
+            (post<-forms::filter::add::Keyword) add_keyword_to_filter[FilterId]: "filters/{}/keywords" => filter::Keyword,
+            (get) filter_keyword[KeywordId]: "filters/keywords/{}" => filter::Keyword,
+            (put<-forms::filter::add::Keyword) update_filter_keyword[KeywordId]: "filters/keywords/{}" => filter::Keyword,
+            (delete) delete_filter_keyword[KeywordId]: "filters/keywords/{}" => (),
+            (get) filter_statuses[FilterId]: "filters/{}/statuses" => Vec<filter::Status>,
+            (post<-forms::filter::Status) add_status_to_filter[FilterId]: "filters/{}/statuses" => filter::Status,
+            (get) filter_status[StatusId]: "filters/statuses/{}" => filter::Status,
+            (delete) disassociate_status_from_filter[StatusId]: "filters/statuses/{}" => (),
+        }
+    }
+
+    with_route_metadata! {
+        streaming as STREAMING_ROUTES("/api/v1/streaming/", paged: false) {
+            "returns events that are relevant to the authorized user, i.e. home timeline & notifications"
+            stream_user@"user",
+            "All public posts known to the server. Analogous to the federated timeline."
+            stream_public@"public",
+            "All public posts known to the server, filtered for media attachments. Analogous to the federated timeline with 'only media' enabled."
+            stream_public_media@"public/media",
+            "All public posts originating from this server."
+            stream_local(flag only_media)@"public/local",
+            "All public posts originating from other servers."
+            stream_remote(flag only_media)@"public/remote",
+            "All public posts using a certain hashtag."
+            stream_hashtag(tag: impl AsRef<str>, like "#bots")@"hashtag",
+            "All public posts using a certain hashtag, originating from this server."
+            stream_local_hashtag(tag: impl AsRef<str>, like "#bots")@"hashtag/local",
+            "Notifications for the current user."
+            stream_notifications@"user/notification",
+            "Updates to a specific list."
+            stream_list(list: impl AsRef<str>, like "12345")@"list",
+            "Updates to direct conversations."
+            stream_direct@"direct",
+        }
+    }
+
+    /// Every API route this client implements, for introspection, e.g. to
+    /// check which scopes an application needs.
+    pub fn routes() -> impl Iterator<Item = &'static Route> {
+        let routes = [
+            Self::PAGED_ROUTES,
+            Self::PAGED_ROUTES_WITH_ID,
+            Self::ROUTES,
+            Self::ROUTES_V2,
+            Self::ROUTES_WITH_ID,
+            Self::ROUTES_V2_WITH_ID,
+            Self::ADMIN_PAGED_ROUTES,
+            Self::ADMIN_ROUTES,
+            Self::ADMIN_ROUTES_WITH_ID,
+            Self::STREAMING_ROUTES,
+            Self::HAND_WRITTEN_ROUTES,
+            Self::HAND_WRITTEN_ACCOUNT_ROUTES,
+        ]
+        .into_iter()
+        .flatten();
+        #[cfg(feature = "websocket")]
+        let routes = routes.chain(Self::WEBSOCKET_ROUTES);
+        #[cfg(feature = "pleroma")]
+        let routes = routes.chain(crate::pleroma::ROUTES);
+        routes
+    }
+
+    /// Start building a client whose HTTP client has proxies, certificates,
+    /// timeouts or a user agent set. See [`MastodonBuilder`].
+    pub fn builder(data: Data) -> MastodonBuilder {
+        MastodonBuilder::new(data)
     }
 
     /// A new instance.
+    ///
+    /// This can't fail, so `data.base` isn't checked, and may be plain
+    /// `http` to any host. Use [`Mastodon::builder`], whose
+    /// [`build`](MastodonBuilder::build) refuses that unless
+    /// [allowed](MastodonBuilder::allow_plaintext).
     pub fn new(client: Client, data: Data) -> Self {
-        Mastodon(Arc::new(MastodonClient { client, data }))
+        Mastodon(Arc::new(MastodonClient {
+            client,
+            data,
+            status_defaults: Default::default(),
+            capabilities: OnceLock::new(),
+            rate_limiter: None,
+            rate_limit: Default::default(),
+            retry: None,
+            timeouts: Default::default(),
+            granted_scopes: None,
+            cache_sync: None,
+            metrics: None,
+            credentials: None,
+            http_send: Arc::new(HttpSender),
+        }))
+    }
+
+    /// Return a new client which shares this one's connection and
+    /// credentials, but applies the given defaults to new statuses.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{entities::status::new::Defaults, prelude::*};
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default()).with_status_defaults(Defaults {
+    ///         visibility: Some(Visibility::Unlisted),
+    ///         ..Default::default()
+    ///     });
+    ///     let status = StatusBuilder::with_defaults(&client)
+    ///         .status("posted unlisted")
+    ///         .build()
+    ///         .unwrap();
+    ///     client.new_status(status).await.unwrap();
+    /// });
+    /// ```
+    pub fn with_status_defaults(&self, status_defaults: status::new::Defaults) -> Self {
+        Mastodon(Arc::new(MastodonClient {
+            status_defaults,
+            ..(*self.0).clone()
+        }))
+    }
+
+    /// Return a new client which shares this one's connection pool and
+    /// configuration, but authenticates with the given access token, e.g. to
+    /// act on behalf of another user of the same instance.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let admin = Mastodon::from(Data::default());
+    ///     let user = admin.clone_with_token("user-access-token");
+    ///     let account = user.verify_credentials().await.unwrap();
+    ///     println!("acting as {}", account.acct);
+    /// });
+    /// ```
+    pub fn clone_with_token(&self, token: impl Into<Cow<'static, str>>) -> Self {
+        Mastodon(Arc::new(MastodonClient {
+            data: Data {
+                token: token.into(),
+                scopes: None,
+                ..self.data.clone()
+            },
+            rate_limiter: None,
+            rate_limit: Default::default(),
+            granted_scopes: None,
+            cache_sync: None,
+            credentials: None,
+            ..(*self.0).clone()
+        }))
+    }
+
+    /// Return a new client which shares this one's connection and
+    /// credentials, but waits for the given [`Pacer`] before each request
+    /// instead of running into the server's rate limit.
+    ///
+    /// The pacer is kept up to date with the `X-RateLimit-*` and
+    /// `Retry-After` headers of every response, so requests are held back
+    /// until the limit resets once the server says none remain.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{pacing::Pacer, prelude::*};
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default()).with_pacer(Pacer::default());
+    ///     for id in ["1", "2", "3"] {
+    ///         client.favourite(&StatusId::new(id)).await.unwrap();
+    ///     }
+    /// });
+    /// ```
+    pub fn with_pacer(&self, pacer: Pacer) -> Self {
+        self.with_rate_limiter(pacer.into())
+    }
+
+    /// Return a new client which shares this one's connection and
+    /// credentials, but waits for the given [`RateLimiter`] before each
+    /// request, with separate budgets for routes Mastodon limits more
+    /// strictly, such as media uploads.
+    ///
+    /// The limiter lives in the state shared by all clones of the returned
+    /// client, so tasks using different clones pace their requests
+    /// together.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{pacing::RateLimiter, prelude::*};
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default()).with_rate_limiter(RateLimiter::default());
+    ///     for path in ["1.png", "2.png", "3.png"] {
+    ///         let upload = forms::media::Upload::builder(path).build().unwrap();
+    ///         client.media(upload).await.unwrap();
+    ///     }
+    /// });
+    /// ```
+    pub fn with_rate_limiter(&self, rate_limiter: RateLimiter) -> Self {
+        Mastodon(Arc::new(MastodonClient {
+            rate_limiter: Some(rate_limiter),
+            ..(*self.0).clone()
+        }))
     }
 
-    fn route(&self, url: impl AsRef<str>) -> String {
+    /// Return a new client which shares this one's connection and
+    /// credentials, but retries requests which fail for transient reasons.
+    ///
+    /// A request is retried if the connection fails or times out, or if the
+    /// server responds with `429 Too Many Requests` or a server error other
+    /// than `501 Not Implemented`. The server's `Retry-After` header is
+    /// honoured if it sent one, otherwise attempts are spaced out according
+    /// to `backoff`, until `backoff.max_retries` retries have failed. Requests
+    /// with a streamed body, such as media uploads, are never retried.
+    ///
+    /// Every attempt at a `POST` carries the same `Idempotency-Key` header, so
+    /// that if the server acted on it before failing, endpoints which support
+    /// the header, such as posting a status, don't act on it again. A key set
+    /// on the request beforehand is kept. Other endpoints may still repeat
+    /// their effect when retried after a server error.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{event_stream::Backoff, prelude::*};
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default()).with_retries(Backoff {
+    ///         max_retries: Some(3),
+    ///         ..Default::default()
+    ///     });
+    ///     client.get_home_timeline().await.unwrap();
+    /// });
+    /// ```
+    pub fn with_retries(&self, backoff: Backoff) -> Self {
+        Mastodon(Arc::new(MastodonClient {
+            retry: Some(backoff),
+            ..(*self.0).clone()
+        }))
+    }
+
+    /// Return a new client which shares this one's connection and
+    /// credentials, but checks each request against the scopes granted to
+    /// its access token before sending it, returning
+    /// [`Error::MissingScope`] instead of waiting for the server to refuse.
+    ///
+    /// Only the broad scope of each request is checked, as listed by
+    /// [`Route::scope`], so a request may still be refused if the token was
+    /// only granted a narrower scope which doesn't cover it.
+    ///
+    /// ```
+    /// use mastodon_async::{prelude::*, Error};
+    /// tokio_test::block_on(async {
+    ///     let data = Data {
+    ///         base: "https://example.com".into(),
+    ///         ..Default::default()
+    ///     };
+    ///     let client = Mastodon::from(data).with_granted_scopes(Scopes::read_all());
+    ///     let result = client.favourite(&StatusId::new("1")).await;
+    ///     assert!(matches!(result, Err(Error::MissingScope { required: "write", .. })));
+    /// });
+    /// ```
+    pub fn with_granted_scopes(&self, scopes: Scopes) -> Self {
+        Mastodon(Arc::new(MastodonClient {
+            granted_scopes: Some(scopes),
+            ..(*self.0).clone()
+        }))
+    }
+
+    /// Return a new client which shares this one's connection and
+    /// credentials, but gives up on requests which take longer than
+    /// `timeouts` allows.
+    pub(crate) fn with_timeouts(&self, timeouts: Timeouts) -> Self {
+        Mastodon(Arc::new(MastodonClient {
+            timeouts,
+            ..(*self.0).clone()
+        }))
+    }
+
+    /// Return a new client which shares this one's connection and
+    /// credentials, but checks each request against the scopes the server
+    /// said it granted to the access token, stored in
+    /// [`Data::scopes`](crate::Data::scopes), as
+    /// [`Mastodon::with_granted_scopes`] does.
+    ///
+    /// `Data` saved before the scopes were stored doesn't have them, and
+    /// neither does a client made with [`Mastodon::clone_with_token`]; such
+    /// clients are returned as they are, without checking requests.
+    ///
+    /// ```
+    /// use mastodon_async::{prelude::*, Error};
+    /// tokio_test::block_on(async {
+    ///     let data = Data {
+    ///         base: "https://example.com".into(),
+    ///         scopes: Some(Scopes::read_all()),
+    ///         ..Default::default()
+    ///     };
+    ///     let client = Mastodon::from(data).with_scope_guard();
+    ///     let result = client.favourite(&StatusId::new("1")).await;
+    ///     assert!(matches!(result, Err(Error::MissingScope { required: "write", .. })));
+    /// });
+    /// ```
+    pub fn with_scope_guard(&self) -> Self {
+        match &self.data.scopes {
+            Some(scopes) => self.with_granted_scopes(scopes.clone()),
+            None => self.clone(),
+        }
+    }
+
+    /// The scopes granted to the access token: those set with
+    /// [`Mastodon::with_granted_scopes`] if there are any, otherwise those
+    /// the server said it granted, if it did.
+    pub fn granted_scopes(&self) -> Option<&Scopes> {
+        self.0.granted_scopes.as_ref().or(self.data.scopes.as_ref())
+    }
+
+    /// Return a new client which shares this one's connection and
+    /// credentials, but tells `metrics` about every request it makes.
+    pub fn with_metrics(&self, metrics: impl Metrics + 'static) -> Self {
+        Mastodon(Arc::new(MastodonClient {
+            metrics: Some(Arc::new(metrics)),
+            ..(*self.0).clone()
+        }))
+    }
+
+    /// Return a new client which shares this one's connection and
+    /// credentials, but notifies `cache_sync` of the changes it makes.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{cache_sync::CacheSync, prelude::*};
+    ///
+    /// #[derive(Debug)]
+    /// struct Log;
+    ///
+    /// impl CacheSync for Log {
+    ///     fn followed(&self, relationship: &Relationship) {
+    ///         println!("now following {}", relationship.id);
+    ///     }
+    /// }
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default()).with_cache_sync(Log);
+    ///     client.follow(&AccountId::new("42")).await.unwrap();
+    /// });
+    /// ```
+    pub fn with_cache_sync(&self, cache_sync: impl CacheSync + 'static) -> Self {
+        Mastodon(Arc::new(MastodonClient {
+            cache_sync: Some(Arc::new(cache_sync)),
+            ..(*self.0).clone()
+        }))
+    }
+
+    /// Return a new client which shares this one's connection and
+    /// credentials, but sends its requests with `http_send`, e.g. to route
+    /// them through a proxy, sign them, or answer them in tests. See
+    /// [`HttpSend`] for an example.
+    ///
+    /// Streaming over a websocket still connects directly.
+    pub fn with_http_send(&self, http_send: impl HttpSend + 'static) -> Self {
+        Mastodon(Arc::new(MastodonClient {
+            http_send: Arc::new(http_send),
+            ..(*self.0).clone()
+        }))
+    }
+
+    /// Return a new client which shares this one's connection and
+    /// credentials, but keeps responses which carry an `ETag` or
+    /// `Last-Modified` header in `store`, and only downloads them again if
+    /// they have changed. See [`Caching`].
+    ///
+    /// ```no_run
+    /// use mastodon_async::{prelude::*, response_cache::MemoryStore};
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default()).with_response_cache(MemoryStore::new(64));
+    ///     let emojis = client.get_emojis().await.unwrap();
+    ///     // answered with `304 Not Modified` if the server's emojis haven't changed
+    ///     let emojis = client.get_emojis().await.unwrap();
+    /// });
+    /// ```
+    pub fn with_response_cache(&self, store: impl CacheStore + 'static) -> Self {
+        self.with_http_send(Caching::with_store(self.http_send.clone(), store))
+    }
+
+    /// Exchange the refresh token for a new access token, and return a client
+    /// which uses it.
+    ///
+    /// Save the new client's [`data`](MastodonClient::data) in place of the
+    /// old one, as the server may revoke the old tokens.
+    ///
+    /// # Errors
+    /// [`Error::RefreshTokenRequired`] if the server didn't issue a refresh
+    /// token.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let client = client.refresh_token().await.unwrap();
+    ///     println!("the new token expires at {:?}", client.data.expires_at);
+    /// });
+    /// ```
+    pub async fn refresh_token(&self) -> Result<Self> {
+        let mut data = self.current_data().await;
+        self.request_token_refresh(&data).await?.update(&mut data);
+        Ok(Mastodon(Arc::new(MastodonClient {
+            data: data.clone(),
+            credentials: self
+                .credentials
+                .as_ref()
+                .map(|_| Arc::new(tokio::sync::Mutex::new(data))),
+            ..(*self.0).clone()
+        })))
+    }
+
+    /// Return a new client which shares this one's connection, but refreshes
+    /// its access token when it is about to expire, or when the server
+    /// rejects it. Nothing is refreshed if the server didn't issue a refresh
+    /// token.
+    ///
+    /// The refreshed credentials are shared by clones of the new client, but
+    /// not stored in its [`data`](MastodonClient::data). Get them with
+    /// [`Mastodon::current_data`] to save them.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default()).with_auto_refresh();
+    ///     client.get_home_timeline().await.unwrap();
+    ///     let data = client.current_data().await;
+    ///     println!("the current token expires at {:?}", data.expires_at);
+    /// });
+    /// ```
+    pub fn with_auto_refresh(&self) -> Self {
+        Mastodon(Arc::new(MastodonClient {
+            credentials: Some(Arc::new(tokio::sync::Mutex::new(self.data.clone()))),
+            ..(*self.0).clone()
+        }))
+    }
+
+    /// The client's data, including the latest access token if it has been
+    /// refreshed automatically.
+    pub async fn current_data(&self) -> Data {
+        match &self.credentials {
+            Some(credentials) => credentials.lock().await.clone(),
+            None => self.data.clone(),
+        }
+    }
+
+    /// The rate limit the server reported with the most recent response, if
+    /// any has been received.
+    pub fn last_rate_limit(&self) -> Option<RateLimit> {
+        *self
+            .rate_limit
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Fetch the authenticated user's [`Preferences`] and return a new client
+    /// which applies their posting defaults to new statuses.
+    pub async fn with_preferred_status_defaults(&self) -> Result<Self> {
+        let preferences = self.preferences().await?;
+        Ok(self.with_status_defaults(preferences.into()))
+    }
+
+    pub(crate) fn route(&self, url: impl AsRef<str>) -> String {
         format!("{}{}", self.data.base, url.as_ref())
     }
 
-    /// Update the user credentials
-    pub async fn update_credentials(
+    with_route_metadata! {
+        hand_written as HAND_WRITTEN_ROUTES {
+            #[route(patch "/api/v1/accounts/update_credentials" => Account)]
+            /// Update the user credentials
+            pub async fn update_credentials(
+                &self,
+                changes: account::CredentialsBuilder,
+            ) -> Result<Account> {
+                let url = self.route("/api/v1/accounts/update_credentials");
+                let request = self.client.patch(&url).json(&changes.build()?);
+                let response = self.send(request).await?;
+
+                read_response(response).await
+            }
+
+            #[route(post "/api/v1/statuses" => Status)]
+            /// Post a new status to the account.
+            pub async fn new_status(&self, status: NewStatus) -> Result<Status> {
+                let url = self.route("/api/v1/statuses");
+                let request = self.authenticated(self.client.post(&url)).json(&status);
+                let response = self.send(request).await?;
+                debug!(
+                    status:serde = crate::helpers::log::Status::from(&response), url = url,
+                    headers:serde = crate::helpers::log::Headers::from(&response);
+                    "received API response"
+                );
+                read_response(response).await
+            }
+
+            #[route(post "/api/v1/statuses" => Status)]
+            /// Post a new status with the given `Idempotency-Key` header. If a status
+            /// is posted again with the same key, e.g. because the response to the
+            /// first attempt was lost, the server returns the status it already
+            /// posted instead of posting it twice.
+            pub async fn new_status_with_idempotency_key(
+                &self,
+                status: NewStatus,
+                key: &str,
+            ) -> Result<Status> {
+                let url = self.route("/api/v1/statuses");
+                let request = self
+                    .authenticated(self.client.post(&url))
+                    .header(IDEMPOTENCY_KEY, key)
+                    .json(&status);
+                let response = self.send(request).await?;
+                debug!(
+                    status:serde = crate::helpers::log::Status::from(&response), url = url,
+                    idempotency_key = key;
+                    "received API response"
+                );
+                read_response(response).await
+            }
+
+            #[route(post "/api/v1/statuses" => status::Scheduled)]
+            /// Schedule a status to be posted at its
+            /// [`scheduled_at`](NewStatus::scheduled_at) time, which must be set.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            /// use time::{Duration, OffsetDateTime};
+            ///
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let status = StatusBuilder::default()
+            ///         .status("posted tomorrow")
+            ///         .scheduled_at(OffsetDateTime::now_utc() + Duration::days(1))
+            ///         .build()
+            ///         .unwrap();
+            ///     let scheduled = client.schedule_status(status).await.unwrap();
+            ///     println!("scheduled as {}", scheduled.id);
+            /// });
+            /// ```
+            pub async fn schedule_status(&self, status: NewStatus) -> Result<status::Scheduled> {
+                if status.scheduled_at.is_none() {
+                    return Err(crate::format_err!(
+                        "a scheduled status needs a scheduled_at time"
+                    ));
+                }
+                let url = self.route("/api/v1/statuses");
+                debug!(url = url, method = "post", body:serde = status; "making API request");
+                let request = self.authenticated(self.client.post(&url)).json(&status);
+                let response = self.send(request).await?;
+                read_response(response).await
+            }
+
+            #[route(post "/api/v2/media" => Attachment)]
+            /// Upload media from memory rather than from a file, such as an image
+            /// generated by a bot. `file_name` is sent along with it, for the server
+            /// to guess its type from, unless its MIME type, e.g. `image/png`, is
+            /// given. The description/alt-text and the focal point used to crop
+            /// previews are optional.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            ///
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let chart: Vec<u8> = vec![/* rendered PNG */];
+            ///     let attachment = client
+            ///         .media_from_bytes(
+            ///             chart,
+            ///             "chart.png",
+            ///             Some("image/png"),
+            ///             Some("Requests per hour, peaking at noon".to_string()),
+            ///             Some(attachment::FocalPoint { x: 0.0, y: 0.5 }),
+            ///         )
+            ///         .await
+            ///         .unwrap();
+            /// });
+            /// ```
+            pub async fn media_from_bytes(
+                &self,
+                bytes: impl Into<Bytes>,
+                file_name: impl Into<String>,
+                mime: Option<&str>,
+                description: Option<String>,
+                focus: Option<attachment::FocalPoint>,
+            ) -> Result<Attachment> {
+                let part = Part::stream(Body::from(bytes.into())).file_name(file_name.into());
+                let part = match mime {
+                    Some(mime) => part.mime_str(mime)?,
+                    None => part,
+                };
+                self.upload_media(part, None, description, focus).await
+            }
+
+            #[route(post "/api/v2/media" => Attachment)]
+            /// Upload media read from `reader`, such as a file opened with
+            /// `tokio::fs` or a socket. It is streamed to the server as it is read.
+            /// The other arguments are those of [`Mastodon::media_from_bytes`].
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            ///
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let file = tokio::fs::File::open("/tmp/cat.jpg").await.unwrap();
+            ///     let attachment = client
+            ///         .media_from_reader(
+            ///             file,
+            ///             "cat.jpg",
+            ///             Some("image/jpeg"),
+            ///             Some("a sleeping cat".to_string()),
+            ///             None,
+            ///         )
+            ///         .await
+            ///         .unwrap();
+            /// });
+            /// ```
+            pub async fn media_from_reader(
+                &self,
+                reader: impl AsyncRead + Send + 'static,
+                file_name: impl Into<String>,
+                mime: Option<&str>,
+                description: Option<String>,
+                focus: Option<attachment::FocalPoint>,
+            ) -> Result<Attachment> {
+                let part = upload::reader_part(reader, file_name);
+                let part = match mime {
+                    Some(mime) => part.mime_str(mime)?,
+                    None => part,
+                };
+                self.upload_media(part, None, description, focus).await
+            }
+
+            #[route(put "/api/v1/media/{}" => Attachment)]
+            /// Change the description/alt-text, the focal point or the thumbnail of
+            /// media which has been uploaded but not attached to a status yet. Only
+            /// what is given is changed.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            ///
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let attachment = client
+            ///         .update_media(
+            ///             &AttachmentId::new("22345792"),
+            ///             Some("A cat asleep in a sunbeam".to_string()),
+            ///             Some(attachment::FocalPoint { x: -0.4, y: 0.2 }),
+            ///             None,
+            ///         )
+            ///         .await
+            ///         .unwrap();
+            /// });
+            /// ```
+            pub async fn update_media(
+                &self,
+                id: &AttachmentId,
+                description: Option<String>,
+                focus: Option<attachment::FocalPoint>,
+                thumbnail: Option<&Path>,
+            ) -> Result<Attachment> {
+                use reqwest::multipart::Form;
+
+                let mut form_data = Form::new();
+                if let Some(description) = description {
+                    form_data = form_data.text("description", description);
+                }
+                if let Some(focus) = focus {
+                    form_data = form_data.text("focus", upload::focus_field(&focus));
+                }
+                if let Some(thumbnail) = thumbnail {
+                    form_data = form_data.part("thumbnail", Self::get_form_part(thumbnail).await?);
+                }
+                let url = self.route(format!("/api/v1/media/{id}"));
+                debug!(url = url, multipart_form_data:? = form_data; "making API request");
+                let request = self
+                    .authenticated(self.client.put(&url))
+                    .multipart(form_data)
+                    .header("Accept", "application/json");
+                read_response(self.send(request).await?).await
+            }
+
+            #[route(post "/api/v2/media" => Attachment)]
+            /// Upload media from a file, along with its thumbnail, description/alt-text
+            /// and focal point, if the form has them. The files are streamed from
+            /// disk as they are sent.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            ///
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let upload = forms::media::Upload::builder("/tmp/cat.jpg")
+            ///         .description("A sleeping cat")
+            ///         .build()
+            ///         .unwrap();
+            ///     let attachment = client.media(upload).await.unwrap();
+            /// });
+            /// ```
+            pub async fn media(&self, form: forms::media::Upload) -> Result<Attachment> {
+                let part = Self::get_form_part(&form.file).await?;
+                let thumbnail = match &form.thumbnail {
+                    Some(thumbnail) => Some(Self::get_form_part(thumbnail).await?),
+                    None => None,
+                };
+                self.upload_media(part, thumbnail, form.description, form.focus)
+                    .await
+            }
+
+            /// Upload media as the given multipart form part.
+            async fn upload_media(
+                &self,
+                part: Part,
+                thumbnail: Option<Part>,
+                description: Option<String>,
+                focus: Option<attachment::FocalPoint>,
+            ) -> Result<Attachment> {
+                use reqwest::multipart::Form;
+
+                let mut form_data = Form::new().part("file", part);
+                if let Some(thumbnail) = thumbnail {
+                    form_data = form_data.part("thumbnail", thumbnail);
+                }
+                if let Some(description) = description {
+                    form_data = form_data.text("description", description);
+                }
+                if let Some(focus) = focus {
+                    form_data = form_data.text("focus", upload::focus_field(&focus));
+                }
+                let url = self.route("/api/v2/media");
+                debug!(url = url, multipart_form_data:? = form_data; "making API request");
+                let request = self
+                    .authenticated(self.client.post(&url))
+                    .multipart(form_data)
+                    .header("Accept", "application/json");
+                read_response(self.send(request).await?).await
+            }
+
+            #[route(post "/api/v2/media" => Attachment)]
+            /// Upload media from a file, optionally with a description/alt-text,
+            /// calling `on_progress` as the file is sent, e.g. to show a progress
+            /// bar for a large video.
+            ///
+            /// Like [`Mastodon::media`], the file is streamed from disk rather than
+            /// read into memory first, so a failed upload isn't retried, even if the
+            /// client is configured to retry requests.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            ///
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let attachment = client
+            ///         .media_with_progress("holiday.mp4", None, |progress| {
+            ///             println!("{:.0}%", progress.fraction() * 100.0);
+            ///         })
+            ///         .await
+            ///         .unwrap();
+            /// });
+            /// ```
+            pub async fn media_with_progress(
+                &self,
+                file: impl AsRef<Path>,
+                description: Option<String>,
+                on_progress: impl FnMut(UploadProgress) + Send + 'static,
+            ) -> Result<Attachment> {
+                let part = upload::file_part(file.as_ref(), on_progress).await?;
+                self.upload_media(part, None, description, None).await
+            }
+
+            /// Determine what the server supports, based on the version it
+            /// advertises. This is fetched on first use and cached for the lifetime
+            /// of the client.
+            pub async fn capabilities(&self) -> Result<Capabilities> {
+                if let Some(capabilities) = self.capabilities.get() {
+                    return Ok(capabilities.clone());
+                }
+                #[derive(Deserialize, Serialize)]
+                struct InstanceVersion {
+                    version: String,
+                }
+                let instance: InstanceVersion = self.get(self.route("/api/v1/instance")).await?;
+                let capabilities = Capabilities::from_version_string(instance.version);
+                debug!(capabilities:serde = capabilities; "determined server capabilities");
+                Ok(self.capabilities.get_or_init(|| capabilities).clone())
+            }
+
+            #[route(get "/api/v1/statuses" => Vec<Status>)]
+            /// Fetch several statuses at once.
+            ///
+            /// On servers which support it (Mastodon 4.3+), this is a single request
+            /// to `GET /api/v1/statuses`. Otherwise, the statuses are fetched
+            /// concurrently one at a time. In either case, statuses which don't exist
+            /// or aren't visible are omitted from the result.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let ids = [StatusId::new("1"), StatusId::new("2")];
+            ///     let statuses = client.get_statuses(&ids).await.unwrap();
+            /// });
+            /// ```
+            pub async fn get_statuses(&self, ids: &[StatusId]) -> Result<Vec<Status>> {
+                if ids.is_empty() {
+                    return Ok(vec![]);
+                }
+                if self.capabilities().await?.batch_statuses() {
+                    let mut url: Url = self.route("/api/v1/statuses").parse()?;
+                    {
+                        let mut query = url.query_pairs_mut();
+                        for id in ids {
+                            query.append_pair("id[]", id.as_ref());
+                        }
+                    }
+                    return self.get(url.as_str()).await;
+                }
+                let statuses = try_join_all(ids.iter().map(|id| async move {
+                    match self.get_status(id).await {
+                        Ok(status) => Ok(Some(status)),
+                        Err(Error::Api { status, .. })
+                            if status == reqwest::StatusCode::NOT_FOUND =>
+                        {
+                            Ok(None)
+                        }
+                        Err(err) => Err(err),
+                    }
+                }))
+                .await?;
+                Ok(statuses.into_iter().flatten().collect())
+            }
+
+            #[route(get "/api/v1/statuses/{}/quotes" => Status, paged)]
+            /// The statuses which quote a status of the user's. Only supported by
+            /// servers with quote posts (Mastodon 4.5+); others fail with
+            /// [`Error::Unsupported`] without a request being made.
+            pub async fn status_quotes(&self, id: &StatusId) -> Result<Page<Status>> {
+                let capabilities = self.capabilities().await?;
+                capabilities.require(capabilities.quotes(), "quote posts")?;
+                let url = self.route(format!("/api/v1/statuses/{id}/quotes"));
+                Page::from_request(self.clone(), self.client.get(&url)).await
+            }
+
+            #[route(post "/api/v1/statuses/{}/quotes/{}/revoke" => Status)]
+            /// Withdraw the approval for `quoting` to quote the user's status `id`.
+            /// Only supported by servers with quote posts (Mastodon 4.5+); others
+            /// fail with [`Error::Unsupported`] without a request being made.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            ///
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let mine = StatusId::new("1");
+            ///     let quotes = client.status_quotes(&mine).await.unwrap();
+            ///     for quote in quotes.initial_items {
+            ///         if quote.account.bot {
+            ///             client.revoke_quote(&mine, &quote.id).await.unwrap();
+            ///         }
+            ///     }
+            /// });
+            /// ```
+            pub async fn revoke_quote(&self, id: &StatusId, quoting: &StatusId) -> Result<Status> {
+                let capabilities = self.capabilities().await?;
+                capabilities.require(capabilities.quotes(), "quote posts")?;
+                self.post(self.route(format!("/api/v1/statuses/{id}/quotes/{quoting}/revoke")))
+                    .await
+            }
+
+            #[route(get "/api/v1/timelines/tag/{}" => Status, paged)]
+            /// Statuses using a hashtag, given with or without the leading `#`,
+            /// paginated and filtered by `options`, which can also ask for statuses
+            /// using other hashtags.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            ///
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let options = forms::timeline::TagOptions::builder()
+            ///         .any_of("coffee")
+            ///         .local(true)
+            ///         .build()
+            ///         .unwrap();
+            ///     let page = client.get_tagged_timeline("tea", options).await.unwrap();
+            /// });
+            /// ```
+            pub async fn get_tagged_timeline(
+                &self,
+                hashtag: &str,
+                options: forms::timeline::TagOptions,
+            ) -> Result<Page<Status>> {
+                let path = crate::timeline::Timeline::Hashtag(hashtag.to_string()).path();
+                let query = options.to_query_string();
+                let url = if query.is_empty() {
+                    self.route(path)
+                } else {
+                    self.route(format!("{path}?{query}"))
+                };
+                Page::from_request(self.clone(), self.client.get(&url)).await
+            }
+
+            /// Statuses from the members of one of the user's lists, paginated and
+            /// filtered by `options`.
+            pub async fn get_list_timeline_with_options(
+                &self,
+                id: &ListId,
+                options: forms::timeline::Options,
+            ) -> Result<Page<Status>> {
+                let timeline = crate::timeline::Timeline::List(id.clone());
+                self.timeline(&timeline, options).await
+            }
+
+            #[route(get "/api/v1/timelines/{}" => Status, paged)]
+            /// The first page of any timeline, paginated and filtered by `options`.
+            ///
+            /// ```no_run
+            /// use mastodon_async::{prelude::*, timeline::Timeline};
+            ///
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let options = forms::timeline::Options::builder()
+            ///         .only_media(true)
+            ///         .build()
+            ///         .unwrap();
+            ///     let page = client.timeline(&Timeline::Local, options).await.unwrap();
+            ///     for status in page.initial_items {
+            ///         println!("{}", status.content);
+            ///     }
+            /// });
+            /// ```
+            pub async fn timeline(
+                &self,
+                timeline: &crate::timeline::Timeline,
+                options: forms::timeline::Options,
+            ) -> Result<Page<Status>> {
+                let path = timeline.path();
+                let query = serde_urlencoded::to_string(&options)?;
+                let url = if query.is_empty() {
+                    self.route(path)
+                } else if path.contains('?') {
+                    self.route(format!("{path}&{query}"))
+                } else {
+                    self.route(format!("{path}?{query}"))
+                };
+                Page::from_request(self.clone(), self.client.get(&url)).await
+            }
+
+            #[route(get "/api/v1/streaming/{}" => Event)]
+            /// The events of any timeline from the streaming API, as they happen.
+            /// See [`Mastodon::stream_user`] and its siblings for examples.
+            pub async fn stream(
+                &self,
+                timeline: &crate::timeline::Timeline,
+            ) -> Result<impl TryStream<Ok = (Event, Mastodon), Error = Error> + Send + 'static> {
+                use crate::event_stream::event_stream;
+                let url = self.route(timeline.streaming_path());
+                let response = self
+                    .send(
+                        self.authenticated(self.client.get(&url))
+                            .header("Accept", "application/json"),
+                    )
+                    .await?;
+                debug!(
+                    status:serde = crate::helpers::log::Status::from(&response), url = &url,
+                    headers:serde = crate::helpers::log::Headers::from(&response);
+                    "received API response"
+                );
+                let status = response.status();
+                if status.is_success() {
+                    Ok(event_stream(response, url, self))
+                } else {
+                    let retry_after = RetryAfter::from_headers(response.headers());
+                    let response = response.json().await?;
+                    Err(Error::Api {
+                        status,
+                        response,
+                        retry_after,
+                    })
+                }
+            }
+
+            #[route(get "/api/v1/accounts/{}/statuses" => Status, paged)]
+            /// Get statuses of a single account by id. Optionally only with pictures
+            /// and or excluding replies.
+            ///
+            /// // Example
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            /// tokio_test::block_on(async {
+            ///     let data = Data::default();
+            ///     let client = Mastodon::from(data);
+            ///     let statuses = client.statuses(&AccountId::new("user-id"), Default::default()).await.unwrap();
+            /// });
+            /// ```
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            /// tokio_test::block_on(async {
+            ///     let data = Data::default();
+            ///     let client = Mastodon::from(data);
+            ///     let mut request = StatusesRequest::new();
+            ///     request.only_media();
+            ///     let statuses = client.statuses(&AccountId::new("user-id"), request).await.unwrap();
+            /// });
+            /// ```
+            pub async fn statuses<'a, 'b: 'a>(
+                &'b self,
+                id: &'b AccountId,
+                request: StatusesRequest<'a>,
+            ) -> Result<Page<Status>> {
+                let call_id = Uuid::new_v4();
+                let mut url = format!("{}/api/v1/accounts/{}/statuses", self.data.base, id);
+
+                url += request.to_query_string()?.as_str();
+
+                debug!(url = url, method = "get", call_id:? = call_id; "making API request");
+                let response = self.send(self.client.get(&url)).await?;
+
+                Page::new(self.clone(), response, call_id).await
+            }
+        }
+    }
+
+    /// The block the instance has placed on the given domain or one of its
+    /// parent domains, if any, including blocks whose domain the instance
+    /// obfuscated.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     if let Some(block) = client.is_domain_blocked("example.com").await.unwrap() {
+    ///         println!("blocked ({:?}): {:?}", block.severity, block.comment);
+    ///     }
+    /// });
+    /// ```
+    pub async fn is_domain_blocked(&self, domain: &str) -> Result<Option<DomainBlock>> {
+        // the server returns every block in a single page
+        let blocks = self.instance_domain_blocks().await?.initial_items;
+        Ok(blocks.into_iter().find(|block| block.matches(domain)))
+    }
+
+    /// Every status of an account, fetching pages of them as they're
+    /// consumed until there are none left.
+    ///
+    /// If the server reports that the rate limit has been used up, fetching
+    /// the next page waits until it resets. Unlike [`Page::items_iter`], the
+    /// stream ends with the first error encountered.
+    ///
+    /// ```no_run
+    /// use futures_util::TryStreamExt;
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let id = AccountId::new("user-id");
+    ///     client
+    ///         .all_statuses(&id, StatusesRequest::new())
+    ///         .try_for_each(|status| async move {
+    ///             println!("{}", status.content);
+    ///             Ok(())
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    pub fn all_statuses<'a>(
+        &'a self,
+        id: &'a AccountId,
+        options: StatusesRequest<'a>,
+    ) -> impl TryStream<Ok = Status, Error = Error> + 'a {
+        enum Pages<'a> {
+            First(StatusesRequest<'a>),
+            Next(Page<Status>),
+            Done,
+        }
+
+        try_unfold(Pages::First(options), move |pages| async move {
+            let (items, next) = match pages {
+                Pages::First(options) => {
+                    let mut page = self.statuses(id, options).await?;
+                    let items = std::mem::take(&mut page.initial_items);
+                    (items, page)
+                }
+                Pages::Next(mut page) => {
+                    if let Some(delay) = page.rate_limit.map(|it| it.delay()) {
+                        if !delay.is_zero() {
+                            debug!(delay:? = delay, call_id:? = page.call_id; "waiting for the rate limit to reset");
+                            tokio::time::sleep(delay).await;
+                        }
+                    }
+                    match page.next_page().await? {
+                        Some(items) => (items, page),
+                        None => return Ok::<_, Error>(None),
+                    }
+                }
+                Pages::Done => return Ok(None),
+            };
+            let next = if items.is_empty() || next.next.is_none() {
+                Pages::Done
+            } else {
+                Pages::Next(next)
+            };
+            Ok(Some((stream::iter(items.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
+
+    /// New statuses on a timeline, oldest first, found by checking it every
+    /// `interval`, for when the streaming API isn't available.
+    ///
+    /// Polling starts after `since_id`, or with the most recent statuses if
+    /// it's `None`. To resume later, pass the ID of the last status
+    /// received. When there are more new statuses than fit in one request,
+    /// the rest are fetched without waiting. Statuses already received are
+    /// never repeated.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use futures_util::TryStreamExt;
+    /// use mastodon_async::{prelude::*, timeline::Timeline};
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let interval = Duration::from_secs(60).into();
+    ///     client
+    ///         .poll_timeline(Timeline::Local, interval, None)
+    ///         .try_for_each(|status| async move {
+    ///             println!("{}", status.content);
+    ///             Ok(())
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    pub fn poll_timeline(
         &self,
-        changes: account::CredentialsBuilder,
-    ) -> Result<Account> {
-        let url = self.route("/api/v1/accounts/update_credentials");
-        let response = self
-            .client
-            .patch(&url)
-            .json(&changes.build()?)
-            .send()
+        timeline: crate::timeline::Timeline,
+        interval: PollingTime,
+        since_id: Option<StatusId>,
+    ) -> impl TryStream<Ok = Status, Error = Error> + '_ {
+        poll(
+            interval,
+            since_id,
+            |status: &Status| &status.id,
+            move |since_id| {
+                let timeline = timeline.clone();
+                async move {
+                    let mut options = forms::timeline::Options::builder();
+                    options.limit(POLL_LIMIT);
+                    if let Some(since_id) = since_id {
+                        options.min_id(since_id);
+                    }
+                    let page = self.timeline(&timeline, options.build()?).await?;
+                    Ok(page.initial_items)
+                }
+            },
+        )
+    }
+
+    /// New statuses on the home timeline, found by checking it every
+    /// `interval`. See [`Mastodon::poll_timeline`].
+    pub fn poll_home_timeline(
+        &self,
+        interval: PollingTime,
+        since_id: Option<StatusId>,
+    ) -> impl TryStream<Ok = Status, Error = Error> + '_ {
+        self.poll_timeline(crate::timeline::Timeline::Home, interval, since_id)
+    }
+
+    /// New notifications, oldest first, found by checking for them every
+    /// `interval`. See [`Mastodon::poll_timeline`].
+    pub fn poll_notifications(
+        &self,
+        interval: PollingTime,
+        since_id: Option<NotificationId>,
+    ) -> impl TryStream<Ok = Notification, Error = Error> + '_ {
+        poll(
+            interval,
+            since_id,
+            |notification: &Notification| &notification.id,
+            move |since_id| async move {
+                let mut options = forms::notification::Options::builder();
+                options.limit(POLL_LIMIT);
+                if let Some(since_id) = since_id {
+                    options.min_id(since_id);
+                }
+                let page = self.notifications_with_options(options.build()?).await?;
+                Ok(page.initial_items)
+            },
+        )
+    }
+
+    /// Page through an endpoint the crate doesn't wrap, such as one specific
+    /// to a fork. `path` is relative to the instance's base URL, e.g.
+    /// `/api/v1/fork/circles`, and `query` is sent as its query string.
+    ///
+    /// See also [`Page::from_request`], for requests which need more than a
+    /// query string.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let circles: Vec<List> = client
+    ///         .get_paged_custom("/api/v1/fork/circles", &[("limit", 40)])
+    ///         .await
+    ///         .unwrap()
+    ///         .fetch_all_bounded(200, 5)
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    pub async fn get_paged_custom<T: for<'de> serde::Deserialize<'de> + serde::Serialize>(
+        &self,
+        path: &str,
+        query: &impl serde::Serialize,
+    ) -> Result<Page<T>> {
+        let query = serde_urlencoded::to_string(query)?;
+        let url = if query.is_empty() {
+            self.route(path)
+        } else {
+            self.route(format!("{path}?{query}"))
+        };
+        Page::from_request(self.clone(), self.client.get(&url)).await
+    }
+
+    /// Pages of search results, starting with those for `options` and
+    /// fetching the next as they're consumed, until a page comes back empty.
+    ///
+    /// Mastodon only pages through results of a single type, so set
+    /// `search_type` on the options. Without it, only the first page is
+    /// returned.
+    ///
+    /// ```no_run
+    /// use futures_util::TryStreamExt;
+    /// use mastodon_async::{entities::forms::search::Type, prelude::*};
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let options = forms::search::Options::builder("rust")
+    ///         .search_type(Type::Hashtags)
+    ///         .build()
+    ///         .unwrap();
+    ///     client
+    ///         .search_pages(options)
+    ///         .try_for_each(|page| async move {
+    ///             for tag in page.hashtags {
+    ///                 println!("#{}", tag.name);
+    ///             }
+    ///             Ok(())
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    pub fn search_pages(
+        &self,
+        options: forms::search::Options,
+    ) -> impl TryStream<Ok = SearchResult, Error = Error> + '_ {
+        try_unfold(Some(options), move |options| async move {
+            let Some(options) = options else {
+                return Ok(None);
+            };
+            let results = self.search_with_options(options.clone()).await?;
+            let next = options.next_page(&results);
+            Ok(Some((results, next)))
+        })
+    }
+
+    /// A client for the admin API, if the authenticated account's role grants
+    /// any of the permissions it needs. Otherwise, [`Error::NotAdmin`] is
+    /// returned.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let admin = client.admin().await.unwrap();
+    ///     for report in admin.reports().await.unwrap().initial_items {
+    ///         println!("{}: {}", report.id, report.comment);
+    ///     }
+    /// });
+    /// ```
+    pub async fn admin(&self) -> Result<AdminClient> {
+        let me: CredentialAccount = self
+            .get(self.route("/api/v1/accounts/verify_credentials"))
             .await?;
+        AdminClient::new(self.clone(), me.role)
+    }
+
+    /// The statuses attached to a report, to triage it.
+    pub(crate) async fn admin_report_statuses(&self, id: &ReportId) -> Result<Vec<Status>> {
+        Ok(self.admin_report(id).await?.statuses)
+    }
+
+    /// Fetch an account along with its pinned statuses, up to `limit` of its
+    /// most recent statuses, and the client account's relationship to it.
+    ///
+    /// The four requests are made concurrently.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let overview = client.account_overview(&AccountId::new("user-id"), 5).await.unwrap();
+    ///     println!("{} has {} pinned posts", overview.account.acct, overview.pinned.len());
+    /// });
+    /// ```
+    pub async fn account_overview(&self, id: &AccountId, limit: usize) -> Result<AccountOverview> {
+        let mut pinned = StatusesRequest::new();
+        pinned.pinned();
+        let mut recent = StatusesRequest::new();
+        recent.limit(limit);
+        let ids = [id];
+        let (account, pinned, recent, relationships) = futures::try_join!(
+            self.get_account(id),
+            self.statuses(id, pinned),
+            self.statuses(id, recent),
+            self.relationships(&ids),
+        )?;
+        Ok(AccountOverview {
+            account,
+            pinned: pinned.initial_items,
+            recent: recent.initial_items,
+            relationship: relationships.initial_items.into_iter().next(),
+        })
+    }
+
+    /// The URL of the streaming API, as advertised by the server, falling
+    /// back to the instance's own domain.
+    pub async fn streaming_url(&self) -> Result<Url> {
+        #[derive(Deserialize, Serialize)]
+        struct InstanceUrls {
+            configuration: Configuration,
+        }
+        #[derive(Deserialize, Serialize)]
+        struct Configuration {
+            urls: Option<instance::configuration::Urls>,
+        }
+        let instance: InstanceUrls = self.get(self.route("/api/v2/instance")).await?;
+        if let Some(urls) = instance.configuration.urls {
+            return Ok(urls.streaming);
+        }
+        let mut url: Url = self.data.base.parse()?;
+        let scheme = if url.scheme() == "http" { "ws" } else { "wss" };
+        url.set_scheme(scheme)
+            .map_err(|_| crate::format_err!("can't stream from {url}"))?;
+        Ok(url)
+    }
+
+    #[cfg(feature = "websocket")]
+    with_route_metadata! {
+        hand_written as WEBSOCKET_ROUTES {
+            #[route(get "/api/v1/streaming" => Event)]
+            /// Open a WebSocket connection to the streaming API, subscribed to the
+            /// given timelines.
+            ///
+            /// Events from every subscribed timeline are multiplexed over the one
+            /// connection. The returned [`Subscriptions`](crate::event_stream::websocket::Subscriptions)
+            /// handle can be used to subscribe to or unsubscribe from timelines while
+            /// the stream is running.
+            ///
+            /// ```no_run
+            /// use futures_util::TryStreamExt;
+            /// use mastodon_async::{event_stream::websocket::Timeline, prelude::*};
+            ///
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let (subscriptions, stream) = client
+            ///         .stream_websocket(&[Timeline::User])
+            ///         .await
+            ///         .unwrap();
+            ///     subscriptions
+            ///         .subscribe(&Timeline::Hashtag { tag: "bots".to_string() })
+            ///         .unwrap();
+            ///     stream
+            ///         .try_for_each(|(event, _client)| async move {
+            ///             println!("{event:?}");
+            ///             Ok(())
+            ///         })
+            ///         .await
+            ///         .unwrap();
+            /// });
+            /// ```
+            pub async fn stream_websocket(
+                &self,
+                timelines: &[crate::event_stream::websocket::Timeline],
+            ) -> Result<(
+                crate::event_stream::websocket::Subscriptions,
+                impl TryStream<Ok = (Event, Mastodon), Error = Error> + Send + 'static,
+            )> {
+                use crate::event_stream::websocket::websocket_event_stream;
+                use reqwest::header::{HeaderValue, AUTHORIZATION};
+                use tokio_tungstenite::{connect_async, tungstenite::client::IntoClientRequest};
+
+                let mut url = self.streaming_url().await?;
+                url.set_path("/api/v1/streaming");
+                let location = url.to_string();
+                let mut request = location.as_str().into_client_request()?;
+                request.headers_mut().insert(
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("Bearer {}", self.data.token))
+                        .map_err(|_| crate::format_err!("invalid access token"))?,
+                );
+                debug!(url = location; "opening WebSocket connection");
+                let (socket, response) = connect_async(request).await?;
+                debug!(status:? = response.status(), url = location; "WebSocket connection opened");
+                let (subscriptions, stream) = websocket_event_stream(socket, location, self);
+                for timeline in timelines {
+                    subscriptions.subscribe(timeline)?;
+                }
+                Ok((subscriptions, stream))
+            }
+        }
+    }
+
+    with_route_metadata! {
+        hand_written as HAND_WRITTEN_ACCOUNT_ROUTES {
+            #[route(get "/api/v1/accounts/relationships" => Relationship, paged)]
+            /// Returns the client account's relationship to a list of other accounts.
+            /// Such as whether they follow them or vice versa.
+            pub async fn relationships(&self, ids: &[&AccountId]) -> Result<Page<Relationship>> {
+                let call_id = Uuid::new_v4();
+                let mut url = self.route("/api/v1/accounts/relationships?");
+
+                if ids.len() == 1 {
+                    url += "id=";
+                    url += ids[0].as_ref();
+                } else {
+                    for id in ids {
+                        url += "id[]=";
+                        url += id.as_ref();
+                        url += "&";
+                    }
+                    url.pop();
+                }
+
+                debug!(
+                    url = url, method = "get",
+                    call_id:? = call_id, account_ids:serde = ids;
+                    "making API request"
+                );
+                let response = self.send(self.client.get(&url)).await?;
+
+                Page::new(self.clone(), response, call_id).await
+            }
+
+            #[route(post "/api/v1/push/subscription" => Subscription)]
+            /// Add a push notifications subscription
+            pub async fn add_push_subscription(
+                &self,
+                request: &AddPushRequest,
+            ) -> Result<Subscription> {
+                let call_id = Uuid::new_v4();
+                let request = request.build();
+                let url = &self.route("/api/v1/push/subscription");
+                debug!(
+                    url = url, method = stringify!($method),
+                    call_id:? = call_id, post_body:serde = request;
+                    "making API request"
+                );
+                let response = self.send(self.client.post(url).json(&request)).await?;
+
+                read_response(response).await
+            }
+
+            #[route(post "/api/v1/push/subscription" => Subscription)]
+            /// Update the `data` portion of the push subscription associated with this
+            /// access token
+            pub async fn update_push_data(
+                &self,
+                request: &UpdatePushRequest,
+            ) -> Result<Subscription> {
+                let call_id = Uuid::new_v4();
+                let request = request.build();
+                let url = &self.route("/api/v1/push/subscription");
+                debug!(
+                    url = url, method = stringify!($method),
+                    call_id:? = call_id, post_body:serde = request;
+                    "making API request"
+                );
+                let response = self.send(self.client.post(url).json(&request)).await?;
+
+                read_response(response).await
+            }
+
+            /// Remove several accounts from the follow suggestions, one at a time, so
+            /// the requests are paced if a [`Pacer`] has been set with
+            /// [`Mastodon::with_pacer`]. Stops at the first failure.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let staff_picks: Vec<AccountId> = client
+            ///         .follow_suggestions()
+            ///         .await
+            ///         .unwrap()
+            ///         .into_iter()
+            ///         .filter(|it| it.sources.contains(&account::SuggestionReason::Featured))
+            ///         .map(|it| it.account.id)
+            ///         .collect();
+            ///     client.dismiss_suggestions(&staff_picks).await.unwrap();
+            /// });
+            /// ```
+            pub async fn dismiss_suggestions(&self, ids: &[AccountId]) -> Result<()> {
+                for id in ids {
+                    self.delete_from_suggestions(id).await?;
+                }
+                Ok(())
+            }
+
+            /// Equivalent to `get /api/v1/accounts/search`
+            #[deprecated(note = "use `search_accounts_with_options`, which supports every option")]
+            pub async fn search_accounts(
+                &self,
+                q: &str,
+                limit: Option<u64>,
+                following: bool,
+            ) -> Result<Page<Account>> {
+                let mut search = forms::account::Search::builder(q);
+                search.following(following);
+                if let Some(limit) = limit {
+                    search.limit(limit);
+                }
+                self.search_accounts_with_options(search.build()?).await
+            }
+
+            /// Follow the account with the given webfinger address, such as
+            /// `@user@instance.tld`, or `user` for a local account.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let relationship = client.follow_by_acct("@Gargron@mastodon.social").await.unwrap();
+            ///     assert!(relationship.following || relationship.requested);
+            /// });
+            /// ```
+            pub async fn follow_by_acct(&self, acct: &str) -> Result<Relationship> {
+                let account = self.lookup_account(acct.trim_start_matches('@')).await?;
+                self.follow(&account.id).await
+            }
+
+            #[route(post "/api/v1/accounts/{}/follow" => Relationship)]
+            /// Follow an account, choosing whether to see its boosts, be notified
+            /// of its statuses, and which languages to see them in.
+            ///
+            /// Calls [`CacheSync::followed`](crate::cache_sync::CacheSync::followed)
+            /// on success, if set.
+            pub async fn follow_with_options(
+                &self,
+                id: &AccountId,
+                options: forms::account::FollowOptions,
+            ) -> Result<Relationship> {
+                let url = self.route(format!("/api/v1/accounts/{id}/follow"));
+                debug!(url = url, method = "post", body:serde = options; "making API request");
+                let request = self.authenticated(self.client.post(&url)).json(&options);
+                let response = self.send(request).await?;
+                let result: Relationship = read_response(response).await?;
+                if let Some(cache_sync) = &self.cache_sync {
+                    cache_sync.followed(&result);
+                }
+                Ok(result)
+            }
+
+            /// Change how an account which is already followed is followed. Only the
+            /// options which are set change; to send only those which differ from
+            /// the current relationship, use [`Relationship::diff`].
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            /// use isolang::Language;
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let options = forms::account::FollowOptions::builder()
+            ///         .notify(true)
+            ///         .languages(vec![Language::Eng])
+            ///         .build()
+            ///         .unwrap();
+            ///     let relationship = client.update_follow(&AccountId::new("42"), options).await.unwrap();
+            ///     assert!(relationship.notifying);
+            /// });
+            /// ```
+            pub async fn update_follow(
+                &self,
+                id: &AccountId,
+                options: forms::account::FollowOptions,
+            ) -> Result<Relationship> {
+                self.follow_with_options(id, options).await
+            }
+
+            /// Unfollow the account with the given webfinger address, such as
+            /// `@user@instance.tld`, or `user` for a local account.
+            pub async fn unfollow_by_acct(&self, acct: &str) -> Result<Relationship> {
+                let account = self.lookup_account(acct.trim_start_matches('@')).await?;
+                self.unfollow(&account.id).await
+            }
+
+            /// How many accounts have asked to follow the user and are waiting for
+            /// an answer.
+            pub async fn follow_request_count(&self) -> Result<u64> {
+                let account = self.verify_credentials().await?;
+                let source = account
+                    .source
+                    .ok_or_else(|| crate::format_err!("no source in the user's credentials"))?;
+                Ok(source.follow_requests_count)
+            }
 
-        read_response(response).await
-    }
+            /// Accept every pending follow request, going through all the pages of
+            /// them, one request at a time, so the requests are paced if a [`Pacer`]
+            /// has been set with [`Mastodon::with_pacer`]. Stops at the first
+            /// failure.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     if client.follow_request_count().await.unwrap() > 0 {
+            ///         let accepted = client.accept_all_follow_requests().await.unwrap();
+            ///         println!("{} new followers", accepted.len());
+            ///     }
+            /// });
+            /// ```
+            pub async fn accept_all_follow_requests(&self) -> Result<Vec<Relationship>> {
+                let requests = self
+                    .follow_requests()
+                    .await?
+                    .fetch_all_bounded(usize::MAX, usize::MAX)
+                    .await?;
+                let mut relationships = Vec::with_capacity(requests.len());
+                for account in requests {
+                    relationships.push(self.authorize_follow_request(&account.id).await?);
+                }
+                Ok(relationships)
+            }
 
-    /// Post a new status to the account.
-    pub async fn new_status(&self, status: NewStatus) -> Result<Status> {
-        let url = self.route("/api/v1/statuses");
-        let response = self
-            .authenticated(self.client.post(&url))
-            .json(&status)
-            .send()
-            .await?;
-        debug!(
-            status:serde = crate::helpers::log::Status::from(&response), url = url,
-            headers:serde = crate::helpers::log::Headers::from(&response);
-            "received API response"
-        );
-        read_response(response).await
-    }
+            /// Reject the pending follow request from the account with the given
+            /// webfinger address, such as `@user@instance.tld`, or `user` for a local
+            /// account, looking through the pages of follow requests for it.
+            pub async fn reject_follow_request_by_acct(&self, acct: &str) -> Result<Relationship> {
+                let id = self.find_follow_request(acct).await?;
+                self.reject_follow_request(&id).await
+            }
 
-    /// Get timeline filtered by a hashtag(eg. `#coffee`) either locally or
-    /// federated.
-    pub async fn get_tagged_timeline(&self, hashtag: String, local: bool) -> Result<Vec<Status>> {
-        let base = "/api/v1/timelines/tag/";
-        let url = if local {
-            self.route(format!("{base}{hashtag}?local=1"))
-        } else {
-            self.route(format!("{base}{hashtag}"))
-        };
+            /// The ID of the account with the given address among those with a
+            /// pending follow request.
+            async fn find_follow_request(&self, acct: &str) -> Result<AccountId> {
+                let acct = acct.trim_start_matches('@');
+                let mut page = self.follow_requests().await?;
+                let mut accounts = std::mem::take(&mut page.initial_items);
+                loop {
+                    if let Some(account) = accounts
+                        .into_iter()
+                        .find(|account| account.acct.eq_ignore_ascii_case(acct))
+                    {
+                        return Ok(account.id);
+                    }
+                    match page.next_page().await? {
+                        Some(next) if !next.is_empty() => accounts = next,
+                        _ => {
+                            return Err(crate::format_err!(
+                                "no pending follow request from {acct}"
+                            ))
+                        }
+                    }
+                }
+            }
 
-        self.get(url).await
-    }
+            #[route(get "/api/pleroma/aliases" => Vec<String>)]
+            /// The addresses of the accounts the user has declared as aliases, which
+            /// are allowed to move to this account. Only supported by servers which
+            /// allow it through the API (Pleroma and Akkoma); others fail with
+            /// [`Error::Unsupported`] without a request being made.
+            pub async fn get_account_aliases(&self) -> Result<Vec<String>> {
+                #[derive(Deserialize, Serialize)]
+                struct Aliases {
+                    aliases: Vec<String>,
+                }
+                let capabilities = self.capabilities().await?;
+                capabilities.require(capabilities.account_migration(), "account aliases")?;
+                let aliases: Aliases = self.get(self.route("/api/pleroma/aliases")).await?;
+                Ok(aliases.aliases)
+            }
 
-    /// Get statuses of a single account by id. Optionally only with pictures
-    /// and or excluding replies.
-    ///
-    /// // Example
-    ///
-    /// ```no_run
-    /// use mastodon_async::prelude::*;
-    /// tokio_test::block_on(async {
-    ///     let data = Data::default();
-    ///     let client = Mastodon::from(data);
-    ///     let statuses = client.statuses(&AccountId::new("user-id"), Default::default()).await.unwrap();
-    /// });
-    /// ```
-    ///
-    /// ```no_run
-    /// use mastodon_async::prelude::*;
-    /// tokio_test::block_on(async {
-    ///     let data = Data::default();
-    ///     let client = Mastodon::from(data);
-    ///     let mut request = StatusesRequest::new();
-    ///     request.only_media();
-    ///     let statuses = client.statuses(&AccountId::new("user-id"), request).await.unwrap();
-    /// });
-    /// ```
-    pub async fn statuses<'a, 'b: 'a>(
-        &'b self,
-        id: &'b AccountId,
-        request: StatusesRequest<'a>,
-    ) -> Result<Page<Status>> {
-        let call_id = Uuid::new_v4();
-        let mut url = format!("{}/api/v1/accounts/{}/statuses", self.data.base, id);
+            #[route(put "/api/pleroma/aliases" => Vec<String>)]
+            /// Declare exactly the given accounts as aliases, so that they can move
+            /// to this account, adding and removing aliases as needed. Returns the
+            /// aliases afterwards. Only supported by servers which allow it through
+            /// the API (Pleroma and Akkoma); others fail with [`Error::Unsupported`]
+            /// without a request being made.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let aliases = client.set_account_aliases(&["me@old.example"]).await.unwrap();
+            ///     assert_eq!(aliases, ["me@old.example"]);
+            /// });
+            /// ```
+            pub async fn set_account_aliases(&self, accts: &[&str]) -> Result<Vec<String>> {
+                let wanted: Vec<_> = accts.iter().map(|it| it.trim_start_matches('@')).collect();
+                let current = self.get_account_aliases().await?;
+                for alias in current.iter().filter(|it| !wanted.contains(&it.as_str())) {
+                    self.send_account_alias(reqwest::Method::DELETE, alias)
+                        .await?;
+                }
+                for alias in wanted.iter().filter(|it| !current.iter().any(|a| a == *it)) {
+                    self.send_account_alias(reqwest::Method::PUT, alias).await?;
+                }
+                self.get_account_aliases().await
+            }
 
-        url += request.to_query_string()?.as_str();
+            /// Add (with `PUT`) or remove (with `DELETE`) an account alias.
+            async fn send_account_alias(&self, method: reqwest::Method, alias: &str) -> Result<()> {
+                let url = self.route("/api/pleroma/aliases");
+                let body = json!({ "alias": alias });
+                debug!(
+                    url = url, method = method.as_str(), body:serde = body;
+                    "making API request"
+                );
+                let request = self
+                    .authenticated(self.client.request(method, &url))
+                    .json(&body);
+                let response = self.send(request).await?;
+                let _: serde_json::Value = read_response(response).await?;
+                Ok(())
+            }
 
-        debug!(url = url, method = stringify!($method), call_id:? = call_id; "making API request");
-        let response = self.client.get(&url).send().await?;
+            #[route(post "/api/pleroma/move_account" => ())]
+            /// Move the user's followers to the account `target_acct`, which must
+            /// have declared this account as an alias first, confirming the move
+            /// with the user's password. The account is left redirecting to the new
+            /// one; see [`Account::moved_to`]. Only supported by servers which allow
+            /// it through the API (Pleroma and Akkoma); others fail with
+            /// [`Error::Unsupported`] without a request being made.
+            pub async fn move_account(&self, target_acct: &str, password: &str) -> Result<()> {
+                let capabilities = self.capabilities().await?;
+                capabilities.require(capabilities.account_migration(), "account migration")?;
+                let url = self.route("/api/pleroma/move_account");
+                let body = json!({
+                    "target_account": target_acct.trim_start_matches('@'),
+                    "password": password,
+                });
+                // the body isn't logged, as it holds the password
+                debug!(url = url, method = "post"; "making API request");
+                let response = self
+                    .send(self.authenticated(self.client.post(&url)).json(&body))
+                    .await?;
+                let _: serde_json::Value = read_response(response).await?;
+                Ok(())
+            }
 
-        Page::new(self.clone(), response, call_id).await
-    }
+            #[route(post "/api/v1/statuses/{}/react/{}" => Status)]
+            /// React to a status with an emoji: a unicode emoji, or the shortcode of
+            /// a custom one, with or without the surrounding colons. Returns the
+            /// status with its reactions updated, in [`Status::reactions`], except
+            /// on Pleroma and Akkoma, which list them in `pleroma.emoji_reactions`
+            /// instead; that is only deserialized with the `pleroma` feature. Only
+            /// supported by servers with status reactions, such as glitch-soc,
+            /// Pleroma, Akkoma and Firefish; others fail with [`Error::Unsupported`]
+            /// without a request being made.
+            ///
+            /// ```no_run
+            /// use mastodon_async::prelude::*;
+            /// tokio_test::block_on(async {
+            ///     let client = Mastodon::from(Data::default());
+            ///     let status = client.react_to_status(&StatusId::new("1"), ":blobcat:").await.unwrap();
+            ///     let reaction = status.reactions.iter().find(|it| it.name == "blobcat").unwrap();
+            ///     assert_eq!(reaction.me, Some(true));
+            /// });
+            /// ```
+            pub async fn react_to_status(&self, id: &StatusId, name: &str) -> Result<Status> {
+                self.send_status_reaction(id, name, true).await
+            }
 
-    /// Returns the client account's relationship to a list of other accounts.
-    /// Such as whether they follow them or vice versa.
-    pub async fn relationships(&self, ids: &[&AccountId]) -> Result<Page<Relationship>> {
-        let call_id = Uuid::new_v4();
-        let mut url = self.route("/api/v1/accounts/relationships?");
+            #[route(post "/api/v1/statuses/{}/unreact/{}" => Status)]
+            /// Take back a reaction to a status. See [`Mastodon::react_to_status`].
+            pub async fn unreact_to_status(&self, id: &StatusId, name: &str) -> Result<Status> {
+                self.send_status_reaction(id, name, false).await
+            }
 
-        if ids.len() == 1 {
-            url += "id=";
-            url += ids[0].as_ref();
-        } else {
-            for id in ids {
-                url += "id[]=";
-                url += id.as_ref();
-                url += "&";
+            /// Add or remove a reaction to a status, with whichever API the server
+            /// has.
+            async fn send_status_reaction(
+                &self,
+                id: &StatusId,
+                name: &str,
+                add: bool,
+            ) -> Result<Status> {
+                let capabilities = self.capabilities().await?;
+                capabilities.require(capabilities.status_reactions(), "status reactions")?;
+                let (method, path) = status_reaction_route(&capabilities, id, name, add);
+                let url = self.route(path);
+                debug!(url = url, method = method.as_str(); "making API request");
+                let response = self
+                    .send(self.authenticated(self.client.request(method, &url)))
+                    .await?;
+                read_response(response).await
             }
-            url.pop();
-        }
 
-        debug!(
-            url = url, method = stringify!($method),
-            call_id:? = call_id, account_ids:serde = ids;
-            "making API request"
-        );
-        let response = self.client.get(&url).send().await?;
+            #[route(put "/api/v1/announcements/{}/reactions/{}" => ())]
+            /// React to an announcement with an emoji: a unicode emoji, or the
+            /// shortcode of a custom one, with or without the surrounding colons.
+            pub async fn react_to_announcement(
+                &self,
+                id: &AnnouncementId,
+                name: &str,
+            ) -> Result<()> {
+                self.send_announcement_reaction(reqwest::Method::PUT, id, name)
+                    .await
+            }
 
-        Page::new(self.clone(), response, call_id).await
-    }
+            #[route(delete "/api/v1/announcements/{}/reactions/{}" => ())]
+            /// Take back a reaction to an announcement.
+            pub async fn unreact_to_announcement(
+                &self,
+                id: &AnnouncementId,
+                name: &str,
+            ) -> Result<()> {
+                self.send_announcement_reaction(reqwest::Method::DELETE, id, name)
+                    .await
+            }
 
-    /// Add a push notifications subscription
-    pub async fn add_push_subscription(&self, request: &AddPushRequest) -> Result<Subscription> {
-        let call_id = Uuid::new_v4();
-        let request = request.build();
-        let url = &self.route("/api/v1/push/subscription");
-        debug!(
-            url = url, method = stringify!($method),
-            call_id:? = call_id, post_body:serde = request;
-            "making API request"
-        );
-        let response = self.client.post(url).json(&request).send().await?;
+            /// Add (with `PUT`) or remove (with `DELETE`) a reaction to an
+            /// announcement.
+            async fn send_announcement_reaction(
+                &self,
+                method: reqwest::Method,
+                id: &AnnouncementId,
+                name: &str,
+            ) -> Result<()> {
+                let name = encode_reaction(name);
+                let url = self.route(format!("/api/v1/announcements/{id}/reactions/{name}"));
+                debug!(url = url, method = method.as_str(); "making API request");
+                let response = self
+                    .send(self.authenticated(self.client.request(method, &url)))
+                    .await?;
+                let _: serde_json::Value = read_response(response).await?;
+                Ok(())
+            }
 
-        read_response(response).await
-    }
+            /// Get all accounts that follow the authenticated user
+            pub async fn follows_me(&self) -> Result<Page<Account>> {
+                let me = self.verify_credentials().await?;
+                self.followers(&me.id).await
+            }
 
-    /// Update the `data` portion of the push subscription associated with this
-    /// access token
-    pub async fn update_push_data(&self, request: &UpdatePushRequest) -> Result<Subscription> {
-        let call_id = Uuid::new_v4();
-        let request = request.build();
-        let url = &self.route("/api/v1/push/subscription");
-        debug!(
-            url = url, method = stringify!($method),
-            call_id:? = call_id, post_body:serde = request;
-            "making API request"
-        );
-        let response = self.client.post(url).json(&request).send().await?;
+            /// Get all accounts that the authenticated user follows
+            pub async fn followed_by_me(&self) -> Result<Page<Account>> {
+                let me = self.verify_credentials().await?;
+                self.following(&me.id).await
+            }
 
-        read_response(response).await
-    }
+            #[route(get "/api/v1/markers" => Markers)]
+            /// Get the saved read positions in the home timeline and notifications.
+            pub async fn markers(&self) -> Result<Markers> {
+                self.get(self.route(
+                    "/api/v1/markers?timeline%5B%5D=home&timeline%5B%5D=notifications",
+                ))
+                .await
+            }
 
-    /// Get all accounts that follow the authenticated user
-    pub async fn follows_me(&self) -> Result<Page<Account>> {
-        let me = self.verify_credentials().await?;
-        self.followers(&me.id).await
+            #[route(post "/api/v1/markers" => Marker<NotificationId>)]
+            /// Save the given notification as the most recently read one.
+            pub async fn mark_notifications_read_up_to(
+                &self,
+                id: &NotificationId,
+            ) -> Result<Marker<NotificationId>> {
+                let url = self.route("/api/v1/markers");
+                let body = json!({ "notifications": { "last_read_id": id } });
+                debug!(url = url, method = "post", body:serde = body; "making API request");
+                let request = self.authenticated(self.client.post(&url)).json(&body);
+                let response = self.send(request).await?;
+                let markers: Markers = read_response(response).await?;
+                markers
+                    .notifications
+                    .ok_or_else(|| crate::format_err!("no notifications marker in response"))
+            }
+        }
     }
 
-    /// Get all accounts that the authenticated user follows
-    pub async fn followed_by_me(&self) -> Result<Page<Account>> {
-        let me = self.verify_credentials().await?;
-        self.following(&me.id).await
+    /// Count the notifications received since the one saved as most recently
+    /// read, counting no further than `max`. If no notification has been
+    /// marked as read, all notifications are counted.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     match client.unread_notification_count(100).await.unwrap() {
+    ///         0 => {}
+    ///         100 => println!("99+ unread notifications"),
+    ///         count => println!("{count} unread notifications"),
+    ///     }
+    /// });
+    /// ```
+    pub async fn unread_notification_count(&self, max: usize) -> Result<usize> {
+        // the most notifications the server will return per page.
+        const PAGE_LIMIT: u64 = 80;
+
+        let mut options = forms::notification::Options::builder();
+        options.limit(PAGE_LIMIT);
+        let Some(marker) = self.markers().await?.notifications else {
+            let options = options.build()?;
+            let page = self.notifications_with_options(options).await?;
+            return Ok(page.fetch_all_bounded(max, usize::MAX).await?.len());
+        };
+        // min_id returns the page immediately after the marker, and each
+        // previous page is newer still.
+        let options = options.min_id(marker.last_read_id).build()?;
+        let mut page = self.notifications_with_options(options).await?;
+        let mut count = page.initial_items.len();
+        while count < max {
+            match page.prev_page().await? {
+                Some(notifications) if !notifications.is_empty() => count += notifications.len(),
+                _ => break,
+            }
+        }
+        Ok(count.min(max))
     }
 
     /// Wait for the media to be done processing and return it with the URL.
@@ -335,7 +2195,8 @@ impl Mastodon {
     /// use mastodon_async::prelude::*;
     /// let mastodon = Mastodon::from(Data::default());
     /// tokio_test::block_on(async {
-    ///     let attachment = mastodon.media("/path/to/some/file.jpg", None).await.expect("upload");
+    ///     let upload = forms::media::Upload::builder("/path/to/some/file.jpg").build().expect("form");
+    ///     let attachment = mastodon.media(upload).await.expect("upload");
     ///     let attachment = mastodon.wait_for_processing(attachment, Default::default()).await.expect("processing");
     ///     println!("{}", attachment.url);
     /// });
@@ -347,7 +2208,8 @@ impl Mastodon {
     /// use std::time::Duration;
     /// let mastodon = Mastodon::from(Data::default());
     /// tokio_test::block_on(async {
-    ///     let attachment = mastodon.media("/path/to/some/file.jpg", None).await.expect("upload");
+    ///     let upload = forms::media::Upload::builder("/path/to/some/file.jpg").build().expect("form");
+    ///     let attachment = mastodon.media(upload).await.expect("upload");
     ///     let attachment = mastodon.wait_for_processing(
     ///         attachment,
     ///         Duration::from_secs(1).into(),
@@ -385,29 +2247,214 @@ impl Mastodon {
         request.bearer_auth(&self.data.token)
     }
 
-    /// Return a part for a multipart form submission from a file, including
-    /// the name of the file.
-    fn get_form_part(path: impl AsRef<Path>) -> Result<Part> {
-        use std::io::Read;
-
-        let path = path.as_ref();
+    /// Send the request, waiting for the rate limiter first if there is one,
+    /// and take note of the rate limit reported in the response. Transient
+    /// failures are retried if a retry policy has been set.
+    ///
+    /// Unless the request already has a timeout, it gets the one
+    /// [`Timeouts`] gives requests of its kind.
+    pub(crate) async fn send(&self, mut request: RequestBuilder) -> Result<Response> {
+        if self.timeouts.read.is_some() || self.timeouts.upload.is_some() {
+            let (client, built) = request.build_split();
+            let mut built = built?;
+            if built.timeout().is_none() {
+                *built.timeout_mut() = self
+                    .timeouts
+                    .for_request(built.method().as_str(), built.url().path());
+            }
+            request = RequestBuilder::from_parts(client, built);
+        }
+        if let Some(granted) = &self.granted_scopes {
+            let (client, built) = request.build_split();
+            let built = built?;
+            let required = routes::scope_for(built.method().as_str(), built.url().path());
+            if let Some(required) = required {
+                if !routes::is_granted(granted, required, built.url().path()) {
+                    return Err(Error::MissingScope {
+                        required,
+                        method: built.method().to_string(),
+                        path: built.url().path().to_string(),
+                    });
+                }
+            }
+            request = RequestBuilder::from_parts(client, built);
+        }
+        let Some(credentials) = &self.credentials else {
+            return self.send_with_retries(request).await;
+        };
+        let token = self.refresh_credentials(credentials, None).await?;
+        let retry = request.try_clone();
+        let response = self
+            .send_with_retries(reauthenticate(request, &token)?)
+            .await?;
+        match retry {
+            Some(retry) if response.status() == reqwest::StatusCode::UNAUTHORIZED => {
+                match self.refresh_credentials(credentials, Some(&token)).await {
+                    Ok(refreshed) if refreshed != token => {
+                        self.send_with_retries(reauthenticate(retry, &refreshed)?)
+                            .await
+                    }
+                    _ => Ok(response),
+                }
+            }
+            _ => Ok(response),
+        }
+    }
 
-        match std::fs::File::open(path) {
-            Ok(mut file) => {
-                let mut data = if let Ok(metadata) = file.metadata() {
-                    Vec::with_capacity(metadata.len().try_into()?)
-                } else {
-                    vec![]
-                };
-                file.read_to_end(&mut data)?;
-                // TODO extract filename, error on dirs, etc.
-                Ok(Part::bytes(data).file_name(Cow::Owned(path.to_string_lossy().to_string())))
+    /// Send the request, retrying transient failures if a retry policy has
+    /// been set.
+    async fn send_with_retries(&self, mut request: RequestBuilder) -> Result<Response> {
+        let mut idempotency_key = None;
+        if self.retry.is_some() {
+            (request, idempotency_key) = with_idempotency_key(request)?;
+        }
+        let family = if self.rate_limiter.is_some() {
+            let (client, built) = request.build_split();
+            let built = built?;
+            let family = EndpointFamily::of(built.method().as_str(), built.url().path());
+            request = RequestBuilder::from_parts(client, built);
+            family
+        } else {
+            EndpointFamily::General
+        };
+        let mut failures = 0;
+        loop {
+            // requests with a streamed body can't be cloned, and so are only
+            // attempted once.
+            let retry = self
+                .retry
+                .and_then(|backoff| Some((backoff, request.try_clone()?)));
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire(family).await;
+            }
+            let result = self.execute(request).await;
+            if let Ok(response) = &result {
+                self.observe(response, family);
+            }
+            let Some((backoff, next)) = retry else {
+                return result;
+            };
+            let retry_after = match &result {
+                Ok(response) if is_transient(response.status()) => {
+                    RetryAfter::from_headers(response.headers())
+                }
+                Err(Error::Http(err)) if err.is_connect() || err.is_timeout() => None,
+                _ => return result,
+            };
+            failures += 1;
+            if backoff.exhausted(failures) {
+                return result;
             }
-            Err(err) => {
-                error!(path:? = path, error:? = err; "error reading file contents for multipart form");
-                Err(err.into())
+            let delay = retry_after.map_or_else(|| backoff.delay(failures - 1), |it| it.duration());
+            match &result {
+                Ok(response) => warn!(
+                    status:? = response.status(), url = response.url().as_str(),
+                    failures = failures, delay:? = delay, idempotency_key:? = idempotency_key;
+                    "retrying request"
+                ),
+                Err(err) => warn!(
+                    err:? = err, failures = failures, delay:? = delay,
+                    idempotency_key:? = idempotency_key;
+                    "retrying request"
+                ),
             }
+            tokio::time::sleep(delay).await;
+            request = next;
+        }
+    }
+
+    /// Build the request and hand it to the client's [`HttpSend`], logging
+    /// how it went and telling the client's [`Metrics`], if any.
+    async fn execute(&self, request: RequestBuilder) -> Result<Response> {
+        let (client, request) = request.build_split();
+        let request = request?;
+        let method = request.method().clone();
+        let endpoint = routes::endpoint_label(request.url().path());
+        let started = std::time::Instant::now();
+        let result = self.http_send.send(&client, request).await;
+        let latency = started.elapsed();
+        let status = result.as_ref().ok().map(Response::status);
+        debug!(
+            method = method.as_str(), endpoint = endpoint, status:? = status,
+            latency:? = latency;
+            "API request finished"
+        );
+        if let Some(metrics) = &self.metrics {
+            metrics.record(&RequestMetrics {
+                method,
+                endpoint,
+                status,
+                latency,
+            });
+        }
+        result
+    }
+
+    /// The current access token of an auto-refreshing client, after
+    /// refreshing it if it expires soon, or if it is `rejected`.
+    async fn refresh_credentials(
+        &self,
+        credentials: &tokio::sync::Mutex<Data>,
+        rejected: Option<&str>,
+    ) -> Result<String> {
+        let mut data = credentials.lock().await;
+        let refresh = match rejected {
+            // another request may have refreshed it in the meantime
+            Some(rejected) => data.token == rejected,
+            None => data
+                .expires_at
+                .is_some_and(|expires_at| expires_at - OffsetDateTime::now_utc() < REFRESH_MARGIN),
+        };
+        if refresh && data.refresh_token.is_some() {
+            self.request_token_refresh(&data).await?.update(&mut data);
+        }
+        Ok(data.token.to_string())
+    }
+
+    async fn request_token_refresh(&self, data: &Data) -> Result<AccessToken> {
+        let refresh_token = data
+            .refresh_token
+            .as_deref()
+            .ok_or(Error::RefreshTokenRequired)?;
+        let url = self.route("/oauth/token");
+        debug!(url = url; "refreshing access token");
+        let form = forms::oauth::TokenRequest::refresh_token(
+            data.client_id.as_ref(),
+            data.client_secret.as_ref(),
+            refresh_token,
+        );
+        let request = self.client.post(&url).form(&form);
+        let response = self.execute(request).await?;
+        debug!(
+            status:serde = crate::helpers::log::Status::from(&response), url = url,
+            headers:serde = crate::helpers::log::Headers::from(&response);
+            "received API response"
+        );
+        read_response(response).await
+    }
+
+    fn observe(&self, response: &Response, family: EndpointFamily) {
+        let headers = response.headers();
+        if let Some(rate_limit) = RateLimit::from_headers(headers) {
+            trace!(rate_limit:? = rate_limit; "received rate limit");
+            *self
+                .rate_limit
+                .lock()
+                .unwrap_or_else(|err| err.into_inner()) = Some(rate_limit);
         }
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.observe(family, headers);
+        }
+    }
+
+    /// Return a part for a multipart form submission from a file, including
+    /// the name of the file.
+    async fn get_form_part(path: impl AsRef<Path>) -> Result<Part> {
+        let path = path.as_ref();
+        upload::file_part(path, |_| {}).await.map_err(|err| {
+            error!(path:? = path, error:? = err; "error reading file contents for multipart form");
+            err
+        })
     }
 }
 
@@ -416,17 +2463,38 @@ impl MastodonUnauthenticated {
 
     /// Create a new client for unauthenticated requests to a given Mastodon
     /// instance.
+    ///
+    /// `base` may be a full URL such as `https://mastodon.social`, or a bare
+    /// domain, in which case `https` is assumed, or `http` for a `.onion`
+    /// address.
+    ///
+    /// ## Errors
+    /// If `base` isn't a valid URL, isn't `http` or `https`, or has no host,
+    /// or is plain `http` to a host other than an onion service or this
+    /// machine; see [`HttpConfig::allow_plaintext`] to allow it.
     pub fn new(base: impl AsRef<str>) -> Result<MastodonUnauthenticated> {
-        let base = base.as_ref();
-        let base = if base.starts_with("https://") {
-            base.to_string()
-        } else {
-            format!("https://{}", base.trim_start_matches("http://"))
-        };
-        trace!(base = base; "creating new mastodon client");
+        let base = parse_base(base.as_ref(), false)?;
+        trace!(base = base.as_str(); "creating new mastodon client");
         Ok(MastodonUnauthenticated {
             client: Client::new(),
-            base: Url::parse(&base)?,
+            base,
+        })
+    }
+
+    /// Create a new client for unauthenticated requests to a given Mastodon
+    /// instance, whose HTTP client is set up with `config`.
+    ///
+    /// ## Errors
+    /// As for [`MastodonUnauthenticated::new`], or if the HTTP client can't
+    /// be built. See [`HttpConfig::client`].
+    pub fn new_with_config(
+        base: impl AsRef<str>,
+        config: &HttpConfig,
+    ) -> Result<MastodonUnauthenticated> {
+        let base = parse_base(base.as_ref(), config.allow_plaintext)?;
+        Ok(MastodonUnauthenticated {
+            client: config.client()?,
+            base,
         })
     }
 
@@ -436,24 +2504,19 @@ impl MastodonUnauthenticated {
 
     /// GET /api/v1/statuses/:id
     pub async fn get_status(&self, id: &StatusId) -> Result<Status> {
-        let route = self.route("/api/v1/statuses")?;
-        let route = route.join(id.as_ref())?;
+        let route = self.route(&format!("/api/v1/statuses/{id}"))?;
         self.get(route.as_str()).await
     }
 
     /// GET /api/v1/statuses/:id/context
     pub async fn get_context(&self, id: &StatusId) -> Result<Context> {
-        let route = self.route("/api/v1/statuses")?;
-        let route = route.join(id.as_ref())?;
-        let route = route.join("context")?;
+        let route = self.route(&format!("/api/v1/statuses/{id}/context"))?;
         self.get(route.as_str()).await
     }
 
     /// GET /api/v1/statuses/:id/card
     pub async fn get_card(&self, id: &StatusId) -> Result<Card> {
-        let route = self.route("/api/v1/statuses")?;
-        let route = route.join(id.as_ref())?;
-        let route = route.join("card")?;
+        let route = self.route(&format!("/api/v1/statuses/{id}/card"))?;
         self.get(route.as_str()).await
     }
 
@@ -462,7 +2525,216 @@ impl MastodonUnauthenticated {
     fn authenticated(&self, request: RequestBuilder) -> RequestBuilder {
         request
     }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        Ok(request.send().await?)
+    }
+}
+/// How long before the access token expires that it is refreshed.
+const REFRESH_MARGIN: time::Duration = time::Duration::minutes(1);
+
+/// Replace the access token of an authenticated request.
+fn reauthenticate(request: RequestBuilder, token: &str) -> Result<RequestBuilder> {
+    use reqwest::header::{HeaderValue, AUTHORIZATION};
+
+    let (client, request) = request.build_split();
+    let mut request = request?;
+    if request.headers().contains_key(AUTHORIZATION) {
+        let mut value = HeaderValue::from_str(&format!("Bearer {token}"))
+            .map_err(|_| crate::format_err!("invalid access token"))?;
+        value.set_sensitive(true);
+        request.headers_mut().insert(AUTHORIZATION, value);
+    }
+    Ok(RequestBuilder::from_parts(client, request))
+}
+
+/// Give a `POST` request a random `Idempotency-Key` header, unless it already
+/// has one, so that each retry of it is recognised as the same request.
+/// Returns the key the request ends up with, if any, for logging.
+fn with_idempotency_key(request: RequestBuilder) -> Result<(RequestBuilder, Option<String>)> {
+    use reqwest::{header::HeaderValue, Method};
+
+    let (client, request) = request.build_split();
+    let mut request = request?;
+    if request.method() == Method::POST && !request.headers().contains_key(IDEMPOTENCY_KEY) {
+        let key = Uuid::new_v4().to_string();
+        debug!(url = request.url().as_str(), idempotency_key = key; "generated idempotency key");
+        let value = HeaderValue::from_str(&key)
+            .map_err(|_| crate::format_err!("invalid idempotency key"))?;
+        request.headers_mut().insert(IDEMPOTENCY_KEY, value);
+    }
+    let key = request
+        .headers()
+        .get(IDEMPOTENCY_KEY)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    Ok((RequestBuilder::from_parts(client, request), key))
+}
+
+/// The header which marks repeated attempts at a request as the same one.
+const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+
+/// How many items each poll asks for. A full batch means there may be more
+/// new items, which are then fetched without waiting.
+const POLL_LIMIT: u64 = 40;
+/// How many of the most recent IDs a poll remembers, to skip items the
+/// server returns twice.
+const POLL_SEEN: usize = 200;
+
+/// The items returned by `fetch`, oldest first, calling it again every
+/// `interval` with the ID of the newest item seen so far.
+fn poll<'a, T, Id, F, Fut>(
+    interval: PollingTime,
+    since_id: Option<Id>,
+    id_of: fn(&T) -> &Id,
+    mut fetch: F,
+) -> impl Stream<Item = Result<T>> + 'a
+where
+    T: 'a,
+    Id: Clone + PartialEq + 'a,
+    F: FnMut(Option<Id>) -> Fut + 'a,
+    Fut: Future<Output = Result<Vec<T>>> + 'a,
+{
+    let seen = VecDeque::new();
+    try_unfold(
+        (since_id, seen, false),
+        move |(mut since_id, mut seen, wait)| {
+            let fetched = fetch(since_id.clone());
+            async move {
+                if wait {
+                    tokio::time::sleep(*interval).await;
+                }
+                let mut items = fetched.await?;
+                let full = items.len() as u64 >= POLL_LIMIT;
+                items.reverse();
+                items.retain(|item| !seen.contains(id_of(item)));
+                if let Some(newest) = items.last() {
+                    since_id = Some(id_of(newest).clone());
+                }
+                seen.extend(items.iter().map(|item| id_of(item).clone()));
+                while seen.len() > POLL_SEEN {
+                    seen.pop_front();
+                }
+                let wait = !full || items.is_empty();
+                Ok::<_, Error>(Some((
+                    stream::iter(items.into_iter().map(Ok)),
+                    (since_id, seen, wait),
+                )))
+            }
+        },
+    )
+    .try_flatten()
+}
+
+/// An ID as a path segment, percent-encoded, so that one given as a string,
+/// such as a tag's name, can't change the rest of the path.
+pub(crate) fn path_segment<T: Display + ?Sized>(id: &T) -> String {
+    const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+        .remove(b'-')
+        .remove(b'.')
+        .remove(b'_')
+        .remove(b'~');
+    utf8_percent_encode(&id.to_string(), PATH_SEGMENT).to_string()
+}
+
+/// The name of a reaction, as a path segment: without the colons around a
+/// custom emoji's shortcode, and percent-encoded.
+fn encode_reaction(name: &str) -> String {
+    utf8_percent_encode(name.trim_matches(':'), NON_ALPHANUMERIC).to_string()
+}
+
+/// The path of the reactions to a status with a given emoji, on Pleroma and
+/// Akkoma.
+pub(crate) fn pleroma_reaction_path(id: &StatusId, name: &str) -> String {
+    let name = encode_reaction(name);
+    format!("/api/v1/pleroma/statuses/{id}/reactions/{name}")
+}
+
+/// The method and path to add or remove a reaction to a status with.
+/// Pleroma and Akkoma have their own routes; glitch-soc's are also used by
+/// the other servers with reactions.
+fn status_reaction_route(
+    capabilities: &Capabilities,
+    id: &StatusId,
+    name: &str,
+    add: bool,
+) -> (reqwest::Method, String) {
+    if capabilities.is_pleroma() {
+        let method = if add {
+            reqwest::Method::PUT
+        } else {
+            reqwest::Method::DELETE
+        };
+        return (method, pleroma_reaction_path(id, name));
+    }
+    let action = if add { "react" } else { "unreact" };
+    let name = encode_reaction(name);
+    (
+        reqwest::Method::POST,
+        format!("/api/v1/statuses/{id}/{action}/{name}"),
+    )
+}
+
+/// Whether a response with the given status is worth retrying.
+pub(crate) fn is_transient(status: reqwest::StatusCode) -> bool {
+    use reqwest::StatusCode;
+
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status.is_server_error() && status != StatusCode::NOT_IMPLEMENTED)
+}
+
+/// Parse the base URL of a Mastodon instance, assuming `https` if no scheme
+/// is given, or `http` for an onion service.
+///
+/// Plain `http` is refused unless `allow_plaintext` is set, other than for
+/// onion services, whose traffic Tor encrypts, and for loopback addresses,
+/// whose traffic never leaves the machine.
+pub(crate) fn parse_base(base: &str, allow_plaintext: bool) -> Result<Url> {
+    let base = base.trim();
+    let url = if base.contains("://") {
+        Url::parse(base)?
+    } else {
+        let mut url = Url::parse(&format!("https://{base}"))?;
+        if is_onion(&url) {
+            url.set_scheme("http")
+                .map_err(|()| crate::format_err!("couldn't use http for {base:?}"))?;
+        }
+        url
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(crate::format_err!(
+            "unsupported scheme {:?} in instance URL {base:?}",
+            url.scheme()
+        ));
+    }
+    if url.host_str().map_or(true, str::is_empty) {
+        return Err(crate::format_err!("no host in instance URL {base:?}"));
+    }
+    if url.scheme() == "http" && !allow_plaintext && !is_onion(&url) && !is_loopback(&url) {
+        return Err(crate::format_err!(
+            "refusing to use plain http for instance URL {base:?} without \
+             HttpConfig::allow_plaintext"
+        ));
+    }
+    Ok(url)
+}
+
+/// Whether the URL is that of a Tor onion service.
+fn is_onion(url: &Url) -> bool {
+    url.host_str()
+        .is_some_and(|host| host.trim_end_matches('.').ends_with(".onion"))
+}
+
+/// Whether the URL is that of this machine.
+fn is_loopback(url: &Url) -> bool {
+    match url.host() {
+        Some(url::Host::Domain(domain)) => domain == "localhost",
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
 }
+
 impl Deref for Mastodon {
     type Target = Arc<MastodonClient>;
 
@@ -471,8 +2743,483 @@ impl Deref for Mastodon {
     }
 }
 
+impl AsRef<status::new::Defaults> for Mastodon {
+    fn as_ref(&self) -> &status::new::Defaults {
+        &self.status_defaults
+    }
+}
+
 impl From<MastodonClient> for Mastodon {
     fn from(value: MastodonClient) -> Self {
         Mastodon(Arc::new(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    #[test]
+    fn test_unauthenticated_base() {
+        let client = MastodonUnauthenticated::new("mastodon.social").expect("bare domain");
+        assert_eq!(client.base.as_str(), "https://mastodon.social/");
+        let client = MastodonUnauthenticated::new("http://localhost:3000").expect("http");
+        assert_eq!(client.base.as_str(), "http://localhost:3000/");
+        let client = MastodonUnauthenticated::new("https://http.example").expect("https");
+        assert_eq!(client.base.as_str(), "https://http.example/");
+        assert!(MastodonUnauthenticated::new("ftp://example.com").is_err());
+        assert!(MastodonUnauthenticated::new("https://").is_err());
+        assert!(MastodonUnauthenticated::new("not a url").is_err());
+    }
+
+    #[test]
+    fn test_plaintext_base() {
+        let onion = "mastodonx7p4ehlsfj2atlbbw4qgelafn5ktmt4kmbj3beuo6o2fqd.onion";
+        let client = MastodonUnauthenticated::new(onion).expect("onion");
+        assert_eq!(client.base.as_str(), format!("http://{onion}/"));
+        let client = MastodonUnauthenticated::new(format!("https://{onion}")).expect("onion");
+        assert_eq!(client.base.scheme(), "https");
+        assert!(MastodonUnauthenticated::new("http://[::1]:3000").is_ok());
+        assert!(MastodonUnauthenticated::new("http://example.com").is_err());
+        let mut config = HttpConfig::default();
+        config.allow_plaintext(true);
+        let client = MastodonUnauthenticated::new_with_config("http://example.com", &config)
+            .expect("plaintext allowed");
+        assert_eq!(client.base.as_str(), "http://example.com/");
+    }
+
+    #[test]
+    fn test_clone_with_token() {
+        let client = Mastodon::from(Data {
+            base: "https://example.com".into(),
+            token: "admin".into(),
+            ..Default::default()
+        });
+        let user = client.clone_with_token("user");
+        assert_eq!(user.data.token, "user");
+        assert_eq!(user.data.base, "https://example.com");
+        assert_eq!(client.data.token, "admin");
+    }
+
+    #[test]
+    fn test_last_rate_limit() {
+        let client = Mastodon::from(Data::default());
+        assert!(client.last_rate_limit().is_none());
+        let rate_limit = RateLimit {
+            limit: 300,
+            remaining: 299,
+            reset: OffsetDateTime::UNIX_EPOCH,
+        };
+        *client.rate_limit.lock().expect("lock") = Some(rate_limit);
+        let paced = client.with_pacer(Pacer::default());
+        assert!(paced.rate_limiter.is_some());
+        assert_eq!(paced.last_rate_limit(), Some(rate_limit));
+        assert!(client.clone_with_token("other").last_rate_limit().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_scope() {
+        let client = Mastodon::from(Data {
+            base: "https://example.com".into(),
+            ..Default::default()
+        })
+        .with_granted_scopes(Scopes::read_all() | Scopes::push());
+        let result = client.unfollow(&AccountId::new("1")).await;
+        match result {
+            Err(Error::MissingScope {
+                required,
+                method,
+                path,
+            }) => {
+                assert_eq!(required, "write");
+                assert_eq!(method, "POST");
+                assert_eq!(path, "/api/v1/accounts/1/unfollow");
+            }
+            other => panic!("expected a missing scope error, got {other:?}"),
+        }
+        assert!(client.clone_with_token("other").granted_scopes.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_scope_guard() {
+        let client = Mastodon::from(Data {
+            base: "https://example.com".into(),
+            scopes: Some(Scopes::read_all() | Scopes::admin_read_all()),
+            ..Default::default()
+        });
+        assert_eq!(
+            client.granted_scopes(),
+            Some(&(Scopes::read_all() | Scopes::admin_read_all()))
+        );
+        let guarded = client.with_scope_guard();
+        let result = guarded
+            .admin_delete_ip_block(&DomainBlockId::new("1"))
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::MissingScope {
+                required: "admin:write",
+                ..
+            })
+        ));
+        let unguarded = client.clone_with_token("other");
+        assert!(unguarded.granted_scopes().is_none());
+        assert!(unguarded.with_scope_guard().0.granted_scopes.is_none());
+    }
+
+    #[test]
+    fn test_reauthenticate() {
+        use reqwest::header::AUTHORIZATION;
+
+        let client = Mastodon::from(Data {
+            base: "https://example.com".into(),
+            token: "old".into(),
+            ..Default::default()
+        });
+        let request = client.authenticated(client.client.get("https://example.com/"));
+        let request = reauthenticate(request, "new")
+            .expect("reauthenticate")
+            .build()
+            .expect("build");
+        let values: Vec<_> = request.headers().get_all(AUTHORIZATION).iter().collect();
+        assert_eq!(values, ["Bearer new"]);
+
+        let request = reauthenticate(client.client.get("https://example.com/"), "new")
+            .expect("reauthenticate")
+            .build()
+            .expect("build");
+        assert!(!request.headers().contains_key(AUTHORIZATION));
+    }
+
+    #[test]
+    fn test_with_idempotency_key() {
+        let client = Client::new();
+        let key = |request: RequestBuilder| {
+            let (request, returned) = with_idempotency_key(request).expect("idempotency key");
+            let key = request
+                .build()
+                .expect("build")
+                .headers()
+                .get(IDEMPOTENCY_KEY)
+                .map(|it| it.to_str().expect("key").to_string());
+            assert_eq!(key, returned);
+            key
+        };
+        let generated = key(client.post("https://example.com/api/v1/statuses")).expect("key");
+        assert!(Uuid::parse_str(&generated).is_ok());
+        assert_ne!(
+            key(client.post("https://example.com/api/v1/statuses")),
+            Some(generated)
+        );
+        let given = client
+            .post("https://example.com/api/v1/statuses")
+            .header(IDEMPOTENCY_KEY, "given");
+        assert_eq!(key(given).as_deref(), Some("given"));
+        assert!(key(client.get("https://example.com/api/v1/statuses/1")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retried_post_is_idempotent() {
+        use std::{
+            sync::atomic::{AtomicUsize, Ordering},
+            time::Duration,
+        };
+
+        use crate::fixtures::{self, Server};
+
+        // a server which posts the status on every attempt, but whose proxy
+        // fails twice before passing its response on.
+        let attempts = AtomicUsize::new(0);
+        let server = Server::new(move |_| {
+            let status = match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => 502,
+                _ => 200,
+            };
+            http::Response::builder()
+                .status(status)
+                .body("{}".to_string())
+                .unwrap()
+        });
+        let client = fixtures::client(&server).with_retries(Backoff {
+            initial: Duration::from_millis(1),
+            max: Duration::from_millis(1),
+            max_retries: Some(3),
+            ..Default::default()
+        });
+        let request = client
+            .client
+            .post(client.route("/api/v1/statuses"))
+            .form(&[("status", "hello")]);
+        let response = client
+            .send(client.authenticated(request))
+            .await
+            .expect("send");
+        assert!(response.status().is_success());
+
+        assert_eq!(server.targets(), ["POST /api/v1/statuses"; 3]);
+        let keys: Vec<_> = server
+            .requests()
+            .iter()
+            .map(|request| request.headers().get("Idempotency-Key").cloned())
+            .collect();
+        assert!(keys[0].is_some());
+        assert!(keys.iter().all(|key| *key == keys[0]));
+    }
+
+    #[tokio::test]
+    async fn test_poll() {
+        let requested = Mutex::new(Vec::new());
+        let mut batches = VecDeque::from([vec![3, 2], vec![4, 3], vec![]]);
+        let items: Vec<u32> = poll(
+            std::time::Duration::from_millis(1).into(),
+            Some(1),
+            |item: &u32| item,
+            |since_id| {
+                requested.lock().unwrap().push(since_id);
+                let batch = batches.pop_front();
+                async move { batch.ok_or_else(|| crate::format_err!("done")) }
+            },
+        )
+        .take_while(|it| std::future::ready(it.is_ok()))
+        .try_collect()
+        .await
+        .unwrap();
+        // oldest first, without the repeated item
+        assert_eq!(items, [2, 3, 4]);
+        assert_eq!(
+            *requested.lock().unwrap(),
+            [Some(1), Some(3), Some(4), Some(4)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reject_follow_request_by_acct() {
+        use crate::fixtures::{self, Server};
+
+        // two pages of follow requests
+        let server = Server::new(|request| {
+            let response = match fixtures::target(request).as_str() {
+                "GET /api/v1/follow_requests" => http::Response::builder()
+                    .header(
+                        "Link",
+                        r#"<https://example.com/api/v1/follow_requests?max_id=1>; rel="next""#,
+                    )
+                    .body(json!([fixtures::account_json("1", "alice")]).to_string()),
+                "GET /api/v1/follow_requests?max_id=1" => http::Response::builder()
+                    .body(json!([fixtures::account_json("2", "bob@example.org")]).to_string()),
+                _ => http::Response::builder().body(fixtures::relationship_json("2").to_string()),
+            };
+            response.unwrap()
+        });
+        let client = fixtures::client(&server);
+        let relationship = client
+            .reject_follow_request_by_acct("@Bob@example.org")
+            .await
+            .expect("reject");
+        assert!(!relationship.followed_by);
+        assert_eq!(
+            server.targets(),
+            [
+                "GET /api/v1/follow_requests",
+                "GET /api/v1/follow_requests?max_id=1",
+                "POST /api/v1/follow_requests/2/reject",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tag_routes_encode_the_name() {
+        use crate::fixtures::{self, Server};
+
+        let server = Server::with_body(
+            json!({
+                "name": "c#",
+                "url": "https://example.com/tags/c%23",
+                "history": [],
+                "following": true
+            })
+            .to_string(),
+        );
+        let client = fixtures::client(&server);
+        let tag = client.get_tag("c#").await.expect("get");
+        assert_eq!(tag.name, "c#");
+        client.follow_tag("c#").await.expect("follow");
+        client.unfollow_tag("rust/../lang").await.expect("unfollow");
+        client.get_tag("rust-lang_2").await.expect("get");
+        assert_eq!(
+            server.targets(),
+            [
+                "GET /api/v1/tags/c%23",
+                "POST /api/v1/tags/c%23/follow",
+                "POST /api/v1/tags/rust%2F..%2Flang/unfollow",
+                "GET /api/v1/tags/rust-lang_2",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_account_aliases() {
+        use crate::fixtures::{self, Server};
+
+        /// A server advertising `version`.
+        fn advertising(version: &'static str) -> Arc<Server> {
+            Server::new(move |request| {
+                let body = match (request.method().as_str(), request.url().path()) {
+                    ("GET", "/api/v1/instance") => json!({ "version": version }),
+                    ("GET", "/api/pleroma/aliases") => {
+                        json!({ "aliases": ["kept@old.example", "stale@old.example"] })
+                    }
+                    _ => json!({ "status": "success" }),
+                };
+                http::Response::new(body.to_string())
+            })
+        }
+
+        /// The alias changes made.
+        fn changes(server: &Server) -> Vec<String> {
+            server
+                .requests()
+                .iter()
+                .filter(|request| request.method() != reqwest::Method::GET)
+                .map(|request| format!("{} {}", request.method(), fixtures::body(request)))
+                .collect()
+        }
+
+        let server = advertising("2.7.2 (compatible; Pleroma 2.5.0)");
+        let client = fixtures::client(&server);
+        client
+            .set_account_aliases(&["@kept@old.example", "new@old.example"])
+            .await
+            .expect("set aliases");
+        assert_eq!(
+            changes(&server),
+            [
+                r#"DELETE {"alias":"stale@old.example"}"#,
+                r#"PUT {"alias":"new@old.example"}"#,
+            ]
+        );
+
+        let server = advertising("4.3.1");
+        let client = fixtures::client(&server);
+        let err = client
+            .move_account("me@new.example", "hunter2")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Unsupported {
+                feature: "account migration",
+                ..
+            }
+        ));
+        assert!(changes(&server).is_empty());
+    }
+
+    #[test]
+    fn test_status_reaction_route() {
+        let id = StatusId::new("1");
+        let glitch = Capabilities::from_version_string("4.3.0+glitch");
+        assert_eq!(
+            status_reaction_route(&glitch, &id, ":blobcat:", true),
+            (
+                reqwest::Method::POST,
+                "/api/v1/statuses/1/react/blobcat".to_string()
+            )
+        );
+        assert_eq!(
+            status_reaction_route(&glitch, &id, "☕", false),
+            (
+                reqwest::Method::POST,
+                "/api/v1/statuses/1/unreact/%E2%98%95".to_string()
+            )
+        );
+        let pleroma = Capabilities::from_version_string("2.7.2 (compatible; Pleroma 2.5.0)");
+        assert_eq!(
+            status_reaction_route(&pleroma, &id, "blobcat", false),
+            (
+                reqwest::Method::DELETE,
+                "/api/v1/pleroma/statuses/1/reactions/blobcat".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_is_transient() {
+        use reqwest::StatusCode;
+
+        assert!(is_transient(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient(StatusCode::BAD_GATEWAY));
+        assert!(is_transient(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_transient(StatusCode::NOT_IMPLEMENTED));
+        assert!(!is_transient(StatusCode::NOT_FOUND));
+        assert!(!is_transient(StatusCode::OK));
+    }
+
+    #[test]
+    fn test_unauthenticated_route() {
+        let client = MastodonUnauthenticated::new("https://example.com").expect("client");
+        assert_eq!(
+            client
+                .route(&format!(
+                    "/api/v1/statuses/{}/context",
+                    StatusId::new("123")
+                ))
+                .expect("route")
+                .as_str(),
+            "https://example.com/api/v1/statuses/123/context"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_pages_without_type() {
+        use crate::fixtures::{self, Server};
+
+        // ignores the offset, as Mastodon does without a type
+        let server = Server::with_body(
+            json!({
+                "accounts": [fixtures::account_json("1", "rust")],
+                "statuses": [],
+                "hashtags": []
+            })
+            .to_string(),
+        );
+        let client = fixtures::client(&server);
+        let options = forms::search::Options::builder("rust").build().unwrap();
+        let pages: Vec<_> = client
+            .search_pages(options)
+            .try_collect()
+            .await
+            .expect("pages");
+        assert_eq!(pages.len(), 1);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_metrics() {
+        use reqwest::StatusCode;
+
+        use crate::fixtures::{self, Server};
+
+        /// Records every request.
+        #[derive(Debug, Default)]
+        struct Recorder(Mutex<Vec<RequestMetrics>>);
+
+        impl Metrics for Recorder {
+            fn record(&self, request: &RequestMetrics) {
+                self.0.lock().unwrap().push(request.clone());
+            }
+        }
+
+        let recorder = Arc::new(Recorder::default());
+        let client = fixtures::client(&Server::with_body("{}")).with_metrics(recorder.clone());
+        // the request is made, but an empty object isn't a status
+        assert!(client.reblog(&StatusId::new("1234")).await.is_err());
+        let recorded = recorder.0.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].method, reqwest::Method::POST);
+        assert_eq!(recorded[0].endpoint, "statuses/:id/reblog");
+        assert_eq!(recorded[0].status, Some(StatusCode::OK));
+    }
+}
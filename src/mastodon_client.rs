@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+
+use crate::{entities::prelude::*, errors::Result, timeline::Timeline, Mastodon, NewStatus};
+
+/// The most common requests, as a trait, so that code which makes them can
+/// be tested against a stand-in instead of a real server.
+///
+/// [`Mastodon`] implements it by making the requests; with the "testing"
+/// feature, [`MockMastodon`](crate::testing::MockMastodon) implements it
+/// with canned responses. Requests for paged lists return the entities of
+/// the first page.
+///
+/// ```no_run
+/// use mastodon_async::{prelude::*, MastodonClient, Result};
+///
+/// async fn announce(client: &impl MastodonClient, text: &str) -> Result<Status> {
+///     let status = NewStatusBuilder::default().status(text).build()?;
+///     client.new_status(status).await
+/// }
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     announce(&client, "hello").await.unwrap();
+/// });
+/// ```
+#[async_trait]
+pub trait MastodonClient: Send + Sync {
+    /// The account the client is authenticated as.
+    async fn verify_credentials(&self) -> Result<Account>;
+
+    /// Post a new status.
+    async fn new_status(&self, status: NewStatus) -> Result<Status>;
+
+    /// A single status.
+    async fn get_status(&self, id: &StatusId) -> Result<Status>;
+
+    /// Delete one of the user's statuses.
+    async fn delete_status(&self, id: &StatusId) -> Result<()>;
+
+    /// Favourite a status.
+    async fn favourite(&self, id: &StatusId) -> Result<Status>;
+
+    /// Undo the favouriting of a status.
+    async fn unfavourite(&self, id: &StatusId) -> Result<Status>;
+
+    /// Boost a status.
+    async fn reblog(&self, id: &StatusId) -> Result<Status>;
+
+    /// Undo the boosting of a status.
+    async fn unreblog(&self, id: &StatusId) -> Result<Status>;
+
+    /// The first page of the user's home timeline.
+    async fn get_home_timeline(&self) -> Result<Vec<Status>>;
+
+    /// The first page of any timeline.
+    async fn timeline(
+        &self,
+        timeline: &Timeline,
+        options: forms::timeline::Options,
+    ) -> Result<Vec<Status>>;
+
+    /// The first page of the user's notifications.
+    async fn notifications(&self) -> Result<Vec<Notification>>;
+
+    /// A single account.
+    async fn get_account(&self, id: &AccountId) -> Result<Account>;
+
+    /// Follow an account.
+    async fn follow(&self, id: &AccountId) -> Result<Relationship>;
+
+    /// Unfollow an account.
+    async fn unfollow(&self, id: &AccountId) -> Result<Relationship>;
+
+    /// Upload media, to attach to a status.
+    async fn media(&self, form: forms::media::Upload) -> Result<Attachment>;
+}
+
+#[async_trait]
+impl MastodonClient for Mastodon {
+    async fn verify_credentials(&self) -> Result<Account> {
+        Mastodon::verify_credentials(self).await
+    }
+
+    async fn new_status(&self, status: NewStatus) -> Result<Status> {
+        Mastodon::new_status(self, status).await
+    }
+
+    async fn get_status(&self, id: &StatusId) -> Result<Status> {
+        Mastodon::get_status(self, id).await
+    }
+
+    async fn delete_status(&self, id: &StatusId) -> Result<()> {
+        Mastodon::delete_status(self, id).await
+    }
+
+    async fn favourite(&self, id: &StatusId) -> Result<Status> {
+        Mastodon::favourite(self, id).await
+    }
+
+    async fn unfavourite(&self, id: &StatusId) -> Result<Status> {
+        Mastodon::unfavourite(self, id).await
+    }
+
+    async fn reblog(&self, id: &StatusId) -> Result<Status> {
+        Mastodon::reblog(self, id).await
+    }
+
+    async fn unreblog(&self, id: &StatusId) -> Result<Status> {
+        Mastodon::unreblog(self, id).await
+    }
+
+    async fn get_home_timeline(&self) -> Result<Vec<Status>> {
+        Ok(Mastodon::get_home_timeline(self).await?.initial_items)
+    }
+
+    async fn timeline(
+        &self,
+        timeline: &Timeline,
+        options: forms::timeline::Options,
+    ) -> Result<Vec<Status>> {
+        Ok(Mastodon::timeline(self, timeline, options)
+            .await?
+            .initial_items)
+    }
+
+    async fn notifications(&self) -> Result<Vec<Notification>> {
+        Ok(Mastodon::notifications(self).await?.initial_items)
+    }
+
+    async fn get_account(&self, id: &AccountId) -> Result<Account> {
+        Mastodon::get_account(self, id).await
+    }
+
+    async fn follow(&self, id: &AccountId) -> Result<Relationship> {
+        Mastodon::follow(self, id).await
+    }
+
+    async fn unfollow(&self, id: &AccountId) -> Result<Relationship> {
+        Mastodon::unfollow(self, id).await
+    }
+
+    async fn media(&self, form: forms::media::Upload) -> Result<Attachment> {
+        Mastodon::media(self, form).await
+    }
+}
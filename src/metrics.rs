@@ -0,0 +1,68 @@
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+use reqwest::{Method, StatusCode};
+
+/// What happened to one HTTP request the client made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestMetrics {
+    /// The HTTP method of the request.
+    pub method: Method,
+    /// The endpoint the request was made to, with any IDs replaced, e.g.
+    /// `statuses/:id/reblog`. See
+    /// [`endpoint_label`](crate::routes::endpoint_label).
+    pub endpoint: String,
+    /// The status of the response, or `None` if there wasn't one, e.g.
+    /// because the connection failed or timed out.
+    pub status: Option<StatusCode>,
+    /// How long it took for the response to arrive, not counting reading
+    /// its body.
+    pub latency: Duration,
+}
+
+/// Called with the outcome of every HTTP request the client makes, e.g. to
+/// export metrics about them. Set it with
+/// [`Mastodon::with_metrics`](crate::Mastodon::with_metrics).
+///
+/// Each attempt at a request which is retried is recorded separately. It is
+/// called before the response is handed on, so it should be quick.
+///
+/// ```
+/// use std::{
+///     collections::HashMap,
+///     sync::Mutex,
+///     time::Duration,
+/// };
+///
+/// use mastodon_async::{
+///     metrics::{Metrics, RequestMetrics},
+///     prelude::*,
+/// };
+///
+/// /// The number of requests and their total latency, by endpoint and status.
+/// #[derive(Debug, Default)]
+/// struct Totals(Mutex<HashMap<(String, u16), (u64, Duration)>>);
+///
+/// impl Metrics for Totals {
+///     fn record(&self, request: &RequestMetrics) {
+///         let status = request.status.map_or(0, |status| status.as_u16());
+///         let mut totals = self.0.lock().unwrap();
+///         let total = totals.entry((request.endpoint.clone(), status)).or_default();
+///         total.0 += 1;
+///         total.1 += request.latency;
+///     }
+/// }
+///
+/// let client = Mastodon::from(Data::default()).with_metrics(Totals::default());
+/// ```
+pub trait Metrics: Debug + Send + Sync {
+    /// A request was made.
+    fn record(&self, request: &RequestMetrics);
+}
+
+/// Lets metrics be shared with, and read by, other code, e.g. a task which
+/// exports them.
+impl<T: Metrics + ?Sized> Metrics for Arc<T> {
+    fn record(&self, request: &RequestMetrics) {
+        (**self).record(request)
+    }
+}
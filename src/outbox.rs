@@ -0,0 +1,343 @@
+use std::{collections::VecDeque, fmt::Debug, sync::Mutex};
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex as AsyncMutex;
+use uuid::Uuid;
+
+use crate::{entities::prelude::*, errors::Result, polling_time::PollingTime, Mastodon, NewStatus};
+
+/// Where an [`OutboxQueue`] keeps the posts waiting to be sent, so that they
+/// aren't lost if the program exits before it's back online.
+///
+/// It's implemented for a `Mutex<`[`Store`](crate::store::Store)`>` when the
+/// "store-sqlite" feature is enabled, and for a `Mutex<Vec<QueuedPost>>`,
+/// which keeps them in memory only.
+pub trait OutboxStorage: Debug + Send + Sync {
+    /// Replace the stored posts with `posts`, oldest first.
+    fn save(&self, posts: &[QueuedPost]) -> Result<()>;
+
+    /// The stored posts, oldest first.
+    fn load(&self) -> Result<Vec<QueuedPost>>;
+}
+
+impl OutboxStorage for Mutex<Vec<QueuedPost>> {
+    fn save(&self, posts: &[QueuedPost]) -> Result<()> {
+        *self.lock().unwrap_or_else(|err| err.into_inner()) = posts.to_vec();
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<QueuedPost>> {
+        Ok(self.lock().unwrap_or_else(|err| err.into_inner()).clone())
+    }
+}
+
+#[cfg(feature = "store-sqlite")]
+impl OutboxStorage for Mutex<crate::store::Store> {
+    fn save(&self, posts: &[QueuedPost]) -> Result<()> {
+        self.lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .save_outbox(posts)
+    }
+
+    fn load(&self) -> Result<Vec<QueuedPost>> {
+        self.lock().unwrap_or_else(|err| err.into_inner()).outbox()
+    }
+}
+
+/// A status waiting in an [`OutboxQueue`] to be posted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedPost {
+    /// Sent with every attempt to post the status, so that the server posts
+    /// it only once.
+    pub idempotency_key: String,
+    /// The status to post. The media below are attached to it along with
+    /// any it already has.
+    pub status: NewStatus,
+    /// Media to upload before posting the status.
+    pub media: Vec<QueuedMedia>,
+}
+
+impl QueuedPost {
+    /// A status to post, with a new idempotency key.
+    pub fn new(status: NewStatus) -> Self {
+        QueuedPost {
+            idempotency_key: Uuid::new_v4().to_string(),
+            status,
+            media: vec![],
+        }
+    }
+
+    /// Upload `media` and attach it to the status before posting it.
+    pub fn with_media(mut self, media: QueuedMedia) -> Self {
+        self.media.push(media);
+        self
+    }
+}
+
+/// Media waiting in an [`OutboxQueue`] to be uploaded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedMedia {
+    /// The name of the file, for the server to guess its type from.
+    pub file_name: String,
+    /// The contents of the file. They're dropped once it's uploaded.
+    pub bytes: Vec<u8>,
+    /// A description/alt-text for the media.
+    pub description: Option<String>,
+    /// The ID of the attachment, once uploaded, so that it isn't uploaded
+    /// again if posting the status fails.
+    pub uploaded: Option<AttachmentId>,
+}
+
+impl QueuedMedia {
+    /// Media to upload, with no description.
+    pub fn new(file_name: impl Into<String>, bytes: Vec<u8>) -> Self {
+        QueuedMedia {
+            file_name: file_name.into(),
+            bytes,
+            description: None,
+            uploaded: None,
+        }
+    }
+
+    /// Set a description/alt-text for the media.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// Queues statuses while the server can't be reached, and posts them in
+/// order once it can.
+///
+/// Every queued post is saved to an [`OutboxStorage`] as soon as it's queued
+/// and whenever progress is made sending it, and each has its own
+/// idempotency key, so a status is never posted twice, even if the response
+/// to posting it was lost or the program was restarted.
+///
+/// ```no_run
+/// use std::sync::Mutex;
+/// use mastodon_async::{outbox::{OutboxQueue, QueuedMedia}, prelude::*};
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let outbox = OutboxQueue::new(client, Mutex::new(vec![])).unwrap();
+///     let status = NewStatusBuilder::default().status("hello").build().unwrap();
+///     let media = QueuedMedia::new("cat.jpg", std::fs::read("cat.jpg").unwrap())
+///         .with_description("a cat");
+///     match outbox.post(status, vec![media]).await.unwrap() {
+///         Some(status) => println!("posted {}", status.id),
+///         // try again later
+///         None => println!("queued"),
+///     }
+///     let posted = outbox.flush().await.unwrap();
+/// });
+/// ```
+#[derive(Debug)]
+pub struct OutboxQueue<S: OutboxStorage> {
+    client: Mastodon,
+    storage: S,
+    posts: AsyncMutex<VecDeque<QueuedPost>>,
+}
+
+impl<S: OutboxStorage> OutboxQueue<S> {
+    /// A queue which posts with `client`, starting with the posts already in
+    /// `storage`.
+    pub fn new(client: Mastodon, storage: S) -> Result<Self> {
+        let posts = storage.load()?;
+        Ok(OutboxQueue {
+            client,
+            storage,
+            posts: AsyncMutex::new(posts.into()),
+        })
+    }
+
+    /// The storage the queue is saved to.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Queue a status to be posted by the next [`flush`](Self::flush).
+    pub async fn push(&self, status: NewStatus, media: Vec<QueuedMedia>) -> Result<()> {
+        let mut posts = self.posts.lock().await;
+        posts.push_back(QueuedPost {
+            media,
+            ..QueuedPost::new(status)
+        });
+        self.storage.save(posts.make_contiguous())
+    }
+
+    /// Queue a status, then try to post everything in the queue.
+    ///
+    /// Returns the new status if it was posted, or `None` if the server
+    /// couldn't be reached or failed for a reason that may pass, in which
+    /// case it's left in the queue. Other errors are returned, and the post
+    /// which caused them is left at the front of the queue, to be
+    /// [discarded](Self::discard) or fixed by the caller.
+    pub async fn post(&self, status: NewStatus, media: Vec<QueuedMedia>) -> Result<Option<Status>> {
+        self.push(status, media).await?;
+        match self.flush().await {
+            Ok(mut posted) => Ok(posted.pop()),
+            Err(err) if err.is_transient() => {
+                warn!(err:? = err; "queued status to post later");
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Post the queued statuses, oldest first, returning them.
+    ///
+    /// This stops at the first error, which is returned, leaving the post it
+    /// happened on and those after it in the queue. Those posted before it
+    /// are removed from the queue.
+    pub async fn flush(&self) -> Result<Vec<Status>> {
+        let mut posts = self.posts.lock().await;
+        let mut posted = vec![];
+        while !posts.is_empty() {
+            for i in 0..posts[0].media.len() {
+                if posts[0].media[i].uploaded.is_some() {
+                    continue;
+                }
+                let media = &posts[0].media[i];
+                let attachment = self
+                    .client
+                    .media_from_bytes(
+                        media.bytes.clone(),
+                        media.file_name.clone(),
+                        None,
+                        media.description.clone(),
+                        None,
+                    )
+                    .await?;
+                let attachment = self
+                    .client
+                    .wait_for_processing(attachment, PollingTime::default())
+                    .await?;
+                let media = &mut posts[0].media[i];
+                media.uploaded = Some(attachment.id);
+                media.bytes = vec![];
+                self.storage.save(posts.make_contiguous())?;
+            }
+            let post = &posts[0];
+            let mut status = post.status.clone();
+            let uploaded: Vec<_> = post
+                .media
+                .iter()
+                .filter_map(|media| media.uploaded.clone())
+                .collect();
+            if !uploaded.is_empty() {
+                status
+                    .media_ids
+                    .get_or_insert_with(Vec::new)
+                    .extend(uploaded);
+            }
+            let status = self
+                .client
+                .new_status_with_idempotency_key(status, &post.idempotency_key)
+                .await?;
+            debug!(id = status.id.as_ref(), idempotency_key = post.idempotency_key; "posted queued status");
+            posts.pop_front();
+            self.storage.save(posts.make_contiguous())?;
+            posted.push(status);
+        }
+        Ok(posted)
+    }
+
+    /// Remove the post with the given idempotency key from the queue,
+    /// returning it if it was there.
+    pub async fn discard(&self, idempotency_key: &str) -> Result<Option<QueuedPost>> {
+        let mut posts = self.posts.lock().await;
+        let Some(index) = posts
+            .iter()
+            .position(|post| post.idempotency_key == idempotency_key)
+        else {
+            return Ok(None);
+        };
+        let post = posts.remove(index);
+        self.storage.save(posts.make_contiguous())?;
+        Ok(post)
+    }
+
+    /// The posts waiting to be sent, oldest first.
+    pub async fn pending(&self) -> Vec<QueuedPost> {
+        self.posts.lock().await.iter().cloned().collect()
+    }
+
+    /// The number of posts waiting to be sent.
+    pub async fn len(&self) -> usize {
+        self.posts.lock().await.len()
+    }
+
+    /// Whether there are no posts waiting to be sent.
+    pub async fn is_empty(&self) -> bool {
+        self.posts.lock().await.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use super::*;
+    use crate::Data;
+
+    fn status(text: &str) -> NewStatus {
+        NewStatus {
+            status: Some(text.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// A client for a server which refuses connections.
+    fn offline() -> Mastodon {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let base = format!("http://{}", listener.local_addr().expect("address"));
+        drop(listener);
+        Mastodon::from(Data {
+            base: base.into(),
+            token: "token".into(),
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_queue_while_offline() {
+        let outbox = OutboxQueue::new(offline(), Mutex::new(vec![])).expect("outbox");
+        let media = QueuedMedia::new("cat.jpg", vec![1, 2, 3]).with_description("a cat");
+        assert!(outbox
+            .post(status("first"), vec![media.clone()])
+            .await
+            .expect("post")
+            .is_none());
+        outbox.push(status("second"), vec![]).await.expect("push");
+        let err = outbox.flush().await.expect_err("offline");
+        assert!(err.is_transient());
+
+        let pending = outbox.pending().await;
+        assert_eq!(outbox.len().await, 2);
+        assert_eq!(pending[0].status, status("first"));
+        assert_eq!(pending[0].media, [media]);
+        assert_eq!(pending[1].status, status("second"));
+        assert_ne!(pending[0].idempotency_key, pending[1].idempotency_key);
+        assert_eq!(outbox.storage().load().expect("load"), pending);
+
+        // a restarted program picks up where it left off, with the same keys
+        let storage = Mutex::new(pending.clone());
+        let outbox = OutboxQueue::new(offline(), storage).expect("outbox");
+        assert_eq!(outbox.pending().await, pending);
+
+        let discarded = outbox
+            .discard(&pending[0].idempotency_key)
+            .await
+            .expect("discard");
+        assert_eq!(discarded.as_ref(), Some(&pending[0]));
+        assert!(outbox
+            .discard(&pending[0].idempotency_key)
+            .await
+            .expect("discard")
+            .is_none());
+        assert_eq!(outbox.storage().load().expect("load"), &pending[1..]);
+        assert!(!outbox.is_empty().await);
+    }
+}
@@ -0,0 +1,558 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use log::{debug, trace};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use time::{
+    format_description::well_known::{Iso8601, Rfc2822},
+    OffsetDateTime,
+};
+use tokio::time::{sleep, Instant};
+
+/// The rate limit Mastodon applies to most API routes: 300 requests per
+/// five minutes.
+pub const DEFAULT_LIMIT: u32 = 300;
+/// The window over which [`DEFAULT_LIMIT`] applies.
+pub const DEFAULT_PERIOD: Duration = Duration::from_secs(5 * 60);
+
+/// The parsed value of a `Retry-After` header.
+///
+/// See also [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#field.retry-after)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAfter {
+    /// The server asked to wait for a number of seconds.
+    Delay(Duration),
+    /// The server asked to wait until the given time.
+    Date(OffsetDateTime),
+}
+
+impl RetryAfter {
+    /// Parse the `Retry-After` header from the given headers, if present.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        headers.get(RETRY_AFTER)?.to_str().ok()?.parse().ok()
+    }
+
+    /// How long from now the server asked to wait.
+    pub fn duration(&self) -> Duration {
+        match self {
+            RetryAfter::Delay(delay) => *delay,
+            RetryAfter::Date(date) => until(*date),
+        }
+    }
+}
+
+impl std::str::FromStr for RetryAfter {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(seconds) = s.parse() {
+            Ok(RetryAfter::Delay(Duration::from_secs(seconds)))
+        } else if let Ok(date) = OffsetDateTime::parse(s, &Rfc2822) {
+            Ok(RetryAfter::Date(date))
+        } else {
+            Err(crate::format_err!("invalid Retry-After value {s:?}"))
+        }
+    }
+}
+
+/// The rate limit state reported by the server in the `X-RateLimit-*`
+/// headers.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/api/rate-limits/)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Number of requests permitted per time period.
+    pub limit: u32,
+    /// Number of requests you can still make.
+    pub remaining: u32,
+    /// When the current time period resets.
+    pub reset: OffsetDateTime,
+}
+
+impl RateLimit {
+    /// Parse the rate limit headers from the given headers, if all are
+    /// present.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok();
+        Some(RateLimit {
+            limit: header("x-ratelimit-limit")?.parse().ok()?,
+            remaining: header("x-ratelimit-remaining")?.parse().ok()?,
+            reset: OffsetDateTime::parse(header("x-ratelimit-reset")?, &Iso8601::PARSING).ok()?,
+        })
+    }
+
+    /// How long to wait before making another request: until the period
+    /// resets if no requests remain, otherwise not at all.
+    pub fn delay(&self) -> Duration {
+        if self.remaining > 0 {
+            Duration::ZERO
+        } else {
+            until(self.reset)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    capacity: f64,
+    tokens: f64,
+    refill_per_second: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl State {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() * self.refill_per_second).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Take a token if one is available, otherwise return how long to wait
+    /// before trying again.
+    fn take(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(paused_until) = self.paused_until {
+            if paused_until > now {
+                return Err(paused_until - now);
+            }
+            self.paused_until = None;
+        }
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - self.tokens;
+            Err(Duration::from_secs_f64(missing / self.refill_per_second))
+        }
+    }
+
+    fn pause_until(&mut self, until: Instant) {
+        self.tokens = 0.0;
+        self.last_refill = until;
+        self.paused_until = Some(self.paused_until.map_or(until, |it| it.max(until)));
+    }
+}
+
+/// A token bucket for pacing requests so they stay within the server's rate
+/// limits.
+///
+/// The bucket starts full, and refills continuously at `limit` tokens per
+/// `period`. It can additionally be told about the server's view of the rate
+/// limit with [`Pacer::observe`], so that `Retry-After` and exhausted
+/// `X-RateLimit-Remaining` headers pause all users of the pacer.
+///
+/// Clones share the same bucket, so a single pacer may be handed to several
+/// tasks.
+///
+/// ```no_run
+/// use mastodon_async::{pacing::Pacer, prelude::*};
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let pacer = Pacer::default();
+///     for id in ["1", "2", "3"] {
+///         pacer.acquire().await;
+///         client.favourite(&StatusId::new(id)).await.unwrap();
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pacer(Arc<Mutex<State>>);
+
+impl Default for Pacer {
+    /// A pacer matching Mastodon's default limit of 300 requests per five
+    /// minutes.
+    fn default() -> Self {
+        Pacer::new(DEFAULT_LIMIT, DEFAULT_PERIOD)
+    }
+}
+
+impl Pacer {
+    /// A pacer allowing `limit` requests per `period`.
+    ///
+    /// ## Panics
+    /// If `limit` is zero or `period` is empty.
+    pub fn new(limit: u32, period: Duration) -> Self {
+        assert!(limit > 0, "pacer limit must be non-zero");
+        assert!(!period.is_zero(), "pacer period must be non-zero");
+        let capacity = f64::from(limit);
+        Pacer(Arc::new(Mutex::new(State {
+            capacity,
+            tokens: capacity,
+            refill_per_second: capacity / period.as_secs_f64(),
+            last_refill: Instant::now(),
+            paused_until: None,
+        })))
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        // the state is always left consistent, so a poisoned lock is still
+        // usable.
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Wait until a request may be made, and consume a token for it.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = match self.state().take(Instant::now()) {
+                Ok(()) => return,
+                Err(wait) => wait,
+            };
+            trace!(wait:? = wait; "pacing request");
+            sleep(wait).await;
+        }
+    }
+
+    /// Consume a token if one is available right now, without waiting.
+    pub fn try_acquire(&self) -> bool {
+        self.state().take(Instant::now()).is_ok()
+    }
+
+    /// The number of whole tokens currently available.
+    pub fn available(&self) -> u32 {
+        let mut state = self.state();
+        let now = Instant::now();
+        if state.paused_until.is_some_and(|until| until > now) {
+            return 0;
+        }
+        state.refill(now);
+        state.tokens as u32
+    }
+
+    /// Stop handing out tokens for the given duration.
+    pub fn pause_for(&self, duration: Duration) {
+        debug!(duration:? = duration; "pausing requests");
+        self.state().pause_until(Instant::now() + duration);
+    }
+
+    /// Update the pacer with the server's view of the rate limit.
+    ///
+    /// Never hands out more tokens than the server says remain, and pauses
+    /// until the reset time if none remain.
+    pub fn observe_rate_limit(&self, rate_limit: &RateLimit) {
+        let mut state = self.state();
+        let now = Instant::now();
+        state.refill(now);
+        state.tokens = state.tokens.min(f64::from(rate_limit.remaining));
+        if rate_limit.remaining == 0 {
+            debug!(reset:? = rate_limit.reset; "rate limit exhausted");
+            state.pause_until(now + until(rate_limit.reset));
+        }
+    }
+
+    /// Update the pacer from the headers of an API response, taking into
+    /// account both `Retry-After` and the `X-RateLimit-*` headers.
+    pub fn observe(&self, headers: &HeaderMap) {
+        if let Some(rate_limit) = RateLimit::from_headers(headers) {
+            self.observe_rate_limit(&rate_limit);
+        }
+        if let Some(retry_after) = RetryAfter::from_headers(headers) {
+            self.pause_for(retry_after.duration());
+        }
+    }
+}
+
+/// A group of API routes which Mastodon rate limits separately, on top of
+/// the [`DEFAULT_LIMIT`] shared by all of them.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/api/rate-limits/)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndpointFamily {
+    /// Routes with no limit of their own.
+    General,
+    /// Uploading media, limited to 30 uploads per 30 minutes.
+    Media,
+    /// Deleting statuses and undoing reblogs, limited to 30 per 30
+    /// minutes.
+    StatusDeletion,
+    /// Creating accounts, limited to 5 per 30 minutes.
+    Registration,
+    /// Signing in and requesting access tokens, such as those the
+    /// streaming API is authenticated with, limited to 25 per 5 minutes.
+    Authentication,
+}
+
+impl EndpointFamily {
+    /// The family of a request with the given method to the given path.
+    ///
+    /// ```
+    /// use mastodon_async::pacing::EndpointFamily;
+    /// assert_eq!(EndpointFamily::of("POST", "/api/v2/media"), EndpointFamily::Media);
+    /// assert_eq!(EndpointFamily::of("GET", "/api/v1/media/1"), EndpointFamily::General);
+    /// ```
+    pub fn of(method: &str, path: &str) -> Self {
+        let path = path.trim_end_matches('/');
+        let method = method.to_ascii_uppercase();
+        match method.as_str() {
+            "POST" if path == "/api/v1/media" || path == "/api/v2/media" => EndpointFamily::Media,
+            "POST" if path.starts_with("/api/v1/statuses/") && path.ends_with("/unreblog") => {
+                EndpointFamily::StatusDeletion
+            }
+            "DELETE" if path.starts_with("/api/v1/statuses/") => EndpointFamily::StatusDeletion,
+            "POST" if path == "/api/v1/accounts" => EndpointFamily::Registration,
+            "POST" if path == "/oauth/token" || path == "/auth/sign_in" => {
+                EndpointFamily::Authentication
+            }
+            _ => EndpointFamily::General,
+        }
+    }
+
+    /// The number of requests Mastodon allows by default, and the window
+    /// it allows them over.
+    pub fn default_limit(&self) -> (u32, Duration) {
+        match self {
+            EndpointFamily::General => (DEFAULT_LIMIT, DEFAULT_PERIOD),
+            EndpointFamily::Media | EndpointFamily::StatusDeletion => {
+                (30, Duration::from_secs(30 * 60))
+            }
+            EndpointFamily::Registration => (5, Duration::from_secs(30 * 60)),
+            EndpointFamily::Authentication => (25, Duration::from_secs(5 * 60)),
+        }
+    }
+
+    const LIMITED: [EndpointFamily; 4] = [
+        EndpointFamily::Media,
+        EndpointFamily::StatusDeletion,
+        EndpointFamily::Registration,
+        EndpointFamily::Authentication,
+    ];
+}
+
+/// Paces requests with a budget for each [`EndpointFamily`], so they stay
+/// within all of Mastodon's rate limits.
+///
+/// Every request takes a token from the general pacer, and requests to a
+/// family with a limit of its own also take one from that family's pacer.
+/// The headers of each response update the pacer of the request's family.
+///
+/// Clones share the same budgets, as do all clones of a
+/// [`Mastodon`](crate::Mastodon) client the limiter is set on with
+/// [`Mastodon::with_rate_limiter`](crate::Mastodon::with_rate_limiter), so
+/// tasks holding different clones can't collectively exceed the limits.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mastodon_async::{pacing::{EndpointFamily, Pacer, RateLimiter}, prelude::*};
+///
+/// tokio_test::block_on(async {
+///     // an instance which allows more uploads than usual
+///     let limiter = RateLimiter::default()
+///         .with_family(EndpointFamily::Media, Pacer::new(100, Duration::from_secs(30 * 60)));
+///     let client = Mastodon::from(Data::default()).with_rate_limiter(limiter);
+///     let uploader = client.clone();
+///     let upload = |path| forms::media::Upload::builder(path).build().unwrap();
+///     tokio::spawn(async move { uploader.media(upload("cat.png")).await });
+///     client.media(upload("dog.png")).await.unwrap();
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    general: Pacer,
+    families: HashMap<EndpointFamily, Pacer>,
+}
+
+impl Default for RateLimiter {
+    /// A limiter matching Mastodon's default limits for every family.
+    fn default() -> Self {
+        let mut limiter = RateLimiter::from(Pacer::default());
+        for family in EndpointFamily::LIMITED {
+            let (limit, period) = family.default_limit();
+            limiter = limiter.with_family(family, Pacer::new(limit, period));
+        }
+        limiter
+    }
+}
+
+impl From<Pacer> for RateLimiter {
+    /// A limiter which only paces requests with the given general pacer.
+    fn from(general: Pacer) -> Self {
+        RateLimiter {
+            general,
+            families: HashMap::new(),
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Pace requests to the given family with `pacer`, as well as the
+    /// general one. Setting the pacer of [`EndpointFamily::General`]
+    /// replaces the general pacer.
+    pub fn with_family(mut self, family: EndpointFamily, pacer: Pacer) -> Self {
+        if family == EndpointFamily::General {
+            self.general = pacer;
+        } else {
+            self.families.insert(family, pacer);
+        }
+        self
+    }
+
+    /// The pacer requests to the given family are limited by: the family's
+    /// own, if it has one, otherwise the general pacer.
+    pub fn pacer(&self, family: EndpointFamily) -> &Pacer {
+        self.families.get(&family).unwrap_or(&self.general)
+    }
+
+    /// Wait until a request to the given family may be made, and consume
+    /// tokens for it.
+    pub async fn acquire(&self, family: EndpointFamily) {
+        if let Some(pacer) = self.families.get(&family) {
+            pacer.acquire().await;
+        }
+        self.general.acquire().await;
+    }
+
+    /// Update the pacer of the given family from the headers of a response
+    /// to a request to it.
+    pub fn observe(&self, family: EndpointFamily, headers: &HeaderMap) {
+        self.pacer(family).observe(headers);
+    }
+}
+
+/// How long from now until the given time, or zero if it has passed.
+fn until(time: OffsetDateTime) -> Duration {
+    (time - OffsetDateTime::now_utc())
+        .try_into()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(
+            "120".parse::<RetryAfter>().expect("parse delay"),
+            RetryAfter::Delay(Duration::from_secs(120))
+        );
+        let RetryAfter::Date(date) = "Wed, 21 Oct 2015 07:28:00 GMT"
+            .parse::<RetryAfter>()
+            .expect("parse date")
+        else {
+            panic!("expected a date");
+        };
+        assert_eq!(date.unix_timestamp(), 1445412480);
+        assert_eq!(RetryAfter::Date(date).duration(), Duration::ZERO);
+        assert!("soon".parse::<RetryAfter>().is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(RateLimit::from_headers(&headers).is_none());
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("300"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("299"));
+        headers.insert(
+            "x-ratelimit-reset",
+            HeaderValue::from_static("2022-11-16T06:15:00.000Z"),
+        );
+        let subject = RateLimit::from_headers(&headers).expect("parse rate limit");
+        assert_eq!(subject.limit, 300);
+        assert_eq!(subject.remaining, 299);
+        assert_eq!(subject.reset.unix_timestamp(), 1668579300);
+    }
+
+    #[test]
+    fn test_rate_limit_delay() {
+        let mut subject = RateLimit {
+            limit: 300,
+            remaining: 1,
+            reset: OffsetDateTime::now_utc() + Duration::from_secs(60),
+        };
+        assert_eq!(subject.delay(), Duration::ZERO);
+        subject.remaining = 0;
+        assert!(subject.delay() > Duration::from_secs(50));
+        subject.reset = OffsetDateTime::now_utc() - Duration::from_secs(60);
+        assert_eq!(subject.delay(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let pacer = Pacer::new(2, Duration::from_secs(3600));
+        assert_eq!(pacer.available(), 2);
+        assert!(pacer.try_acquire());
+        assert!(pacer.clone().try_acquire());
+        assert!(!pacer.try_acquire());
+    }
+
+    #[test]
+    fn test_observe() {
+        let pacer = Pacer::new(10, Duration::from_secs(1));
+        pacer.observe_rate_limit(&RateLimit {
+            limit: 300,
+            remaining: 1,
+            reset: OffsetDateTime::now_utc() + time::Duration::minutes(5),
+        });
+        assert_eq!(pacer.available(), 1);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("60"));
+        pacer.observe(&headers);
+        assert_eq!(pacer.available(), 0);
+        assert!(!pacer.try_acquire());
+    }
+
+    #[test]
+    fn test_endpoint_family() {
+        use EndpointFamily::*;
+        assert_eq!(EndpointFamily::of("post", "/api/v1/media"), Media);
+        assert_eq!(EndpointFamily::of("PUT", "/api/v1/media/1"), General);
+        assert_eq!(
+            EndpointFamily::of("DELETE", "/api/v1/statuses/1"),
+            StatusDeletion
+        );
+        assert_eq!(
+            EndpointFamily::of("POST", "/api/v1/statuses/1/unreblog"),
+            StatusDeletion
+        );
+        assert_eq!(
+            EndpointFamily::of("POST", "/api/v1/statuses/1/reblog"),
+            General
+        );
+        assert_eq!(EndpointFamily::of("POST", "/api/v1/accounts"), Registration);
+        assert_eq!(EndpointFamily::of("POST", "/oauth/token"), Authentication);
+        assert_eq!(EndpointFamily::of("GET", "/api/v1/timelines/home"), General);
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::from(Pacer::new(3, Duration::from_secs(3600))).with_family(
+            EndpointFamily::Media,
+            Pacer::new(1, Duration::from_secs(3600)),
+        );
+        let shared = limiter.clone();
+        assert!(shared.pacer(EndpointFamily::Media).try_acquire());
+        assert_eq!(limiter.pacer(EndpointFamily::Media).available(), 0);
+        // families without a pacer of their own use the general one
+        assert_eq!(limiter.pacer(EndpointFamily::Registration).available(), 3);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("60"));
+        limiter.observe(EndpointFamily::Authentication, &headers);
+        assert_eq!(shared.pacer(EndpointFamily::General).available(), 0);
+
+        let limiter = RateLimiter::default();
+        assert_eq!(
+            limiter.pacer(EndpointFamily::General).available(),
+            DEFAULT_LIMIT
+        );
+        assert_eq!(limiter.pacer(EndpointFamily::Registration).available(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_refill() {
+        let pacer = Pacer::new(1, Duration::from_millis(50));
+        pacer.acquire().await;
+        let start = Instant::now();
+        pacer.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -1,9 +1,15 @@
 use super::{Mastodon, Result};
-use crate::{entities::itemsiter::ItemsIter, helpers::read_response::read_response, Error};
-use futures::Stream;
+use crate::{
+    entities::{itemsiter::ItemsIter, notification::Notification, status::Status},
+    helpers::read_response::read_response,
+    pacing::{RateLimit, RetryAfter},
+    Error,
+};
+use futures::{future, stream::Filter, Stream, StreamExt};
 use log::{debug, error, trace};
-use reqwest::{header::LINK, Response, Url};
+use reqwest::{header::LINK, RequestBuilder, Response, Url};
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 macro_rules! pages {
@@ -32,10 +38,12 @@ macro_rules! pages {
                     "making API request"
                 );
                 let url: String = url.to_string();
-                let response = self.mastodon.authenticated(self.mastodon.client.get(&url)).send().await?;
+                let request = self.mastodon.authenticated(self.mastodon.client.get(&url));
+                let response = self.mastodon.send(request).await?;
                 match response.error_for_status() {
                     Ok(response) => {
                         let (prev, next) = get_links(&response, self.call_id)?;
+                        self.rate_limit = RateLimit::from_headers(response.headers());
                         let response: Vec<T> = read_response(response).await?;
                         if response.is_empty() && prev.is_none() && next.is_none() {
                             debug!(
@@ -111,6 +119,9 @@ pub struct Page<T: for<'de> Deserialize<'de> + Serialize> {
     pub prev: Option<Url>,
     /// Initial set of items
     pub initial_items: Vec<T>,
+    /// The rate limit reported with the most recently fetched page, if the
+    /// server sent one.
+    pub rate_limit: Option<RateLimit>,
     pub(crate) call_id: Uuid,
 }
 
@@ -125,6 +136,7 @@ impl<'a, T: for<'de> Deserialize<'de> + Serialize> Page<T> {
         let status = response.status();
         if status.is_success() {
             let (prev, next) = get_links(&response, call_id)?;
+            let rate_limit = RateLimit::from_headers(response.headers());
             let initial_items = read_response(response).await?;
             debug!(
                 initial_items:serde = initial_items, prev:? = prev,
@@ -135,16 +147,131 @@ impl<'a, T: for<'de> Deserialize<'de> + Serialize> Page<T> {
                 initial_items,
                 next,
                 prev,
+                rate_limit,
                 mastodon,
                 call_id,
             })
         } else {
+            let retry_after = RetryAfter::from_headers(response.headers());
             let response = response.json().await?;
-            Err(Error::Api { status, response })
+            Err(Error::Api {
+                status,
+                response,
+                retry_after,
+            })
         }
     }
 }
 
+impl<T: for<'de> Deserialize<'de> + Serialize> Page<T> {
+    /// Fetch the page at the given URL, such as one saved from [`Page::next`]
+    /// or [`Page::prev`], to resume paging where an earlier run left off.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{page::Page, prelude::*};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let page = client.favourites().await.unwrap();
+    ///     let checkpoint = page.next.clone().unwrap();
+    ///     // ...later, perhaps after a restart
+    ///     let resumed: Page<Status> = Page::from_cursor(client, checkpoint).await.unwrap();
+    /// });
+    /// ```
+    pub async fn from_cursor(mastodon: Mastodon, url: Url) -> Result<Self> {
+        let call_id = Uuid::new_v4();
+        debug!(
+            url = url.as_str(), method = "get", call_id:? = call_id;
+            "resuming pagination"
+        );
+        let request = mastodon.authenticated(mastodon.client.get(url.as_str()));
+        let response = mastodon.send(request).await?;
+        Self::new(mastodon, response, call_id).await
+    }
+
+    /// Send a request for the first page of an endpoint the crate doesn't
+    /// wrap, such as one specific to a fork, and page through the results
+    /// using the `Link` headers of the responses, as for any other page.
+    ///
+    /// The request is sent with the client's access token, whichever
+    /// `reqwest::Client` it was built with.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{page::Page, prelude::*};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let request = reqwest::Client::new()
+    ///         .get(format!("{}/api/v1/fork/circles", client.data.base))
+    ///         .query(&[("limit", "40")]);
+    ///     let page: Page<List> = Page::from_request(client, request).await.unwrap();
+    /// });
+    /// ```
+    pub async fn from_request(mastodon: Mastodon, request: RequestBuilder) -> Result<Self> {
+        let call_id = Uuid::new_v4();
+        debug!(call_id:? = call_id; "making custom paged API request");
+        let request = mastodon
+            .authenticated(request)
+            .header("Accept", "application/json");
+        let response = mastodon.send(request).await?;
+        Self::new(mastodon, response, call_id).await
+    }
+
+    /// The `max_id` of the next, older, page, if there is one.
+    pub fn next_max_id(&self) -> Option<String> {
+        query_param(self.next.as_ref()?, "max_id")
+    }
+
+    /// The ID which the previous, newer, page starts after, if there is one.
+    ///
+    /// This is the `min_id` of the page's URL, or its `since_id` for servers
+    /// which use that instead.
+    pub fn prev_since_id(&self) -> Option<String> {
+        let prev = self.prev.as_ref()?;
+        query_param(prev, "min_id").or_else(|| query_param(prev, "since_id"))
+    }
+
+    /// Collect the items of this page and the pages after it, stopping once
+    /// `max_items` items have been collected or `max_pages` pages, including
+    /// this one, have been read, whichever comes first.
+    ///
+    /// Unlike [`Page::items_iter`], this returns the first error encountered
+    /// fetching a page.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let followers = client
+    ///         .followers_with_limit(&AccountId::new("some-id"), 80)
+    ///         .await
+    ///         .unwrap()
+    ///         .fetch_all_bounded(1000, 20)
+    ///         .await
+    ///         .unwrap();
+    ///     assert!(followers.len() <= 1000);
+    /// });
+    /// ```
+    pub async fn fetch_all_bounded(mut self, max_items: usize, max_pages: usize) -> Result<Vec<T>> {
+        let mut items = std::mem::take(&mut self.initial_items);
+        let mut pages = 1;
+        while items.len() < max_items && pages < max_pages {
+            match self.next_page().await? {
+                Some(page) if !page.is_empty() => items.extend(page),
+                _ => break,
+            }
+            pages += 1;
+        }
+        debug!(
+            item_count = items.len(), page_count = pages, call_id:? = self.call_id;
+            "finished fetching pages"
+        );
+        items.truncate(max_items);
+        Ok(items)
+    }
+}
+
 impl<T: Clone + for<'de> Deserialize<'de> + Serialize> Page<T> {
     /// Returns an iterator that provides a stream of `T`s
     ///
@@ -176,6 +303,142 @@ impl<T: Clone + for<'de> Deserialize<'de> + Serialize> Page<T> {
     pub fn items_iter(self) -> impl Stream<Item = T> {
         ItemsIter::new(self).stream()
     }
+
+    /// Like [`Page::items_iter`], but ends after `n` items, without fetching
+    /// any more pages.
+    pub fn take_items(self, n: usize) -> impl Stream<Item = T> {
+        ItemsIter::new(self).limit(n).stream()
+    }
+}
+
+impl<T: PageItem + Clone + for<'de> Deserialize<'de> + Serialize> Page<T> {
+    /// Like [`Page::items_iter`], but ends just before the item with the
+    /// given ID, such as the newest one seen on an earlier run, without
+    /// fetching any more pages.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// use futures_util::StreamExt;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let last_seen = StatusId::new("109");
+    ///     let unseen: Vec<Status> = client
+    ///         .get_home_timeline()
+    ///         .await
+    ///         .unwrap()
+    ///         .until_id(last_seen.as_ref())
+    ///         .collect()
+    ///         .await;
+    /// });
+    /// ```
+    pub fn until_id(self, id: &str) -> impl Stream<Item = T> {
+        let id = id.to_string();
+        self.items_iter()
+            .take_while(move |item| future::ready(item.item_id() != id))
+    }
+
+    /// Like [`Page::items_iter`], but ends at the first item created before
+    /// `time`, without fetching any more pages. Items are expected to be
+    /// ordered newest first, as Mastodon returns them.
+    pub fn since(self, time: OffsetDateTime) -> impl Stream<Item = T> {
+        self.items_iter()
+            .take_while(move |item| future::ready(item.created_at() >= time))
+    }
+}
+
+/// Items in a page which can be bounded by ID or date with
+/// [`Page::until_id`] and [`Page::since`].
+pub trait PageItem {
+    /// The ID of the item, which the server paginates by.
+    fn item_id(&self) -> &str;
+    /// When the item was created.
+    fn created_at(&self) -> OffsetDateTime;
+}
+
+impl PageItem for Status {
+    fn item_id(&self) -> &str {
+        self.id.as_ref()
+    }
+
+    fn created_at(&self) -> OffsetDateTime {
+        self.created_at
+    }
+}
+
+impl PageItem for Notification {
+    fn item_id(&self) -> &str {
+        self.id.as_ref()
+    }
+
+    fn created_at(&self) -> OffsetDateTime {
+        self.created_at
+    }
+}
+
+/// Whether a status is a reblog of another one.
+pub(crate) fn is_reblog(status: &Status) -> bool {
+    status.reblog.is_some()
+}
+
+/// Whether a status replies to someone else. Replies to oneself, which
+/// continue a thread, don't count, as with the `exclude_replies` option of
+/// [`Mastodon::statuses`].
+pub(crate) fn is_reply(status: &Status) -> bool {
+    status.in_reply_to_id.is_some()
+        && status.in_reply_to_account_id.as_ref() != Some(&status.account.id)
+}
+
+/// Combinators for streams of statuses, such as those returned by
+/// [`Page::items_iter`].
+///
+/// The home timeline can't exclude reblogs or replies on the server, as
+/// other timelines can, so these skip them as they arrive instead.
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use mastodon_async::{page::StatusStreamExt, prelude::*};
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let originals: Vec<Status> = client
+///         .get_home_timeline()
+///         .await
+///         .unwrap()
+///         .items_iter()
+///         .without_reblogs()
+///         .without_replies()
+///         .take(20)
+///         .collect()
+///         .await;
+/// });
+/// ```
+pub trait StatusStreamExt: Stream<Item = Status> + Sized {
+    /// Skip reblogs.
+    fn without_reblogs(self) -> SkipStatuses<Self> {
+        let keep: fn(&Status) -> future::Ready<bool> = |status| future::ready(!is_reblog(status));
+        self.filter(keep)
+    }
+
+    /// Skip replies to other accounts. Replies continuing a thread by the
+    /// same account are kept.
+    fn without_replies(self) -> SkipStatuses<Self> {
+        let keep: fn(&Status) -> future::Ready<bool> = |status| future::ready(!is_reply(status));
+        self.filter(keep)
+    }
+}
+
+impl<S: Stream<Item = Status>> StatusStreamExt for S {}
+
+/// Stream for [`StatusStreamExt::without_reblogs`] and
+/// [`StatusStreamExt::without_replies`].
+pub type SkipStatuses<S> = Filter<S, future::Ready<bool>, fn(&Status) -> future::Ready<bool>>;
+
+/// The value of the given query parameter of `url`, if it has it.
+fn query_param(url: &Url, name: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
 }
 
 fn get_links(response: &Response, call_id: Uuid) -> Result<(Option<Url>, Option<Url>)> {
@@ -204,3 +467,202 @@ fn get_links(response: &Response, call_id: Uuid) -> Result<(Option<Url>, Option<
 
     Ok((prev, next))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entities::{AccountId, StatusId},
+        fixtures, Data,
+    };
+
+    fn page(items: Vec<u32>) -> Page<u32> {
+        Page {
+            mastodon: Mastodon::from(Data::default()),
+            next: None,
+            prev: None,
+            initial_items: items,
+            rate_limit: None,
+            call_id: Uuid::new_v4(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_from_request() {
+        use std::{
+            io::{BufRead, BufReader, Write},
+            net::TcpListener,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let base = format!("http://{}", listener.local_addr().expect("address"));
+        let link = format!(r#"<{base}/api/v1/fork/circles?max_id=2>; rel="next""#);
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let mut lines = BufReader::new(stream.try_clone().expect("clone")).lines();
+            let request_line = lines.next().expect("request").expect("read");
+            let headers: Vec<_> = lines
+                .map(|line| line.expect("read"))
+                .take_while(|line| !line.is_empty())
+                .collect();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nLink: {link}\r\n\
+                 Content-Length: 5\r\nConnection: close\r\n\r\n[1,2]"
+            )
+            .expect("write");
+            (request_line, headers)
+        });
+
+        let client = Mastodon::from(Data {
+            base: base.clone().into(),
+            token: "token".into(),
+            ..Default::default()
+        });
+        let request = reqwest::Client::new()
+            .get(format!("{base}/api/v1/fork/circles"))
+            .query(&[("limit", "2")]);
+        let page: Page<u32> = Page::from_request(client, request).await.expect("page");
+        assert_eq!(page.initial_items, [1, 2]);
+        assert_eq!(page.next_max_id().as_deref(), Some("2"));
+        assert!(page.prev.is_none());
+
+        let (request_line, headers) = server.join().expect("server");
+        assert_eq!(request_line, "GET /api/v1/fork/circles?limit=2 HTTP/1.1");
+        assert!(headers
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case("authorization: Bearer token")));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_bounded() {
+        let items = page(vec![1, 2, 3])
+            .fetch_all_bounded(2, 10)
+            .await
+            .expect("fetch");
+        assert_eq!(items, [1, 2]);
+        let items = page(vec![1, 2, 3])
+            .fetch_all_bounded(10, 10)
+            .await
+            .expect("fetch");
+        assert_eq!(items, [1, 2, 3]);
+        let items = page(vec![1, 2, 3])
+            .fetch_all_bounded(10, 0)
+            .await
+            .expect("fetch");
+        assert_eq!(items, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_take_items() {
+        let items: Vec<_> = page(vec![1, 2, 3]).take_items(2).collect().await;
+        assert_eq!(items, [1, 2]);
+        let items: Vec<_> = page(vec![1, 2, 3]).take_items(5).collect().await;
+        assert_eq!(items, [1, 2, 3]);
+        let items: Vec<_> = page(vec![1, 2, 3]).take_items(0).collect().await;
+        assert!(items.is_empty());
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    struct Item(String, i64);
+
+    impl PageItem for Item {
+        fn item_id(&self) -> &str {
+            &self.0
+        }
+
+        fn created_at(&self) -> OffsetDateTime {
+            OffsetDateTime::from_unix_timestamp(self.1).unwrap()
+        }
+    }
+
+    fn status(id: &str, reblog: bool, in_reply_to_account_id: Option<&str>) -> Status {
+        let mut status = fixtures::status(id);
+        if let Some(account_id) = in_reply_to_account_id {
+            status.in_reply_to_id = Some(StatusId::new("100"));
+            status.in_reply_to_account_id = Some(AccountId::new(account_id));
+        }
+        if reblog {
+            status.reblog = Some(Box::new(status.clone()));
+        }
+        status
+    }
+
+    #[tokio::test]
+    async fn test_without_reblogs_and_replies() {
+        let timeline = || Page {
+            mastodon: Mastodon::from(Data::default()),
+            next: None,
+            prev: None,
+            initial_items: vec![
+                status("1", false, None),
+                status("2", true, None),
+                status("3", false, Some("2")),
+                status("4", false, Some("1")),
+            ],
+            rate_limit: None,
+            call_id: Uuid::new_v4(),
+        };
+        let ids = |statuses: Vec<Status>| -> Vec<String> {
+            statuses.into_iter().map(|it| it.id.to_string()).collect()
+        };
+        let statuses = timeline().items_iter().without_reblogs().collect().await;
+        assert_eq!(ids(statuses), ["1", "3", "4"]);
+        // a reply to oneself continues a thread, and is kept
+        let statuses = timeline().items_iter().without_replies().collect().await;
+        assert_eq!(ids(statuses), ["1", "2", "4"]);
+        let statuses = timeline()
+            .items_iter()
+            .without_reblogs()
+            .without_replies()
+            .collect()
+            .await;
+        assert_eq!(ids(statuses), ["1", "4"]);
+    }
+
+    #[tokio::test]
+    async fn test_bounds() {
+        let items = || Page {
+            mastodon: Mastodon::from(Data::default()),
+            next: None,
+            prev: None,
+            initial_items: vec![
+                Item("3".to_string(), 300),
+                Item("2".to_string(), 200),
+                Item("1".to_string(), 100),
+            ],
+            rate_limit: None,
+            call_id: Uuid::new_v4(),
+        };
+        let ids = |items: Vec<Item>| items.into_iter().map(|it| it.0).collect::<Vec<_>>();
+        assert_eq!(ids(items().until_id("2").collect().await), ["3"]);
+        assert_eq!(ids(items().until_id("0").collect().await), ["3", "2", "1"]);
+        let since = OffsetDateTime::from_unix_timestamp(200).unwrap();
+        assert_eq!(ids(items().since(since).collect().await), ["3", "2"]);
+    }
+
+    #[test]
+    fn test_cursors() {
+        let mut page = page(vec![]);
+        assert_eq!(page.next_max_id(), None);
+        assert_eq!(page.prev_since_id(), None);
+        page.next = Some(
+            "https://example.com/api/v1/favourites?limit=20&max_id=103"
+                .parse()
+                .unwrap(),
+        );
+        page.prev = Some(
+            "https://example.com/api/v1/favourites?limit=20&min_id=109"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(page.next_max_id().as_deref(), Some("103"));
+        assert_eq!(page.prev_since_id().as_deref(), Some("109"));
+        page.prev = Some(
+            "https://example.com/api/v1/favourites?since_id=110"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(page.prev_since_id().as_deref(), Some("110"));
+    }
+}
@@ -0,0 +1,224 @@
+use log::debug;
+
+use crate::{
+    entities::{
+        pleroma::{Chat, ChatMessage, EmojiReaction},
+        prelude::*,
+    },
+    errors::Result,
+    helpers::read_response::read_response,
+    mastodon::pleroma_reaction_path,
+    routes::Route,
+    Mastodon, Page,
+};
+
+/// The extension routes, which the route macros don't know about.
+pub(crate) const ROUTES: &[Route] = &[
+    Route {
+        name: "pleroma_reactions",
+        method: "get",
+        path: "/api/v1/pleroma/statuses/{}/reactions",
+        returns: "Vec<EmojiReaction>",
+        paged: false,
+    },
+    Route {
+        name: "pleroma_react",
+        method: "put",
+        path: "/api/v1/pleroma/statuses/{}/reactions/{}",
+        returns: "Status",
+        paged: false,
+    },
+    Route {
+        name: "pleroma_unreact",
+        method: "delete",
+        path: "/api/v1/pleroma/statuses/{}/reactions/{}",
+        returns: "Status",
+        paged: false,
+    },
+    Route {
+        name: "pleroma_chats",
+        method: "get",
+        path: "/api/v2/pleroma/chats",
+        returns: "Chat",
+        paged: true,
+    },
+    Route {
+        name: "pleroma_chat",
+        method: "get",
+        path: "/api/v1/pleroma/chats/{}",
+        returns: "Chat",
+        paged: false,
+    },
+    Route {
+        name: "pleroma_chat_with",
+        method: "post",
+        path: "/api/v1/pleroma/chats/by-account-id/{}",
+        returns: "Chat",
+        paged: false,
+    },
+    Route {
+        name: "pleroma_chat_messages",
+        method: "get",
+        path: "/api/v1/pleroma/chats/{}/messages",
+        returns: "ChatMessage",
+        paged: true,
+    },
+    Route {
+        name: "pleroma_send_chat_message",
+        method: "post",
+        path: "/api/v1/pleroma/chats/{}/messages",
+        returns: "ChatMessage",
+        paged: false,
+    },
+    Route {
+        name: "pleroma_delete_chat_message",
+        method: "delete",
+        path: "/api/v1/pleroma/chats/{}/messages/{}",
+        returns: "ChatMessage",
+        paged: false,
+    },
+    Route {
+        name: "pleroma_read_chat",
+        method: "post",
+        path: "/api/v1/pleroma/chats/{}/read",
+        returns: "Chat",
+        paged: false,
+    },
+];
+
+impl Mastodon {
+    /// The emoji reactions to a status, with the accounts which reacted
+    /// with each.
+    pub async fn pleroma_reactions(&self, id: &StatusId) -> Result<Vec<EmojiReaction>> {
+        self.get(self.route(format!("/api/v1/pleroma/statuses/{id}/reactions")))
+            .await
+    }
+
+    /// React to a status with an emoji: a unicode emoji, or the shortcode of
+    /// a custom one, with or without the surrounding colons.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let status = client.pleroma_react(&StatusId::new("1"), "☕").await.unwrap();
+    ///     let reactions = status.pleroma.unwrap().emoji_reactions;
+    ///     assert!(reactions.iter().any(|it| it.name == "☕" && it.me));
+    /// });
+    /// ```
+    pub async fn pleroma_react(&self, id: &StatusId, emoji: &str) -> Result<Status> {
+        let url = self.route(pleroma_reaction_path(id, emoji));
+        debug!(url = url, method = "put"; "making API request");
+        let response = self.send(self.authenticated(self.client.put(&url))).await?;
+        read_response(response).await
+    }
+
+    /// Take back a reaction to a status.
+    pub async fn pleroma_unreact(&self, id: &StatusId, emoji: &str) -> Result<Status> {
+        self.delete(self.route(pleroma_reaction_path(id, emoji)))
+            .await
+    }
+
+    /// The user's chats, most recently updated first.
+    pub async fn pleroma_chats(&self) -> Result<Page<Chat>> {
+        let url = self.route("/api/v2/pleroma/chats");
+        Page::from_request(self.clone(), self.client.get(&url)).await
+    }
+
+    /// A single chat.
+    pub async fn pleroma_chat(&self, id: &ChatId) -> Result<Chat> {
+        self.get(self.route(format!("/api/v1/pleroma/chats/{id}")))
+            .await
+    }
+
+    /// The chat with an account, which is started if there isn't one yet.
+    pub async fn pleroma_chat_with(&self, id: &AccountId) -> Result<Chat> {
+        self.post(self.route(format!("/api/v1/pleroma/chats/by-account-id/{id}")))
+            .await
+    }
+
+    /// The messages in a chat, newest first.
+    pub async fn pleroma_chat_messages(&self, id: &ChatId) -> Result<Page<ChatMessage>> {
+        let url = self.route(format!("/api/v1/pleroma/chats/{id}/messages"));
+        Page::from_request(self.clone(), self.client.get(&url)).await
+    }
+
+    /// Send a message in a chat.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let chat = client.pleroma_chat_with(&AccountId::new("9tga")).await.unwrap();
+    ///     let message = forms::pleroma::ChatMessage::text("hi");
+    ///     client.pleroma_send_chat_message(&chat.id, message).await.unwrap();
+    /// });
+    /// ```
+    pub async fn pleroma_send_chat_message(
+        &self,
+        id: &ChatId,
+        message: forms::pleroma::ChatMessage,
+    ) -> Result<ChatMessage> {
+        let url = self.route(format!("/api/v1/pleroma/chats/{id}/messages"));
+        debug!(url = url, method = "post", body:serde = message; "making API request");
+        let request = self.authenticated(self.client.post(&url)).json(&message);
+        let response = self.send(request).await?;
+        read_response(response).await
+    }
+
+    /// Delete one of the user's messages from a chat.
+    pub async fn pleroma_delete_chat_message(
+        &self,
+        id: &ChatId,
+        message_id: &ChatMessageId,
+    ) -> Result<ChatMessage> {
+        self.delete(self.route(format!("/api/v1/pleroma/chats/{id}/messages/{message_id}")))
+            .await
+    }
+
+    /// Mark the messages in a chat as read, up to and including the given
+    /// one.
+    pub async fn pleroma_read_chat(&self, id: &ChatId, up_to: &ChatMessageId) -> Result<Chat> {
+        let url = self.route(format!("/api/v1/pleroma/chats/{id}/read"));
+        let body = json!({ "last_read_id": up_to });
+        debug!(url = url, method = "post", body:serde = body; "making API request");
+        let request = self.authenticated(self.client.post(&url)).json(&body);
+        let response = self.send(request).await?;
+        read_response(response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, Server};
+
+    #[tokio::test]
+    async fn test_reaction_path() {
+        let server = Server::with_body("[]");
+        let client = fixtures::client(&server);
+        let reactions = client
+            .pleroma_reactions(&StatusId::new("1"))
+            .await
+            .expect("reactions");
+        assert!(reactions.is_empty());
+        // the request is made, but an empty list isn't a status
+        assert!(client
+            .pleroma_unreact(&StatusId::new("1"), ":blob:")
+            .await
+            .is_err());
+        assert_eq!(
+            server.targets(),
+            [
+                "GET /api/v1/pleroma/statuses/1/reactions",
+                "DELETE /api/v1/pleroma/statuses/1/reactions/blob",
+            ]
+        );
+        assert_eq!(
+            pleroma_reaction_path(&StatusId::new("1"), "☕"),
+            "/api/v1/pleroma/statuses/1/reactions/%E2%98%95"
+        );
+    }
+}
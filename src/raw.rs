@@ -0,0 +1,160 @@
+use async_trait::async_trait;
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{errors::Result, helpers::read_response::read_response, Mastodon};
+
+/// A response body deserialized into `T`, kept alongside the JSON it was
+/// deserialized from, so that a body which doesn't quite match `T` isn't
+/// lost.
+///
+/// ```
+/// use mastodon_async::{prelude::*, raw::Typed};
+/// use serde_json::json;
+///
+/// // a server which leaves out the tag's history
+/// let typed = Typed::<Tag>::from_value(json!({
+///     "name": "tea",
+///     "url": "https://example.com/tags/tea",
+/// }));
+/// assert!(typed.parsed.is_err());
+/// assert_eq!(typed.raw["name"], "tea");
+/// ```
+#[derive(Debug)]
+pub struct Typed<T> {
+    /// The body deserialized into `T`, or why it couldn't be.
+    pub parsed: Result<T>,
+    /// The body as it was received.
+    pub raw: Value,
+}
+
+impl<T: DeserializeOwned> Typed<T> {
+    /// Deserialize `raw` into `T`, keeping it either way.
+    pub fn from_value(raw: Value) -> Self {
+        let parsed = serde_json::from_value(raw.clone()).map_err(Into::into);
+        Typed { parsed, raw }
+    }
+}
+
+impl<T> Typed<T> {
+    /// The deserialized body, or the error which prevented it, dropping the
+    /// raw body.
+    pub fn into_result(self) -> Result<T> {
+        self.parsed
+    }
+
+    /// The deserialized body, or `fallback` called with the raw body if it
+    /// couldn't be deserialized, e.g. to pick out the fields which could be.
+    pub fn or_else_raw(self, fallback: impl FnOnce(Value) -> Result<T>) -> Result<T> {
+        match self.parsed {
+            Ok(parsed) => Ok(parsed),
+            Err(_) => fallback(self.raw),
+        }
+    }
+}
+
+/// Reading a response as raw JSON, as well as or instead of deserializing
+/// it into an entity.
+///
+/// Responses with an error status still fail with
+/// [`Error::Api`](crate::Error::Api), as with any other request, and so do
+/// bodies which aren't JSON at all.
+#[async_trait]
+pub trait ResponseExt {
+    /// The body of the response, as JSON.
+    async fn raw_json(self) -> Result<Value>;
+
+    /// The body of the response, deserialized into `T` and as JSON.
+    async fn typed<T: DeserializeOwned>(self) -> Result<Typed<T>>;
+}
+
+#[async_trait]
+impl ResponseExt for Response {
+    async fn raw_json(self) -> Result<Value> {
+        read_response(self).await
+    }
+
+    async fn typed<T: DeserializeOwned>(self) -> Result<Typed<T>> {
+        Ok(Typed::from_value(self.raw_json().await?))
+    }
+}
+
+impl Mastodon {
+    /// Make an authenticated `GET` request, returning the body as JSON
+    /// without deserializing it into an entity. `path` is relative to the
+    /// instance's base URL, e.g. `/api/v1/statuses/1`, and may include a
+    /// query string.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let status = client.get_raw("/api/v1/statuses/1").await.unwrap();
+    ///     println!("{}", status["content"]);
+    /// });
+    /// ```
+    pub async fn get_raw(&self, path: &str) -> Result<Value> {
+        self.get(self.route(path)).await
+    }
+
+    /// Make an authenticated `GET` request, deserializing the body into `T`
+    /// but keeping it as JSON too, so that the request isn't wasted if the
+    /// server sends something `T` doesn't expect.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let typed = client.get_typed::<Status>("/api/v1/statuses/1").await.unwrap();
+    ///     let content = match typed.parsed {
+    ///         Ok(status) => status.content,
+    ///         Err(_) => typed.raw["content"].as_str().unwrap_or_default().to_string(),
+    ///     };
+    /// });
+    /// ```
+    pub async fn get_typed<T: DeserializeOwned>(&self, path: &str) -> Result<Typed<T>> {
+        Ok(Typed::from_value(self.get_raw(path).await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entities::prelude::*,
+        fixtures::{self, Server},
+    };
+
+    #[tokio::test]
+    async fn test_get_typed() {
+        // the second entry isn't a tag
+        let server = Server::with_body(
+            r#"[
+                {"name": "tea", "url": "https://example.com/tags/tea", "history": []},
+                {"name": 5}
+            ]"#,
+        );
+        let client = fixtures::client(&server);
+        let typed: Typed<Vec<Tag>> = client
+            .get_typed("/api/v1/followed_tags")
+            .await
+            .expect("request");
+        assert!(typed.parsed.is_err());
+        assert_eq!(typed.raw[0]["name"], "tea");
+        let tags = typed
+            .or_else_raw(|raw| {
+                Ok(raw
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|tag| serde_json::from_value(tag.clone()).ok())
+                    .collect())
+            })
+            .expect("fallback");
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "tea");
+    }
+}
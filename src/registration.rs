@@ -1,11 +1,12 @@
 use log::{debug, error, trace};
-use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use reqwest::Client;
+use time::OffsetDateTime;
+use url::Url;
 use uuid::Uuid;
 
 use crate::{
-    entities::forms, entities::prelude::*, helpers::read_response::read_response, Data, Error,
-    Mastodon, Result,
+    builder::HttpConfig, entities::forms, entities::prelude::*,
+    helpers::read_response::read_response, mastodon::parse_base, Data, Error, Mastodon, Result,
 };
 
 const DEFAULT_REDIRECT_URI: &str = "urn:ietf:wg:oauth:2.0:oob";
@@ -14,7 +15,7 @@ const DEFAULT_REDIRECT_URI: &str = "urn:ietf:wg:oauth:2.0:oob";
 /// you cache your data struct to avoid registering on every run.
 #[derive(Debug, Clone)]
 pub struct Registration {
-    base: String,
+    base: Url,
     client: Client,
     app_builder: forms::ApplicationBuilder,
     force_login: bool,
@@ -33,8 +34,33 @@ fn default_redirect_uri() -> String {
 }
 
 #[derive(Serialize, Deserialize)]
-struct AccessToken {
-    access_token: String,
+pub(crate) struct AccessToken {
+    pub(crate) access_token: String,
+    #[serde(default, skip_serializing)]
+    pub(crate) refresh_token: Option<String>,
+    #[serde(default)]
+    pub(crate) expires_in: Option<u64>,
+    #[serde(default)]
+    pub(crate) scope: Option<Scopes>,
+}
+
+impl AccessToken {
+    /// Store the token in `data`, replacing the previous one.
+    pub(crate) fn update(self, data: &mut Data) {
+        data.token = self.access_token.into();
+        data.expires_at = self
+            .expires_in
+            .and_then(|secs| i64::try_from(secs).ok())
+            .map(|secs| OffsetDateTime::now_utc() + time::Duration::seconds(secs));
+        // servers may keep the same refresh token instead of issuing a new one
+        if let Some(refresh_token) = self.refresh_token {
+            data.refresh_token = Some(refresh_token.into());
+        }
+        // a refreshed token keeps the scopes of the one it replaces
+        if let Some(scope) = self.scope {
+            data.scopes = Some(scope);
+        }
+    }
 }
 
 impl Registration {
@@ -42,9 +68,16 @@ impl Registration {
     /// ```
     /// use mastodon_async::prelude::*;
     ///
-    /// let registration = Registration::new("https://botsin.space");
+    /// let registration = Registration::new("https://botsin.space").unwrap();
     /// ```
-    pub fn new<I: Into<String>>(base: I) -> Self {
+    ///
+    /// ## Errors
+    /// If `base` isn't a valid instance URL, or is a plain `http` one other
+    /// than a loopback address or an onion service. Use
+    /// [`Registration::new_with_config`] with
+    /// [`HttpConfig::allow_plaintext`](crate::HttpConfig::allow_plaintext) to
+    /// register with such an instance anyway.
+    pub fn new<I: Into<String>>(base: I) -> Result<Self> {
         Registration::new_with_client(base, Client::new())
     }
 
@@ -54,24 +87,40 @@ impl Registration {
     /// use mastodon_async::prelude::*;
     ///
     /// let client = reqwest::Client::builder().user_agent("my cool app").build().unwrap();
-    /// let registration = Registration::new_with_client("https://botsin.space", client);
+    /// let registration = Registration::new_with_client("https://botsin.space", client).unwrap();
     /// ```
-    pub fn new_with_client<I: Into<String>>(base: I, client: Client) -> Self {
-        Registration {
-            base: base.into(),
-            client,
-            app_builder: forms::ApplicationBuilder::default(),
-            force_login: false,
-        }
+    ///
+    /// ## Errors
+    /// As for [`Registration::new`].
+    pub fn new_with_client<I: Into<String>>(base: I, client: Client) -> Result<Self> {
+        let base = parse_base(&base.into(), false)?;
+        Ok(Registration::with_base(base, client))
+    }
+
+    /// Construct a new registration process to the instance of the `base` url,
+    /// with an HTTP client set up with `config`. The client made by
+    /// [`Registration::complete`] shares it.
+    ///
+    /// ## Errors
+    /// If `base` is plain `http` and that isn't allowed by `config`, as for
+    /// [`MastodonUnauthenticated::new`](crate::MastodonUnauthenticated::new),
+    /// or if the HTTP client can't be built. See [`HttpConfig::client`].
+    pub fn new_with_config<I: Into<String>>(base: I, config: &HttpConfig) -> Result<Self> {
+        let base = parse_base(&base.into(), config.allow_plaintext)?;
+        Ok(Registration::with_base(base, config.client()?))
     }
 }
 
 impl Registration {
     #[allow(dead_code)]
-    pub(crate) fn with_sender<I: Into<String>>(base: I) -> Self {
+    pub(crate) fn with_sender<I: Into<String>>(base: I) -> Result<Self> {
+        Registration::new(base)
+    }
+
+    fn with_base(base: Url, client: Client) -> Self {
         Registration {
-            base: base.into(),
-            client: Client::new(),
+            base,
+            client,
             app_builder: forms::ApplicationBuilder::default(),
             force_login: false,
         }
@@ -123,6 +172,7 @@ impl Registration {
     ///     app.client_name("mastodon-async_test");
     ///
     ///     let registration = Registration::new("https://botsin.space")
+    ///         .unwrap()
     ///         .register(app)
     ///         .await
     ///         .unwrap();
@@ -160,6 +210,7 @@ impl Registration {
     ///
     /// tokio_test::block_on(async {
     ///     let registration = Registration::new("https://botsin.space")
+    ///         .unwrap()
     ///         .client_name("mastodon-async_test")
     ///         .build()
     ///         .await
@@ -189,16 +240,16 @@ impl Registration {
     }
 
     async fn send_app(&self, app: &forms::Application) -> Result<OAuth> {
-        let url = format!("{}/api/v1/apps", self.base);
+        let url = format!("{}/api/v1/apps", base_str(&self.base));
         let call_id = Uuid::new_v4();
         debug!(url = url, app:serde = app, call_id:? = call_id; "registering app");
         let response = self.client.post(&url).json(&app).send().await?;
 
         match response.error_for_status() {
             Ok(response) => {
-                let response = read_response(response).await?;
+                let response: OAuth = read_response(response).await?;
                 debug!(
-                    response:serde = response, app:serde = app,
+                    client_id = response.client_id, app:serde = app,
                     url = url, method = stringify!($method),
                     call_id:? = call_id;
                     "received API response"
@@ -234,7 +285,8 @@ impl Registered {
     ///         "https://example.com/redirect",
     ///         Scopes::read_all(),
     ///         false,
-    ///     );
+    ///     )
+    ///     .unwrap();
     ///     let url = registration.authorize_url().unwrap();
     ///     // Here you now need to open the url in the browser
     ///     // And handle a the redirect url coming back with the code.
@@ -244,6 +296,9 @@ impl Registered {
     ///     println!("{:?}", mastodon.get_home_timeline().await.unwrap().initial_items);
     /// });
     /// ```
+    ///
+    /// ## Errors
+    /// If `base` isn't a valid instance URL, as for [`Registration::new`].
     pub fn from_parts(
         base: &str,
         client_id: &str,
@@ -251,16 +306,16 @@ impl Registered {
         redirect: &str,
         scopes: Scopes,
         force_login: bool,
-    ) -> Registered {
-        Registered {
-            base: base.to_string(),
+    ) -> Result<Registered> {
+        Ok(Registered {
+            base: parse_base(base, false)?,
             client: Client::new(),
             client_id: client_id.to_string(),
             client_secret: client_secret.to_string(),
             redirect: redirect.to_string(),
             scopes,
             force_login,
-        }
+        })
     }
 }
 
@@ -287,7 +342,8 @@ impl Registered {
     ///     orig_redirect,
     ///     orig_scopes.clone(),
     ///     orig_force_login,
-    /// );
+    /// )
+    /// .unwrap();
     ///
     /// let (base, client_id, client_secret, redirect, scopes, force_login) = registered.into_parts();
     ///
@@ -300,7 +356,7 @@ impl Registered {
     /// ```
     pub fn into_parts(self) -> (String, String, String, String, Scopes, bool) {
         (
-            self.base,
+            base_str(&self.base).to_string(),
             self.client_id,
             self.client_secret,
             self.redirect,
@@ -312,33 +368,29 @@ impl Registered {
     /// Returns the full url needed for authorization. This needs to be opened
     /// in a browser.
     pub fn authorize_url(&self) -> Result<String> {
-        let scopes = format!("{}", self.scopes);
-        let scopes: String = utf8_percent_encode(&scopes, NON_ALPHANUMERIC).collect();
-        let url = if self.force_login {
-            format!(
-                "{}/oauth/authorize?client_id={}&redirect_uri={}&scope={}&force_login=true&\
-                 response_type=code",
-                self.base, self.client_id, self.redirect, scopes,
-            )
-        } else {
-            format!(
-                "{}/oauth/authorize?client_id={}&redirect_uri={}&scope={}&response_type=code",
-                self.base, self.client_id, self.redirect, scopes,
+        let request = forms::oauth::AuthorizationRequest {
+            force_login: self.force_login,
+            ..forms::oauth::AuthorizationRequest::new(
+                &self.client_id,
+                &self.redirect,
+                self.scopes.clone(),
             )
         };
-
-        Ok(url)
+        let query = serde_urlencoded::to_string(&request)?;
+        Ok(format!("{}/oauth/authorize?{query}", base_str(&self.base)))
     }
 
     /// Construct authentication data once token is known
-    fn registered(&self, token: String) -> Data {
-        Data {
-            base: self.base.clone().into(),
+    fn registered(&self, token: AccessToken) -> Data {
+        let mut data = Data {
+            base: base_str(&self.base).to_string().into(),
             client_id: self.client_id.clone().into(),
             client_secret: self.client_secret.clone().into(),
             redirect: self.redirect.clone().into(),
-            token: token.into(),
-        }
+            ..Default::default()
+        };
+        token.update(&mut data);
+        data
     }
 
     /// Create an access token from the client id, client secret, and code
@@ -347,33 +399,43 @@ impl Registered {
     where
         C: AsRef<str>,
     {
-        let url =
-            format!(
-            "{}/oauth/token?client_id={}&client_secret={}&code={}&grant_type=authorization_code&\
-             redirect_uri={}",
-            self.base, self.client_id, self.client_secret, code.as_ref(), self.redirect
+        let url = format!("{}/oauth/token", base_str(&self.base));
+        let form = forms::oauth::TokenRequest::authorization_code(
+            &self.client_id,
+            &self.client_secret,
+            code.as_ref(),
+            &self.redirect,
         );
         debug!(url = url; "completing registration");
-        let response = self.client.post(&url).send().await?;
+        let response = self.client.post(&url).form(&form).send().await?;
         debug!(
             status:serde = crate::helpers::log::Status::from(&response), url = url,
             headers:serde = crate::helpers::log::Headers::from(&response);
             "received API response"
         );
         let token: AccessToken = read_response(response).await?;
-        debug!(url = url, body:serde = token; "parsed response body");
-        let data = self.registered(token.access_token);
-        trace!(auth_data:serde = data; "registered");
+        debug!(
+            url = url, expires_in:? = token.expires_in, scope:? = token.scope;
+            "parsed response body"
+        );
+        let data = self.registered(token);
+        trace!(base = data.base.as_ref(), client_id = data.client_id.as_ref(); "registered");
 
         Ok(Mastodon::new(self.client.clone(), data))
     }
 }
 
+/// The instance URL as it is stored in [`Data::base`], without a trailing
+/// slash, which the routes are appended to.
+fn base_str(base: &Url) -> &str {
+    base.as_str().trim_end_matches('/')
+}
+
 /// Represents the state of the auth flow when the app has been registered but
 /// the user is not authenticated
 #[derive(Debug, Clone)]
 pub struct Registered {
-    base: String,
+    base: Url,
     client: Client,
     client_id: String,
     client_secret: String,
@@ -388,24 +450,42 @@ mod tests {
 
     #[test]
     fn test_registration_new() {
-        let r = Registration::new("https://example.com");
-        assert_eq!(r.base, "https://example.com".to_string());
+        let r = Registration::new("https://example.com").expect("registration");
+        assert_eq!(r.base.as_str(), "https://example.com/");
         assert_eq!(r.app_builder, forms::ApplicationBuilder::default());
     }
 
+    #[test]
+    fn test_registration_refuses_plaintext() {
+        assert!(Registration::new("http://example.com").is_err());
+        assert!(Registration::new("http://localhost:3000").is_ok());
+        assert!(Registration::new("http://127.0.0.1:3000").is_ok());
+        let onion = "http://2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion";
+        assert!(Registration::new(onion).is_ok());
+        assert!(Registered::from_parts(
+            "http://example.com",
+            "app",
+            "s3cr3t",
+            "urn:ietf:wg:oauth:2.0:oob",
+            Scopes::read_all(),
+            false,
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_registration_with_sender() {
-        let r = Registration::with_sender("https://example.com");
-        assert_eq!(r.base, "https://example.com".to_string());
+        let r = Registration::with_sender("https://example.com").expect("registration");
+        assert_eq!(r.base.as_str(), "https://example.com/");
         assert_eq!(r.app_builder, forms::ApplicationBuilder::default());
     }
 
     #[test]
     fn test_set_client_name() {
-        let mut r = Registration::new("https://example.com");
+        let mut r = Registration::new("https://example.com").expect("registration");
         r.client_name("foo-test");
 
-        assert_eq!(r.base, "https://example.com".to_string());
+        assert_eq!(r.base.as_str(), "https://example.com/");
         assert_eq!(
             &mut r.app_builder,
             forms::ApplicationBuilder::default().client_name("foo-test")
@@ -414,10 +494,10 @@ mod tests {
 
     #[test]
     fn test_set_redirect_uris() {
-        let mut r = Registration::new("https://example.com");
+        let mut r = Registration::new("https://example.com").expect("registration");
         r.redirect_uris("https://foo.com");
 
-        assert_eq!(r.base, "https://example.com".to_string());
+        assert_eq!(r.base.as_str(), "https://example.com/");
         assert_eq!(
             &mut r.app_builder,
             forms::ApplicationBuilder::default().redirect_uris("https://foo.com")
@@ -426,10 +506,10 @@ mod tests {
 
     #[test]
     fn test_set_scopes() {
-        let mut r = Registration::new("https://example.com");
+        let mut r = Registration::new("https://example.com").expect("registration");
         r.scopes(Scopes::all());
 
-        assert_eq!(r.base, "https://example.com".to_string());
+        assert_eq!(r.base.as_str(), "https://example.com/");
         assert_eq!(
             &mut r.app_builder,
             forms::ApplicationBuilder::default().scopes(Scopes::all())
@@ -438,18 +518,68 @@ mod tests {
 
     #[test]
     fn test_set_website() {
-        let mut r = Registration::new("https://example.com");
+        let mut r = Registration::new("https://example.com").expect("registration");
         r.website("https://website.example.com");
 
-        assert_eq!(r.base, "https://example.com".to_string());
+        assert_eq!(r.base.as_str(), "https://example.com/");
         assert_eq!(
             &mut r.app_builder,
             forms::ApplicationBuilder::default().website("https://website.example.com")
         );
     }
 
+    #[test]
+    fn test_authorize_url() {
+        let registered = Registered::from_parts(
+            "https://example.com",
+            "app",
+            "s3cr3t",
+            "urn:ietf:wg:oauth:2.0:oob",
+            Scopes::read_all() | Scopes::write_all(),
+            true,
+        )
+        .expect("registered");
+        assert_eq!(
+            registered.authorize_url().expect("url"),
+            "https://example.com/oauth/authorize?client_id=app&\
+             redirect_uri=urn%3Aietf%3Awg%3Aoauth%3A2.0%3Aoob&scope=read+write&\
+             response_type=code&force_login=true"
+        );
+    }
+
     #[test]
     fn test_default_redirect_uri() {
         assert_eq!(&default_redirect_uri()[..], DEFAULT_REDIRECT_URI);
     }
+
+    #[test]
+    fn test_access_token_update() {
+        let mut data = Data::default();
+        let token: AccessToken = serde_json::from_str(
+            r#"{
+                "access_token": "first",
+                "refresh_token": "refresh",
+                "expires_in": 3600,
+                "scope": "read write:statuses"
+            }"#,
+        )
+        .expect("deserialize");
+        token.update(&mut data);
+        assert_eq!(data.token, "first");
+        assert_eq!(data.refresh_token.as_deref(), Some("refresh"));
+        assert_eq!(
+            data.scopes,
+            Some(Scopes::read_all() | Scopes::write(scopes::Write::Statuses))
+        );
+        let expires_in = data.expires_at.expect("expiry") - OffsetDateTime::now_utc();
+        assert!(expires_in > time::Duration::minutes(59));
+
+        let token: AccessToken =
+            serde_json::from_str(r#"{"access_token": "second"}"#).expect("deserialize");
+        token.update(&mut data);
+        assert_eq!(data.token, "second");
+        assert_eq!(data.refresh_token.as_deref(), Some("refresh"));
+        assert!(data.expires_at.is_none());
+        assert!(data.scopes.is_some());
+    }
 }
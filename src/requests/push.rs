@@ -1,4 +1,4 @@
-use mastodon_async_entities::push::Alerts;
+use mastodon_async_entities::push::{Alerts, Policy};
 
 use crate::entities::push::{add_subscription, update_data};
 
@@ -71,6 +71,7 @@ pub struct AddPushRequest {
     auth: String,
 
     alerts: Alerts,
+    policy: Option<Policy>,
 }
 
 impl AddPushRequest {
@@ -99,6 +100,13 @@ impl AddPushRequest {
         self
     }
 
+    /// Set whose notifications should be pushed. The server pushes
+    /// everyone's if this isn't set.
+    pub fn policy(&mut self, policy: Policy) -> &mut Self {
+        self.policy = Some(policy);
+        self
+    }
+
     /// Build the form.
     pub fn build(&self) -> add_subscription::Form {
         use crate::entities::push::add_subscription::{Data, Form, Keys, Subscription};
@@ -113,9 +121,10 @@ impl AddPushRequest {
             data: None,
         };
 
-        if self.alerts.is_some() {
+        if self.alerts.is_some() || self.policy.is_some() {
             form.data = Some(Data {
-                alerts: Some(self.alerts),
+                alerts: Some(self.alerts).filter(Alerts::is_some),
+                policy: self.policy,
             });
         }
 
@@ -154,6 +163,7 @@ impl AddPushRequest {
 pub struct UpdatePushRequest {
     id: String,
     alerts: Alerts,
+    policy: Option<Policy>,
 }
 
 impl UpdatePushRequest {
@@ -177,9 +187,15 @@ impl UpdatePushRequest {
         self
     }
 
+    /// Set whose notifications should be pushed from now on.
+    pub fn policy(&mut self, policy: Policy) -> &mut Self {
+        self.policy = Some(policy);
+        self
+    }
+
     /// Build the form from the update
     pub fn build(&self) -> update_data::Form {
-        use crate::entities::push::update_data::{Data, Form};
+        use crate::entities::push::update_data::Form;
 
         let mut form = Form {
             id: self.id.clone(),
@@ -187,10 +203,9 @@ impl UpdatePushRequest {
         };
 
         if self.alerts.is_some() {
-            form.data = Data {
-                alerts: Some(self.alerts),
-            };
+            form.data.alerts = Some(self.alerts);
         }
+        form.data.policy = self.policy;
         form
     }
 }
@@ -200,7 +215,7 @@ mod tests {
     use mastodon_async_entities::push::AlertsBuilder;
 
     use super::*;
-    use crate::entities::push::{add_subscription, update_data, Alerts};
+    use crate::entities::push::{add_subscription, update_data, Alerts, Policy};
 
     #[test]
     fn test_keys_new() {
@@ -247,7 +262,8 @@ mod tests {
                         alerts: Alerts {
                             $set: Some(true),
                             ..Default::default()
-                        }
+                        },
+                        policy: None,
                     }
                 );
             }
@@ -261,6 +277,8 @@ mod tests {
         test_add_push_request_favourite, favourite;
         test_add_push_request_reblog, reblog;
         test_add_push_request_mention, mention;
+        test_add_push_request_status, status;
+        test_add_push_request_update, update;
     );
 
     #[test]
@@ -292,11 +310,22 @@ mod tests {
                         reblog: Some(true),
                         ..Default::default()
                     }),
+                    policy: None,
                 }),
             }
         );
     }
 
+    #[test]
+    fn test_add_push_request_build_policy() {
+        let keys = Keys::new("anetohias===", "oeatssah=");
+        let mut req = AddPushRequest::new("https://example.com/push/endpoint", &keys);
+        req.policy(Policy::Followed);
+        let data = req.build().data.expect("data");
+        assert_eq!(data.alerts, None);
+        assert_eq!(data.policy, Some(Policy::Followed));
+    }
+
     #[test]
     fn test_update_push_request_new() {
         let req = UpdatePushRequest::new("some-id");
@@ -322,7 +351,8 @@ mod tests {
                         alerts: Alerts {
                             $set: Some(true),
                             ..Default::default()
-                        }
+                        },
+                        policy: None,
                     }
                 );
             }
@@ -337,7 +367,8 @@ mod tests {
         test_update_push_request_favourite, favourite;
         test_update_push_request_reblog, reblog;
         test_update_push_request_mention, mention;
-
+        test_update_push_request_status, status;
+        test_update_push_request_update, update;
     }
 
     #[test]
@@ -348,7 +379,10 @@ mod tests {
             form,
             update_data::Form {
                 id: "some-id".to_string(),
-                data: update_data::Data { alerts: None },
+                data: update_data::Data {
+                    alerts: None,
+                    policy: None,
+                },
             }
         );
     }
@@ -370,8 +404,19 @@ mod tests {
                         favourite: Some(false),
                         ..Default::default()
                     }),
+                    policy: None,
                 },
             }
         );
     }
+
+    #[test]
+    fn test_update_push_request_build_policy() {
+        let mut req = UpdatePushRequest::new("some-id");
+        req.policy(Policy::None)
+            .alerts(AlertsBuilder::default().update(true).build().unwrap());
+        let form = req.build();
+        assert_eq!(form.data.policy, Some(Policy::None));
+        assert_eq!(form.data.alerts.and_then(|it| it.update), Some(true));
+    }
 }
@@ -38,6 +38,9 @@ pub struct StatusesRequest<'a> {
     exclude_replies: bool,
     #[serde(skip_serializing_if = "bool_qs_serialize::is_false")]
     #[serde(serialize_with = "bool_qs_serialize::serialize")]
+    exclude_reblogs: bool,
+    #[serde(skip_serializing_if = "bool_qs_serialize::is_false")]
+    #[serde(serialize_with = "bool_qs_serialize::serialize")]
     pinned: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_id: Option<Cow<'a, str>>,
@@ -47,6 +50,8 @@ pub struct StatusesRequest<'a> {
     limit: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     min_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tagged: Option<Cow<'a, str>>,
 }
 
 impl<'a> From<&'a mut StatusesRequest<'a>> for Option<StatusesRequest<'a>> {
@@ -54,11 +59,13 @@ impl<'a> From<&'a mut StatusesRequest<'a>> for Option<StatusesRequest<'a>> {
         Some(StatusesRequest {
             only_media: sr.only_media,
             exclude_replies: sr.exclude_replies,
+            exclude_reblogs: sr.exclude_reblogs,
             pinned: sr.pinned,
             max_id: sr.max_id.clone(),
             since_id: sr.since_id.clone(),
             limit: sr.limit,
             min_id: sr.min_id.clone(),
+            tagged: sr.tagged.clone(),
         })
     }
 }
@@ -103,6 +110,26 @@ impl<'a> StatusesRequest<'a> {
         self
     }
 
+    /// Set the `?exclude_reblogs=1` flag for the .statuses() request
+    ///
+    /// // Example
+    ///
+    /// ```
+    /// use mastodon_async::requests::StatusesRequest;
+    /// let mut request = StatusesRequest::new();
+    /// assert_eq!(
+    ///     &request
+    ///         .exclude_reblogs()
+    ///         .to_query_string()
+    ///         .expect("Couldn't serialize qs"),
+    ///     "?exclude_reblogs=1"
+    /// );
+    /// ```
+    pub fn exclude_reblogs(&mut self) -> &mut Self {
+        self.exclude_reblogs = true;
+        self
+    }
+
     /// Set the `?pinned=1` flag for the .statuses() request
     ///
     /// // Example
@@ -203,6 +230,27 @@ impl<'a> StatusesRequest<'a> {
         self
     }
 
+    /// Set the `?tagged=:tagged` flag for the .statuses() request, to only
+    /// return statuses using the given hashtag, without the leading `#`
+    ///
+    /// // Example
+    ///
+    /// ```
+    /// use mastodon_async::requests::StatusesRequest;
+    /// let mut request = StatusesRequest::new();
+    /// assert_eq!(
+    ///     &request
+    ///         .tagged("tea")
+    ///         .to_query_string()
+    ///         .expect("Couldn't serialize qs"),
+    ///     "?tagged=tea"
+    /// );
+    /// ```
+    pub fn tagged<S: Into<Cow<'a, str>>>(&mut self, tagged: S) -> &mut Self {
+        self.tagged = Some(tagged.into());
+        self
+    }
+
     /// Serialize into a query string
     pub fn to_query_string(&self) -> Result<String, Error> {
         Ok(format!("?{}", serde_urlencoded::to_string(self)?))
@@ -221,11 +269,13 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
                 max_id: None,
                 since_id: None,
                 limit: None,
                 min_id: None,
+                tagged: None,
             }
         );
     }
@@ -239,11 +289,13 @@ mod tests {
             StatusesRequest {
                 only_media: true,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
                 max_id: None,
                 since_id: None,
                 limit: None,
                 min_id: None,
+                tagged: None,
             }
         );
     }
@@ -257,11 +309,13 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: true,
+                exclude_reblogs: false,
                 pinned: false,
                 max_id: None,
                 since_id: None,
                 limit: None,
                 min_id: None,
+                tagged: None,
             }
         );
     }
@@ -274,11 +328,13 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: true,
                 max_id: None,
                 since_id: None,
                 limit: None,
                 min_id: None,
+                tagged: None,
             }
         );
     }
@@ -291,11 +347,13 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
                 max_id: Some("foo".into()),
                 since_id: None,
                 limit: None,
                 min_id: None,
+                tagged: None,
             }
         );
     }
@@ -308,11 +366,13 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
                 max_id: None,
                 since_id: Some("foo".into()),
                 limit: None,
                 min_id: None,
+                tagged: None,
             }
         );
     }
@@ -325,11 +385,13 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
                 max_id: None,
                 since_id: None,
                 limit: Some(42),
                 min_id: None,
+                tagged: None,
             }
         );
     }
@@ -342,11 +404,51 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
                 max_id: None,
                 since_id: None,
                 limit: None,
                 min_id: Some("foo".into()),
+                tagged: None,
+            }
+        );
+    }
+    #[test]
+    fn test_exclude_reblogs() {
+        let mut request = StatusesRequest::new();
+        request.exclude_reblogs();
+        assert_eq!(
+            request,
+            StatusesRequest {
+                only_media: false,
+                exclude_replies: false,
+                exclude_reblogs: true,
+                pinned: false,
+                max_id: None,
+                since_id: None,
+                limit: None,
+                min_id: None,
+                tagged: None,
+            }
+        );
+    }
+    #[test]
+    fn test_tagged() {
+        let mut request = StatusesRequest::new();
+        request.tagged("tea");
+        assert_eq!(
+            request,
+            StatusesRequest {
+                only_media: false,
+                exclude_replies: false,
+                exclude_reblogs: false,
+                pinned: false,
+                max_id: None,
+                since_id: None,
+                limit: None,
+                min_id: None,
+                tagged: Some("tea".into()),
             }
         );
     }
@@ -579,5 +681,51 @@ mod tests {
             },
             "?since_id=foo&limit=42"
         );
+        qs_test!(
+            |request| {
+                request.min_id("foo");
+            },
+            "?min_id=foo"
+        );
+        qs_test!(
+            |request| {
+                request.exclude_reblogs();
+            },
+            "?exclude_reblogs=1"
+        );
+        qs_test!(
+            |request| {
+                request.tagged("tea");
+            },
+            "?tagged=tea"
+        );
+        qs_test!(
+            |request| {
+                request.tagged("tea").exclude_reblogs().exclude_replies();
+            },
+            "?exclude_replies=1&exclude_reblogs=1&tagged=tea"
+        );
+        qs_test!(
+            |request| {
+                request.tagged("#tea");
+            },
+            "?tagged=%23tea"
+        );
+        qs_test!(
+            |request| {
+                request
+                    .tagged("tea")
+                    .min_id("foo")
+                    .limit(42)
+                    .since_id("bar")
+                    .max_id("baz")
+                    .pinned()
+                    .exclude_reblogs()
+                    .exclude_replies()
+                    .only_media();
+            },
+            "?only_media=1&exclude_replies=1&exclude_reblogs=1&pinned=1&max_id=baz&since_id=bar&\
+             limit=42&min_id=foo&tagged=tea"
+        );
     }
 }
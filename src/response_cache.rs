@@ -0,0 +1,282 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use log::trace;
+use reqwest::{
+    header::{
+        HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, ETAG,
+        IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+    },
+    Client, Method, Request, Response, StatusCode,
+};
+
+use crate::{errors::Result, helpers::canonical, http_send::HttpSend};
+
+/// A successful response kept to revalidate with the server later.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedResponse {
+    /// The headers of the response, including its `ETag` or `Last-Modified`
+    /// header.
+    pub headers: HeaderMap,
+    /// The body of the response.
+    pub body: Bytes,
+}
+
+impl CachedResponse {
+    /// The headers to make a request conditional on the response having
+    /// changed since this one.
+    fn validators(&self) -> impl Iterator<Item = (HeaderName, HeaderValue)> + '_ {
+        let etag = self.headers.get(ETAG).map(|it| (IF_NONE_MATCH, it.clone()));
+        let modified = self
+            .headers
+            .get(LAST_MODIFIED)
+            .map(|it| (IF_MODIFIED_SINCE, it.clone()));
+        etag.into_iter().chain(modified)
+    }
+
+    /// Take the headers of a newer response, other than those describing
+    /// its body, which is kept already decoded.
+    fn update_headers(&mut self, headers: &HeaderMap) {
+        for (name, value) in headers {
+            if name != CONTENT_ENCODING && name != CONTENT_LENGTH {
+                self.headers.insert(name, value.clone());
+            }
+        }
+    }
+
+    fn to_response(&self) -> Response {
+        let mut response = http::Response::new(self.body.clone());
+        *response.headers_mut() = self.headers.clone();
+        response.into()
+    }
+}
+
+/// Where a [`Caching`] sender keeps responses. Implement it to keep them
+/// somewhere other than in memory.
+pub trait CacheStore: Debug + Send + Sync {
+    /// The response stored under `key`, if there is one.
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Store a response under `key`, replacing any stored before.
+    fn put(&self, key: &str, response: CachedResponse);
+}
+
+/// Keeps the most recently used responses in memory, forgetting the least
+/// recently used once there are more than its capacity.
+#[derive(Debug)]
+pub struct MemoryStore {
+    capacity: usize,
+    entries: Mutex<(HashMap<String, CachedResponse>, VecDeque<String>)>,
+}
+
+impl MemoryStore {
+    /// A store which keeps up to `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        MemoryStore {
+            capacity,
+            entries: Default::default(),
+        }
+    }
+}
+
+impl Default for MemoryStore {
+    /// A store which keeps up to 256 responses.
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut entries = self.entries.lock().expect("cache lock poisoned");
+        let (responses, order) = &mut *entries;
+        let response = responses.get(key)?.clone();
+        order.retain(|it| it != key);
+        order.push_back(key.to_string());
+        Some(response)
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let mut entries = self.entries.lock().expect("cache lock poisoned");
+        let (responses, order) = &mut *entries;
+        order.retain(|it| it != key);
+        order.push_back(key.to_string());
+        responses.insert(key.to_string(), response);
+        while order.len() > self.capacity {
+            if let Some(oldest) = order.pop_front() {
+                responses.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Sends requests with another [`HttpSend`], but keeps the responses to
+/// `GET` requests which carry an `ETag` or `Last-Modified` header, and asks
+/// the server for each again only if it has changed since. A
+/// `304 Not Modified` answer is replaced with the kept response, so callers
+/// see no difference other than the bandwidth saved.
+///
+/// Set it with [`Mastodon::with_response_cache`](crate::Mastodon::with_response_cache),
+/// or wrap a sender of your own with [`Caching::new`].
+#[derive(Debug)]
+pub struct Caching<S, C = MemoryStore> {
+    inner: S,
+    store: C,
+}
+
+impl<S: HttpSend> Caching<S> {
+    /// Cache the responses to requests sent with `inner` in memory.
+    pub fn new(inner: S) -> Self {
+        Self::with_store(inner, MemoryStore::default())
+    }
+}
+
+impl<S: HttpSend, C: CacheStore> Caching<S, C> {
+    /// Cache the responses to requests sent with `inner` in `store`.
+    pub fn with_store(inner: S, store: C) -> Self {
+        Caching { inner, store }
+    }
+
+    /// The store responses are kept in.
+    pub fn store(&self) -> &C {
+        &self.store
+    }
+}
+
+/// The key a response is stored under: the URL, and a hash of the
+/// credentials, as different accounts may see different responses. The hash
+/// is the same in every version of this crate, so that keys in a persistent
+/// store stay valid.
+fn cache_key(request: &Request) -> Result<String> {
+    let credentials = request
+        .headers()
+        .get(AUTHORIZATION)
+        .map(HeaderValue::as_bytes);
+    let credentials = canonical::key(&credentials)?;
+    Ok(format!("{credentials} {}", request.url()))
+}
+
+#[async_trait]
+impl<S: HttpSend, C: CacheStore> HttpSend for Caching<S, C> {
+    async fn send(&self, client: &Client, mut request: Request) -> Result<Response> {
+        if request.method() != Method::GET {
+            return self.inner.send(client, request).await;
+        }
+        let key = cache_key(&request)?;
+        let cached = self.store.get(&key);
+        if let Some(cached) = &cached {
+            for (name, value) in cached.validators() {
+                request.headers_mut().entry(name).or_insert(value);
+            }
+        }
+        let url = request.url().to_string();
+        let response = self.inner.send(client, request).await?;
+        match (response.status(), cached) {
+            (StatusCode::NOT_MODIFIED, Some(mut cached)) => {
+                trace!(url = url; "cached response not modified");
+                // the new headers, such as the rate limit, take precedence
+                cached.update_headers(response.headers());
+                self.store.put(&key, cached.clone());
+                Ok(cached.to_response())
+            }
+            (StatusCode::OK, _)
+                if response.headers().contains_key(ETAG)
+                    || response.headers().contains_key(LAST_MODIFIED) =>
+            {
+                let mut cached = CachedResponse {
+                    headers: HeaderMap::new(),
+                    body: Bytes::new(),
+                };
+                cached.update_headers(response.headers());
+                cached.body = response.bytes().await?;
+                trace!(url = url; "caching response");
+                self.store.put(&key, cached.clone());
+                Ok(cached.to_response())
+            }
+            _ => Ok(response),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, Server};
+
+    #[test]
+    fn test_memory_store_evicts_least_recently_used() {
+        let store = MemoryStore::new(2);
+        let response = CachedResponse {
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        };
+        store.put("a", response.clone());
+        store.put("b", response.clone());
+        assert!(store.get("a").is_some());
+        store.put("c", response);
+        assert!(store.get("a").is_some());
+        assert!(store.get("b").is_none());
+        assert!(store.get("c").is_some());
+    }
+
+    #[test]
+    fn test_cache_key_is_stable() {
+        let client = Client::new();
+        let url = "https://example.com/api/v1/instance";
+        let anonymous = client.get(url).build().expect("build");
+        assert_eq!(
+            cache_key(&anonymous).expect("key"),
+            "692d4a7bd3757277b806e961fef351c4 https://example.com/api/v1/instance"
+        );
+        let authenticated = client.get(url).bearer_auth("token").build().expect("build");
+        assert_eq!(
+            cache_key(&authenticated).expect("key"),
+            "e958e37fda8a12630bf0083f35069d29 https://example.com/api/v1/instance"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revalidates() {
+        // answers with an ETag, or with `304 Not Modified` if the request
+        // already has it
+        let server = Server::new(|request| {
+            let etag = HeaderValue::from_static("\"1\"");
+            let response = http::Response::builder()
+                .header("X-RateLimit-Limit", "300")
+                .header("X-RateLimit-Reset", "2026-01-01T00:00:00.000Z");
+            let response = if request.headers().get(IF_NONE_MATCH) == Some(&etag) {
+                response
+                    .status(StatusCode::NOT_MODIFIED)
+                    .header("X-RateLimit-Remaining", "299")
+                    .body(String::new())
+            } else {
+                response
+                    .header(ETAG, etag)
+                    .header("X-RateLimit-Remaining", "300")
+                    .body("[]".to_string())
+            };
+            response.unwrap()
+        });
+        let client = fixtures::client(&server).with_response_cache(MemoryStore::default());
+        let emojis = client.get_emojis().await.expect("emojis");
+        assert!(emojis.initial_items.is_empty());
+        let emojis = client.get_emojis().await.expect("cached emojis");
+        assert!(emojis.initial_items.is_empty());
+        let if_none_match: Vec<_> = server
+            .requests()
+            .iter()
+            .map(|request| request.headers().get(IF_NONE_MATCH).cloned())
+            .collect();
+        assert_eq!(
+            if_none_match,
+            [None, Some(HeaderValue::from_static("\"1\""))]
+        );
+        let rate_limit = client.last_rate_limit().expect("rate limit");
+        assert_eq!(rate_limit.remaining, 299);
+    }
+}
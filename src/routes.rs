@@ -0,0 +1,369 @@
+use std::sync::OnceLock;
+
+use serde::Serialize;
+
+use crate::{entities::auth::scopes::Scopes, Mastodon};
+
+/// Description of an API route implemented by [`Mastodon`](crate::Mastodon),
+/// as returned by [`Mastodon::routes`](crate::Mastodon::routes).
+///
+/// ```
+/// use mastodon_async::prelude::*;
+///
+/// let follow = Mastodon::routes()
+///     .find(|route| route.name == "follow")
+///     .unwrap();
+/// assert_eq!(follow.method, "post");
+/// assert_eq!(follow.path, "/api/v1/accounts/{}/follow");
+/// assert_eq!(follow.scope(), Some("write"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Route {
+    /// The name of the method on `Mastodon` which calls this route. Those of
+    /// admin routes, prefixed with `admin_`, are crate-private; they're
+    /// called through [`AdminClient`](crate::admin::AdminClient).
+    pub name: &'static str,
+    /// The HTTP method, in lowercase.
+    pub method: &'static str,
+    /// The path of the route, with `{}` in place of any ID.
+    pub path: &'static str,
+    /// The name of the type the response is deserialized into.
+    pub returns: &'static str,
+    /// Whether the response is returned as a [`Page`](crate::page::Page) of
+    /// `returns`.
+    pub paged: bool,
+}
+
+impl Route {
+    /// The broadest OAuth scope needed to call this route, or `None` if it
+    /// needs none.
+    ///
+    /// The server may also accept a narrower scope, such as `read:statuses`,
+    /// and some public routes can be called without authenticating at all.
+    pub fn scope(&self) -> Option<&'static str> {
+        scope_for(self.method, self.path)
+    }
+
+    /// Whether the given scopes are enough to call this route. A narrower
+    /// scope counts if it's the one the route belongs to, such as
+    /// `write:favourites` for favouriting a status, or if it isn't known
+    /// which narrower scope the route belongs to.
+    ///
+    /// ```
+    /// use mastodon_async::prelude::*;
+    ///
+    /// let favourite = Mastodon::routes()
+    ///     .find(|route| route.name == "favourite")
+    ///     .unwrap();
+    /// assert!(favourite.is_granted_by(&Scopes::write(scopes::Write::Favourites)));
+    /// assert!(!favourite.is_granted_by(&Scopes::write(scopes::Write::Media)));
+    /// assert!(!favourite.is_granted_by(&Scopes::read_all()));
+    /// ```
+    pub fn is_granted_by(&self, scopes: &Scopes) -> bool {
+        self.scope()
+            .map_or(true, |required| is_granted(scopes, required, self.path))
+    }
+}
+
+/// The broadest OAuth scope needed to make a request with the given method
+/// to the given path, or `None` for the OAuth endpoints, which are used to
+/// get a token in the first place.
+pub(crate) fn scope_for(method: &str, path: &str) -> Option<&'static str> {
+    if path.starts_with("/oauth/") {
+        return None;
+    }
+    if path.starts_with("/api/v1/push/") {
+        return Some("push");
+    }
+    let admin = path.starts_with("/api/v1/admin/");
+    let write = !method.eq_ignore_ascii_case("get");
+    Some(match (admin, write) {
+        (true, true) => "admin:write",
+        (true, false) => "admin:read",
+        (false, true) => "write",
+        (false, false) => "read",
+    })
+}
+
+/// The endpoint a request to `path` was made to, with any IDs replaced by
+/// `:id` and without the `/api/v1/` prefix, e.g. `statuses/:id/reblog`, so
+/// that requests to the same endpoint can be logged and counted together.
+///
+/// The path is matched against those of [`Mastodon::routes`]; any other
+/// path has the segments which contain a digit replaced instead.
+///
+/// ```
+/// use mastodon_async::routes::endpoint_label;
+///
+/// assert_eq!(endpoint_label("/api/v1/statuses/1234/reblog"), "statuses/:id/reblog");
+/// assert_eq!(
+///     endpoint_label("/api/v1/accounts/verify_credentials"),
+///     "accounts/verify_credentials",
+/// );
+/// assert_eq!(endpoint_label("/api/v2/media"), "v2/media");
+/// ```
+pub fn endpoint_label(path: &str) -> String {
+    static TEMPLATES: OnceLock<Vec<Vec<&'static str>>> = OnceLock::new();
+    let templates = TEMPLATES.get_or_init(|| {
+        Mastodon::routes()
+            .map(|route| {
+                let path = route.path.split('?').next().unwrap_or_default();
+                path.split('/').collect()
+            })
+            .collect()
+    });
+    let segments: Vec<&str> = path.split('/').collect();
+    let template = templates
+        .iter()
+        .filter(|template| {
+            template.len() == segments.len()
+                && template
+                    .iter()
+                    .zip(&segments)
+                    .all(|(expected, actual)| *expected == "{}" || expected == actual)
+        })
+        // the most specific, e.g. accounts/verify_credentials, not accounts/:id
+        .min_by_key(|template| template.iter().filter(|it| **it == "{}").count());
+    let label: Vec<&str> = match template {
+        Some(template) => template
+            .iter()
+            .map(|it| if *it == "{}" { ":id" } else { it })
+            .collect(),
+        None => segments
+            .iter()
+            .map(|it| {
+                if it.bytes().any(|b| b.is_ascii_digit()) && !is_version(it) {
+                    ":id"
+                } else {
+                    it
+                }
+            })
+            .collect(),
+    };
+    let label = label.join("/");
+    match label.strip_prefix("/api/v1/") {
+        Some(label) => label.to_string(),
+        None => label
+            .strip_prefix("/api/")
+            .or_else(|| label.strip_prefix('/'))
+            .unwrap_or(&label)
+            .to_string(),
+    }
+}
+
+/// Whether a path segment is an API version, such as `v2`.
+fn is_version(segment: &str) -> bool {
+    segment
+        .strip_prefix('v')
+        .is_some_and(|it| !it.is_empty() && it.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Whether `granted` includes `required`, or the narrower scope of it which
+/// covers `path`. If it isn't known which narrower scope covers `path`, any
+/// of them is taken to, and the server has the final say.
+pub(crate) fn is_granted(granted: &Scopes, required: &str, path: &str) -> bool {
+    let narrower = narrower_scope(path);
+    granted.iter().any(|scope| {
+        let scope = scope.to_string();
+        let narrowed = scope
+            .strip_prefix(required)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .is_some_and(|rest| narrower.map_or(true, |narrower| rest == narrower));
+        scope == required
+            || narrowed
+            || (required == "write" && scope == "follow" && is_follow_path(path))
+    })
+}
+
+/// The part after `read:`, `write:` or `admin:*:` of the narrower scope
+/// which covers requests to `path`, such as `statuses` for
+/// `/api/v1/statuses/1/reblog`, if it's known.
+fn narrower_scope(path: &str) -> Option<&'static str> {
+    let path = path.split('?').next().unwrap_or_default();
+    let path = path
+        .strip_prefix("/api/v1/")
+        .or_else(|| path.strip_prefix("/api/v2/"))?;
+    let segments: Vec<&str> = path.split('/').collect();
+    Some(match segments.as_slice() {
+        ["admin", "accounts", ..] => "accounts",
+        ["admin", "canonical_email_blocks", ..] => "canonical_email_blocks",
+        ["admin", "domain_allows", ..] => "domain_allows",
+        ["admin", "domain_blocks", ..] => "domain_blocks",
+        ["admin", "email_domain_blocks", ..] => "email_domain_blocks",
+        ["admin", "ip_blocks", ..] => "ip_blocks",
+        ["admin", "reports", ..] => "reports",
+        ["accounts", "relationships"] => "follows",
+        ["accounts", _, "follow" | "unfollow" | "remove_from_followers"] => "follows",
+        ["accounts", _, "block" | "unblock"] => "blocks",
+        ["accounts", _, "mute" | "unmute"] => "mutes",
+        ["accounts", _, "statuses"] => "statuses",
+        ["accounts", _, "lists"] => "lists",
+        ["accounts", ..] | ["endorsements"] | ["featured_tags", ..] => "accounts",
+        // bookmarks have narrower scopes of their own, which `Scopes` lacks
+        ["statuses", _, "bookmark" | "unbookmark"] | ["bookmarks"] => return None,
+        ["statuses", _, "favourite" | "unfavourite"] => "favourites",
+        ["statuses", ..] | ["scheduled_statuses", ..] | ["polls", ..] => "statuses",
+        ["timelines", "list", ..] | ["lists", ..] => "lists",
+        ["timelines", ..] | ["conversations", ..] => "statuses",
+        ["media", ..] => "media",
+        ["favourites"] => "favourites",
+        ["blocks"] | ["domain_blocks"] => "blocks",
+        ["mutes"] => "mutes",
+        ["filters", ..] => "filters",
+        ["follow_requests", ..] | ["followed_tags"] => "follows",
+        ["tags", _, "follow" | "unfollow"] => "follows",
+        ["notifications", ..] => "notifications",
+        ["reports", ..] => "reports",
+        ["search"] => "search",
+        _ => return None,
+    })
+}
+
+/// Whether `path` is one the deprecated `follow` scope covers: (un)following,
+/// blocking and muting accounts, following tags, answering follow requests
+/// and blocking domains.
+fn is_follow_path(path: &str) -> bool {
+    let path = path.split('?').next().unwrap_or_default();
+    let Some(path) = path.strip_prefix("/api/v1/") else {
+        return false;
+    };
+    let segments: Vec<&str> = path.split('/').collect();
+    match segments.as_slice() {
+        ["domain_blocks"] => true,
+        ["accounts", _, action] => matches!(
+            *action,
+            "follow" | "unfollow" | "block" | "unblock" | "mute" | "unmute"
+        ),
+        ["follow_requests", _, action] => matches!(*action, "authorize" | "reject"),
+        ["tags", _, action] => matches!(*action, "follow" | "unfollow"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::Mastodon;
+
+    #[test]
+    fn test_endpoint_label() {
+        use super::endpoint_label;
+
+        assert_eq!(
+            endpoint_label("/api/v1/accounts/9tga/follow"),
+            "accounts/:id/follow"
+        );
+        assert_eq!(endpoint_label("/api/v1/streaming/user"), "streaming/user");
+        assert_eq!(endpoint_label("/oauth/token"), "oauth/token");
+        // not a route this crate knows about
+        assert_eq!(
+            endpoint_label("/api/v3/widgets/42/spin"),
+            "v3/widgets/:id/spin"
+        );
+    }
+
+    fn route(name: &str) -> super::Route {
+        *Mastodon::routes()
+            .find(|route| route.name == name)
+            .unwrap_or_else(|| panic!("no route named {name}"))
+    }
+
+    #[test]
+    fn test_names_are_unique() {
+        let mut names = HashSet::new();
+        for route in Mastodon::routes() {
+            assert!(names.insert(route.name), "duplicate route {}", route.name);
+        }
+    }
+
+    #[test]
+    fn test_macro_routes() {
+        let favourites = route("favourites");
+        assert_eq!(favourites.method, "get");
+        assert_eq!(favourites.path, "/api/v1/favourites");
+        assert_eq!(favourites.returns, "Status");
+        assert!(favourites.paged);
+        assert_eq!(favourites.scope(), Some("read"));
+
+        let unfollow = route("unfollow");
+        assert_eq!(unfollow.path, "/api/v1/accounts/{}/unfollow");
+        assert_eq!(unfollow.scope(), Some("write"));
+        assert!(!unfollow.paged);
+
+        let stream = route("stream_hashtag");
+        assert_eq!(stream.path, "/api/v1/streaming/hashtag");
+        assert_eq!(stream.returns, "Event");
+    }
+
+    #[test]
+    fn test_admin_scopes() {
+        assert_eq!(route("admin_ip_blocks").scope(), Some("admin:read"));
+        assert_eq!(route("admin_delete_ip_block").scope(), Some("admin:write"));
+    }
+
+    #[test]
+    fn test_is_granted() {
+        use super::is_granted;
+        use crate::entities::auth::scopes::{Admin, Read, Scopes, Write};
+
+        let path = "/api/v1/statuses";
+        assert!(is_granted(&Scopes::read_all(), "read", path));
+        assert!(is_granted(&Scopes::read(Read::Statuses), "read", path));
+        assert!(!is_granted(&Scopes::read_all(), "write", path));
+        assert!(is_granted(&Scopes::write(Write::Statuses), "write", path));
+        assert!(!is_granted(&Scopes::write(Write::Media), "write", path));
+        assert!(is_granted(
+            &Scopes::write(Write::Media),
+            "write",
+            "/api/v2/media"
+        ));
+        // `write:bookmarks` isn't modelled, so any narrower scope will do
+        assert!(is_granted(
+            &Scopes::write(Write::Media),
+            "write",
+            "/api/v1/statuses/1/bookmark"
+        ));
+        assert!(!is_granted(&Scopes::follow(), "write", path));
+        assert!(is_granted(
+            &Scopes::follow(),
+            "write",
+            "/api/v1/accounts/1/follow"
+        ));
+        assert!(is_granted(
+            &Scopes::follow(),
+            "write",
+            "/api/v1/domain_blocks"
+        ));
+        assert!(is_granted(
+            &Scopes::push(),
+            "push",
+            "/api/v1/push/subscription"
+        ));
+        assert!(!is_granted(&Scopes::read_all(), "admin:read", path));
+        assert!(!is_granted(&Scopes::admin_read_all(), "admin:write", path));
+        let path = "/api/v1/admin/accounts/1/action";
+        assert!(is_granted(
+            &Scopes::admin_write(Admin::Accounts),
+            "admin:write",
+            path
+        ));
+        assert!(!is_granted(
+            &Scopes::admin_write(Admin::Reports),
+            "admin:write",
+            path
+        ));
+        assert!(route("follow").is_granted_by(&Scopes::follow()));
+        assert!(!route("new_status").is_granted_by(&Scopes::follow()));
+        assert_eq!(route("get_push_subscription").scope(), Some("push"));
+        assert_eq!(super::scope_for("post", "/oauth/token"), None);
+    }
+
+    #[test]
+    fn test_hand_written_routes() {
+        let update = route("update_credentials");
+        assert_eq!(update.method, "patch");
+        assert_eq!(update.scope(), Some("write"));
+        assert!(route("relationships").paged);
+    }
+}
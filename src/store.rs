@@ -0,0 +1,433 @@
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Params};
+use serde::de::DeserializeOwned;
+use time::OffsetDateTime;
+
+use crate::{entities::prelude::*, outbox::QueuedPost, Result};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS accounts (
+    id TEXT PRIMARY KEY NOT NULL,
+    acct TEXT NOT NULL,
+    json TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS accounts_acct ON accounts (acct);
+CREATE TABLE IF NOT EXISTS statuses (
+    id TEXT PRIMARY KEY NOT NULL,
+    account_id TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    json TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS statuses_account ON statuses (account_id, created_at);
+CREATE INDEX IF NOT EXISTS statuses_created_at ON statuses (created_at);
+CREATE TABLE IF NOT EXISTS notifications (
+    id TEXT PRIMARY KEY NOT NULL,
+    created_at INTEGER NOT NULL,
+    json TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS notifications_created_at ON notifications (created_at);
+CREATE TABLE IF NOT EXISTS markers (
+    timeline TEXT PRIMARY KEY NOT NULL,
+    json TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS outbox (
+    position INTEGER PRIMARY KEY NOT NULL,
+    json TEXT NOT NULL
+);
+";
+
+/// Newest first. IDs are only compared if the timestamps are equal, and as
+/// they are numeric strings, longer ones are newer.
+const NEWEST_FIRST: &str = "ORDER BY created_at DESC, length(id) DESC, id DESC";
+
+/// A SQLite database of statuses, accounts, notifications and markers
+/// fetched from the server, for clients which work offline. Behind a
+/// `Mutex`, it can also keep the posts of an
+/// [`OutboxQueue`](crate::outbox::OutboxQueue).
+///
+/// Entities are stored as JSON, keyed by ID, so storing one again replaces
+/// it with the newer version. The tables are created when the store is
+/// opened, if they don't exist yet.
+///
+/// ```no_run
+/// use mastodon_async::{prelude::*, store::Store};
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let mut store = Store::open("mastodon.sqlite3").unwrap();
+///     let timeline = client.get_home_timeline().await.unwrap();
+///     store.upsert_statuses(&timeline.initial_items).unwrap();
+///     for status in store.recent_statuses(20).unwrap() {
+///         println!("{}: {}", status.account.acct, status.content);
+///     }
+/// });
+/// ```
+#[derive(Debug)]
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    /// Open the database at the given path, creating it if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Open a database which only exists in memory, e.g. for tests.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// Use an open database connection.
+    pub fn from_connection(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// The underlying connection, for queries this type doesn't cover.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Store an account.
+    pub fn upsert_account(&self, account: &Account) -> Result<()> {
+        upsert_account(&self.connection, account)
+    }
+
+    /// The stored account with the given ID.
+    pub fn account(&self, id: &AccountId) -> Result<Option<Account>> {
+        query_one(
+            &self.connection,
+            "SELECT json FROM accounts WHERE id = ?1",
+            [id.as_ref()],
+        )
+    }
+
+    /// The stored account with the given `acct`, i.e. `user` for local
+    /// accounts or `user@domain` for remote ones.
+    pub fn account_by_acct(&self, acct: &str) -> Result<Option<Account>> {
+        query_one(
+            &self.connection,
+            "SELECT json FROM accounts WHERE acct = ?1",
+            [acct],
+        )
+    }
+
+    /// Store a status, along with its author and the status it boosts, if
+    /// any.
+    pub fn upsert_status(&self, status: &Status) -> Result<()> {
+        upsert_status(&self.connection, status)
+    }
+
+    /// Store several statuses at once, in a single transaction.
+    pub fn upsert_statuses<'a>(
+        &mut self,
+        statuses: impl IntoIterator<Item = &'a Status>,
+    ) -> Result<()> {
+        let transaction = self.connection.transaction()?;
+        for status in statuses {
+            upsert_status(&transaction, status)?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// The stored status with the given ID.
+    pub fn status(&self, id: &StatusId) -> Result<Option<Status>> {
+        query_one(
+            &self.connection,
+            "SELECT json FROM statuses WHERE id = ?1",
+            [id.as_ref()],
+        )
+    }
+
+    /// Up to `limit` of the most recent stored statuses.
+    pub fn recent_statuses(&self, limit: usize) -> Result<Vec<Status>> {
+        query(
+            &self.connection,
+            &format!("SELECT json FROM statuses {NEWEST_FIRST} LIMIT ?1"),
+            [sql_limit(limit)],
+        )
+    }
+
+    /// Up to `limit` of the most recent stored statuses by the given
+    /// account.
+    pub fn statuses_by_account(&self, id: &AccountId, limit: usize) -> Result<Vec<Status>> {
+        query(
+            &self.connection,
+            &format!("SELECT json FROM statuses WHERE account_id = ?1 {NEWEST_FIRST} LIMIT ?2"),
+            params![id.as_ref(), sql_limit(limit)],
+        )
+    }
+
+    /// Remove a status, e.g. once it has been deleted on the server.
+    /// Returns whether it was stored.
+    pub fn delete_status(&self, id: &StatusId) -> Result<bool> {
+        let deleted = self
+            .connection
+            .execute("DELETE FROM statuses WHERE id = ?1", [id.as_ref()])?;
+        Ok(deleted > 0)
+    }
+
+    /// Store a notification, along with the account and status it is about.
+    pub fn upsert_notification(&self, notification: &Notification) -> Result<()> {
+        upsert_notification(&self.connection, notification)
+    }
+
+    /// Store several notifications at once, in a single transaction.
+    pub fn upsert_notifications<'a>(
+        &mut self,
+        notifications: impl IntoIterator<Item = &'a Notification>,
+    ) -> Result<()> {
+        let transaction = self.connection.transaction()?;
+        for notification in notifications {
+            upsert_notification(&transaction, notification)?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// The stored notification with the given ID.
+    pub fn notification(&self, id: &NotificationId) -> Result<Option<Notification>> {
+        query_one(
+            &self.connection,
+            "SELECT json FROM notifications WHERE id = ?1",
+            [id.as_ref()],
+        )
+    }
+
+    /// Up to `limit` of the most recent stored notifications.
+    pub fn recent_notifications(&self, limit: usize) -> Result<Vec<Notification>> {
+        query(
+            &self.connection,
+            &format!("SELECT json FROM notifications {NEWEST_FIRST} LIMIT ?1"),
+            [sql_limit(limit)],
+        )
+    }
+
+    /// Store the read positions which are set in `markers`, keeping the
+    /// stored ones for the others.
+    pub fn save_markers(&self, markers: &Markers) -> Result<()> {
+        if let Some(home) = &markers.home {
+            upsert_marker(&self.connection, "home", home)?;
+        }
+        if let Some(notifications) = &markers.notifications {
+            upsert_marker(&self.connection, "notifications", notifications)?;
+        }
+        Ok(())
+    }
+
+    /// The stored read positions.
+    pub fn markers(&self) -> Result<Markers> {
+        const SQL: &str = "SELECT json FROM markers WHERE timeline = ?1";
+        Ok(Markers {
+            home: query_one(&self.connection, SQL, ["home"])?,
+            notifications: query_one(&self.connection, SQL, ["notifications"])?,
+        })
+    }
+
+    /// Replace the stored outbox with `posts`, oldest first.
+    pub fn save_outbox(&self, posts: &[QueuedPost]) -> Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+        transaction.execute("DELETE FROM outbox", [])?;
+        for (position, post) in posts.iter().enumerate() {
+            transaction
+                .prepare_cached("INSERT INTO outbox (position, json) VALUES (?1, ?2)")?
+                .execute(params![
+                    i64::try_from(position)?,
+                    serde_json::to_string(post)?
+                ])?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// The stored outbox, oldest first.
+    pub fn outbox(&self) -> Result<Vec<QueuedPost>> {
+        query(
+            &self.connection,
+            "SELECT json FROM outbox ORDER BY position",
+            [],
+        )
+    }
+}
+
+fn upsert_account(connection: &Connection, account: &Account) -> Result<()> {
+    connection
+        .prepare_cached("INSERT OR REPLACE INTO accounts (id, acct, json) VALUES (?1, ?2, ?3)")?
+        .execute(params![
+            account.id.as_ref(),
+            account.acct,
+            serde_json::to_string(account)?
+        ])?;
+    Ok(())
+}
+
+fn upsert_status(connection: &Connection, status: &Status) -> Result<()> {
+    upsert_account(connection, &status.account)?;
+    if let Some(reblog) = &status.reblog {
+        upsert_status(connection, reblog)?;
+    }
+    connection
+        .prepare_cached(
+            "INSERT OR REPLACE INTO statuses (id, account_id, created_at, json) \
+             VALUES (?1, ?2, ?3, ?4)",
+        )?
+        .execute(params![
+            status.id.as_ref(),
+            status.account.id.as_ref(),
+            timestamp(status.created_at),
+            serde_json::to_string(status)?
+        ])?;
+    Ok(())
+}
+
+fn upsert_notification(connection: &Connection, notification: &Notification) -> Result<()> {
+    upsert_account(connection, &notification.account)?;
+    if let Some(status) = &notification.status {
+        upsert_status(connection, status)?;
+    }
+    connection
+        .prepare_cached(
+            "INSERT OR REPLACE INTO notifications (id, created_at, json) VALUES (?1, ?2, ?3)",
+        )?
+        .execute(params![
+            notification.id.as_ref(),
+            timestamp(notification.created_at),
+            serde_json::to_string(notification)?
+        ])?;
+    Ok(())
+}
+
+fn upsert_marker<Id: serde::Serialize>(
+    connection: &Connection,
+    timeline: &str,
+    marker: &Marker<Id>,
+) -> Result<()> {
+    connection
+        .prepare_cached("INSERT OR REPLACE INTO markers (timeline, json) VALUES (?1, ?2)")?
+        .execute(params![timeline, serde_json::to_string(marker)?])?;
+    Ok(())
+}
+
+fn query<T: DeserializeOwned>(
+    connection: &Connection,
+    sql: &str,
+    params: impl Params,
+) -> Result<Vec<T>> {
+    let mut statement = connection.prepare_cached(sql)?;
+    let rows = statement.query_map(params, |row| row.get::<_, String>(0))?;
+    rows.map(|json| Ok(serde_json::from_str(&json?)?)).collect()
+}
+
+fn query_one<T: DeserializeOwned>(
+    connection: &Connection,
+    sql: &str,
+    params: impl Params,
+) -> Result<Option<T>> {
+    let json: Option<String> = connection
+        .prepare_cached(sql)?
+        .query_row(params, |row| row.get(0))
+        .optional()?;
+    Ok(json.map(|json| serde_json::from_str(&json)).transpose()?)
+}
+
+/// Milliseconds since the epoch, which is precise enough to order entities.
+fn timestamp(time: OffsetDateTime) -> i64 {
+    (time.unix_timestamp_nanos() / 1_000_000) as i64
+}
+
+fn sql_limit(limit: usize) -> i64 {
+    i64::try_from(limit).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn status(id: &str, created_at: &str, account_id: &str) -> Status {
+        let mut status = fixtures::status_json(id);
+        status["created_at"] = created_at.into();
+        status["account"] = fixtures::account_json(account_id, &format!("user{account_id}"));
+        fixtures::from_json(status)
+    }
+
+    #[test]
+    fn test_statuses() {
+        let mut store = Store::open_in_memory().expect("open");
+        let old = status("1", "2019-12-08T03:48:33.901Z", "1");
+        let new = status("2", "2019-12-09T03:48:33.901Z", "2");
+        store.upsert_statuses([&old, &new]).expect("upsert");
+
+        let ids = |statuses: Vec<Status>| -> Vec<String> {
+            statuses.into_iter().map(|it| it.id.to_string()).collect()
+        };
+        assert_eq!(ids(store.recent_statuses(10).expect("recent")), ["2", "1"]);
+        assert_eq!(ids(store.recent_statuses(1).expect("recent")), ["2"]);
+        assert_eq!(
+            ids(store
+                .statuses_by_account(&AccountId::new("1"), 10)
+                .expect("by account")),
+            ["1"]
+        );
+        assert_eq!(
+            store
+                .account_by_acct("user2")
+                .expect("account")
+                .map(|account| account.id),
+            Some(AccountId::new("2"))
+        );
+
+        let mut edited = old.clone();
+        edited.content = "<p>edited</p>".to_string();
+        store.upsert_status(&edited).expect("upsert");
+        assert_eq!(
+            store.status(&old.id).expect("status").map(|it| it.content),
+            Some("<p>edited</p>".to_string())
+        );
+
+        assert!(store.delete_status(&old.id).expect("delete"));
+        assert!(!store.delete_status(&old.id).expect("delete"));
+        assert!(store.status(&old.id).expect("status").is_none());
+    }
+
+    #[test]
+    fn test_markers() {
+        let store = Store::open_in_memory().expect("open");
+        assert_eq!(store.markers().expect("markers"), Markers::default());
+        let markers: Markers = serde_json::from_str(
+            r#"{
+              "home": {
+                "last_read_id": "103206604258487607",
+                "version": 468,
+                "updated_at": "2019-11-24T19:41:17.317Z"
+              }
+            }"#,
+        )
+        .expect("deserialize");
+        store.save_markers(&markers).expect("save");
+        store.save_markers(&Markers::default()).expect("save");
+        assert_eq!(store.markers().expect("markers"), markers);
+    }
+
+    #[test]
+    fn test_outbox() {
+        let store = Store::open_in_memory().expect("open");
+        assert!(store.outbox().expect("outbox").is_empty());
+        let posts: Vec<_> = ["first", "second", "third"]
+            .into_iter()
+            .map(|text| {
+                QueuedPost::new(NewStatus {
+                    status: Some(text.to_string()),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        store.save_outbox(&posts).expect("save");
+        assert_eq!(store.outbox().expect("outbox"), posts);
+        store.save_outbox(&posts[1..]).expect("save");
+        assert_eq!(store.outbox().expect("outbox"), &posts[1..]);
+    }
+}
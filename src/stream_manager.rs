@@ -0,0 +1,311 @@
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+
+use futures::{stream::unfold, Stream, TryStream, TryStreamExt};
+use log::{debug, warn};
+use tokio::sync::{mpsc, watch};
+use uuid::Uuid;
+
+use crate::{entities::event::Event, errors::Result, Error};
+
+/// How events are delivered to a single subscriber of a [`StreamManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// Only the most recent event is kept for the subscriber. If it falls
+    /// behind, intermediate events are skipped. Never blocks the ingest loop.
+    Lossy,
+    /// Events are queued for the subscriber, up to `capacity` of them. If the
+    /// queue is full when a new event arrives, the subscription ends with
+    /// [`Error::SubscriberOverflow`] rather than silently dropping the event.
+    /// Never blocks the ingest loop.
+    Lossless {
+        /// The maximum number of events to queue for the subscriber.
+        capacity: usize,
+    },
+}
+
+#[derive(Debug)]
+enum Sender {
+    Lossy(watch::Sender<Option<Event>>),
+    Lossless {
+        sender: mpsc::Sender<Event>,
+        overflowed: Arc<AtomicBool>,
+        capacity: usize,
+    },
+}
+
+#[derive(Debug)]
+struct Subscriber {
+    id: Uuid,
+    sender: Sender,
+}
+
+impl Subscriber {
+    /// Deliver the event, returning false if this subscriber should be
+    /// dropped.
+    fn deliver(&self, event: &Event) -> bool {
+        match &self.sender {
+            Sender::Lossy(sender) => {
+                if sender.is_closed() {
+                    return false;
+                }
+                sender.send_replace(Some(event.clone()));
+                true
+            }
+            Sender::Lossless {
+                sender,
+                overflowed,
+                capacity,
+            } => match sender.try_send(event.clone()) {
+                Ok(()) => true,
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    warn!(subscriber:? = self.id, capacity = capacity; "subscriber queue overflowed");
+                    overflowed.store(true, Ordering::Release);
+                    false
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => false,
+            },
+        }
+    }
+}
+
+/// Fans events from a single stream out to several consumers, each with its
+/// own [`Delivery`] mode, so that a slow consumer can neither stall the
+/// ingest loop nor cause other consumers to miss events.
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use mastodon_async::{
+///     prelude::*,
+///     stream_manager::{Delivery, StreamManager},
+/// };
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let manager = StreamManager::new();
+///     let mut reports = manager.subscribe(Delivery::Lossless { capacity: 1024 });
+///     let mut latest = manager.subscribe(Delivery::Lossy);
+///     tokio::spawn(async move {
+///         while let Some(event) = reports.next().await {
+///             // handle every event, or find out that we fell behind
+///             let event = event.unwrap();
+///         }
+///     });
+///     tokio::spawn(async move {
+///         while let Some(event) = latest.next().await {
+///             // only ever see the most recent event
+///         }
+///     });
+///     manager.run(client.stream_user().await.unwrap()).await.unwrap();
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StreamManager {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+}
+
+impl StreamManager {
+    /// Create a manager with no subscribers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn subscribers(&self) -> std::sync::MutexGuard<'_, Vec<Subscriber>> {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Register a new consumer, which will receive every event published
+    /// after this call according to the given delivery mode.
+    pub fn subscribe(&self, delivery: Delivery) -> Subscription {
+        let id = Uuid::new_v4();
+        let (sender, stream): (_, BoxedEvents) = match delivery {
+            Delivery::Lossy => {
+                let (sender, receiver) = watch::channel(None);
+                let stream = unfold(receiver, |mut receiver| async move {
+                    receiver.changed().await.ok()?;
+                    let event = receiver.borrow_and_update().clone()?;
+                    Some((Ok(event), receiver))
+                });
+                (Sender::Lossy(sender), Box::pin(stream))
+            }
+            Delivery::Lossless { capacity } => {
+                let (sender, receiver) = mpsc::channel(capacity.max(1));
+                let overflowed = Arc::new(AtomicBool::new(false));
+                let flag = overflowed.clone();
+                let stream = unfold(Some(receiver), move |receiver| {
+                    let flag = flag.clone();
+                    async move {
+                        let mut receiver = receiver?;
+                        match receiver.recv().await {
+                            Some(event) => Some((Ok(event), Some(receiver))),
+                            None if flag.load(Ordering::Acquire) => {
+                                Some((Err(Error::SubscriberOverflow { capacity }), None))
+                            }
+                            None => None,
+                        }
+                    }
+                });
+                (
+                    Sender::Lossless {
+                        sender,
+                        overflowed,
+                        capacity,
+                    },
+                    Box::pin(stream),
+                )
+            }
+        };
+        debug!(subscriber:? = id, delivery:? = delivery; "new subscriber");
+        self.subscribers().push(Subscriber { id, sender });
+        Subscription { id, stream }
+    }
+
+    /// The number of consumers currently subscribed.
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers().len()
+    }
+
+    /// Deliver an event to every subscriber, without waiting on any of them.
+    ///
+    /// Subscribers which have been dropped, or whose queue overflowed, are
+    /// removed.
+    pub fn publish(&self, event: &Event) {
+        self.subscribers().retain(|subscriber| {
+            let keep = subscriber.deliver(event);
+            if !keep {
+                debug!(subscriber:? = subscriber.id; "removing subscriber");
+            }
+            keep
+        });
+    }
+
+    /// Remove every subscriber. Their streams end once they've consumed any
+    /// events already delivered to them.
+    pub fn close(&self) {
+        self.subscribers().clear();
+    }
+
+    /// Publish every event from the given stream, e.g. one returned by
+    /// [`Mastodon::stream_user`](crate::Mastodon::stream_user), until it ends.
+    ///
+    /// All subscribers are closed when the stream ends or returns an error.
+    pub async fn run<S, T>(&self, stream: S) -> Result<()>
+    where
+        S: TryStream<Ok = (Event, T), Error = Error>,
+    {
+        let result = stream
+            .try_for_each(|(event, _)| async move {
+                self.publish(&event);
+                Ok(())
+            })
+            .await;
+        self.close();
+        result
+    }
+}
+
+type BoxedEvents = Pin<Box<dyn Stream<Item = Result<Event>> + Send>>;
+
+/// A single consumer's view of the events published by a [`StreamManager`].
+pub struct Subscription {
+    id: Uuid,
+    stream: BoxedEvents,
+}
+
+impl Subscription {
+    /// A unique identifier for this subscription, used in log messages.
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
+impl std::fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for Subscription {
+    type Item = Result<Event>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, StreamExt};
+
+    use super::*;
+
+    fn delete(id: &str) -> Event {
+        Event::Delete(id.to_string())
+    }
+
+    fn ids(events: Vec<Result<Event>>) -> Vec<String> {
+        events
+            .into_iter()
+            .map(|event| match event.expect("event") {
+                Event::Delete(id) => id,
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_lossless() {
+        let manager = StreamManager::new();
+        let subscription = manager.subscribe(Delivery::Lossless { capacity: 3 });
+        let events = stream::iter(["1", "2", "3"].map(|id| Ok((delete(id), ()))));
+        manager.run(events).await.expect("run");
+        assert_eq!(manager.subscriber_count(), 0);
+        assert_eq!(ids(subscription.collect().await), vec!["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn test_lossless_overflow() {
+        let manager = StreamManager::new();
+        let mut subscription = manager.subscribe(Delivery::Lossless { capacity: 1 });
+        manager.publish(&delete("1"));
+        manager.publish(&delete("2"));
+        assert_eq!(manager.subscriber_count(), 0);
+        assert!(subscription.next().await.expect("first event").is_ok());
+        assert!(matches!(
+            subscription.next().await,
+            Some(Err(Error::SubscriberOverflow { capacity: 1 }))
+        ));
+        assert!(subscription.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_lossy() {
+        let manager = StreamManager::new();
+        let subscription = manager.subscribe(Delivery::Lossy);
+        for id in ["1", "2", "3"] {
+            manager.publish(&delete(id));
+        }
+        manager.close();
+        assert_eq!(ids(subscription.collect().await), vec!["3"]);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_subscriber_is_removed() {
+        let manager = StreamManager::new();
+        drop(manager.subscribe(Delivery::Lossy));
+        drop(manager.subscribe(Delivery::Lossless { capacity: 1 }));
+        assert_eq!(manager.subscriber_count(), 2);
+        manager.publish(&delete("1"));
+        assert_eq!(manager.subscriber_count(), 0);
+    }
+}
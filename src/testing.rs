@@ -0,0 +1,232 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    entities::prelude::*, errors::Result, format_err, timeline::Timeline, Error, MastodonClient,
+    NewStatus,
+};
+
+/// A request made to a [`MockMastodon`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    /// The name of the [`MastodonClient`] method which was called.
+    pub method: &'static str,
+    /// The arguments it was called with, as JSON.
+    pub args: Value,
+}
+
+/// A [`MastodonClient`] which answers requests with responses queued up
+/// beforehand, and records the requests made to it, for testing code which
+/// uses a client without a server.
+///
+/// Responses are queued per method and returned in order. A method with no
+/// response queued fails, unless it returns `()`.
+///
+/// ```
+/// use mastodon_async::{prelude::*, testing::MockMastodon, MastodonClient};
+/// use serde_json::json;
+///
+/// tokio_test::block_on(async {
+///     let client = MockMastodon::new();
+///     let id = StatusId::new("1");
+///     client.delete_status(&id).await.unwrap();
+///     assert_eq!(client.calls()[0].method, "delete_status");
+///     assert_eq!(client.calls()[0].args, json!(["1"]));
+///     assert!(client.get_status(&id).await.is_err());
+/// });
+/// ```
+#[derive(Debug, Default)]
+pub struct MockMastodon {
+    responses: Mutex<HashMap<&'static str, VecDeque<Result<Value>>>>,
+    calls: Mutex<Vec<Call>>,
+}
+
+impl MockMastodon {
+    /// A mock with no responses queued.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response for the next call to `method` which doesn't have
+    /// one queued yet.
+    pub fn respond(&self, method: &'static str, response: impl Serialize) -> &Self {
+        let response = serde_json::to_value(response).map_err(Error::from);
+        self.queue(method, response)
+    }
+
+    /// Make the next call to `method` which doesn't have a response queued
+    /// yet fail with `error`.
+    pub fn fail(&self, method: &'static str, error: Error) -> &Self {
+        self.queue(method, Err(error))
+    }
+
+    /// The requests made so far, oldest first.
+    pub fn calls(&self) -> Vec<Call> {
+        self.calls.lock().expect("calls lock poisoned").clone()
+    }
+
+    /// The requests made so far to `method`, oldest first.
+    pub fn calls_to(&self, method: &str) -> Vec<Call> {
+        self.calls()
+            .into_iter()
+            .filter(|call| call.method == method)
+            .collect()
+    }
+
+    fn queue(&self, method: &'static str, response: Result<Value>) -> &Self {
+        self.responses
+            .lock()
+            .expect("responses lock poisoned")
+            .entry(method)
+            .or_default()
+            .push_back(response);
+        self
+    }
+
+    /// Record a call and answer it with the next response queued for it.
+    fn call<T: DeserializeOwned>(&self, method: &'static str, args: Value) -> Result<T> {
+        self.calls
+            .lock()
+            .expect("calls lock poisoned")
+            .push(Call { method, args });
+        let response = self
+            .responses
+            .lock()
+            .expect("responses lock poisoned")
+            .get_mut(method)
+            .and_then(VecDeque::pop_front);
+        match response {
+            Some(response) => Ok(serde_json::from_value(response?)?),
+            // only succeeds for methods which return nothing
+            None => serde_json::from_value(Value::Null)
+                .map_err(|_| format_err!("no response queued for {method}")),
+        }
+    }
+}
+
+#[async_trait]
+impl MastodonClient for MockMastodon {
+    async fn verify_credentials(&self) -> Result<Account> {
+        self.call("verify_credentials", json!([]))
+    }
+
+    async fn new_status(&self, status: NewStatus) -> Result<Status> {
+        self.call("new_status", json!([status]))
+    }
+
+    async fn get_status(&self, id: &StatusId) -> Result<Status> {
+        self.call("get_status", json!([id]))
+    }
+
+    async fn delete_status(&self, id: &StatusId) -> Result<()> {
+        self.call("delete_status", json!([id]))
+    }
+
+    async fn favourite(&self, id: &StatusId) -> Result<Status> {
+        self.call("favourite", json!([id]))
+    }
+
+    async fn unfavourite(&self, id: &StatusId) -> Result<Status> {
+        self.call("unfavourite", json!([id]))
+    }
+
+    async fn reblog(&self, id: &StatusId) -> Result<Status> {
+        self.call("reblog", json!([id]))
+    }
+
+    async fn unreblog(&self, id: &StatusId) -> Result<Status> {
+        self.call("unreblog", json!([id]))
+    }
+
+    async fn get_home_timeline(&self) -> Result<Vec<Status>> {
+        self.call("get_home_timeline", json!([]))
+    }
+
+    async fn timeline(
+        &self,
+        timeline: &Timeline,
+        options: forms::timeline::Options,
+    ) -> Result<Vec<Status>> {
+        self.call("timeline", json!([format!("{timeline:?}"), options]))
+    }
+
+    async fn notifications(&self) -> Result<Vec<Notification>> {
+        self.call("notifications", json!([]))
+    }
+
+    async fn get_account(&self, id: &AccountId) -> Result<Account> {
+        self.call("get_account", json!([id]))
+    }
+
+    async fn follow(&self, id: &AccountId) -> Result<Relationship> {
+        self.call("follow", json!([id]))
+    }
+
+    async fn unfollow(&self, id: &AccountId) -> Result<Relationship> {
+        self.call("unfollow", json!([id]))
+    }
+
+    async fn media(&self, form: forms::media::Upload) -> Result<Attachment> {
+        let forms::media::Upload {
+            file,
+            thumbnail,
+            description,
+            focus,
+        } = form;
+        self.call(
+            "media",
+            json!([{
+                "file": file,
+                "thumbnail": thumbnail,
+                "description": description,
+                "focus": focus,
+            }]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_responses_in_order() {
+        let client = MockMastodon::new();
+        client
+            .respond("get_home_timeline", Vec::<Status>::new())
+            .fail("get_home_timeline", format_err!("boom"));
+        assert!(client.get_home_timeline().await.unwrap().is_empty());
+        assert!(client.get_home_timeline().await.is_err());
+        // nothing left
+        assert!(client.get_home_timeline().await.is_err());
+        assert_eq!(client.calls_to("get_home_timeline").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_records_calls() {
+        let client = MockMastodon::new();
+        let status = NewStatus {
+            status: Some("hello".to_string()),
+            ..Default::default()
+        };
+        assert!(client.new_status(status).await.is_err());
+        client.delete_status(&StatusId::new("2")).await.unwrap();
+        let calls = client.calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].method, "new_status");
+        assert_eq!(calls[0].args[0]["status"], "hello");
+        assert_eq!(
+            calls[1],
+            Call {
+                method: "delete_status",
+                args: json!(["2"]),
+            }
+        );
+    }
+}
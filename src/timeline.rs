@@ -0,0 +1,441 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+};
+
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use time::OffsetDateTime;
+
+use crate::entities::{event::Event, ids::ListId, status::Status};
+
+/// A timeline of statuses, which can be fetched with
+/// [`Mastodon::timeline`](crate::Mastodon::timeline) or streamed with
+/// [`Mastodon::stream`](crate::Mastodon::stream), for when which one is only
+/// known at runtime.
+///
+/// ```no_run
+/// use futures_util::TryStreamExt;
+/// use mastodon_async::{prelude::*, timeline::{Timeline, TimelineMerger}};
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let picked = Timeline::Hashtag("rustlang".to_string());
+///     let mut timeline = TimelineMerger::new();
+///     let stream = client.stream(&picked).await.unwrap();
+///     let options = forms::timeline::Options::builder().limit(40u64).build().unwrap();
+///     timeline.extend(client.timeline(&picked, options).await.unwrap().initial_items);
+///     stream.try_for_each(|(event, _client)| {
+///         timeline.apply(&event);
+///         async { Ok(()) }
+///     }).await.unwrap();
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Timeline {
+    /// Statuses from the accounts the user follows. Its stream also carries
+    /// the user's notifications.
+    Home,
+    /// All public statuses known to the server.
+    Public,
+    /// Public statuses originating from this server.
+    Local,
+    /// Public statuses originating from other servers.
+    Remote,
+    /// Public statuses using a hashtag, given with or without the leading
+    /// `#`.
+    Hashtag(String),
+    /// Public statuses using a hashtag, originating from this server.
+    LocalHashtag(String),
+    /// Statuses from the members of one of the user's lists.
+    List(ListId),
+}
+
+impl Timeline {
+    /// The path of the REST endpoint for the timeline, along with any query
+    /// parameters it needs.
+    pub(crate) fn path(&self) -> String {
+        match self {
+            Timeline::Home => "/api/v1/timelines/home".to_string(),
+            Timeline::Public => "/api/v1/timelines/public".to_string(),
+            Timeline::Local => "/api/v1/timelines/public?local=true".to_string(),
+            Timeline::Remote => "/api/v1/timelines/public?remote=true".to_string(),
+            Timeline::Hashtag(tag) => format!("/api/v1/timelines/tag/{}", encode_tag(tag)),
+            Timeline::LocalHashtag(tag) => {
+                format!("/api/v1/timelines/tag/{}?local=true", encode_tag(tag))
+            }
+            Timeline::List(id) => format!("/api/v1/timelines/list/{}", encode(id.as_ref())),
+        }
+    }
+
+    /// The path of the streaming endpoint for the timeline, along with any
+    /// query parameters it needs.
+    pub(crate) fn streaming_path(&self) -> String {
+        match self {
+            Timeline::Home => "/api/v1/streaming/user".to_string(),
+            Timeline::Public => "/api/v1/streaming/public".to_string(),
+            Timeline::Local => "/api/v1/streaming/public/local".to_string(),
+            Timeline::Remote => "/api/v1/streaming/public/remote".to_string(),
+            Timeline::Hashtag(tag) => format!("/api/v1/streaming/hashtag?tag={}", encode_tag(tag)),
+            Timeline::LocalHashtag(tag) => {
+                format!("/api/v1/streaming/hashtag/local?tag={}", encode_tag(tag))
+            }
+            Timeline::List(id) => format!("/api/v1/streaming/list?list={}", encode(id.as_ref())),
+        }
+    }
+}
+
+fn encode(value: &str) -> String {
+    utf8_percent_encode(value, NON_ALPHANUMERIC).to_string()
+}
+
+fn encode_tag(tag: &str) -> String {
+    encode(tag.trim_start_matches('#'))
+}
+
+/// Merges statuses from several sources, such as a stream, REST backfill
+/// and push notifications, into a single timeline.
+///
+/// Statuses are ordered newest first by their creation time, then by ID,
+/// whatever order they arrive in. A status received more than once is kept
+/// only once: the newest edit of it wins, so a stale copy from a backfill
+/// can't overwrite an edit received from the stream. Once a status is
+/// deleted, neither it nor boosts of it are added again, even if an older
+/// copy arrives afterwards, until [`TimelineMerger::truncate`] drops
+/// statuses older than it.
+///
+/// ```no_run
+/// use mastodon_async::{prelude::*, timeline::TimelineMerger};
+/// use futures_util::TryStreamExt;
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let mut timeline = TimelineMerger::new();
+///     let stream = client.stream_user().await.unwrap();
+///     timeline.extend(client.get_home_timeline().await.unwrap().initial_items);
+///     stream.try_for_each(|(event, _client)| {
+///         timeline.apply(&event);
+///         async { Ok(()) }
+///     }).await.unwrap();
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TimelineMerger {
+    statuses: BTreeMap<Key, Status>,
+    keys: HashMap<String, Key>,
+    deleted: HashSet<String>,
+}
+
+/// Orders statuses newest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Key {
+    created_at: OffsetDateTime,
+    id: String,
+}
+
+impl Key {
+    fn of(status: &Status) -> Self {
+        Key {
+            created_at: status.created_at,
+            id: status.id.to_string(),
+        }
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .created_at
+            .cmp(&self.created_at)
+            .then_with(|| cmp_ids(&other.id, &self.id))
+    }
+}
+
+/// Orders status IDs oldest first. Mastodon's IDs are numeric strings, so a
+/// longer ID is a larger one.
+fn cmp_ids(a: &str, b: &str) -> Ordering {
+    (a.len(), a).cmp(&(b.len(), b))
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// When the given version of a status was last changed.
+fn revision(status: &Status) -> OffsetDateTime {
+    status.edited_at.unwrap_or(status.created_at)
+}
+
+impl TimelineMerger {
+    /// An empty timeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a status, or replace an older version of it. Boosts of the
+    /// status already in the timeline are updated too.
+    ///
+    /// Returns whether the timeline changed.
+    pub fn insert(&mut self, status: Status) -> bool {
+        let id = status.id.to_string();
+        let boosts_deleted = status
+            .reblog
+            .as_ref()
+            .is_some_and(|reblog| self.deleted.contains(reblog.id.as_ref()));
+        if boosts_deleted || self.deleted.contains(&id) {
+            return false;
+        }
+        for boost in self.statuses.values_mut() {
+            if let Some(reblog) = boost.reblog.as_mut() {
+                if reblog.id == status.id && revision(reblog) <= revision(&status) {
+                    **reblog = status.clone();
+                }
+            }
+        }
+        if let Some(key) = self.keys.get(&id) {
+            let current = &self.statuses[key];
+            if revision(current) > revision(&status) || *current == status {
+                return false;
+            }
+            let key = key.clone();
+            self.statuses.remove(&key);
+        }
+        let key = Key::of(&status);
+        self.keys.insert(id, key.clone());
+        self.statuses.insert(key, status);
+        true
+    }
+
+    /// Remove a status, and any boosts of it, and keep it from being added
+    /// again. Returns the status if it was in the timeline.
+    pub fn delete(&mut self, id: &str) -> Option<Status> {
+        self.deleted.insert(id.to_string());
+        let boosts: Vec<_> = self
+            .statuses
+            .values()
+            .filter(|status| {
+                status
+                    .reblog
+                    .as_ref()
+                    .is_some_and(|it| it.id.as_ref() == id)
+            })
+            .map(|status| status.id.to_string())
+            .collect();
+        for boost in boosts {
+            self.delete(&boost);
+        }
+        let key = self.keys.remove(id)?;
+        self.statuses.remove(&key)
+    }
+
+    /// Apply an event from a stream: new and edited statuses are inserted
+    /// and deleted ones removed. Other events are ignored.
+    ///
+    /// Returns whether the timeline changed.
+    pub fn apply(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Update(status) | Event::StatusUpdate(status) => self.insert(status.clone()),
+            Event::Delete(id) => self.delete(id).is_some(),
+            _ => false,
+        }
+    }
+
+    /// The status with the given ID, if it is in the timeline.
+    pub fn get(&self, id: &str) -> Option<&Status> {
+        self.keys.get(id).map(|key| &self.statuses[key])
+    }
+
+    /// The statuses in the timeline, newest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Status> {
+        self.statuses.values()
+    }
+
+    /// The number of statuses in the timeline.
+    pub fn len(&self) -> usize {
+        self.statuses.len()
+    }
+
+    /// Whether the timeline has no statuses.
+    pub fn is_empty(&self) -> bool {
+        self.statuses.is_empty()
+    }
+
+    /// Remove all but the newest `len` statuses. Those removed may be added
+    /// again later, unlike deleted ones.
+    ///
+    /// Deleted statuses older than the oldest one kept are forgotten too, so
+    /// that they don't pile up.
+    pub fn truncate(&mut self, len: usize) {
+        while self.statuses.len() > len {
+            if let Some((_, status)) = self.statuses.pop_last() {
+                self.keys.remove(status.id.as_ref());
+            }
+        }
+        match self.statuses.last_key_value() {
+            Some((oldest, _)) => self
+                .deleted
+                .retain(|id| cmp_ids(id, &oldest.id) != Ordering::Less),
+            None => self.deleted.clear(),
+        }
+    }
+}
+
+impl Extend<Status> for TimelineMerger {
+    fn extend<T: IntoIterator<Item = Status>>(&mut self, iter: T) {
+        for status in iter {
+            self.insert(status);
+        }
+    }
+}
+
+impl FromIterator<Status> for TimelineMerger {
+    fn from_iter<T: IntoIterator<Item = Status>>(iter: T) -> Self {
+        let mut timeline = Self::new();
+        timeline.extend(iter);
+        timeline
+    }
+}
+
+impl IntoIterator for TimelineMerger {
+    type Item = Status;
+    type IntoIter = std::vec::IntoIter<Status>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.statuses.into_values().collect::<Vec<_>>().into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{entities::ids::StatusId, fixtures};
+
+    fn status(id: &str, created_at: &str) -> Status {
+        let mut status = fixtures::status_json(id);
+        status["created_at"] = created_at.into();
+        fixtures::from_json(status)
+    }
+
+    fn edit(status: &Status, content: &str, minutes: i64) -> Status {
+        let mut edited = status.clone();
+        edited.content = content.to_string();
+        edited.edited_at = Some(status.created_at + time::Duration::minutes(minutes));
+        edited
+    }
+
+    fn boost(id: &str, status: &Status) -> Status {
+        let mut boost = status.clone();
+        boost.id = StatusId::new(id);
+        boost.created_at += time::Duration::hours(1);
+        boost.reblog = Some(Box::new(status.clone()));
+        boost
+    }
+
+    fn ids(timeline: &TimelineMerger) -> Vec<String> {
+        timeline.iter().map(|it| it.id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_timeline_paths() {
+        assert_eq!(Timeline::Home.path(), "/api/v1/timelines/home");
+        assert_eq!(Timeline::Home.streaming_path(), "/api/v1/streaming/user");
+        assert_eq!(
+            Timeline::Remote.path(),
+            "/api/v1/timelines/public?remote=true"
+        );
+        let tag = Timeline::LocalHashtag("#café".to_string());
+        assert_eq!(tag.path(), "/api/v1/timelines/tag/caf%C3%A9?local=true");
+        assert_eq!(
+            tag.streaming_path(),
+            "/api/v1/streaming/hashtag/local?tag=caf%C3%A9"
+        );
+        let list = Timeline::List(ListId::new("12"));
+        assert_eq!(list.path(), "/api/v1/timelines/list/12");
+        assert_eq!(list.streaming_path(), "/api/v1/streaming/list?list=12");
+    }
+
+    #[test]
+    fn test_order_and_dedup() {
+        let mut timeline = TimelineMerger::new();
+        timeline.insert(status("9", "2019-12-08T03:48:33.901Z"));
+        timeline.insert(status("11", "2019-12-09T03:48:33.901Z"));
+        timeline.insert(status("10", "2019-12-09T03:48:33.901Z"));
+        assert!(!timeline.insert(status("9", "2019-12-08T03:48:33.901Z")));
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(ids(&timeline), ["11", "10", "9"]);
+    }
+
+    #[test]
+    fn test_edits_supersede() {
+        let original = status("1", "2019-12-08T03:48:33.901Z");
+        let mut timeline: TimelineMerger = [original.clone()].into_iter().collect();
+        assert!(timeline.apply(&Event::StatusUpdate(edit(&original, "second", 2))));
+        // a stale copy from a backfill arriving after the edit
+        assert!(!timeline.insert(edit(&original, "first", 1)));
+        assert!(!timeline.insert(original.clone()));
+        assert_eq!(timeline.get("1").unwrap().content, "second");
+        assert_eq!(timeline.len(), 1);
+    }
+
+    #[test]
+    fn test_edits_update_boosts() {
+        let original = status("1", "2019-12-08T03:48:33.901Z");
+        let mut timeline = TimelineMerger::new();
+        timeline.insert(boost("2", &original));
+        timeline.insert(edit(&original, "edited", 1));
+        let boosted = timeline.get("2").unwrap().reblog.as_ref().unwrap();
+        assert_eq!(boosted.content, "edited");
+        assert_eq!(ids(&timeline), ["2", "1"]);
+    }
+
+    #[test]
+    fn test_deletes() {
+        let original = status("1", "2019-12-08T03:48:33.901Z");
+        let mut timeline = TimelineMerger::new();
+        timeline.insert(original.clone());
+        timeline.insert(boost("2", &original));
+        timeline.insert(status("3", "2019-12-10T03:48:33.901Z"));
+        assert!(timeline.apply(&Event::Delete("1".to_string())));
+        assert_eq!(ids(&timeline), ["3"]);
+        // an older copy arriving late doesn't bring it back, nor does a boost
+        assert!(!timeline.insert(boost("4", &original)));
+        assert!(!timeline.insert(original));
+        assert!(!timeline.apply(&Event::Delete("1".to_string())));
+        assert_eq!(timeline.len(), 1);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut timeline: TimelineMerger = [
+            status("1", "2019-12-08T03:48:33.901Z"),
+            status("2", "2019-12-09T03:48:33.901Z"),
+            status("3", "2019-12-10T03:48:33.901Z"),
+        ]
+        .into_iter()
+        .collect();
+        timeline.truncate(2);
+        assert_eq!(ids(&timeline), ["3", "2"]);
+        assert!(timeline.get("1").is_none());
+        assert!(timeline.insert(status("1", "2019-12-08T03:48:33.901Z")));
+        let statuses: Vec<_> = timeline.into_iter().map(|it| it.id.to_string()).collect();
+        assert_eq!(statuses, ["3", "2", "1"]);
+    }
+
+    #[test]
+    fn test_truncate_forgets_old_deletes() {
+        let mut timeline: TimelineMerger = [
+            status("2", "2019-12-08T03:48:33.901Z"),
+            status("3", "2019-12-09T03:48:33.901Z"),
+        ]
+        .into_iter()
+        .collect();
+        timeline.delete("1");
+        timeline.delete("10");
+        timeline.truncate(2);
+        assert!(timeline.insert(status("1", "2019-12-07T03:48:33.901Z")));
+        assert!(!timeline.insert(status("10", "2019-12-10T03:48:33.901Z")));
+        timeline.truncate(0);
+        assert!(timeline.insert(status("10", "2019-12-10T03:48:33.901Z")));
+    }
+}
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use futures::TryStreamExt;
+use reqwest::{multipart::Part, Body};
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+
+use crate::{entities::attachment::FocalPoint, errors::Result};
+
+/// How much of a file has been handed to the connection, as reported by
+/// [`Mastodon::media_with_progress`](crate::Mastodon::media_with_progress).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// The number of bytes of the file sent so far.
+    pub sent: u64,
+    /// The size of the file in bytes.
+    pub total: u64,
+}
+
+impl UploadProgress {
+    /// The fraction of the file sent so far, from 0 to 1.
+    ///
+    /// ```
+    /// use mastodon_async::upload::UploadProgress;
+    ///
+    /// let progress = UploadProgress { sent: 256, total: 1024 };
+    /// assert_eq!(progress.fraction(), 0.25);
+    /// ```
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.sent as f64 / self.total as f64
+        }
+    }
+}
+
+/// A multipart form part which streams the file at `path` from disk, calling
+/// `on_progress` as each chunk of it is sent.
+pub(crate) async fn file_part(
+    path: &Path,
+    mut on_progress: impl FnMut(UploadProgress) + Send + 'static,
+) -> Result<Part> {
+    let file = tokio::fs::File::open(path).await?;
+    let total = file.metadata().await?.len();
+    let mut sent = 0;
+    let chunks = ReaderStream::new(file).inspect_ok(move |chunk| {
+        sent += chunk.len() as u64;
+        on_progress(UploadProgress { sent, total });
+    });
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Ok(Part::stream_with_length(Body::wrap_stream(chunks), total).file_name(file_name))
+}
+
+/// A multipart form part which streams everything `reader` produces, of a
+/// length which isn't known up front.
+pub(crate) fn reader_part(
+    reader: impl AsyncRead + Send + 'static,
+    file_name: impl Into<String>,
+) -> Part {
+    Part::stream(Body::wrap_stream(ReaderStream::new(reader))).file_name(file_name.into())
+}
+
+/// The value of the `focus` form field for a focal point, as `x,y`.
+pub(crate) fn focus_field(focus: &FocalPoint) -> String {
+    format!("{},{}", focus.x, focus.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fraction() {
+        let progress = UploadProgress { sent: 0, total: 10 };
+        assert_eq!(progress.fraction(), 0.0);
+        let progress = UploadProgress {
+            sent: 10,
+            total: 10,
+        };
+        assert_eq!(progress.fraction(), 1.0);
+        let progress = UploadProgress { sent: 0, total: 0 };
+        assert_eq!(progress.fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_focus_field() {
+        assert_eq!(focus_field(&FocalPoint { x: -0.5, y: 0.25 }), "-0.5,0.25");
+        assert_eq!(focus_field(&FocalPoint { x: 0.0, y: 1.0 }), "0,1");
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let result = tokio_test::block_on(file_part(&dir.path().join("missing.mp4"), |_| {}));
+        assert!(matches!(result, Err(crate::Error::Io(_))));
+    }
+}